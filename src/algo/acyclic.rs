@@ -0,0 +1,151 @@
+use core::ops::Deref;
+
+use alloc::{collections::VecDeque, vec::Vec};
+
+use hashbrown::HashMap;
+
+use crate::{ghost::GhostToken, measure::Total, ops::GraphOps, Measure, VertexId};
+
+/// Orders the vertices of a directed graph so that every edge leads
+/// from an earlier vertex to a later one
+///
+/// # Errors
+/// Returns the vertices that couldn't be ordered if the graph
+/// contains a cycle, every one of which is on or after a cycle
+pub fn topological_sort<'id, G: GraphOps<'id>>(
+    graph: &G,
+    token: &GhostToken<'id>,
+) -> Result<Vec<VertexId<'id>>, Vec<VertexId<'id>>> {
+    let mut ids = graph.vertex_ids();
+    ids.sort_unstable();
+
+    let mut in_degree: HashMap<_, usize> = ids.iter().map(|id| (*id, 0)).collect();
+    for id in &ids {
+        for (other, _) in graph.successors(*id, token) {
+            if let Some(degree) = in_degree.get_mut(&other) {
+                *degree += 1;
+            }
+        }
+    }
+
+    let mut queue: VecDeque<_> = ids
+        .iter()
+        .copied()
+        .filter(|id| in_degree[id] == 0)
+        .collect();
+    let mut order = Vec::with_capacity(ids.len());
+
+    while let Some(id) = queue.pop_front() {
+        order.push(id);
+
+        for (other, _) in graph.successors(id, token) {
+            if let Some(degree) = in_degree.get_mut(&other) {
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push_back(other);
+                }
+            }
+        }
+    }
+
+    if order.len() == ids.len() {
+        Ok(order)
+    } else {
+        Err(ids.into_iter().filter(|id| in_degree[id] > 0).collect())
+    }
+}
+
+/// A graph that's been verified to contain no cycles
+///
+/// The only way to construct an [`Acyclic`] is through
+/// [`Acyclic::new`], and it only gives out shared references to the
+/// graph within, so algorithms that need a directed acyclic graph
+/// can take an [`Acyclic`] instead of checking for cycles themselves.
+///
+/// `G` can either be a graph, or a reference to one
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy)]
+pub struct Acyclic<G>(G);
+
+impl<G> Acyclic<G> {
+    /// Verifies that `graph` has no cycles
+    ///
+    /// # Errors
+    /// Returns `graph` back if it contains a cycle
+    pub fn new<'id>(graph: G, token: &GhostToken<'id>) -> Result<Self, G>
+    where
+        G: GraphOps<'id>,
+    {
+        if topological_sort(&graph, token).is_ok() {
+            Ok(Self(graph))
+        } else {
+            Err(graph)
+        }
+    }
+    /// Unwraps the graph, so that it can be mutated again
+    pub fn into_inner(self) -> G {
+        self.0
+    }
+    /// Orders the vertices so that every edge leads from an earlier
+    /// vertex to a later one, see [`topological_sort`]
+    #[must_use]
+    pub fn topological_sort<'id>(&self, token: &GhostToken<'id>) -> Vec<VertexId<'id>>
+    where
+        G: GraphOps<'id>,
+    {
+        topological_sort(&self.0, token).unwrap_or_default()
+    }
+    /// Finds the path with the largest total weight within the graph,
+    /// returning its weight and the vertices along it, or `None` if
+    /// the graph is empty
+    #[must_use]
+    pub fn critical_path<'id>(
+        &self,
+        token: &GhostToken<'id>,
+    ) -> Option<(G::Weight, Vec<VertexId<'id>>)>
+    where
+        G: GraphOps<'id>,
+        G::Weight: Measure,
+    {
+        let order = self.topological_sort(token);
+
+        let mut longest: HashMap<VertexId<'id>, (G::Weight, Option<VertexId<'id>>)> = order
+            .iter()
+            .map(|id| (*id, (G::Weight::ZERO, None)))
+            .collect();
+
+        for id in &order {
+            let (length, _) = longest[id];
+
+            for (other, weight) in self.0.successors(*id, token) {
+                let through = length.saturating_add(*weight);
+                if let Some(best) = longest.get_mut(&other) {
+                    if best.1.is_none() || through > best.0 {
+                        *best = (through, Some(*id));
+                    }
+                }
+            }
+        }
+
+        let (end, (length, _)) = order
+            .iter()
+            .map(|id| (*id, longest[id]))
+            .max_by(|(a, (x, _)), (b, (y, _))| Total(*x).cmp(&Total(*y)).then(b.cmp(a)))?;
+
+        let mut path = alloc::vec![end];
+        while let Some((_, Some(previous))) = longest.get(path.last()?) {
+            path.push(*previous);
+        }
+        path.reverse();
+
+        Some((length, path))
+    }
+}
+
+impl<G> Deref for Acyclic<G> {
+    type Target = G;
+
+    fn deref(&self) -> &G {
+        &self.0
+    }
+}
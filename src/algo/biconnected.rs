@@ -0,0 +1,173 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use hashbrown::HashMap;
+
+use crate::{
+    edge::{EdgeTrait, UnDirectedUnWeightedEdge},
+    ghost::GhostToken,
+    id::EdgeId,
+    Allocator, Graph, VertexId,
+};
+
+use super::{neighbors, DfsTree};
+
+/// A biconnected component of a graph, a maximal set of edges
+/// in which any two edges lie on a common simple cycle, or a
+/// single bridge
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Block<'id> {
+    /// The vertices within the component
+    pub vertices: Vec<VertexId<'id>>,
+    /// The edges within the component
+    pub edges: Vec<EdgeId<'id>>,
+}
+
+/// A vertex within a [`block_cut_tree`]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlockCutNode<'id> {
+    /// A biconnected component of the original graph
+    Block(Block<'id>),
+    /// An articulation point of the original graph
+    Cut(VertexId<'id>),
+}
+
+/// Splits the edges of the graph into its biconnected components,
+/// treating every edge as undirected
+///
+/// Vertices without any edges don't belong to any component
+#[must_use]
+pub fn biconnected_components<
+    'id,
+    Item,
+    Weight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    token: &GhostToken<'id>,
+) -> Vec<Block<'id>> {
+    let tree = DfsTree::forest(graph, token);
+
+    // The component the tree edge leading to each vertex is in
+    let mut component_of = HashMap::with_capacity(tree.len());
+    let mut blocks: Vec<Block<'id>> = Vec::new();
+
+    for &vertex in tree.order() {
+        if let Some((parent, edge)) = tree.parent(vertex) {
+            if tree.low(vertex) >= tree.index(parent) {
+                // `parent` separates `vertex`'s subtree from the
+                // rest of the graph, so a new component starts
+                component_of.insert(vertex, blocks.len());
+                blocks.push(Block {
+                    vertices: alloc::vec![parent, vertex],
+                    edges: alloc::vec![edge],
+                });
+            } else if let Some(&component) = component_of.get(&parent) {
+                component_of.insert(vertex, component);
+                blocks[component].vertices.push(vertex);
+                blocks[component].edges.push(edge);
+            }
+        }
+    }
+
+    // Every back edge belongs to the component of the tree edge
+    // leading to its lower end
+    for &vertex in tree.order() {
+        for (edge, other) in neighbors(graph, vertex, token) {
+            let is_tree_edge = tree.parent(other) == Some((vertex, edge));
+            if !is_tree_edge && tree.index(other) > tree.index(vertex) {
+                if let Some(&component) = component_of.get(&other) {
+                    blocks[component].edges.push(edge);
+                }
+            }
+        }
+    }
+
+    blocks
+}
+
+/// Returns every articulation point of the graph, the vertices
+/// whose removal would increase the number of components
+#[must_use]
+pub fn articulation_points<
+    'id,
+    Item,
+    Weight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    token: &GhostToken<'id>,
+) -> Vec<VertexId<'id>> {
+    cut_vertices(&biconnected_components(graph, token))
+}
+
+/// Builds the block-cut tree of the graph
+///
+/// The tree has a [`BlockCutNode::Block`] for every biconnected
+/// component and a [`BlockCutNode::Cut`] for every articulation
+/// point, with each articulation point connected to the blocks
+/// containing it.
+///
+/// As the tree is a new graph, it needs its own `tree_token`
+#[must_use]
+pub fn block_cut_tree<
+    'id,
+    'new,
+    Item,
+    Weight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    token: &GhostToken<'id>,
+    tree_token: &mut GhostToken<'new>,
+) -> Graph<'new, BlockCutNode<'id>, (), UnDirectedUnWeightedEdge<'new, BlockCutNode<'id>>> {
+    let blocks = biconnected_components(graph, token);
+    let cuts = cut_vertices(&blocks);
+
+    let mut tree = Graph::new();
+
+    let cut_ids: HashMap<_, _> = cuts
+        .into_iter()
+        .map(|cut| (cut, tree.add_vertex(BlockCutNode::Cut(cut))))
+        .collect();
+
+    for block in blocks {
+        let members: Vec<_> = block
+            .vertices
+            .iter()
+            .filter_map(|vertex| cut_ids.get(vertex).copied())
+            .collect();
+
+        let block_id = tree.add_vertex(BlockCutNode::Block(block));
+
+        for cut_id in members {
+            // Every pair is distinct and only connected once
+            let _ = tree.add_edge(block_id, cut_id, (), tree_token);
+        }
+    }
+
+    tree
+}
+
+/// Finds the vertices within more than one block
+fn cut_vertices<'id>(blocks: &[Block<'id>]) -> Vec<VertexId<'id>> {
+    let mut count = HashMap::new();
+
+    for vertex in blocks.iter().flat_map(|block| &block.vertices) {
+        *count.entry(*vertex).or_insert(0_usize) += 1;
+    }
+
+    count
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(vertex, _)| vertex)
+        .collect()
+}
@@ -0,0 +1,279 @@
+// `f64::mul_add` isn't available without std
+#![allow(clippy::suboptimal_flops)]
+
+use alloc::{collections::BinaryHeap, vec::Vec};
+use core::cmp::{Ordering, Reverse};
+
+use hashbrown::HashMap;
+
+use crate::{ghost::GhostToken, ops::GraphOps, SecondaryMap, VertexBitSet, VertexId};
+
+/// An `f64` that's ordered by [`f64::total_cmp`], so it can be
+/// used as a priority
+#[derive(Clone, Copy, PartialEq)]
+struct Total(f64);
+
+impl Eq for Total {}
+
+impl PartialOrd for Total {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Total {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+const fn float(count: usize) -> f64 {
+    count as f64
+}
+
+/// The shortest paths from a single source, with the number of
+/// shortest paths to each vertex and the vertices they arrive from
+struct Search<'id> {
+    settled: Vec<VertexId<'id>>,
+    distance: SecondaryMap<VertexId<'id>, f64>,
+    paths: SecondaryMap<VertexId<'id>, f64>,
+    previous: SecondaryMap<VertexId<'id>, Vec<VertexId<'id>>>,
+}
+
+impl<'id> Search<'id> {
+    fn new<G: GraphOps<'id>>(
+        graph: &G,
+        source: VertexId<'id>,
+        length: &impl Fn(&G::Weight) -> f64,
+        token: &GhostToken<'id>,
+    ) -> Self {
+        let mut search = Self {
+            settled: Vec::new(),
+            distance: SecondaryMap::new(),
+            paths: SecondaryMap::new(),
+            previous: SecondaryMap::new(),
+        };
+        search.distance.insert(source, 0.0);
+        search.paths.insert(source, 1.0);
+
+        let mut done = VertexBitSet::new();
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((Total(0.0), source)));
+
+        while let Some(Reverse((Total(distance), vertex))) = queue.pop() {
+            if !done.insert(vertex) {
+                continue;
+            }
+            search.settled.push(vertex);
+
+            let paths = search.paths[vertex];
+
+            for (other, weight) in graph.successors(vertex, token) {
+                let through = distance + length(weight);
+
+                match search.distance.get(other).copied() {
+                    Some(best) if libm::fabs(through - best) <= f64::EPSILON * best => {
+                        if !done.contains(other) {
+                            *search.paths.get_or_default(other) += paths;
+                            search.previous.get_or_default(other).push(vertex);
+                        }
+                    }
+                    Some(best) if through > best => {}
+                    _ => {
+                        search.distance.insert(other, through);
+                        search.paths.insert(other, paths);
+                        search.previous.insert(other, alloc::vec![vertex]);
+                        queue.push(Reverse((Total(through), other)));
+                    }
+                }
+            }
+        }
+
+        search
+    }
+}
+
+/// The degree centrality of every vertex, which is the number of
+/// edges connected to the vertex divided by the number of other
+/// vertices
+///
+/// For directed graphs both incoming and outgoing edges are counted
+#[must_use]
+pub fn degree<'id, G: GraphOps<'id>>(
+    graph: &G,
+    token: &GhostToken<'id>,
+) -> HashMap<VertexId<'id>, f64> {
+    let others = graph.vertex_len().saturating_sub(1);
+
+    graph
+        .vertex_ids()
+        .into_iter()
+        .map(|id| {
+            let mut edges = graph.successors(id, token).count();
+            if graph.is_directed() {
+                edges += graph.predecessors(id, token).count();
+            }
+
+            let centrality = if others == 0 {
+                0.0
+            } else {
+                float(edges) / float(others)
+            };
+
+            (id, centrality)
+        })
+        .collect()
+}
+
+/// The `PageRank` of every vertex, which sums to `1.0`
+///
+/// `damping` is the chance of following an edge rather than jumping
+/// to a random vertex, usually `0.85`. The rank of vertices with no
+/// edges leaving them is spread evenly over every vertex. Iteration
+/// stops once the ranks change by less than `tolerance` in total, or
+/// after `max_iterations`
+#[must_use]
+pub fn page_rank<'id, G: GraphOps<'id>>(
+    graph: &G,
+    damping: f64,
+    tolerance: f64,
+    max_iterations: usize,
+    token: &GhostToken<'id>,
+) -> HashMap<VertexId<'id>, f64> {
+    let mut ids = graph.vertex_ids();
+    ids.sort_unstable();
+
+    if ids.is_empty() {
+        return HashMap::new();
+    }
+
+    let index: SecondaryMap<_, _> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    let outgoing: Vec<Vec<usize>> = ids
+        .iter()
+        .map(|id| {
+            graph
+                .successors(*id, token)
+                .filter_map(|(other, _)| index.get(other).copied())
+                .collect()
+        })
+        .collect();
+
+    let share = 1.0 / float(ids.len());
+    let mut rank = alloc::vec![share; ids.len()];
+
+    for _ in 0..max_iterations {
+        let dangling: f64 = outgoing
+            .iter()
+            .zip(&rank)
+            .filter(|(edges, _)| edges.is_empty())
+            .map(|(_, rank)| rank)
+            .sum();
+
+        let mut next = alloc::vec![(1.0 - damping) * share + damping * dangling * share; ids.len()];
+
+        for (edges, rank) in outgoing.iter().zip(&rank) {
+            if !edges.is_empty() {
+                let spread = damping * rank / float(edges.len());
+                for other in edges {
+                    next[*other] += spread;
+                }
+            }
+        }
+
+        let change: f64 = next
+            .iter()
+            .zip(&rank)
+            .map(|(new, old)| libm::fabs(new - old))
+            .sum();
+        rank = next;
+
+        if change < tolerance {
+            break;
+        }
+    }
+
+    ids.into_iter().zip(rank).collect()
+}
+
+/// The betweenness centrality of every vertex, which is the number of
+/// shortest paths between other vertices that pass through the
+/// vertex, computed using Brandes' algorithm
+///
+/// `length` is given an edge's weight and returns the length of the
+/// edge, which must never be negative. Use `|_| 1.0` for unweighted
+/// graphs. Where there are several shortest paths between two
+/// vertices each counts fractionally, and for undirected graphs each
+/// pair of vertices is only counted once
+#[must_use]
+pub fn betweenness<'id, G: GraphOps<'id>>(
+    graph: &G,
+    length: impl Fn(&G::Weight) -> f64,
+    token: &GhostToken<'id>,
+) -> HashMap<VertexId<'id>, f64> {
+    let ids = graph.vertex_ids();
+    let mut centrality: HashMap<_, _> = ids.iter().map(|id| (*id, 0.0)).collect();
+
+    for source in ids {
+        let mut search = Search::new(graph, source, &length, token);
+        let mut dependency: SecondaryMap<VertexId<'id>, f64> = SecondaryMap::new();
+
+        while let Some(vertex) = search.settled.pop() {
+            let through = 1.0 + dependency.get(vertex).copied().unwrap_or_default();
+            let paths = search.paths[vertex];
+
+            for previous in search.previous.remove(vertex).unwrap_or_default() {
+                *dependency.get_or_default(previous) += search.paths[previous] / paths * through;
+            }
+
+            if vertex != source {
+                if let Some(total) = centrality.get_mut(&vertex) {
+                    *total += through - 1.0;
+                }
+            }
+        }
+    }
+
+    if !graph.is_directed() {
+        for total in centrality.values_mut() {
+            *total /= 2.0;
+        }
+    }
+
+    centrality
+}
+
+/// The closeness centrality of every vertex, which is the inverse of
+/// the average distance from the vertex to the vertices it can reach
+///
+/// `length` is given an edge's weight and returns the length of the
+/// edge, which must never be negative. Use `|_| 1.0` for unweighted
+/// graphs. The result is scaled by the fraction of other vertices
+/// that can be reached, so that vertices in small components don't
+/// look central
+#[must_use]
+pub fn closeness<'id, G: GraphOps<'id>>(
+    graph: &G,
+    length: impl Fn(&G::Weight) -> f64,
+    token: &GhostToken<'id>,
+) -> HashMap<VertexId<'id>, f64> {
+    let others = graph.vertex_len().saturating_sub(1);
+
+    graph
+        .vertex_ids()
+        .into_iter()
+        .map(|id| {
+            let search = Search::new(graph, id, &length, token);
+            let reached = search.distance.len() - 1;
+            let total: f64 = search.distance.values().sum();
+
+            let centrality = if total > 0.0 {
+                float(reached) / total * float(reached) / float(others)
+            } else {
+                0.0
+            };
+
+            (id, centrality)
+        })
+        .collect()
+}
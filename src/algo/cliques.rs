@@ -0,0 +1,124 @@
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, Allocator, Graph, VertexId};
+
+use super::adjacency;
+
+/// A level of the Bron–Kerbosch search: the clique grown so far, the
+/// vertices that could still join it, the vertices that already have
+/// been tried, and the candidates left to try
+struct Frame<'id> {
+    clique: Vec<VertexId<'id>>,
+    candidates: HashSet<VertexId<'id>>,
+    excluded: HashSet<VertexId<'id>>,
+    to_try: Vec<VertexId<'id>>,
+}
+
+/// An iterator over the maximal cliques of a graph, created by
+/// [`Graph::maximal_cliques`]
+///
+/// Cliques are found one at a time as the iterator is advanced, so
+/// only the cliques along the current branch of the search are held
+/// in memory, rather than every clique in the graph
+pub struct MaximalCliques<'id> {
+    adjacent: HashMap<VertexId<'id>, HashSet<VertexId<'id>>>,
+    stack: Vec<Frame<'id>>,
+}
+
+impl<'id> MaximalCliques<'id> {
+    /// The frame that tries adding every candidate not adjacent to
+    /// a pivot chosen to have as many adjacent candidates as possible
+    fn frame(
+        &self,
+        clique: Vec<VertexId<'id>>,
+        candidates: HashSet<VertexId<'id>>,
+        excluded: HashSet<VertexId<'id>>,
+    ) -> Frame<'id> {
+        let pivot = candidates
+            .iter()
+            .chain(&excluded)
+            .max_by_key(|u| {
+                self.adjacent[u]
+                    .iter()
+                    .filter(|v| candidates.contains(v))
+                    .count()
+            })
+            .map(|u| &self.adjacent[u]);
+
+        let mut to_try: Vec<_> = candidates
+            .iter()
+            .filter(|v| pivot.is_none_or(|adjacent| !adjacent.contains(v)))
+            .copied()
+            .collect();
+        to_try.sort_unstable_by(|a, b| b.cmp(a));
+
+        Frame {
+            clique,
+            candidates,
+            excluded,
+            to_try,
+        }
+    }
+}
+
+impl<'id> Iterator for MaximalCliques<'id> {
+    type Item = Vec<VertexId<'id>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let frame = self.stack.last_mut()?;
+            let Some(v) = frame.to_try.pop() else {
+                self.stack.pop();
+                continue;
+            };
+
+            let adjacent = &self.adjacent[&v];
+            let candidates: HashSet<_> = frame.candidates.intersection(adjacent).copied().collect();
+            let excluded: HashSet<_> = frame.excluded.intersection(adjacent).copied().collect();
+            let mut clique = frame.clique.clone();
+            clique.push(v);
+
+            frame.candidates.remove(&v);
+            frame.excluded.insert(v);
+
+            if candidates.is_empty() {
+                if excluded.is_empty() {
+                    clique.sort_unstable();
+                    return Some(clique);
+                }
+            } else {
+                let frame = self.frame(clique, candidates, excluded);
+                self.stack.push(frame);
+            }
+        }
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Returns an iterator over every maximal clique of the graph,
+    /// treating every edge as undirected, using the Bron–Kerbosch
+    /// algorithm with pivoting
+    ///
+    /// A clique is a set of vertices that are all adjacent to each
+    /// other, and is maximal if no other vertex could be added to
+    /// it. Each clique is ordered by id. Vertices with no edges are
+    /// cliques of their own
+    #[must_use]
+    pub fn maximal_cliques(&self, token: &GhostToken<'id>) -> MaximalCliques<'id> {
+        let mut cliques = MaximalCliques {
+            adjacent: adjacency(self, token),
+            stack: Vec::new(),
+        };
+        if !self.is_empty() {
+            let frame = cliques.frame(vec![], self.vertex_ids().collect(), HashSet::new());
+            cliques.stack.push(frame);
+        }
+
+        cliques
+    }
+}
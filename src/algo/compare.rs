@@ -0,0 +1,218 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, Allocator, Graph, VertexId};
+
+use super::{connected_components, successors};
+
+/// How a single component changed between two snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComponentChange {
+    /// The size of the component in the first snapshot that
+    /// shares the most vertices with this one, or `0` if the
+    /// component is entirely new
+    pub before: usize,
+    /// The size of the component in the second snapshot
+    pub after: usize,
+}
+
+/// A report of how a graph changed between two snapshots, as
+/// computed by [`compare_snapshots`]
+///
+/// Vertices are matched between snapshots by [`VertexId`], and
+/// edges by the [`VertexId`]s of their endpoints
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotComparison<'id> {
+    /// The total variation distance between the degree
+    /// distributions of the two snapshots, from `0.0` when they're
+    /// identical to `1.0` when they share no degrees at all
+    pub degree_divergence: f64,
+    /// The fraction of edges in either snapshot that were added or
+    /// removed, `0.0` when the edges are unchanged
+    pub edge_churn: f64,
+    /// The number of vertices only in the second snapshot
+    pub added_vertices: usize,
+    /// The number of vertices only in the first snapshot
+    pub removed_vertices: usize,
+    /// The number of edges only in the second snapshot
+    pub added_edges: usize,
+    /// The number of edges only in the first snapshot
+    pub removed_edges: usize,
+    /// How each component of the second snapshot changed, largest
+    /// first
+    pub component_changes: Vec<ComponentChange>,
+    /// The vertices whose degree changed the most, along with the
+    /// change, largest change first
+    pub degree_changes: Vec<(VertexId<'id>, isize)>,
+}
+
+/// Compares two snapshots of a graph, reporting up to `top` of
+/// the vertices whose degree changed the most
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn compare_snapshots<'id, I1, W1, E1, I2, W2, E2>(
+    before: &Graph<'id, I1, W1, E1>,
+    after: &Graph<'id, I2, W2, E2>,
+    top: usize,
+    token: &GhostToken<'id>,
+) -> SnapshotComparison<'id>
+where
+    E1: EdgeTrait<'id, I1, W1>,
+    E2: EdgeTrait<'id, I2, W2>,
+{
+    let degrees_before = degrees(before, token);
+    let degrees_after = degrees(after, token);
+
+    let added_vertices = degrees_after
+        .keys()
+        .filter(|id| !degrees_before.contains_key(*id))
+        .count();
+    let removed_vertices = degrees_before
+        .keys()
+        .filter(|id| !degrees_after.contains_key(*id))
+        .count();
+
+    let edges_before = edge_set(before, token);
+    let edges_after = edge_set(after, token);
+
+    let added_edges = edges_after.difference(&edges_before).count();
+    let removed_edges = edges_before.difference(&edges_after).count();
+    let union = edges_before.union(&edges_after).count();
+
+    let edge_churn = if union == 0 {
+        0.
+    } else {
+        (added_edges + removed_edges) as f64 / union as f64
+    };
+
+    let mut degree_changes: Vec<_> = degrees_before
+        .keys()
+        .chain(degrees_after.keys())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .map(|id| {
+            let before = degrees_before.get(id).copied().unwrap_or(0);
+            let after = degrees_after.get(id).copied().unwrap_or(0);
+            (*id, after.cast_signed() - before.cast_signed())
+        })
+        .filter(|(_, change)| *change != 0)
+        .collect();
+    degree_changes.sort_unstable_by_key(|(id, change)| (core::cmp::Reverse(change.abs()), *id));
+    degree_changes.truncate(top);
+
+    SnapshotComparison {
+        degree_divergence: divergence(&degrees_before, &degrees_after),
+        edge_churn,
+        added_vertices,
+        removed_vertices,
+        added_edges,
+        removed_edges,
+        component_changes: component_changes(before, after, token),
+        degree_changes,
+    }
+}
+
+fn degrees<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    token: &GhostToken<'id>,
+) -> HashMap<VertexId<'id>, usize> {
+    graph
+        .vertices()
+        .map(|(id, vertex)| (*id, vertex.borrow(token).edges().len()))
+        .collect()
+}
+
+fn edge_set<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    token: &GhostToken<'id>,
+) -> HashSet<(VertexId<'id>, VertexId<'id>)> {
+    graph
+        .get_all_vertices()
+        .flat_map(|&id| successors(graph, id, token).map(move |(_, other, _)| (id, other)))
+        .map(|(from, to)| {
+            if Edge::DIRECTED || from < to {
+                (from, to)
+            } else {
+                (to, from)
+            }
+        })
+        .collect()
+}
+
+/// The total variation distance between two degree distributions
+#[allow(clippy::cast_precision_loss)]
+fn divergence<'id>(
+    before: &HashMap<VertexId<'id>, usize>,
+    after: &HashMap<VertexId<'id>, usize>,
+) -> f64 {
+    fn histogram<'id>(degrees: &HashMap<VertexId<'id>, usize>) -> HashMap<usize, f64> {
+        let mut histogram = HashMap::new();
+        for degree in degrees.values() {
+            *histogram.entry(*degree).or_insert(0.) += 1. / degrees.len() as f64;
+        }
+        histogram
+    }
+
+    let (before, after) = (histogram(before), histogram(after));
+
+    let difference: f64 = before
+        .keys()
+        .chain(after.keys())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .map(|degree| {
+            let p = before.get(degree).copied().unwrap_or(0.);
+            let q = after.get(degree).copied().unwrap_or(0.);
+            (p - q).abs()
+        })
+        .sum();
+
+    difference / 2.
+}
+
+fn component_changes<'id, I1, W1, E1, I2, W2, E2>(
+    before: &Graph<'id, I1, W1, E1>,
+    after: &Graph<'id, I2, W2, E2>,
+    token: &GhostToken<'id>,
+) -> Vec<ComponentChange>
+where
+    E1: EdgeTrait<'id, I1, W1>,
+    E2: EdgeTrait<'id, I2, W2>,
+{
+    let components_before = connected_components(before, token);
+
+    let component_of: HashMap<_, _> = components_before
+        .iter()
+        .enumerate()
+        .flat_map(|(i, component)| component.iter().map(move |id| (*id, i)))
+        .collect();
+
+    let mut changes: Vec<_> = connected_components(after, token)
+        .into_iter()
+        .map(|component| {
+            let mut overlap = HashMap::new();
+            for id in &component {
+                if let Some(i) = component_of.get(id) {
+                    *overlap.entry(*i).or_insert(0_usize) += 1;
+                }
+            }
+
+            let before = overlap
+                .into_iter()
+                .max_by_key(|(i, count)| (*count, core::cmp::Reverse(*i)))
+                .map_or(0, |(i, _)| components_before[i].len());
+
+            ComponentChange {
+                before,
+                after: component.len(),
+            }
+        })
+        .collect();
+
+    changes.sort_unstable_by_key(|change| core::cmp::Reverse((change.after, change.before)));
+    changes
+}
@@ -0,0 +1,278 @@
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+use hashbrown::{hash_map::Keys, HashMap};
+
+use crate::{
+    edge::EdgeTrait, ghost::GhostToken, id::EdgeId, Allocator, Graph, GraphError, SharedNode,
+    VertexBitSet, VertexId,
+};
+
+use super::{neighbors, successors};
+
+type Condensed<'id, Weight, Edge, S> = Result<
+    Graph<'id, Vec<VertexId<'id>>, Weight, Edge, S>,
+    GraphError<'id, Vec<VertexId<'id>>, Weight, Edge>,
+>;
+
+/// An iterator over the connected components of a graph, created by
+/// [`Graph::components`]
+///
+/// Each component is found as the iterator is advanced, so only the
+/// component being found and the vertices already seen are held in
+/// memory
+pub struct Components<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator> {
+    graph: &'a Graph<'id, Item, Weight, Edge, S, A>,
+    token: &'a GhostToken<'id>,
+    starts: Keys<'a, VertexId<'id>, SharedNode<'id, Item, Weight, Edge>>,
+    seen: VertexBitSet<'id>,
+}
+
+impl<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Iterator for Components<'a, 'id, Item, Weight, Edge, S, A>
+{
+    type Item = Vec<VertexId<'id>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let seen = &self.seen;
+        let start = *self.starts.find(|id| !seen.contains(**id))?;
+        self.seen.insert(start);
+
+        let mut component = vec![start];
+        let mut next = 0;
+
+        while let Some(&vertex) = component.get(next) {
+            next += 1;
+
+            for (_, other) in neighbors(self.graph, vertex, self.token) {
+                if self.seen.insert(other) {
+                    component.push(other);
+                }
+            }
+        }
+
+        Some(component)
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Returns an iterator over the connected components of the
+    /// graph, treating every edge as undirected
+    ///
+    /// For a directed graph these are its weakly connected
+    /// components, within which every vertex can reach every other if
+    /// directed edges could be followed either way. Each of its
+    /// [`strongly_connected_components`] is within one of these
+    ///
+    /// To find every component at once, see [`connected_components`]
+    ///
+    /// ```rust
+    /// use graph::{
+    ///     algo::strongly_connected_components, edge::DirectedWeightedEdge, ghost::GhostToken,
+    ///     Graph,
+    /// };
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let a = graph.add_vertex(());
+    ///     let b = graph.add_vertex(());
+    ///     let c = graph.add_vertex(());
+    ///     graph.add_edge(a, b, (), &mut token).unwrap();
+    ///
+    ///     assert_eq!(graph.components(&token).count(), 2);
+    ///     assert_eq!(strongly_connected_components(&graph, &token).len(), 3);
+    /// })
+    /// ```
+    #[doc(alias = "weakly_connected_components")]
+    #[must_use]
+    pub fn components<'a>(
+        &'a self,
+        token: &'a GhostToken<'id>,
+    ) -> Components<'a, 'id, Item, Weight, Edge, S, A> {
+        Components {
+            graph: self,
+            token,
+            starts: self.vertices.keys(),
+            seen: self.vertex_set(),
+        }
+    }
+}
+
+/// Splits the vertices of the graph into its connected components,
+/// treating every edge as undirected
+///
+/// This collects [`Graph::components`]
+#[must_use]
+pub fn connected_components<
+    'id,
+    Item,
+    Weight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    token: &GhostToken<'id>,
+) -> Vec<Vec<VertexId<'id>>> {
+    graph.components(token).collect()
+}
+
+/// Splits the vertices of the graph into its strongly connected
+/// components, within which every vertex can reach every other
+/// following the direction of directed edges, using Tarjan's algorithm
+///
+/// The components are in topological order, so no edge leads from a
+/// component to an earlier one, and the vertices within each
+/// component are ordered by id
+#[must_use]
+pub fn strongly_connected_components<
+    'id,
+    Item,
+    Weight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    token: &GhostToken<'id>,
+) -> Vec<Vec<VertexId<'id>>> {
+    let mut ids: Vec<_> = graph.vertex_ids().collect();
+    ids.sort_unstable();
+
+    let mut index = graph.vertex_map();
+    let mut low = graph.vertex_map();
+    let mut on_stack = graph.vertex_set();
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+
+    let successors_of = |id| -> Vec<_> {
+        successors(graph, id, token)
+            .map(|(_, other, _)| other)
+            .collect()
+    };
+
+    for root in ids {
+        if index.contains_key(root) {
+            continue;
+        }
+
+        let root_index = index.len();
+        index.insert(root, root_index);
+        low.insert(root, root_index);
+        stack.push(root);
+        on_stack.insert(root);
+        // Each vertex being searched, with the successors it has yet
+        // to search
+        let mut searching = vec![(root, successors_of(root), 0)];
+
+        while let Some((id, others, next)) = searching.last_mut() {
+            let id = *id;
+
+            if let Some(&other) = others.get(*next) {
+                *next += 1;
+
+                if let Some(&other_index) = index.get(other) {
+                    if on_stack.contains(other) {
+                        let low = low.get_or_default(id);
+                        *low = (*low).min(other_index);
+                    }
+                } else {
+                    let other_index = index.len();
+                    index.insert(other, other_index);
+                    low.insert(other, other_index);
+                    stack.push(other);
+                    on_stack.insert(other);
+                    searching.push((other, successors_of(other), 0));
+                }
+                continue;
+            }
+
+            searching.pop();
+            let id_low = low[id];
+            if let Some((parent, ..)) = searching.last() {
+                let low = low.get_or_default(*parent);
+                *low = (*low).min(id_low);
+            }
+
+            if id_low == index[id] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack.remove(member);
+                    component.push(member);
+                    if member == id {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                components.push(component);
+            }
+        }
+    }
+
+    // Tarjan's algorithm finds each component after every component
+    // it has edges to
+    components.reverse();
+    components
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Constructs the condensation of the graph, a directed acyclic
+    /// graph with a vertex for each of its
+    /// [`strongly_connected_components`]
+    ///
+    /// Each vertex holds the [`VertexId`]s of the component's members,
+    /// and is added in the topological order the components are
+    /// found in. Components are joined by a single edge leading the
+    /// same way as the edges between their members, whose weight is
+    /// the [`EdgeId`]s of those edges, ordered by id
+    ///
+    /// # Errors
+    /// Returns a [`GraphError`] if any edge can't be added to the
+    /// condensation, which can only happen if `CEdge` is undirected
+    /// and can't join components both ways
+    pub fn condensation<CEdge, CS>(
+        &self,
+        token: &mut GhostToken<'id>,
+    ) -> Condensed<'id, Vec<EdgeId<'id>>, CEdge, CS>
+    where
+        CEdge: EdgeTrait<'id, Vec<VertexId<'id>>, Vec<EdgeId<'id>>>,
+        CS: BuildHasher + Clone + Default,
+    {
+        let components = strongly_connected_components(self, token);
+        let component: HashMap<_, _> = components
+            .iter()
+            .enumerate()
+            .flat_map(|(i, members)| members.iter().map(move |id| (*id, i)))
+            .collect();
+
+        let mut crossing: HashMap<(usize, usize), Vec<EdgeId<'id>>> = HashMap::new();
+        for (id, i) in &component {
+            for (e_id, other, _) in successors(self, *id, token) {
+                let j = component[&other];
+                if *i != j {
+                    crossing.entry((*i, j)).or_default().push(e_id);
+                }
+            }
+        }
+        let mut crossing: Vec<_> = crossing.into_iter().collect();
+        crossing.sort_unstable_by_key(|(pair, _)| *pair);
+
+        let mut condensed =
+            Graph::with_capacity_and_hasher(components.len(), crossing.len(), CS::default());
+        let ids: Vec<_> = components
+            .into_iter()
+            .map(|members| condensed.add_vertex(members))
+            .collect();
+        for ((i, j), mut edges) in crossing {
+            edges.sort_unstable();
+            edges.dedup();
+            condensed.add_edge(ids[i], ids[j], edges, token)?;
+        }
+
+        Ok(condensed)
+    }
+}
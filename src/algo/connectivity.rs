@@ -0,0 +1,174 @@
+use hashbrown::HashMap;
+
+use crate::{ghost::GhostToken, ops::GraphOps, EdgeId, GraphObserver, VertexId};
+
+/// Which vertices are connected to each other, kept up to date as
+/// edges are added using a disjoint-set forest
+///
+/// Adding an edge and asking whether two vertices are connected take
+/// close to constant time, rather than finding every component of
+/// the graph again. Edges are followed in either direction, so the
+/// components of directed graphs are weakly connected.
+///
+/// The forest can be updated by hand, with
+/// [`DynamicConnectivity::add_vertex`] and
+/// [`DynamicConnectivity::union`], or kept in sync with a graph by
+/// giving it to [`Graph::set_observer`](crate::Graph::set_observer)
+/// inside a [`SharedObserver`](crate::SharedObserver). A disjoint-set
+/// forest can't split components apart again, so once an edge or
+/// vertex is removed, or an edge is changed, the forest is
+/// [stale](DynamicConnectivity::is_stale), and may still consider
+/// vertices connected until it's [rebuilt](DynamicConnectivity::rebuild)
+#[derive(Debug, Clone, Default)]
+pub struct DynamicConnectivity<'id> {
+    /// The parent of every vertex in the forest, where the roots are
+    /// their own parents
+    parent: HashMap<VertexId<'id>, VertexId<'id>>,
+    /// The number of vertices in the component of every root
+    size: HashMap<VertexId<'id>, usize>,
+    stale: bool,
+}
+
+impl<'id> DynamicConnectivity<'id> {
+    /// Constructs a new forest without any vertices
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Constructs a forest holding every vertex and edge of `graph`
+    #[must_use]
+    pub fn from_graph<G: GraphOps<'id>>(graph: &G, token: &GhostToken<'id>) -> Self {
+        let mut connectivity = Self::new();
+
+        let ids = graph.vertex_ids();
+        for id in &ids {
+            connectivity.add_vertex(*id);
+        }
+        for id in ids {
+            for (other, _) in graph.successors(id, token) {
+                connectivity.union(id, other);
+            }
+        }
+
+        connectivity
+    }
+    /// Replaces everything in the forest with the vertices and edges
+    /// of `graph`, so that it's no longer stale
+    pub fn rebuild<G: GraphOps<'id>>(&mut self, graph: &G, token: &GhostToken<'id>) {
+        *self = Self::from_graph(graph, token);
+    }
+    /// Adds `id` in a component of its own, returning `false` if it
+    /// was already within the forest
+    pub fn add_vertex(&mut self, id: VertexId<'id>) -> bool {
+        if self.parent.contains_key(&id) {
+            return false;
+        }
+
+        self.parent.insert(id, id);
+        self.size.insert(id, 1);
+        true
+    }
+    /// Joins the components of `a` and `b`, as adding an edge between
+    /// them does, returning `false` if they were already connected
+    ///
+    /// Either vertex is added first if it isn't within the forest
+    pub fn union(&mut self, a: VertexId<'id>, b: VertexId<'id>) -> bool {
+        self.add_vertex(a);
+        self.add_vertex(b);
+
+        let (Some(a), Some(b)) = (self.find(a), self.find(b)) else {
+            return false;
+        };
+        if a == b {
+            return false;
+        }
+
+        // The smaller component goes beneath the larger one, which
+        // keeps the trees shallow
+        let (small, large) = if self.size[&a] < self.size[&b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let small_size = self.size.remove(&small).unwrap_or(1);
+        self.parent.insert(small, large);
+        *self.size.entry(large).or_insert(0) += small_size;
+
+        true
+    }
+    /// The vertex standing for the component `id` is in, or `None`
+    /// if `id` isn't within the forest
+    ///
+    /// Two vertices are connected exactly when they have the same
+    /// representative, until the forest next changes
+    pub fn find(&mut self, id: VertexId<'id>) -> Option<VertexId<'id>> {
+        let mut current = id;
+        let mut parent = *self.parent.get(&current)?;
+
+        // Every vertex passed is pointed at its grandparent, halving
+        // the path for the next search
+        while parent != current {
+            let grandparent = self.parent[&parent];
+            self.parent.insert(current, grandparent);
+            current = parent;
+            parent = grandparent;
+        }
+
+        Some(current)
+    }
+    /// Whether there's a path between `a` and `b`, ignoring the
+    /// direction of edges
+    ///
+    /// Returns `false` if either vertex isn't within the forest
+    pub fn connected(&mut self, a: VertexId<'id>, b: VertexId<'id>) -> bool {
+        match (self.find(a), self.find(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+    /// The number of vertices in the component `id` is in, or `None`
+    /// if `id` isn't within the forest
+    pub fn component_size(&mut self, id: VertexId<'id>) -> Option<usize> {
+        let root = self.find(id)?;
+        self.size.get(&root).copied()
+    }
+    /// The number of components in the forest
+    #[must_use]
+    pub fn component_len(&self) -> usize {
+        self.size.len()
+    }
+    /// The number of vertices in the forest
+    #[must_use]
+    pub fn vertex_len(&self) -> usize {
+        self.parent.len()
+    }
+    /// Whether an edge or vertex has been removed from the graph, or
+    /// an edge changed, since the forest was built, so that it may
+    /// consider vertices connected which no longer are
+    #[must_use]
+    pub const fn is_stale(&self) -> bool {
+        self.stale
+    }
+}
+
+impl<'id> GraphObserver<'id> for DynamicConnectivity<'id> {
+    fn on_vertex_added(&mut self, id: VertexId<'id>) {
+        self.add_vertex(id);
+    }
+    fn on_vertex_removed(&mut self, _: VertexId<'id>) {
+        self.stale = true;
+    }
+    fn on_edge_added(&mut self, _: EdgeId<'id>, from: VertexId<'id>, to: VertexId<'id>) {
+        self.union(from, to);
+    }
+    fn on_edge_changed(&mut self, _: EdgeId<'id>) {
+        // The edge may now join different vertices
+        self.stale = true;
+    }
+    fn on_edge_removed(&mut self, _: EdgeId<'id>) {
+        self.stale = true;
+    }
+    fn on_cleared(&mut self) {
+        *self = Self::new();
+    }
+}
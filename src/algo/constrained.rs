@@ -0,0 +1,154 @@
+use alloc::{collections::BinaryHeap, vec::Vec};
+use core::{cmp::Reverse, hash::BuildHasher};
+
+use crate::{
+    edge::EdgeTrait, ghost::GhostToken, id::EdgeId, measure::Total, Allocator, Graph, GraphError,
+    Measure, VertexId,
+};
+
+use super::successors;
+
+/// The cheapest path found by [`resource_constrained_path`], along
+/// with the total cost and resource it uses
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConstrainedPath<'id, Cost, Resource> {
+    /// The total cost of every edge along the path
+    pub cost: Cost,
+    /// The total resource used by every edge along the path
+    pub resource: Resource,
+    /// The vertices along the path, starting with the source
+    pub vertices: Vec<VertexId<'id>>,
+    /// The edges along the path, in the order they're followed
+    pub edges: Vec<EdgeId<'id>>,
+}
+
+struct Label<'id, Cost, Resource> {
+    vertex: VertexId<'id>,
+    cost: Cost,
+    resource: Resource,
+    previous: Option<(usize, EdgeId<'id>)>,
+    dominated: bool,
+}
+
+/// Finds the cheapest path from `source` to `target` whose total
+/// resource use stays within `budget`, such as the shortest route an
+/// electric vehicle can drive without running out of charge
+///
+/// `measure` is given an edge's weight and returns the cost and
+/// resource used by following it, both of which must never be
+/// negative. Partial paths are kept as labels, and a label is
+/// discarded once another label at the same vertex is no more
+/// costly and uses no more of the resource.
+///
+/// Returns `None` if `target` can't be reached within `budget`
+///
+/// # Errors
+/// Returns [`GraphError::VertexNotFound`] if either `source` or
+/// `target` isn't within the graph
+pub fn resource_constrained_path<'id, Item, Weight, Edge, Cost, Resource, S, A: Allocator>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    source: VertexId<'id>,
+    target: VertexId<'id>,
+    budget: Resource,
+    measure: impl Fn(&Weight) -> (Cost, Resource),
+    token: &GhostToken<'id>,
+) -> Result<Option<ConstrainedPath<'id, Cost, Resource>>, GraphError<'id, Item, Weight, Edge>>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    Cost: Measure,
+    Resource: Measure,
+    S: BuildHasher,
+{
+    for id in [source, target] {
+        if graph.get_vertex(id).is_none() {
+            return Err(GraphError::VertexNotFound(id));
+        }
+    }
+
+    let mut labels = alloc::vec![Label {
+        vertex: source,
+        cost: Cost::ZERO,
+        resource: Resource::ZERO,
+        previous: None,
+        dominated: false,
+    }];
+    let mut frontier = graph.vertex_map::<Vec<usize>>();
+    frontier.insert(source, alloc::vec![0]);
+
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((Total(Cost::ZERO), Total(Resource::ZERO), 0)));
+
+    while let Some(Reverse((Total(cost), Total(resource), index))) = queue.pop() {
+        if labels[index].dominated {
+            continue;
+        }
+
+        let vertex = labels[index].vertex;
+        if vertex == target {
+            return Ok(Some(trace(&labels, index)));
+        }
+
+        for (edge, other, weight) in successors(graph, vertex, token) {
+            let (edge_cost, edge_resource) = measure(weight);
+            let cost = cost.saturating_add(edge_cost);
+            let resource = resource.saturating_add(edge_resource);
+
+            if resource > budget {
+                continue;
+            }
+
+            let existing = frontier.get_or_default(other);
+            if existing
+                .iter()
+                .any(|label| labels[*label].cost <= cost && labels[*label].resource <= resource)
+            {
+                continue;
+            }
+
+            existing.retain(|label| {
+                let dominated = cost <= labels[*label].cost && resource <= labels[*label].resource;
+                labels[*label].dominated |= dominated;
+                !dominated
+            });
+
+            existing.push(labels.len());
+            queue.push(Reverse((Total(cost), Total(resource), labels.len())));
+            labels.push(Label {
+                vertex: other,
+                cost,
+                resource,
+                previous: Some((index, edge)),
+                dominated: false,
+            });
+        }
+    }
+
+    Ok(None)
+}
+
+/// Follows the labels back from `index` to the source
+fn trace<'id, Cost: Copy, Resource: Copy>(
+    labels: &[Label<'id, Cost, Resource>],
+    index: usize,
+) -> ConstrainedPath<'id, Cost, Resource> {
+    let mut vertices = alloc::vec![labels[index].vertex];
+    let mut edges = Vec::new();
+    let mut current = index;
+
+    while let Some((previous, edge)) = labels[current].previous {
+        vertices.push(labels[previous].vertex);
+        edges.push(edge);
+        current = previous;
+    }
+
+    vertices.reverse();
+    edges.reverse();
+
+    ConstrainedPath {
+        cost: labels[index].cost,
+        resource: labels[index].resource,
+        vertices,
+        edges,
+    }
+}
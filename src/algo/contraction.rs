@@ -0,0 +1,351 @@
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+use core::{cmp::Reverse, hash::BuildHasher};
+
+use hashbrown::HashMap;
+
+use crate::{
+    edge::EdgeTrait, ghost::GhostToken, id::EdgeId, measure::Total, Allocator, Graph, Measure,
+    SecondaryMap, VertexId,
+};
+
+use super::{successors, ShortestPath};
+
+/// The most vertices a witness search settles before giving up and
+/// adding a shortcut, which is never wrong, only wasteful
+const WITNESS_LIMIT: usize = 256;
+
+/// What an arc of a [`ContractionHierarchy`] stands for
+#[derive(Debug, Clone, Copy)]
+enum Via<'id> {
+    /// An edge of the original graph
+    Edge(EdgeId<'id>),
+    /// A shortcut through the vertex at the given index
+    Shortcut(usize),
+}
+
+/// A graph preprocessed for answering many shortest path queries
+/// quickly, built by [`ContractionHierarchy::build`]
+///
+/// Vertices are contracted one at a time, least important first,
+/// adding shortcut edges to keep the shortest paths between the
+/// vertices that remain. A query then only needs to search upwards
+/// from both of its ends, which on road-like graphs visits a tiny
+/// fraction of the vertices that [`dijkstra`](super::dijkstra)
+/// would.
+///
+/// The hierarchy is a snapshot, so it doesn't see changes made to
+/// the graph afterwards, and doesn't need a [`GhostToken`] to query
+#[derive(Debug, Clone)]
+pub struct ContractionHierarchy<'id, Distance> {
+    ids: Vec<VertexId<'id>>,
+    index: SecondaryMap<VertexId<'id>, usize>,
+    /// The arcs leading from each vertex to a more important one
+    upwards: Vec<Vec<(usize, Distance)>>,
+    /// The arcs leading to each vertex from a more important one
+    downwards: Vec<Vec<(usize, Distance)>>,
+    /// The shortest arc between every pair of vertices joined by one
+    arcs: HashMap<(usize, usize), (Distance, Via<'id>)>,
+    shortcuts: usize,
+}
+
+/// The graph left while vertices are being contracted
+struct Remaining<Distance> {
+    outgoing: Vec<HashMap<usize, Distance>>,
+    incoming: Vec<HashMap<usize, Distance>>,
+}
+
+impl<Distance: Measure> Remaining<Distance> {
+    /// How late `v` should be contracted, given how many of its
+    /// neighbors already have been
+    #[allow(clippy::cast_possible_wrap)]
+    fn priority(
+        &self,
+        v: usize,
+        contracted: usize,
+        distances: &mut HashMap<usize, Distance>,
+    ) -> isize {
+        let removed = self.outgoing[v].len() + self.incoming[v].len();
+        let added = self.shortcuts(v, distances).len();
+        added as isize - removed as isize + contracted as isize
+    }
+    /// The shortcuts needed to keep every shortest path through `v`
+    /// once it's removed
+    fn shortcuts(
+        &self,
+        v: usize,
+        distances: &mut HashMap<usize, Distance>,
+    ) -> Vec<(usize, usize, Distance)> {
+        let mut shortcuts = Vec::new();
+
+        for (&from, &first) in &self.incoming[v] {
+            let Some(longest) = self.outgoing[v]
+                .iter()
+                .filter(|(to, _)| **to != from)
+                .map(|(_, second)| first.saturating_add(*second))
+                .max_by_key(|through| Total(*through))
+            else {
+                continue;
+            };
+
+            self.witness_search(from, v, longest, distances);
+
+            for (&to, &second) in &self.outgoing[v] {
+                let through = first.saturating_add(second);
+                if to != from && distances.get(&to).is_none_or(|witness| *witness > through) {
+                    shortcuts.push((from, to, through));
+                }
+            }
+        }
+
+        shortcuts
+    }
+    /// Finds the shortest distances from `source` up to `limit`
+    /// without passing through `avoid`
+    fn witness_search(
+        &self,
+        source: usize,
+        avoid: usize,
+        limit: Distance,
+        distances: &mut HashMap<usize, Distance>,
+    ) {
+        distances.clear();
+        distances.insert(source, Distance::ZERO);
+
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((Total(Distance::ZERO), source)));
+        let mut settled = 0;
+
+        while let Some(Reverse((Total(distance), vertex))) = queue.pop() {
+            if distances.get(&vertex).is_some_and(|best| *best < distance) {
+                continue;
+            }
+            settled += 1;
+            if distance > limit || settled > WITNESS_LIMIT {
+                break;
+            }
+
+            for (&other, &length) in &self.outgoing[vertex] {
+                let through = distance.saturating_add(length);
+                if other != avoid && distances.get(&other).is_none_or(|best| through < *best) {
+                    distances.insert(other, through);
+                    queue.push(Reverse((Total(through), other)));
+                }
+            }
+        }
+    }
+}
+
+impl<'id, Distance: Measure> ContractionHierarchy<'id, Distance> {
+    /// Builds a contraction hierarchy of `graph`
+    ///
+    /// `length` is given an edge's weight and returns the length of
+    /// the edge, which must never be negative. Building takes far
+    /// longer than a single search, so it's only worth it when many
+    /// queries are made
+    #[must_use]
+    pub fn build<Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>(
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        length: impl Fn(&Weight) -> Distance,
+        token: &GhostToken<'id>,
+    ) -> Self {
+        let mut ids: Vec<_> = graph.vertex_ids().collect();
+        ids.sort_unstable();
+        let index: SecondaryMap<_, _> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        let n = ids.len();
+        let mut remaining = Remaining {
+            outgoing: vec![HashMap::new(); n],
+            incoming: vec![HashMap::new(); n],
+        };
+        let mut arcs: HashMap<(usize, usize), (Distance, Via<'id>)> = HashMap::new();
+
+        for (from, id) in ids.iter().enumerate() {
+            for (edge, other, weight) in successors(graph, *id, token) {
+                let (to, length) = (index[other], length(weight));
+                if arcs.get(&(from, to)).is_none_or(|(best, _)| length < *best) {
+                    arcs.insert((from, to), (length, Via::Edge(edge)));
+                    remaining.outgoing[from].insert(to, length);
+                    remaining.incoming[to].insert(from, length);
+                }
+            }
+        }
+
+        // Vertices are contracted in order of their edge difference,
+        // the number of shortcuts contracting them would add less the
+        // arcs it would remove, plus how many neighbors have already
+        // been contracted to spread contraction evenly
+        let mut distances = HashMap::new();
+        let mut contracted_neighbors = vec![0; n];
+
+        let mut queue: BinaryHeap<_> = (0..n)
+            .map(|v| Reverse((remaining.priority(v, 0, &mut distances), v)))
+            .collect();
+        let mut rank = vec![usize::MAX; n];
+        let mut next_rank = 0;
+        let mut shortcuts = 0;
+
+        while let Some(Reverse((_, v))) = queue.pop() {
+            if rank[v] != usize::MAX {
+                continue;
+            }
+
+            // Priorities are only updated lazily, so a vertex whose
+            // priority has grown goes back in the queue
+            let priority = remaining.priority(v, contracted_neighbors[v], &mut distances);
+            if queue
+                .peek()
+                .is_some_and(|Reverse((next, _))| priority > *next)
+            {
+                queue.push(Reverse((priority, v)));
+                continue;
+            }
+
+            for (from, to, length) in remaining.shortcuts(v, &mut distances) {
+                if remaining.outgoing[from]
+                    .get(&to)
+                    .is_none_or(|best| length < *best)
+                {
+                    remaining.outgoing[from].insert(to, length);
+                    remaining.incoming[to].insert(from, length);
+                    arcs.insert((from, to), (length, Via::Shortcut(v)));
+                    shortcuts += 1;
+                }
+            }
+
+            let (outgoing, incoming) = (
+                core::mem::take(&mut remaining.outgoing[v]),
+                core::mem::take(&mut remaining.incoming[v]),
+            );
+            for &to in outgoing.keys() {
+                remaining.incoming[to].remove(&v);
+                contracted_neighbors[to] += 1;
+            }
+            for &from in incoming.keys() {
+                remaining.outgoing[from].remove(&v);
+                contracted_neighbors[from] += 1;
+            }
+
+            rank[v] = next_rank;
+            next_rank += 1;
+        }
+
+        let mut upwards = vec![Vec::new(); n];
+        let mut downwards = vec![Vec::new(); n];
+        for (&(from, to), &(length, _)) in &arcs {
+            if rank[from] < rank[to] {
+                upwards[from].push((to, length));
+            } else {
+                downwards[to].push((from, length));
+            }
+        }
+
+        Self {
+            ids,
+            index,
+            upwards,
+            downwards,
+            arcs,
+            shortcuts,
+        }
+    }
+    /// The number of shortcut edges added while building the
+    /// hierarchy
+    #[must_use]
+    pub const fn shortcut_len(&self) -> usize {
+        self.shortcuts
+    }
+    /// Finds the shortest path from `from` to `to`, or `None` if
+    /// there's no such path or either isn't within the hierarchy
+    #[must_use]
+    pub fn query(
+        &self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+    ) -> Option<ShortestPath<'id, Distance>> {
+        let (source, target) = (*self.index.get(from)?, *self.index.get(to)?);
+
+        let forwards = Self::search(&self.upwards, source);
+        let backwards = Self::search(&self.downwards, target);
+
+        let (length, meeting) = forwards
+            .iter()
+            .filter_map(|(v, (distance, _))| {
+                backwards
+                    .get(v)
+                    .map(|(other, _)| (distance.saturating_add(*other), *v))
+            })
+            .min_by_key(|(length, meeting)| (Total(*length), *meeting))?;
+
+        let mut hops = Vec::new();
+        let mut v = meeting;
+        while let Some((_, Some(previous))) = forwards.get(&v) {
+            hops.push((*previous, v));
+            v = *previous;
+        }
+        hops.reverse();
+        let mut v = meeting;
+        while let Some((_, Some(next))) = backwards.get(&v) {
+            hops.push((v, *next));
+            v = *next;
+        }
+
+        let mut path = ShortestPath {
+            length,
+            vertices: vec![from],
+            edges: Vec::new(),
+        };
+        for hop in hops {
+            self.unpack(hop, &mut path);
+        }
+
+        Some(path)
+    }
+    /// Searches `arcs` from `start`, returning the distance to every
+    /// vertex reached along with the vertex it was reached from
+    fn search(
+        arcs: &[Vec<(usize, Distance)>],
+        start: usize,
+    ) -> HashMap<usize, (Distance, Option<usize>)> {
+        let mut reached = HashMap::new();
+        reached.insert(start, (Distance::ZERO, None));
+
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((Total(Distance::ZERO), start)));
+
+        while let Some(Reverse((Total(distance), vertex))) = queue.pop() {
+            if reached
+                .get(&vertex)
+                .is_some_and(|(best, _)| *best < distance)
+            {
+                continue;
+            }
+
+            for &(other, length) in &arcs[vertex] {
+                let through = distance.saturating_add(length);
+                if reached.get(&other).is_none_or(|(best, _)| through < *best) {
+                    reached.insert(other, (through, Some(vertex)));
+                    queue.push(Reverse((Total(through), other)));
+                }
+            }
+        }
+
+        reached
+    }
+    /// Appends the edges that the arc `hop` stands for to `path`
+    fn unpack(&self, hop: (usize, usize), path: &mut ShortestPath<'id, Distance>) {
+        let mut stack = vec![hop];
+
+        while let Some((from, to)) = stack.pop() {
+            match self.arcs[&(from, to)].1 {
+                Via::Edge(edge) => {
+                    path.edges.push(edge);
+                    path.vertices.push(self.ids[to]);
+                }
+                Via::Shortcut(middle) => {
+                    stack.push((middle, to));
+                    stack.push((from, middle));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use core::hash::BuildHasher;
+
+use hashbrown::HashMap;
+
+use crate::{
+    edge::{DirectedWeightedEdge, EdgeTrait, Flow},
+    ghost::GhostToken,
+    id::EdgeId,
+    Allocator, Graph, GraphError, Measure, SecondaryMap, VertexId,
+};
+
+use super::neighbors;
+
+type FlowResult<'id, Item, Capacity, T> = Result<
+    T,
+    GraphError<'id, Item, Flow<Capacity>, DirectedWeightedEdge<'id, Item, Flow<Capacity>>>,
+>;
+
+/// A cut splitting the vertices of a graph in two, found by
+/// [`Graph::min_cut`] or [`Graph::global_min_cut`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MinCut<'id, Capacity> {
+    /// The total capacity of the edges crossing the cut
+    pub value: Capacity,
+    /// The edges crossing the cut, ordered by id
+    pub edges: Vec<EdgeId<'id>>,
+    /// The vertices on the side of the source, ordered by id
+    pub source_side: Vec<VertexId<'id>>,
+    /// The vertices on the side of the sink, ordered by id
+    pub sink_side: Vec<VertexId<'id>>,
+}
+
+impl<'id, Item, Capacity: Measure, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Flow<Capacity>, DirectedWeightedEdge<'id, Item, Flow<Capacity>>, S, A>
+{
+    /// Sends as much as can be sent from `source` to `sink`, on top
+    /// of whatever already flows through the network, using the
+    /// Edmonds–Karp algorithm, returning how much more was sent
+    ///
+    /// Each step sends what it can along a shortest path with
+    /// [residual capacity](Graph::residual_capacity) left, until
+    /// there are none left, at which point the flow is a maximum
+    /// flow. Use [`Graph::clear_flow`] first to find the maximum flow
+    /// from nothing
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if either `source` or
+    /// `sink` isn't within the graph, or
+    /// [`GraphError::IdenticalVertex`] if they're the same vertex
+    pub fn max_flow(
+        &mut self,
+        source: VertexId<'id>,
+        sink: VertexId<'id>,
+        token: &mut GhostToken<'id>,
+    ) -> FlowResult<'id, Item, Capacity, Capacity> {
+        self.check_terminals(source, sink)?;
+
+        let mut total = Capacity::ZERO;
+        loop {
+            let previous = self.residual_search(source, token);
+            if !previous.contains_key(sink) {
+                return Ok(total);
+            }
+
+            let mut path = vec![sink];
+            while let Some(before) = previous.get(path[path.len() - 1]) {
+                path.push(*before);
+            }
+            path.reverse();
+
+            let mut amount = Capacity::MAX;
+            for pair in path.windows(2) {
+                let residual = self.residual_capacity(pair[0], pair[1], token);
+                if residual < amount {
+                    amount = residual;
+                }
+            }
+            for pair in path.windows(2) {
+                self.augment(pair[0], pair[1], amount, token);
+            }
+            total = total.saturating_add(amount);
+        }
+    }
+    /// Finds a minimum cut between `source` and `sink`, the edges with
+    /// the least total capacity which, if removed, would leave no path
+    /// from `source` to `sink`
+    ///
+    /// Runs [`Graph::max_flow`] first, leaving a maximum flow within
+    /// the network. The source side is every vertex `source` can still
+    /// send more to, and the cut is every edge leading from the source
+    /// side to the sink side, each of which is saturated, so its value
+    /// is the same as the maximum flow
+    ///
+    /// ```rust
+    /// use graph::{
+    ///     edge::{Flow, FlowEdge},
+    ///     ghost::GhostToken,
+    ///     Graph,
+    /// };
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), Flow<u32>, FlowEdge<_, _>> = Graph::new();
+    ///     let s = graph.add_vertex(());
+    ///     let a = graph.add_vertex(());
+    ///     let t = graph.add_vertex(());
+    ///     graph.add_edge(s, a, Flow::new(5), &mut token).unwrap();
+    ///     let narrow = graph.add_edge(a, t, Flow::new(2), &mut token).unwrap();
+    ///
+    ///     let cut = graph.min_cut(s, t, &mut token).unwrap();
+    ///     assert_eq!(cut.value, 2);
+    ///     assert_eq!(cut.edges, vec![narrow]);
+    ///     assert_eq!(cut.source_side, vec![s, a]);
+    ///     assert_eq!(cut.sink_side, vec![t]);
+    /// })
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if either `source` or
+    /// `sink` isn't within the graph, or
+    /// [`GraphError::IdenticalVertex`] if they're the same vertex
+    pub fn min_cut(
+        &mut self,
+        source: VertexId<'id>,
+        sink: VertexId<'id>,
+        token: &mut GhostToken<'id>,
+    ) -> FlowResult<'id, Item, Capacity, MinCut<'id, Capacity>> {
+        self.max_flow(source, sink, token)?;
+
+        let previous = self.residual_search(source, token);
+        let on_source_side = |id| id == source || previous.contains_key(id);
+
+        let mut value = Capacity::ZERO;
+        let mut edges = Vec::new();
+        for (e_id, from, to, flow) in self.unique_edges(token) {
+            if on_source_side(from) && !on_source_side(to) {
+                value = value.saturating_add(flow.capacity);
+                edges.push(e_id);
+            }
+        }
+        edges.sort_unstable();
+
+        let (mut source_side, mut sink_side): (Vec<_>, Vec<_>) =
+            self.vertex_ids().partition(|id| on_source_side(*id));
+        source_side.sort_unstable();
+        sink_side.sort_unstable();
+
+        Ok(MinCut {
+            value,
+            edges,
+            source_side,
+            sink_side,
+        })
+    }
+    fn check_terminals(
+        &self,
+        source: VertexId<'id>,
+        sink: VertexId<'id>,
+    ) -> FlowResult<'id, Item, Capacity, ()> {
+        for id in [source, sink] {
+            if self.get_vertex(id).is_none() {
+                return Err(GraphError::VertexNotFound(id));
+            }
+        }
+        if source == sink {
+            return Err(GraphError::IdenticalVertex(source));
+        }
+        Ok(())
+    }
+    /// Searches breadth first from `source` through the residual
+    /// network, returning the vertex each vertex reached was reached
+    /// from
+    fn residual_search(
+        &self,
+        source: VertexId<'id>,
+        token: &GhostToken<'id>,
+    ) -> SecondaryMap<VertexId<'id>, VertexId<'id>> {
+        let mut previous = self.vertex_map();
+        let mut queue = VecDeque::from(vec![source]);
+
+        while let Some(vertex) = queue.pop_front() {
+            let mut others: Vec<_> = neighbors(self, vertex, token)
+                .into_iter()
+                .map(|(_, other)| other)
+                .collect();
+            others.sort_unstable();
+
+            for other in others {
+                if other != source
+                    && !previous.contains_key(other)
+                    && self.residual_capacity(vertex, other, token) > Capacity::ZERO
+                {
+                    previous.insert(other, vertex);
+                    queue.push_back(other);
+                }
+            }
+        }
+
+        previous
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Finds a global minimum cut of the graph, the edges with the
+    /// least total weight which, if removed, would split the graph in
+    /// two, using the Stoer–Wagner algorithm
+    ///
+    /// `weight` is given an edge's weight and returns its capacity,
+    /// which must never be negative. Every edge is treated as
+    /// undirected, and self-loops are left out. The source side is the
+    /// side holding the vertex with the lowest id. A graph which is
+    /// already in pieces has a cut with a value of zero. Returns
+    /// `None` if the graph has fewer than two vertices
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+    ///     // Two strongly joined pairs, weakly joined to each other
+    ///     graph.add_edge(v[0], v[1], 10, &mut token).unwrap();
+    ///     graph.add_edge(v[2], v[3], 10, &mut token).unwrap();
+    ///     let one = graph.add_edge(v[1], v[2], 1, &mut token).unwrap();
+    ///     let two = graph.add_edge(v[3], v[0], 2, &mut token).unwrap();
+    ///
+    ///     let cut = graph.global_min_cut(|weight| *weight, &token).unwrap();
+    ///     assert_eq!(cut.value, 3);
+    ///     assert_eq!(cut.edges, vec![one, two]);
+    ///     assert_eq!(cut.source_side, vec![v[0], v[1]]);
+    /// })
+    /// ```
+    #[must_use]
+    pub fn global_min_cut<Capacity: Measure>(
+        &self,
+        weight: impl Fn(&Weight) -> Capacity,
+        token: &GhostToken<'id>,
+    ) -> Option<MinCut<'id, Capacity>> {
+        let mut ids: Vec<_> = self.vertex_ids().collect();
+        ids.sort_unstable();
+        if ids.len() < 2 {
+            return None;
+        }
+        let index: SecondaryMap<_, _> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        // Parallel edges between two vertices are joined into one
+        let mut adjacent: Vec<HashMap<usize, Capacity>> = vec![HashMap::new(); ids.len()];
+        for (_, from, to, edge_weight) in self.unique_edges(token) {
+            let (from, to) = (index[from], index[to]);
+            if from != to {
+                let capacity = weight(edge_weight);
+                for (one, two) in [(from, to), (to, from)] {
+                    let total = adjacent[one].entry(two).or_insert(Capacity::ZERO);
+                    *total = total.saturating_add(capacity);
+                }
+            }
+        }
+
+        let (_, side) = stoer_wagner(adjacent);
+        let mut on_side = vec![false; ids.len()];
+        for i in side {
+            on_side[i] = true;
+        }
+        // The side holding the lowest id is the source side
+        let source_side_is = !on_side[0];
+
+        let mut value = Capacity::ZERO;
+        let mut edges = Vec::new();
+        for (e_id, from, to, edge_weight) in self.unique_edges(token) {
+            if on_side[index[from]] != on_side[index[to]] {
+                value = value.saturating_add(weight(edge_weight));
+                edges.push(e_id);
+            }
+        }
+        edges.sort_unstable();
+
+        let (source_side, sink_side) = ids
+            .iter()
+            .partition(|id| on_side[index[**id]] != source_side_is);
+
+        Some(MinCut {
+            value,
+            edges,
+            source_side,
+            sink_side,
+        })
+    }
+}
+
+/// The value of the lightest cut of the graph with the weights
+/// between vertices in `adjacent`, and the vertices on one side of it
+fn stoer_wagner<Capacity: Measure>(
+    mut adjacent: Vec<HashMap<usize, Capacity>>,
+) -> (Capacity, Vec<usize>) {
+    let n = adjacent.len();
+    // The original vertices merged into each vertex
+    let mut merged: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+    let mut active: Vec<usize> = (0..n).collect();
+    let mut best: Option<(Capacity, Vec<usize>)> = None;
+
+    while active.len() > 1 {
+        // Orders the vertices by how strongly each is joined to those
+        // before it, the last two being the most tightly joined
+        let mut added = vec![false; n];
+        let mut joined = vec![Capacity::ZERO; n];
+        let mut order = Vec::with_capacity(active.len());
+
+        for _ in 0..active.len() {
+            let mut next = None;
+            for &v in &active {
+                if !added[v] && next.is_none_or(|u: usize| joined[v] > joined[u]) {
+                    next = Some(v);
+                }
+            }
+            let Some(next) = next else {
+                break;
+            };
+
+            added[next] = true;
+            order.push(next);
+            for (other, capacity) in &adjacent[next] {
+                if !added[*other] {
+                    joined[*other] = joined[*other].saturating_add(*capacity);
+                }
+            }
+        }
+
+        let last = order[order.len() - 1];
+        let before = order[order.len() - 2];
+        let cut = joined[last];
+        if best.as_ref().is_none_or(|(value, _)| cut < *value) {
+            best = Some((cut, merged[last].clone()));
+        }
+
+        // Merges the last vertex into the one before it
+        let edges: Vec<_> = adjacent[last].drain().collect();
+        for (other, capacity) in edges {
+            adjacent[other].remove(&last);
+            if other != before {
+                for (one, two) in [(before, other), (other, before)] {
+                    let total = adjacent[one].entry(two).or_insert(Capacity::ZERO);
+                    *total = total.saturating_add(capacity);
+                }
+            }
+        }
+        let moved = core::mem::take(&mut merged[last]);
+        merged[before].extend(moved);
+        active.retain(|v| *v != last);
+    }
+
+    best.unwrap_or((Capacity::ZERO, Vec::new()))
+}
@@ -0,0 +1,93 @@
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+use crate::{edge::EdgeTrait, Allocator, Graph};
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// The number of vertices with each degree, where the count at
+    /// index `d` is the number of vertices with a [`Graph::degree`]
+    /// of `d`, up to the highest degree in the graph
+    ///
+    /// The histogram is empty if the graph is. As degrees are kept up
+    /// to date as the graph changes, no [`GhostToken`] is needed
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let hub = graph.add_vertex(());
+    ///     for _ in 0..3 {
+    ///         let leaf = graph.add_vertex(());
+    ///         graph.add_edge(hub, leaf, (), &mut token).unwrap();
+    ///     }
+    ///     graph.add_vertex(());
+    ///
+    ///     assert_eq!(graph.degree_histogram(), vec![1, 3, 0, 1]);
+    /// })
+    /// ```
+    ///
+    /// [`GhostToken`]: crate::ghost::GhostToken
+    #[must_use]
+    pub fn degree_histogram(&self) -> Vec<usize> {
+        let degrees: Vec<_> = self.vertex_ids().filter_map(|id| self.degree(id)).collect();
+        let Some(highest) = degrees.iter().max() else {
+            return Vec::new();
+        };
+
+        let mut histogram = vec![0; highest + 1];
+        for degree in degrees {
+            histogram[degree] += 1;
+        }
+        histogram
+    }
+    /// An estimate of the exponent `α` of a power law, `p(d) ∝ d^-α`,
+    /// fitted to the degrees of the vertices with a degree of at least
+    /// `min_degree`, which must be at least `1`
+    ///
+    /// Uses the discrete maximum likelihood approximation of Clauset,
+    /// Shalizi and Newman, `1 + n / Σ ln(d / (min_degree - 0.5))`,
+    /// which is only a good fit when the degrees really do follow a
+    /// power law from `min_degree` upwards. Returns `None` if
+    /// `min_degree` is `0`, or if fewer than two vertices have a high
+    /// enough degree to fit
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let a = graph.add_vertex(());
+    ///     let b = graph.add_vertex(());
+    ///     graph.add_edge(a, b, (), &mut token).unwrap();
+    ///
+    ///     // ln(1 / 0.5) summed over both vertices
+    ///     let alpha = graph.power_law_exponent(1).unwrap();
+    ///     assert!((alpha - (1.0 + 1.0 / 2f64.ln())).abs() < 1e-12);
+    ///     assert_eq!(graph.power_law_exponent(2), None);
+    /// })
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn power_law_exponent(&self, min_degree: usize) -> Option<f64> {
+        if min_degree == 0 {
+            return None;
+        }
+        let shift = min_degree as f64 - 0.5;
+
+        let (count, sum) = self
+            .vertex_ids()
+            .filter_map(|id| self.degree(id))
+            .filter(|degree| *degree >= min_degree)
+            .fold((0_usize, 0.0), |(count, sum), degree| {
+                (count + 1, sum + libm::log(degree as f64 / shift))
+            });
+
+        if count < 2 {
+            return None;
+        }
+        Some(1.0 + count as f64 / sum)
+    }
+}
@@ -0,0 +1,272 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use crate::{
+    edge::EdgeTrait, ghost::GhostToken, id::EdgeId, Allocator, Graph, GraphError, SecondaryMap,
+    VertexId,
+};
+
+use super::{neighbors, successors};
+
+/// A depth-first search tree, treating every edge as undirected,
+/// along with the discovery index and low-link value of every
+/// vertex reached
+///
+/// The low-link of a vertex is the smallest discovery index
+/// reachable from its subtree using at most one non-tree edge,
+/// which is the basis of most connectivity algorithms
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DfsTree<'id> {
+    order: Vec<VertexId<'id>>,
+    index: SecondaryMap<VertexId<'id>, usize>,
+    parent: SecondaryMap<VertexId<'id>, (VertexId<'id>, EdgeId<'id>)>,
+    low: SecondaryMap<VertexId<'id>, usize>,
+}
+
+impl<'id> DfsTree<'id> {
+    /// Builds the [`DfsTree`] of the component containing `root`
+    ///
+    /// If `root` isn't within the graph, the tree will be empty
+    #[must_use]
+    pub fn new<Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>(
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        root: VertexId<'id>,
+        token: &GhostToken<'id>,
+    ) -> Self {
+        let mut tree = Self {
+            order: Vec::new(),
+            index: graph.vertex_map(),
+            parent: graph.vertex_map(),
+            low: graph.vertex_map(),
+        };
+
+        if graph.get_vertex(root).is_some() {
+            tree.search(graph, root, token);
+        }
+
+        tree
+    }
+    /// Builds a [`DfsTree`] for every component of the graph,
+    /// with each component's root having no parent
+    #[must_use]
+    pub fn forest<
+        Item,
+        Weight,
+        Edge: EdgeTrait<'id, Item, Weight>,
+        S: BuildHasher,
+        A: Allocator,
+    >(
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        token: &GhostToken<'id>,
+    ) -> Self {
+        let mut tree = Self {
+            order: Vec::with_capacity(graph.vertex_len()),
+            index: graph.vertex_map(),
+            parent: graph.vertex_map(),
+            low: graph.vertex_map(),
+        };
+
+        for id in graph.get_all_vertices() {
+            if !tree.index.contains_key(*id) {
+                tree.search(graph, *id, token);
+            }
+        }
+
+        tree
+    }
+    fn search<Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>(
+        &mut self,
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        root: VertexId<'id>,
+        token: &GhostToken<'id>,
+    ) {
+        self.visit(root);
+
+        let mut stack = alloc::vec![(root, neighbors(graph, root, token), 0)];
+
+        while let Some((vertex, edges, position)) = stack.last_mut() {
+            let vertex = *vertex;
+
+            if let Some(&(e_id, other)) = edges.get(*position) {
+                *position += 1;
+
+                if let Some(&other_index) = self.index.get(other) {
+                    // A non-tree edge, ignoring the edge to the parent
+                    if self.parent.get(vertex).map(|(_, e)| *e) != Some(e_id) {
+                        if let Some(low) = self.low.get_mut(vertex) {
+                            *low = (*low).min(other_index);
+                        }
+                    }
+                } else {
+                    self.visit(other);
+                    self.parent.insert(other, (vertex, e_id));
+                    stack.push((other, neighbors(graph, other, token), 0));
+                }
+            } else {
+                stack.pop();
+
+                if let Some((parent, _)) = self.parent.get(vertex) {
+                    let child_low = self.low[vertex];
+                    if let Some(low) = self.low.get_mut(*parent) {
+                        *low = (*low).min(child_low);
+                    }
+                }
+            }
+        }
+    }
+    fn visit(&mut self, vertex: VertexId<'id>) {
+        let index = self.order.len();
+        self.order.push(vertex);
+        self.index.insert(vertex, index);
+        self.low.insert(vertex, index);
+    }
+    /// The vertices in the order they were discovered
+    #[must_use]
+    pub fn order(&self) -> &[VertexId<'id>] {
+        &self.order
+    }
+    /// The discovery index of `vertex`, or `None` if
+    /// it wasn't reached
+    #[must_use]
+    pub fn index(&self, vertex: VertexId<'id>) -> Option<usize> {
+        self.index.get(vertex).copied()
+    }
+    /// The low-link value of `vertex`, or `None` if
+    /// it wasn't reached
+    #[must_use]
+    pub fn low(&self, vertex: VertexId<'id>) -> Option<usize> {
+        self.low.get(vertex).copied()
+    }
+    /// The parent of `vertex` within the tree, and the
+    /// edge connecting them. Returns `None` for roots, and
+    /// vertices that weren't reached
+    #[must_use]
+    pub fn parent(&self, vertex: VertexId<'id>) -> Option<(VertexId<'id>, EdgeId<'id>)> {
+        self.parent.get(vertex).copied()
+    }
+    /// The number of vertices within the tree
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.order.len()
+    }
+    /// Whether the tree is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+}
+
+/// The class of an edge within a depth-first search, found by
+/// [`Graph::classify_edges`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EdgeClass {
+    /// An edge the search followed to reach a new vertex
+    Tree,
+    /// An edge leading back to a vertex on the current path of the
+    /// search, so which closes a cycle, including self-loops
+    Back,
+    /// An edge leading to a vertex already reached from the vertex
+    /// it leads from, further down its subtree
+    Forward,
+    /// An edge leading to a vertex in a subtree the search had
+    /// already finished
+    Cross,
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Searches the graph depth first from each of `roots` in turn,
+    /// following the direction of directed edges, and returns the
+    /// [`EdgeClass`] of every edge the search came across
+    ///
+    /// Each root already reached from an earlier root is skipped.
+    /// Edges are followed in order of the [`VertexId`] they lead to,
+    /// then of their [`EdgeId`], and edges that can't be reached from
+    /// any root are left out. Undirected edges are classified the
+    /// first time they're seen, from either end, so are only ever
+    /// [`EdgeClass::Tree`] or [`EdgeClass::Back`]
+    ///
+    /// In a control-flow graph searched from its entry, the back
+    /// edges are those which leave a loop to return to its start
+    ///
+    /// ```rust
+    /// use graph::{algo::EdgeClass, edge::DirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let entry = graph.add_vertex(());
+    ///     let body = graph.add_vertex(());
+    ///     let exit = graph.add_vertex(());
+    ///     graph.add_edge(entry, body, (), &mut token).unwrap();
+    ///     let repeat = graph.add_edge(body, entry, (), &mut token).unwrap();
+    ///     let skip = graph.add_edge(entry, exit, (), &mut token).unwrap();
+    ///
+    ///     let kinds = graph.classify_edges([entry], &token).unwrap();
+    ///     assert_eq!(kinds[repeat], EdgeClass::Back);
+    ///     assert_eq!(kinds[skip], EdgeClass::Tree);
+    /// })
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if any of `roots` isn't
+    /// within the graph
+    pub fn classify_edges(
+        &self,
+        roots: impl IntoIterator<Item = VertexId<'id>>,
+        token: &GhostToken<'id>,
+    ) -> Result<SecondaryMap<EdgeId<'id>, EdgeClass>, GraphError<'id, Item, Weight, Edge>> {
+        let mut kinds = self.edge_map();
+        let mut discovered = self.vertex_map();
+        let mut finished = self.vertex_set();
+
+        let followed = |vertex| {
+            let mut edges: Vec<_> = successors(self, vertex, token)
+                .map(|(e_id, other, _)| (other, e_id))
+                .collect();
+            edges.sort_unstable();
+            edges
+        };
+
+        for root in roots {
+            if self.get_vertex(root).is_none() {
+                return Err(GraphError::VertexNotFound(root));
+            }
+            if discovered.contains_key(root) {
+                continue;
+            }
+
+            discovered.insert(root, discovered.len());
+            let mut stack = alloc::vec![(root, followed(root), 0)];
+
+            while let Some((vertex, edges, position)) = stack.last_mut() {
+                let vertex = *vertex;
+                let Some(&(other, e_id)) = edges.get(*position) else {
+                    finished.insert(vertex);
+                    stack.pop();
+                    continue;
+                };
+                *position += 1;
+
+                if kinds.contains_key(e_id) {
+                    continue;
+                }
+                let kind = match discovered.get(other) {
+                    None => {
+                        discovered.insert(other, discovered.len());
+                        stack.push((other, followed(other), 0));
+                        EdgeClass::Tree
+                    }
+                    Some(_) if !finished.contains(other) => EdgeClass::Back,
+                    Some(index) if discovered[vertex] < *index => EdgeClass::Forward,
+                    Some(_) => EdgeClass::Cross,
+                };
+                kinds.insert(e_id, kind);
+            }
+        }
+
+        Ok(kinds)
+    }
+}
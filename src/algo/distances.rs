@@ -0,0 +1,70 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use hashbrown::HashMap;
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, Allocator, Graph, GraphError, Measure, VertexId};
+
+use super::{dijkstra, ShortestPaths, TraversalOptions};
+
+type Distances<'id, Item, Weight, Edge, Distance> =
+    Result<HashMap<VertexId<'id>, Distance>, GraphError<'id, Item, Weight, Edge>>;
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// The shortest distance from `source` to every vertex it can
+    /// reach, including itself, taking each edge's weight as its
+    /// length, which must never be negative
+    ///
+    /// For graphs whose edges have no weights, see
+    /// [`Graph::hops_from`]
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if `source` isn't
+    /// within the graph
+    pub fn distances_from(
+        &self,
+        source: VertexId<'id>,
+        token: &GhostToken<'id>,
+    ) -> Distances<'id, Item, Weight, Edge, Weight>
+    where
+        Weight: Measure,
+    {
+        dijkstra(self, source, |weight| *weight, token).map(ShortestPaths::into_distances)
+    }
+    /// The fewest edges that need following to get from `source` to
+    /// every vertex it can reach, including itself
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if `source` isn't
+    /// within the graph
+    pub fn hops_from(
+        &self,
+        source: VertexId<'id>,
+        token: &GhostToken<'id>,
+    ) -> Distances<'id, Item, Weight, Edge, usize> {
+        let options = TraversalOptions::new();
+        let mut bfs = self.bfs_iter(source, &options, token)?;
+
+        let mut hops = HashMap::new();
+        while let Some(id) = bfs.next() {
+            hops.insert(id, bfs.depth());
+        }
+        Ok(hops)
+    }
+    /// Splits the vertices `source` can reach by how many edges need
+    /// following to get to them, so that the `i`th layer holds the
+    /// vertices `i` edges away, ordered by id
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if `source` isn't
+    /// within the graph
+    pub fn bfs_layers(
+        &self,
+        source: VertexId<'id>,
+        token: &GhostToken<'id>,
+    ) -> Result<Vec<Vec<VertexId<'id>>>, GraphError<'id, Item, Weight, Edge>> {
+        self.bfs_layers_with(source, &TraversalOptions::new(), token)
+    }
+}
@@ -0,0 +1,127 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use hashbrown::HashSet;
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, id::EdgeId, Allocator, Graph, VertexId};
+
+use super::{neighbors, DfsTree};
+
+/// A single ear within an ear decomposition, a path whose
+/// endpoints are within the previous ears, or the initial cycle
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ear<'id> {
+    /// The vertices along the ear, in order. For a cycle the
+    /// first and last vertices are the same
+    pub vertices: Vec<VertexId<'id>>,
+    /// The edges along the ear, with `edges[i]` connecting
+    /// `vertices[i]` and `vertices[i + 1]`
+    pub edges: Vec<EdgeId<'id>>,
+}
+
+impl<'id> Ear<'id> {
+    /// Whether the ear starts and ends at the same vertex
+    #[must_use]
+    pub fn is_cycle(&self) -> bool {
+        self.vertices.first() == self.vertices.last()
+    }
+}
+
+/// Computes an ear decomposition of the graph using Schmidt's
+/// chain decomposition, treating every edge as undirected
+///
+/// The first ear is always a cycle, and every edge of the graph
+/// appears in exactly one ear
+///
+/// # Errors
+/// Returns `None` if the graph isn't 2-edge-connected
+#[must_use]
+pub fn ear_decomposition<
+    'id,
+    Item,
+    Weight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    token: &GhostToken<'id>,
+) -> Option<Vec<Ear<'id>>> {
+    let root = match graph.get_all_vertices().next() {
+        Some(root) => *root,
+        None => return Some(Vec::new()),
+    };
+
+    let tree = DfsTree::new(graph, root, token);
+
+    if tree.len() != graph.vertex_len() {
+        return None;
+    }
+
+    let mut visited = HashSet::with_capacity(graph.vertex_len());
+    let mut ears = Vec::new();
+    let mut covered = 0;
+
+    for &vertex in tree.order() {
+        let index = tree.index(vertex)?;
+
+        for (e_id, other) in neighbors(graph, vertex, token) {
+            // Only follow back edges downwards from their ancestor
+            let is_tree_edge = tree.parent(other) == Some((vertex, e_id));
+            if is_tree_edge || tree.index(other)? < index {
+                continue;
+            }
+
+            visited.insert(vertex);
+
+            let mut ear = Ear {
+                vertices: alloc::vec![vertex, other],
+                edges: alloc::vec![e_id],
+            };
+
+            let mut current = other;
+            while visited.insert(current) {
+                let (parent, edge) = tree.parent(current)?;
+                ear.vertices.push(parent);
+                ear.edges.push(edge);
+                current = parent;
+            }
+
+            covered += ear.edges.len();
+            ears.push(ear);
+        }
+    }
+
+    if covered == graph.edge_len() {
+        Some(ears)
+    } else {
+        None
+    }
+}
+
+/// Computes an open ear decomposition of the graph, in which
+/// only the first ear is a cycle
+///
+/// # Errors
+/// Returns `None` if the graph isn't biconnected
+#[must_use]
+pub fn open_ear_decomposition<
+    'id,
+    Item,
+    Weight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    token: &GhostToken<'id>,
+) -> Option<Vec<Ear<'id>>> {
+    let ears = ear_decomposition(graph, token)?;
+
+    if ears.iter().skip(1).any(Ear::is_cycle) {
+        None
+    } else {
+        Some(ears)
+    }
+}
@@ -0,0 +1,81 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use crate::{
+    algo::TraversalOptions, edge::EdgeTrait, ghost::GhostToken, Allocator, Graph, GraphError,
+    VertexId,
+};
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Constructs the ego network of `center`, the subgraph induced by
+    /// the vertices at most `hops` edges away from it, following the
+    /// direction of directed edges as [`Graph::bfs`] does
+    ///
+    /// The ego network holds a copy of each of those vertices, and of
+    /// every edge between two of them, not only the edges followed to
+    /// reach them, under the same ids as in the graph
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<&str, (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let alice = graph.add_vertex("alice");
+    ///     let bob = graph.add_vertex("bob");
+    ///     let carol = graph.add_vertex("carol");
+    ///     let dave = graph.add_vertex("dave");
+    ///     graph.add_edge(alice, bob, (), &mut token).unwrap();
+    ///     graph.add_edge(alice, carol, (), &mut token).unwrap();
+    ///     let friends = graph.add_edge(bob, carol, (), &mut token).unwrap();
+    ///     graph.add_edge(carol, dave, (), &mut token).unwrap();
+    ///
+    ///     let ego = graph.ego_network(alice, 1, &mut token).unwrap();
+    ///     assert_eq!(ego.vertex_len(), 3);
+    ///     assert!(ego.get_vertex(dave).is_none());
+    ///     assert!(ego.get_edge(friends).is_some());
+    /// })
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if `center` isn't within
+    /// the graph, or a [`GraphError`] if an edge can't be added to the
+    /// ego network
+    pub fn ego_network(
+        &self,
+        center: VertexId<'id>,
+        hops: usize,
+        token: &mut GhostToken<'id>,
+    ) -> Result<Self, GraphError<'id, Item, Weight, Edge>>
+    where
+        Item: Clone,
+        Weight: Clone,
+        S: Clone + Default,
+        A: Clone + Default,
+    {
+        let options = TraversalOptions::new().max_depth(hops);
+        let mut within = self.vertex_set();
+        within.extend(self.bfs_iter(center, &options, token)?);
+
+        let mut ego =
+            Self::with_capacity_and_hasher_in(within.len(), 0, S::default(), A::default());
+        for id in within.iter() {
+            if let Some(vertex) = self.get_vertex(id) {
+                ego.insert_vertex(id, vertex.borrow(token).get_item().clone());
+            }
+        }
+
+        let mut edges: Vec<_> = self
+            .unique_edges(token)
+            .filter(|(_, from, to, _)| within.contains(*from) && within.contains(*to))
+            .map(|(e_id, from, to, weight)| (e_id, from, to, weight.clone()))
+            .collect();
+        edges.sort_unstable_by_key(|(e_id, ..)| *e_id);
+        for (e_id, from, to, weight) in edges {
+            ego.insert_edge(e_id, from, to, weight, token)?;
+        }
+
+        Ok(ego)
+    }
+}
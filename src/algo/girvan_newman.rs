@@ -0,0 +1,297 @@
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+use core::{cmp::Reverse, hash::BuildHasher};
+
+use hashbrown::HashMap;
+
+use crate::{
+    edge::EdgeTrait, ghost::GhostToken, id::EdgeId, measure::Total, Allocator, Graph, SecondaryMap,
+    VertexId,
+};
+
+/// The edges leaving each vertex by index, with the index of the
+/// vertex each leads to and its length
+type Adjacency<'id> = Vec<Vec<(usize, EdgeId<'id>, f64)>>;
+
+/// The graph's vertices ordered by id, and the edges between them by
+/// index, which follow edge direction only if `directed`
+///
+/// Self-loops are left out, as they're never part of a shortest path
+fn adjacency<'id, Item, Weight, Edge, S, A>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    directed: bool,
+    length: impl Fn(&Weight) -> f64,
+    token: &GhostToken<'id>,
+) -> (Vec<VertexId<'id>>, Adjacency<'id>)
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    let mut ids: Vec<_> = graph.vertex_ids().collect();
+    ids.sort_unstable();
+    let index: SecondaryMap<_, _> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+    let mut adjacency = vec![Vec::new(); ids.len()];
+    for (e_id, from, to, weight) in graph.unique_edges(token) {
+        let (from, to) = (index[from], index[to]);
+        if from != to {
+            let length = length(weight);
+            adjacency[from].push((to, e_id, length));
+            if !directed || !Edge::DIRECTED {
+                adjacency[to].push((from, e_id, length));
+            }
+        }
+    }
+    // Sorting keeps the order paths are found in, and so the rounding
+    // of their sums, the same from one run to the next
+    for edges in &mut adjacency {
+        edges.sort_unstable_by_key(|(other, e_id, _)| (*other, *e_id));
+    }
+
+    (ids, adjacency)
+}
+
+/// The edge betweenness of every edge in `adjacency`, using Brandes'
+/// algorithm from every vertex in turn, with each undirected path
+/// found from both of its ends
+fn brandes<'id>(adjacency: &Adjacency<'id>) -> HashMap<EdgeId<'id>, f64> {
+    let mut centrality: HashMap<EdgeId<'id>, f64> = adjacency
+        .iter()
+        .flatten()
+        .map(|(_, e_id, _)| (*e_id, 0.0))
+        .collect();
+
+    for source in 0..adjacency.len() {
+        let mut settled = Vec::new();
+        let mut distance: Vec<Option<f64>> = vec![None; adjacency.len()];
+        let mut paths = vec![0.0; adjacency.len()];
+        let mut previous: Vec<Vec<(usize, EdgeId<'id>)>> = vec![Vec::new(); adjacency.len()];
+        let mut done = vec![false; adjacency.len()];
+
+        distance[source] = Some(0.0);
+        paths[source] = 1.0;
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((Total(0.0), source)));
+
+        while let Some(Reverse((Total(through), vertex))) = queue.pop() {
+            if done[vertex] {
+                continue;
+            }
+            done[vertex] = true;
+            settled.push(vertex);
+
+            for (other, e_id, length) in &adjacency[vertex] {
+                let other = *other;
+                let through = through + length;
+
+                match distance[other] {
+                    Some(best) if libm::fabs(through - best) <= f64::EPSILON * best => {
+                        if !done[other] {
+                            paths[other] += paths[vertex];
+                            previous[other].push((vertex, *e_id));
+                        }
+                    }
+                    Some(best) if through > best => {}
+                    _ => {
+                        distance[other] = Some(through);
+                        paths[other] = paths[vertex];
+                        previous[other] = vec![(vertex, *e_id)];
+                        queue.push(Reverse((Total(through), other)));
+                    }
+                }
+            }
+        }
+
+        let mut dependency = vec![0.0; adjacency.len()];
+        while let Some(vertex) = settled.pop() {
+            let through = 1.0 + dependency[vertex];
+            for (before, e_id) in &previous[vertex] {
+                let share = paths[*before] / paths[vertex] * through;
+                dependency[*before] += share;
+                if let Some(total) = centrality.get_mut(e_id) {
+                    *total += share;
+                }
+            }
+        }
+    }
+
+    centrality
+}
+
+/// The edge betweenness of every edge, which is the number of
+/// shortest paths between pairs of vertices that follow the edge,
+/// computed using Brandes' algorithm
+///
+/// `length` is given an edge's weight and returns the length of the
+/// edge, which must never be negative. Use `|_| 1.0` for unweighted
+/// graphs. Where there are several shortest paths between two
+/// vertices each counts fractionally, and for undirected graphs each
+/// pair of vertices is only counted once
+///
+/// ```rust
+/// use graph::{algo::edge_betweenness, edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+///
+/// GhostToken::new(|mut token| {
+///     let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+///     let a = graph.add_vertex(());
+///     let b = graph.add_vertex(());
+///     let c = graph.add_vertex(());
+///     let ab = graph.add_edge(a, b, (), &mut token).unwrap();
+///     let bc = graph.add_edge(b, c, (), &mut token).unwrap();
+///
+///     // `ab` is on the paths from `a` to `b` and to `c`
+///     let scores = edge_betweenness(&graph, |_| 1.0, &token);
+///     assert_eq!(scores[&ab], 2.0);
+///     assert_eq!(scores[&bc], 2.0);
+/// })
+/// ```
+#[must_use]
+pub fn edge_betweenness<'id, Item, Weight, Edge, S, A>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    length: impl Fn(&Weight) -> f64,
+    token: &GhostToken<'id>,
+) -> HashMap<EdgeId<'id>, f64>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    let (_, adjacency) = adjacency(graph, true, length, token);
+    let mut centrality = brandes(&adjacency);
+
+    for (e_id, ..) in graph.unique_edges(token) {
+        centrality.entry(e_id).or_default();
+    }
+    if !Edge::DIRECTED {
+        for total in centrality.values_mut() {
+            *total /= 2.0;
+        }
+    }
+
+    centrality
+}
+
+/// The connected components of `adjacency`, each ordered by index,
+/// and ordered by their lowest index
+fn components(adjacency: &Adjacency) -> Vec<Vec<usize>> {
+    let mut seen = vec![false; adjacency.len()];
+    let mut components = Vec::new();
+
+    for start in 0..adjacency.len() {
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+
+        let mut component = vec![start];
+        let mut next = 0;
+        while let Some(&vertex) = component.get(next) {
+            next += 1;
+            for (other, ..) in &adjacency[vertex] {
+                if !seen[*other] {
+                    seen[*other] = true;
+                    component.push(*other);
+                }
+            }
+        }
+
+        component.sort_unstable();
+        components.push(component);
+    }
+
+    components
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Clusters the graph into communities using the Girvan–Newman
+    /// algorithm, returning the connected components the graph falls
+    /// apart into as the edges between communities are removed
+    ///
+    /// Every edge is treated as undirected and as having the same
+    /// length. The edge with the highest [`edge_betweenness`] is
+    /// removed again and again, with ties going to the lowest
+    /// [`EdgeId`], and the components are recorded each time removing
+    /// an edge splits one of them. The first level is the graph's own
+    /// connected components, followed by up to `levels` more, each
+    /// with one more component than the last. Each component is
+    /// ordered by id, and the components are ordered by their lowest
+    /// id. The graph itself is left unchanged
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let v: Vec<_> = (0..6).map(|_| graph.add_vertex(())).collect();
+    ///     // Two triangles joined by a single edge
+    ///     for (a, b) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+    ///         graph.add_edge(v[a], v[b], (), &mut token).unwrap();
+    ///     }
+    ///
+    ///     let levels = graph.girvan_newman(1, &token);
+    ///     assert_eq!(levels, vec![vec![v.clone()], vec![v[..3].to_vec(), v[3..].to_vec()]]);
+    /// })
+    /// ```
+    ///
+    /// [`edge_betweenness`]: crate::algo::edge_betweenness
+    #[must_use]
+    pub fn girvan_newman(
+        &self,
+        levels: usize,
+        token: &GhostToken<'id>,
+    ) -> Vec<Vec<Vec<VertexId<'id>>>> {
+        let (ids, mut adjacency) = adjacency(self, false, |_| 1.0, token);
+        let mut split = components(&adjacency);
+        let mut dendrogram = Vec::new();
+
+        loop {
+            let count = split.len();
+            dendrogram.push(split);
+            if dendrogram.len() > levels {
+                break;
+            }
+
+            // Edges are removed until one of the components falls apart
+            split = loop {
+                let scores = brandes(&adjacency);
+                let Some(highest) = highest(&scores) else {
+                    break Vec::new();
+                };
+                for edges in &mut adjacency {
+                    edges.retain(|(_, e_id, _)| *e_id != highest);
+                }
+
+                let split = components(&adjacency);
+                if split.len() > count {
+                    break split;
+                }
+            };
+            if split.is_empty() {
+                break;
+            }
+        }
+
+        dendrogram
+            .into_iter()
+            .map(|level| {
+                level
+                    .into_iter()
+                    .map(|component| component.into_iter().map(|i| ids[i]).collect())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// The edge with the highest score, with scores that differ only by
+/// rounding treated as equal, and ties going to the lowest id
+fn highest<'id>(scores: &HashMap<EdgeId<'id>, f64>) -> Option<EdgeId<'id>> {
+    let top = scores.values().copied().reduce(f64::max)?;
+    scores
+        .iter()
+        .filter(|(_, score)| top - **score <= 1e-9 * top.max(1.0))
+        .map(|(e_id, _)| *e_id)
+        .min()
+}
@@ -0,0 +1,271 @@
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+use crate::{
+    edge::EdgeTrait, ghost::GhostToken, id::EdgeId, measure::Total, Allocator, Graph, GraphError,
+    IndexedHeap, Measure, SecondaryMap, VertexBitSet, VertexId,
+};
+
+use super::{dijkstra, ShortestPaths};
+
+/// The shortest paths from a source to every vertex it can reach,
+/// kept up to date as the edges of the graph change
+///
+/// After an edge is added, removed, or has its length changed, only
+/// the vertices whose shortest paths could have changed are searched
+/// again, rather than the whole graph. When an edge gets shorter,
+/// that's the vertices it now leads to more quickly. When an edge on
+/// a shortest path gets longer or is removed, that's every vertex
+/// whose shortest path went through it.
+///
+/// The paths don't see changes made to the graph on their own, so
+/// [`IncrementalShortestPaths::update_edge`] should be called after
+/// every change, before anything else changes
+///
+/// ```rust
+/// use graph::{
+///     algo::IncrementalShortestPaths, edge::DirectedWeightedEdge, ghost::GhostToken, Graph,
+/// };
+///
+/// GhostToken::new(|mut token| {
+///     let mut graph: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+///     let a = graph.add_vertex(());
+///     let b = graph.add_vertex(());
+///     let c = graph.add_vertex(());
+///     graph.add_edge(a, b, 5, &mut token).unwrap();
+///     graph.add_edge(b, c, 5, &mut token).unwrap();
+///
+///     let mut paths = IncrementalShortestPaths::new(&graph, a, |w| *w, &token).unwrap();
+///     assert_eq!(paths.paths().distance(c), Some(10));
+///
+///     graph.add_edge(a, c, 3, &mut token).unwrap();
+///     paths.update_edge(&graph, a, c, |w| *w, &token);
+///     assert_eq!(paths.paths().distance(c), Some(3));
+/// })
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalShortestPaths<'id, Distance> {
+    paths: ShortestPaths<'id, Distance>,
+}
+
+impl<'id, Distance: Measure> IncrementalShortestPaths<'id, Distance> {
+    /// Finds the shortest paths from `source`, as [`dijkstra`] does
+    ///
+    /// `length` is given an edge's weight and returns the length of
+    /// the edge, which must never be negative. The same `length`
+    /// should be given to every update
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if `source` isn't
+    /// within the graph
+    pub fn new<Item, Weight, Edge, S, A>(
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        source: VertexId<'id>,
+        length: impl Fn(&Weight) -> Distance,
+        token: &GhostToken<'id>,
+    ) -> Result<Self, GraphError<'id, Item, Weight, Edge>>
+    where
+        Edge: EdgeTrait<'id, Item, Weight>,
+        S: BuildHasher,
+        A: Allocator,
+    {
+        dijkstra(graph, source, length, token).map(|paths| Self { paths })
+    }
+    /// The current shortest paths
+    #[must_use]
+    pub const fn paths(&self) -> &ShortestPaths<'id, Distance> {
+        &self.paths
+    }
+    /// Consumes the [`IncrementalShortestPaths`], returning the
+    /// current shortest paths
+    #[must_use]
+    pub fn into_paths(self) -> ShortestPaths<'id, Distance> {
+        self.paths
+    }
+    /// Brings the paths up to date after an edge from `from` to `to`
+    /// has been added, removed, or had its weight changed
+    ///
+    /// Undirected edges can be given either way around. If several
+    /// edges have changed, this should be called for each of them
+    pub fn update_edge<Item, Weight, Edge, S, A>(
+        &mut self,
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        length: impl Fn(&Weight) -> Distance,
+        token: &GhostToken<'id>,
+    ) where
+        Edge: EdgeTrait<'id, Item, Weight>,
+        S: BuildHasher,
+        A: Allocator,
+    {
+        self.repair(graph, to, &length, token);
+        if !Edge::DIRECTED && from != to {
+            self.repair(graph, from, &length, token);
+        }
+    }
+    /// Brings the paths up to date after an edge leading to `id` has
+    /// changed
+    fn repair<Item, Weight, Edge, S, A>(
+        &mut self,
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        id: VertexId<'id>,
+        length: &impl Fn(&Weight) -> Distance,
+        token: &GhostToken<'id>,
+    ) where
+        Edge: EdgeTrait<'id, Item, Weight>,
+        S: BuildHasher,
+        A: Allocator,
+    {
+        if id == self.paths.source() {
+            return;
+        }
+
+        let current = self.paths.distance(id);
+        let best = self.best_incoming(graph, id, length, token);
+
+        // The edge got shorter, so only the vertices it now leads to
+        // more quickly need searching
+        if let Some((distance, previous)) = best {
+            if current.is_none_or(|current| distance < current) {
+                self.paths.distance.insert(id, distance);
+                self.paths.previous.insert(id, previous);
+
+                let mut queue = IndexedHeap::new();
+                queue.push(id, distance);
+                self.paths.settle(graph, queue, length, token);
+                return;
+            }
+        }
+
+        let Some(current) = current else {
+            return;
+        };
+        if self.previous_holds(graph, id, current, length, token) {
+            return;
+        }
+
+        // The edge `id` was reached by got longer or was removed, so
+        // every vertex reached through it is searched for again
+        let affected = self.subtree(id);
+        for vertex in &affected {
+            self.paths.distance.remove(vertex);
+            self.paths.previous.remove(vertex);
+        }
+
+        let mut queue = IndexedHeap::new();
+        for vertex in affected {
+            if let Some((distance, previous)) = self.best_incoming(graph, vertex, length, token) {
+                self.paths.distance.insert(vertex, distance);
+                self.paths.previous.insert(vertex, previous);
+                queue.push(vertex, distance);
+            }
+        }
+        self.paths.settle(graph, queue, length, token);
+    }
+    /// The shortest way to reach `id` along a single edge from a
+    /// vertex that's already been reached, if there is one
+    fn best_incoming<Item, Weight, Edge, S, A>(
+        &self,
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        id: VertexId<'id>,
+        length: &impl Fn(&Weight) -> Distance,
+        token: &GhostToken<'id>,
+    ) -> Option<(Distance, (VertexId<'id>, EdgeId<'id>))>
+    where
+        Edge: EdgeTrait<'id, Item, Weight>,
+        S: BuildHasher,
+        A: Allocator,
+    {
+        incoming(graph, id, token)
+            .filter_map(|(edge, other, weight)| {
+                let through = self.paths.distance(other)?.saturating_add(length(weight));
+                Some((through, (other, edge)))
+            })
+            .min_by_key(|(through, (other, edge))| (Total(*through), *other, *edge))
+    }
+    /// Whether the edge `id` was last reached by still exists, and
+    /// still gives it the distance `current`
+    fn previous_holds<Item, Weight, Edge, S, A>(
+        &self,
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        id: VertexId<'id>,
+        current: Distance,
+        length: &impl Fn(&Weight) -> Distance,
+        token: &GhostToken<'id>,
+    ) -> bool
+    where
+        Edge: EdgeTrait<'id, Item, Weight>,
+        S: BuildHasher,
+        A: Allocator,
+    {
+        let Some((previous, edge)) = self.paths.previous(id) else {
+            return false;
+        };
+        let Some(distance) = self.paths.distance(previous) else {
+            return false;
+        };
+
+        incoming(graph, id, token).any(|(e_id, other, weight)| {
+            e_id == edge && other == previous && distance.saturating_add(length(weight)) == current
+        })
+    }
+    /// `id` and every vertex whose shortest path passes through it
+    fn subtree(&self, id: VertexId<'id>) -> Vec<VertexId<'id>> {
+        let mut children: SecondaryMap<VertexId<'id>, Vec<VertexId<'id>>> = SecondaryMap::new();
+        for (child, (parent, _)) in &self.paths.previous {
+            children.get_or_default(*parent).push(*child);
+        }
+
+        let mut seen = VertexBitSet::new();
+        seen.insert(id);
+        let mut stack = vec![id];
+        let mut subtree = Vec::new();
+
+        while let Some(vertex) = stack.pop() {
+            subtree.push(vertex);
+            for child in children.get(vertex).into_iter().flatten() {
+                if seen.insert(*child) {
+                    stack.push(*child);
+                }
+            }
+        }
+
+        subtree
+    }
+}
+
+/// Iterates over the edges that can be followed to the vertex `id`,
+/// respecting edge direction, along with the vertex each edge leads
+/// from and its weight
+fn incoming<'a, 'id, Item, Weight, Edge, S, A>(
+    graph: &'a Graph<'id, Item, Weight, Edge, S, A>,
+    id: VertexId<'id>,
+    token: &'a GhostToken<'id>,
+) -> impl Iterator<Item = (EdgeId<'id>, VertexId<'id>, &'a Weight)> + 'a
+where
+    'id: 'a,
+    Item: 'a,
+    Weight: 'a,
+    Edge: EdgeTrait<'id, Item, Weight> + 'a,
+    S: BuildHasher + 'a,
+    A: Allocator,
+{
+    graph
+        .get_vertex(id)
+        .into_iter()
+        .flat_map(move |vertex| vertex.borrow(token).edges())
+        .filter_map(move |(e_id, edge)| {
+            let edge = edge.borrow(token);
+            let (first, second) = edge.endpoints();
+
+            let other = if second == id {
+                first
+            } else if !Edge::DIRECTED && first == id {
+                second
+            } else {
+                return None;
+            };
+            Some((*e_id, other, edge.get_weight()))
+        })
+}
@@ -0,0 +1,435 @@
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, Allocator, Graph, SecondaryMap, VertexId};
+
+/// The vertices of one of the graphs being matched, by index, with
+/// the distinct vertices each shares an edge with, ignoring direction
+struct Side<'id> {
+    ids: Vec<VertexId<'id>>,
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl<'id> Side<'id> {
+    /// The vertices of `graph` ordered by id, with no neighbors yet,
+    /// along with the index of each
+    fn new<Item, Weight, Edge, S, A>(
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    ) -> (Self, SecondaryMap<VertexId<'id>, usize>)
+    where
+        Edge: EdgeTrait<'id, Item, Weight>,
+        S: BuildHasher,
+        A: Allocator,
+    {
+        let mut ids: Vec<_> = graph.vertex_ids().collect();
+        ids.sort_unstable();
+        let index = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let neighbors = vec![Vec::new(); ids.len()];
+
+        (Self { ids, neighbors }, index)
+    }
+    /// Records that `one` and `two` share an edge
+    fn link(&mut self, one: usize, two: usize) {
+        if one != two {
+            self.neighbors[one].push(two);
+            self.neighbors[two].push(one);
+        }
+    }
+    /// Removes repeated neighbors, left by parallel edges
+    fn dedup(&mut self) {
+        for neighbors in &mut self.neighbors {
+            neighbors.sort_unstable();
+            neighbors.dedup();
+        }
+    }
+}
+
+/// A level of the search: the target vertices left to try for the
+/// pattern vertex at this depth, and the one it's mapped to now
+struct Frame {
+    candidates: Vec<usize>,
+    chosen: Option<usize>,
+}
+
+/// An iterator over the ways a pattern graph can be found within a
+/// target graph, created by [`find_subgraph_isomorphisms`]
+///
+/// Each mapping is found as the iterator is advanced, searching with
+/// the VF2 algorithm, so only the current branch of the search is
+/// held in memory
+pub struct SubgraphIsomorphisms<'id> {
+    pattern: Side<'id>,
+    target: Side<'id>,
+    /// The endpoints of every pattern edge by index
+    pattern_edges: Vec<(usize, usize)>,
+    /// The pattern edges between each pair of pattern vertices, keyed
+    /// by the lower index first
+    between: HashMap<(usize, usize), Vec<usize>>,
+    /// The target edges that can be followed between each pair of
+    /// target vertices, keyed by the vertex they're followed from
+    links: HashMap<(usize, usize), Vec<usize>>,
+    /// Whether pattern edges must be matched in their direction
+    directed: bool,
+    /// Whether each pattern vertex may be mapped to each target vertex
+    items: Vec<Vec<bool>>,
+    /// Whether each pattern edge may be mapped to each target edge
+    weights: Vec<Vec<bool>>,
+    /// The pattern vertices in the order they're mapped
+    order: Vec<usize>,
+    pattern_core: Vec<Option<usize>>,
+    target_core: Vec<Option<usize>>,
+    stack: Vec<Frame>,
+    /// Whether the single, empty, mapping of an empty pattern is
+    /// still to be given
+    empty: bool,
+}
+
+impl<'id> SubgraphIsomorphisms<'id> {
+    /// The order pattern vertices are mapped in, each chosen to share
+    /// as many edges as possible with those before it, so mismatches
+    /// are found early, then by degree
+    fn order(pattern: &Side) -> Vec<usize> {
+        let mut order = Vec::with_capacity(pattern.ids.len());
+        let mut placed = vec![false; pattern.ids.len()];
+        let mut links = vec![0; pattern.ids.len()];
+
+        while order.len() < pattern.ids.len() {
+            let next = (0..pattern.ids.len())
+                .filter(|p| !placed[*p])
+                .max_by_key(|p| {
+                    (
+                        links[*p],
+                        pattern.neighbors[*p].len(),
+                        core::cmp::Reverse(*p),
+                    )
+                })
+                .expect("a pattern vertex is left to place");
+
+            placed[next] = true;
+            order.push(next);
+            for other in &pattern.neighbors[next] {
+                links[*other] += 1;
+            }
+        }
+        order
+    }
+    /// The target vertices the pattern vertex `p` could be mapped to,
+    /// last first, which are the unmapped neighbors of the target
+    /// vertex an already mapped neighbor of `p` is mapped to, or every
+    /// unmapped target vertex if `p` has none
+    fn candidates(&self, p: usize) -> Vec<usize> {
+        let mapped = self.pattern.neighbors[p]
+            .iter()
+            .find_map(|other| self.pattern_core[*other]);
+
+        let mut candidates: Vec<_> = mapped.map_or_else(
+            || (0..self.target.ids.len()).collect(),
+            |s| self.target.neighbors[s].clone(),
+        );
+        candidates.retain(|t| self.target_core[*t].is_none());
+        candidates.reverse();
+        candidates
+    }
+    /// Whether the pattern vertex `p` can be mapped to the target
+    /// vertex `t`, given the vertices already mapped
+    fn feasible(&self, p: usize, t: usize) -> bool {
+        if !self.items[p][t] || self.pattern.neighbors[p].len() > self.target.neighbors[t].len() {
+            return false;
+        }
+
+        // The edges to every mapped neighbor, and any self-loops,
+        // must be found between the vertices they're mapped to
+        let mapped = self.pattern.neighbors[p]
+            .iter()
+            .filter_map(|q| Some((*q, self.pattern_core[*q]?)));
+        for (q, s) in mapped.chain([(p, t)]) {
+            if !self.edges_match((p, t), (q, s)) {
+                return false;
+            }
+        }
+
+        // Each unmapped neighbor of `p` needs an unmapped neighbor of
+        // `t` to be mapped to
+        let unmapped = |neighbors: &[usize], core: &[Option<usize>]| {
+            neighbors.iter().filter(|v| core[**v].is_none()).count()
+        };
+        unmapped(&self.pattern.neighbors[p], &self.pattern_core)
+            <= unmapped(&self.target.neighbors[t], &self.target_core)
+    }
+    /// Whether every pattern edge between `p` and `q` can be mapped to
+    /// a different target edge between `t` and `s`, which they're
+    /// mapped to
+    fn edges_match(&self, (p, t): (usize, usize), (q, s): (usize, usize)) -> bool {
+        let Some(edges) = self.between.get(&(p.min(q), p.max(q))) else {
+            return true;
+        };
+        let image = |v| if v == p { t } else { s };
+
+        let options: Vec<Vec<usize>> = edges
+            .iter()
+            .map(|e| {
+                let (from, to) = self.pattern_edges[*e];
+                let (from, to) = (image(from), image(to));
+
+                let mut options: Vec<usize> =
+                    self.links.get(&(from, to)).cloned().unwrap_or_default();
+                if !self.directed {
+                    options.extend(self.links.get(&(to, from)).into_iter().flatten());
+                    options.sort_unstable();
+                    options.dedup();
+                }
+                options.retain(|j| self.weights[*e][*j]);
+                options
+            })
+            .collect();
+
+        let mut owner = HashMap::new();
+        (0..options.len()).all(|e| augment(e, &options, &mut owner, &mut HashSet::new()))
+    }
+    /// The current mapping, from pattern vertex to target vertex
+    fn mapping(&self) -> HashMap<VertexId<'id>, VertexId<'id>> {
+        self.pattern_core
+            .iter()
+            .enumerate()
+            .filter_map(|(p, t)| Some((self.pattern.ids[p], self.target.ids[(*t)?])))
+            .collect()
+    }
+}
+
+/// Finds a target edge for the pattern edge `e`, taking it from
+/// another pattern edge if that one can be given a different target
+/// edge instead, as in Kuhn's matching algorithm
+fn augment(
+    e: usize,
+    options: &[Vec<usize>],
+    owner: &mut HashMap<usize, usize>,
+    seen: &mut HashSet<usize>,
+) -> bool {
+    for j in &options[e] {
+        if !seen.insert(*j) {
+            continue;
+        }
+        let holder = owner.get(j).copied();
+        if holder.is_none_or(|other| augment(other, options, owner, seen)) {
+            owner.insert(*j, e);
+            return true;
+        }
+    }
+    false
+}
+
+impl<'id> Iterator for SubgraphIsomorphisms<'id> {
+    type Item = HashMap<VertexId<'id>, VertexId<'id>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.empty {
+            self.empty = false;
+            return Some(HashMap::new());
+        }
+
+        loop {
+            let depth = self.stack.len().checked_sub(1)?;
+            let p = self.order[depth];
+            let frame = self.stack.last_mut()?;
+
+            if let Some(t) = frame.chosen.take() {
+                self.pattern_core[p] = None;
+                self.target_core[t] = None;
+            }
+            let Some(t) = frame.candidates.pop() else {
+                self.stack.pop();
+                continue;
+            };
+            if !self.feasible(p, t) {
+                continue;
+            }
+
+            self.pattern_core[p] = Some(t);
+            self.target_core[t] = Some(p);
+            if let Some(frame) = self.stack.last_mut() {
+                frame.chosen = Some(t);
+            }
+
+            match self.order.get(depth + 1) {
+                Some(next) => {
+                    let candidates = self.candidates(*next);
+                    self.stack.push(Frame {
+                        candidates,
+                        chosen: None,
+                    });
+                }
+                None => return Some(self.mapping()),
+            }
+        }
+    }
+}
+
+/// Returns an iterator over every way `pattern` can be found within
+/// `target`, using the VF2 algorithm
+///
+/// Each vertex of `pattern` is mapped to a different vertex of
+/// `target`, such that each edge of `pattern` is mapped to a different
+/// edge of `target` between the vertices its own are mapped to. Each mapping is a [`HashMap`] from every vertex of `pattern` to the
+/// vertex of `target` it's mapped to. A pattern vertex can only be
+/// mapped to a target vertex if `match_items` returns `true` for their
+/// items, and a pattern edge to a target edge if `match_weights` does
+/// for their weights. Use `|_, _| true` for either to match on
+/// structure alone
+///
+/// The target may have edges the pattern doesn't, so a pattern is
+/// found wherever it's a subgraph of the target, not only where it's
+/// an induced subgraph. If the pattern's edges are directed, each must
+/// be mapped to a target edge that can be followed the same way,
+/// otherwise to a target edge between the same vertices either way.
+/// Every symmetry of the pattern gives a mapping of its own, and an
+/// empty pattern is mapped once, to nothing
+///
+/// ```rust
+/// use graph::{algo::find_subgraph_isomorphisms, edge::DirectedWeightedEdge, ghost::GhostToken, Graph};
+///
+/// GhostToken::new(|mut token| {
+///     // Finds a store that's later loaded from
+///     let mut pattern: Graph<&str, (), DirectedWeightedEdge<_, _>> = Graph::new();
+///     let store = pattern.add_vertex("store");
+///     let load = pattern.add_vertex("load");
+///     pattern.add_edge(store, load, (), &mut token).unwrap();
+///
+///     let mut program: Graph<&str, (), DirectedWeightedEdge<_, _>> = Graph::new();
+///     let add = program.add_vertex("add");
+///     let write = program.add_vertex("store");
+///     let read = program.add_vertex("load");
+///     program.add_edge(add, write, (), &mut token).unwrap();
+///     program.add_edge(write, read, (), &mut token).unwrap();
+///
+///     let found: Vec<_> = find_subgraph_isomorphisms(
+///         &pattern,
+///         &program,
+///         |one, two| one == two,
+///         |_, _| true,
+///         &token,
+///     )
+///     .collect();
+///
+///     assert_eq!(found.len(), 1);
+///     assert_eq!(found[0][&store], write);
+///     assert_eq!(found[0][&load], read);
+/// })
+/// ```
+#[must_use]
+pub fn find_subgraph_isomorphisms<'id, I1, W1, E1, S1, A1, I2, W2, E2, S2, A2>(
+    pattern: &Graph<'id, I1, W1, E1, S1, A1>,
+    target: &Graph<'id, I2, W2, E2, S2, A2>,
+    match_items: impl Fn(&I1, &I2) -> bool,
+    match_weights: impl Fn(&W1, &W2) -> bool,
+    token: &GhostToken<'id>,
+) -> SubgraphIsomorphisms<'id>
+where
+    E1: EdgeTrait<'id, I1, W1>,
+    E2: EdgeTrait<'id, I2, W2>,
+    S1: BuildHasher,
+    S2: BuildHasher,
+    A1: Allocator,
+    A2: Allocator,
+{
+    let (mut pattern_side, pattern_index) = Side::new(pattern);
+    let (mut target_side, target_index) = Side::new(target);
+
+    let mut pattern_edges = Vec::new();
+    let mut pattern_weights = Vec::new();
+    let mut between: HashMap<_, Vec<_>> = HashMap::new();
+    for (_, from, to, weight) in pattern.unique_edges(token) {
+        let (from, to) = (pattern_index[from], pattern_index[to]);
+        pattern_side.link(from, to);
+        between
+            .entry((from.min(to), from.max(to)))
+            .or_default()
+            .push(pattern_edges.len());
+        pattern_edges.push((from, to));
+        pattern_weights.push(weight);
+    }
+
+    let mut target_weights = Vec::new();
+    let mut links: HashMap<_, Vec<_>> = HashMap::new();
+    for (_, from, to, weight) in target.unique_edges(token) {
+        let (from, to) = (target_index[from], target_index[to]);
+        target_side.link(from, to);
+        links
+            .entry((from, to))
+            .or_default()
+            .push(target_weights.len());
+        if !E2::DIRECTED && from != to {
+            links
+                .entry((to, from))
+                .or_default()
+                .push(target_weights.len());
+        }
+        target_weights.push(weight);
+    }
+    pattern_side.dedup();
+    target_side.dedup();
+
+    let target_items: Vec<_> = target_side
+        .ids
+        .iter()
+        .map(|id| {
+            target
+                .get_vertex(*id)
+                .map(|vertex| vertex.borrow(token).get_item())
+        })
+        .collect();
+    let items = pattern_side
+        .ids
+        .iter()
+        .map(|id| {
+            let item = pattern
+                .get_vertex(*id)
+                .map(|vertex| vertex.borrow(token).get_item());
+            target_items
+                .iter()
+                .map(|other| match (item, other) {
+                    (Some(item), Some(other)) => match_items(item, other),
+                    _ => false,
+                })
+                .collect()
+        })
+        .collect();
+    let weights = pattern_weights
+        .iter()
+        .map(|weight| {
+            target_weights
+                .iter()
+                .map(|other| match_weights(weight, other))
+                .collect()
+        })
+        .collect();
+
+    let order = SubgraphIsomorphisms::order(&pattern_side);
+    let mut isomorphisms = SubgraphIsomorphisms {
+        pattern_core: vec![None; pattern_side.ids.len()],
+        target_core: vec![None; target_side.ids.len()],
+        empty: pattern_side.ids.is_empty(),
+        pattern: pattern_side,
+        target: target_side,
+        pattern_edges,
+        between,
+        links,
+        directed: E1::DIRECTED,
+        items,
+        weights,
+        order,
+        stack: Vec::new(),
+    };
+    if let Some(first) = isomorphisms.order.first() {
+        if isomorphisms.pattern.ids.len() <= isomorphisms.target.ids.len() {
+            let candidates = isomorphisms.candidates(*first);
+            isomorphisms.stack.push(Frame {
+                candidates,
+                chosen: None,
+            });
+        }
+    }
+
+    isomorphisms
+}
@@ -0,0 +1,782 @@
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+use hashbrown::HashMap;
+
+use crate::{
+    edge::EdgeTrait, ghost::GhostToken, id::EdgeId, Allocator, Graph, SecondaryMap, VertexId,
+};
+
+/// A matching of a graph, a set of edges of which no two share a
+/// vertex, found by [`Graph::maximum_weight_matching`] or
+/// [`Graph::maximum_cardinality_matching`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Matching<'id> {
+    /// The total weight of the matched edges
+    pub weight: i64,
+    /// The matched edges, ordered by id
+    pub edges: Vec<EdgeId<'id>>,
+    /// The endpoints of each matched edge, in the same order as
+    /// `edges`, with the lower id first
+    pub pairs: Vec<(VertexId<'id>, VertexId<'id>)>,
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Finds a matching of the graph with the greatest total weight,
+    /// using Edmonds' blossom algorithm, in `O(n³)` time
+    ///
+    /// `weight` is given an edge's weight and returns its weight
+    /// within the matching. Edges with a weight of zero or less are
+    /// never matched, as leaving them out never makes a matching any
+    /// lighter. Every edge is treated as undirected, self-loops are
+    /// left out, and of two edges joining the same pair of vertices
+    /// only the heavier is considered. Weights are integers so that
+    /// the matching found is exactly the heaviest, so scale fractional
+    /// weights up before rounding them
+    ///
+    /// Unlike a matching of a bipartite graph, this works on any graph,
+    /// finding odd cycles, the blossoms, and shrinking each down to a
+    /// single vertex while it searches
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), i64, UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+    ///     // A triangle with a tail, where the heaviest edge isn't matched
+    ///     graph.add_edge(v[0], v[1], 6, &mut token).unwrap();
+    ///     let two = graph.add_edge(v[0], v[2], 5, &mut token).unwrap();
+    ///     graph.add_edge(v[1], v[2], 4, &mut token).unwrap();
+    ///     let three = graph.add_edge(v[1], v[3], 3, &mut token).unwrap();
+    ///
+    ///     let matching = graph.maximum_weight_matching(|weight| *weight, &token);
+    ///     assert_eq!(matching.weight, 8);
+    ///     assert_eq!(matching.edges, vec![two, three]);
+    ///     assert_eq!(matching.pairs, vec![(v[0], v[2]), (v[1], v[3])]);
+    /// })
+    /// ```
+    #[must_use]
+    pub fn maximum_weight_matching(
+        &self,
+        weight: impl Fn(&Weight) -> i64,
+        token: &GhostToken<'id>,
+    ) -> Matching<'id> {
+        self.blossom_matching(weight, false, token)
+    }
+    /// Finds a matching of the graph with as many edges as any
+    /// matching can have, and the greatest total weight of all such
+    /// matchings, using Edmonds' blossom algorithm, in `O(n³)` time
+    ///
+    /// Weights are used in the same way as in
+    /// [`Graph::maximum_weight_matching`], except that edges with a
+    /// weight of zero or less are matched whenever that matches more
+    /// edges. Use `|_| 0` to only find a matching with the most edges
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), i64, UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+    ///     graph.add_edge(v[0], v[1], 1, &mut token).unwrap();
+    ///     graph.add_edge(v[1], v[2], 5, &mut token).unwrap();
+    ///     graph.add_edge(v[2], v[3], 1, &mut token).unwrap();
+    ///
+    ///     assert_eq!(graph.maximum_weight_matching(|w| *w, &token).edges.len(), 1);
+    ///     let matching = graph.maximum_cardinality_matching(|w| *w, &token);
+    ///     assert_eq!(matching.weight, 2);
+    ///     assert_eq!(matching.pairs, vec![(v[0], v[1]), (v[2], v[3])]);
+    /// })
+    /// ```
+    #[must_use]
+    pub fn maximum_cardinality_matching(
+        &self,
+        weight: impl Fn(&Weight) -> i64,
+        token: &GhostToken<'id>,
+    ) -> Matching<'id> {
+        self.blossom_matching(weight, true, token)
+    }
+    fn blossom_matching(
+        &self,
+        weight: impl Fn(&Weight) -> i64,
+        max_cardinality: bool,
+        token: &GhostToken<'id>,
+    ) -> Matching<'id> {
+        let mut ids: Vec<_> = self.vertex_ids().collect();
+        ids.sort_unstable();
+        let index: SecondaryMap<_, _> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        // The heaviest edge between each pair of vertices, the lowest
+        // id winning ties
+        let mut heaviest: HashMap<(usize, usize), (i64, EdgeId<'id>)> = HashMap::new();
+        for (e_id, from, to, edge_weight) in self.unique_edges(token) {
+            let (one, two) = (index[from], index[to]);
+            if one == two {
+                continue;
+            }
+            let candidate = (weight(edge_weight), e_id);
+            let pair = (one.min(two), one.max(two));
+            let best = heaviest.entry(pair).or_insert(candidate);
+            if candidate.0 > best.0 || (candidate.0 == best.0 && candidate.1 < best.1) {
+                *best = candidate;
+            }
+        }
+        let mut edges: Vec<_> = heaviest
+            .into_iter()
+            .map(|((one, two), (edge_weight, e_id))| (e_id, one, two, edge_weight))
+            .collect();
+        edges.sort_unstable_by_key(|(e_id, ..)| *e_id);
+
+        let mut blossom = BlossomSearch::new(
+            ids.len(),
+            edges
+                .iter()
+                .map(|(_, one, two, edge_weight)| (*one, *two, i128::from(*edge_weight)))
+                .collect(),
+        );
+        blossom.solve(max_cardinality);
+
+        let mut matching = Matching {
+            weight: 0,
+            edges: Vec::new(),
+            pairs: Vec::new(),
+        };
+        for (k, (e_id, one, two, edge_weight)) in edges.into_iter().enumerate() {
+            if blossom.mate[one] == 2 * k + 1 {
+                matching.weight += edge_weight;
+                matching.edges.push(e_id);
+                matching.pairs.push((ids[one], ids[two]));
+            }
+        }
+        matching
+    }
+}
+
+/// Stands in for a missing vertex, edge or endpoint
+const NONE: usize = usize::MAX;
+
+/// The label of a top level blossom or vertex not yet reached
+const FREE: u8 = 0;
+/// The label of an outer, or even, top level blossom or vertex
+const OUTER: u8 = 1;
+/// The label of an inner, or odd, top level blossom or vertex
+const INNER: u8 = 2;
+/// Marks outer blossoms already passed while looking for a new blossom
+const BREADCRUMB: u8 = 4;
+
+/// The state of the blossom algorithm, after Galil's `O(n³)` version,
+/// working on the vertices and edges of a graph by index
+///
+/// Each edge `k` has two endpoints, `2k` being its first vertex and
+/// `2k + 1` its second, so that `p ^ 1` is the other end of endpoint
+/// `p`. Indices from `vertices` up to `2 * vertices` are blossoms
+/// containing more than one vertex. Weights are doubled, keeping every
+/// dual variable an integer
+struct BlossomSearch {
+    vertices: usize,
+    edges: Vec<(usize, usize, i128)>,
+    /// The vertex at each endpoint
+    endpoint: Vec<usize>,
+    /// The endpoints at the far end of each vertex's edges
+    far_ends: Vec<Vec<usize>>,
+    /// The endpoint each vertex is matched to
+    mate: Vec<usize>,
+    /// The label of each vertex and blossom
+    label: Vec<u8>,
+    /// The endpoint through which each vertex and blossom got its label
+    label_end: Vec<usize>,
+    /// The top level blossom containing each vertex
+    in_blossom: Vec<usize>,
+    /// The blossom directly containing each vertex and blossom
+    parent: Vec<usize>,
+    /// The blossoms and vertices directly within each blossom, in
+    /// order around it, starting from its base
+    children: Vec<Vec<usize>>,
+    /// The vertex each blossom is based at
+    base: Vec<usize>,
+    /// The endpoints of the edges joining each blossom's children,
+    /// `ends[b][i]` joining `children[b][i]` to the next child
+    ends: Vec<Vec<usize>>,
+    /// The edge of least slack from each free vertex, or outer
+    /// blossom, to an outer blossom
+    best_edge: Vec<usize>,
+    /// The edges of least slack from each outer blossom to each other
+    /// outer blossom
+    best_edges: Vec<Option<Vec<usize>>>,
+    /// The blossom indices not in use
+    unused: Vec<usize>,
+    /// The dual variable of each vertex and blossom
+    dual: Vec<i128>,
+    /// Whether each edge has no slack left, so may be followed
+    allowed: Vec<bool>,
+    /// The outer vertices still to be scanned
+    queue: Vec<usize>,
+}
+
+#[allow(clippy::many_single_char_names)]
+impl BlossomSearch {
+    fn new(vertices: usize, edges: Vec<(usize, usize, i128)>) -> Self {
+        let edges: Vec<_> = edges.into_iter().map(|(i, j, w)| (i, j, 2 * w)).collect();
+        let max_weight = edges.iter().map(|(.., w)| *w).max().unwrap_or(0).max(0);
+
+        let mut endpoint = Vec::with_capacity(2 * edges.len());
+        let mut far_ends = vec![Vec::new(); vertices];
+        for (k, (i, j, _)) in edges.iter().enumerate() {
+            endpoint.push(*i);
+            endpoint.push(*j);
+            far_ends[*i].push(2 * k + 1);
+            far_ends[*j].push(2 * k);
+        }
+
+        let mut dual = vec![max_weight; vertices];
+        dual.resize(2 * vertices, 0);
+        let mut base: Vec<_> = (0..vertices).collect();
+        base.resize(2 * vertices, NONE);
+
+        Self {
+            vertices,
+            endpoint,
+            far_ends,
+            mate: vec![NONE; vertices],
+            label: vec![FREE; 2 * vertices],
+            label_end: vec![NONE; 2 * vertices],
+            in_blossom: (0..vertices).collect(),
+            parent: vec![NONE; 2 * vertices],
+            children: vec![Vec::new(); 2 * vertices],
+            base,
+            ends: vec![Vec::new(); 2 * vertices],
+            best_edge: vec![NONE; 2 * vertices],
+            best_edges: vec![None; 2 * vertices],
+            unused: (vertices..2 * vertices).collect(),
+            dual,
+            allowed: vec![false; edges.len()],
+            queue: Vec::new(),
+            edges,
+        }
+    }
+    fn slack(&self, k: usize) -> i128 {
+        let (i, j, w) = self.edges[k];
+        self.dual[i] + self.dual[j] - 2 * w
+    }
+    /// The vertices within blossom `b`
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        let mut leaves = Vec::new();
+        let mut stack = vec![b];
+        while let Some(b) = stack.pop() {
+            if b < self.vertices {
+                leaves.push(b);
+            } else {
+                stack.extend(self.children[b].iter().rev());
+            }
+        }
+        leaves
+    }
+    /// Labels vertex `w`, and the top level blossom holding it, with
+    /// `label`, reached through endpoint `p`, labelling the mate of an
+    /// inner blossom as outer
+    fn assign_label(&mut self, w: usize, label: u8, p: usize) {
+        let b = self.in_blossom[w];
+        self.label[w] = label;
+        self.label[b] = label;
+        self.label_end[w] = p;
+        self.label_end[b] = p;
+        self.best_edge[w] = NONE;
+        self.best_edge[b] = NONE;
+
+        if label == OUTER {
+            let leaves = self.leaves(b);
+            self.queue.extend(leaves);
+        } else {
+            let mate = self.mate[self.base[b]];
+            self.assign_label(self.endpoint[mate], OUTER, mate ^ 1);
+        }
+    }
+    /// Traces back from outer vertices `v` and `w` towards the roots of
+    /// their trees, returning the base of a new blossom if the two
+    /// meet, or [`NONE`] if they're in different trees
+    fn scan_blossom(&mut self, mut v: usize, mut w: usize) -> usize {
+        let mut path = Vec::new();
+        let mut base = NONE;
+
+        while v != NONE || w != NONE {
+            let mut b = self.in_blossom[v];
+            if self.label[b] & BREADCRUMB != 0 {
+                base = self.base[b];
+                break;
+            }
+            path.push(b);
+            self.label[b] = OUTER | BREADCRUMB;
+
+            if self.label_end[b] == NONE {
+                // The root of the tree
+                v = NONE;
+            } else {
+                v = self.endpoint[self.label_end[b]];
+                b = self.in_blossom[v];
+                v = self.endpoint[self.label_end[b]];
+            }
+            if w != NONE {
+                core::mem::swap(&mut v, &mut w);
+            }
+        }
+
+        for b in path {
+            self.label[b] = OUTER;
+        }
+        base
+    }
+    /// Shrinks the odd cycle formed by edge `k` and the paths from its
+    /// endpoints to `base` into a new blossom
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (mut v, mut w, _) = self.edges[k];
+        let bb = self.in_blossom[base];
+        let mut bv = self.in_blossom[v];
+        let mut bw = self.in_blossom[w];
+
+        let Some(b) = self.unused.pop() else {
+            return;
+        };
+        self.base[b] = base;
+        self.parent[b] = NONE;
+        self.parent[bb] = b;
+
+        let mut children = Vec::new();
+        let mut ends = Vec::new();
+        while bv != bb {
+            self.parent[bv] = b;
+            children.push(bv);
+            ends.push(self.label_end[bv]);
+            v = self.endpoint[self.label_end[bv]];
+            bv = self.in_blossom[v];
+        }
+        children.push(bb);
+        children.reverse();
+        ends.reverse();
+        ends.push(2 * k);
+        while bw != bb {
+            self.parent[bw] = b;
+            children.push(bw);
+            ends.push(self.label_end[bw] ^ 1);
+            w = self.endpoint[self.label_end[bw]];
+            bw = self.in_blossom[w];
+        }
+
+        self.label[b] = OUTER;
+        self.label_end[b] = self.label_end[bb];
+        self.dual[b] = 0;
+        self.children[b] = children;
+        self.ends[b] = ends;
+
+        for v in self.leaves(b) {
+            if self.label[self.in_blossom[v]] == INNER {
+                // Inner vertices become outer within the blossom
+                self.queue.push(v);
+            }
+            self.in_blossom[v] = b;
+        }
+
+        let mut best_to = vec![NONE; 2 * self.vertices];
+        for bv in self.children[b].clone() {
+            let lists = self.best_edges[bv].take().map_or_else(
+                || {
+                    self.leaves(bv)
+                        .into_iter()
+                        .map(|v| self.far_ends[v].iter().map(|p| p / 2).collect())
+                        .collect()
+                },
+                |list| vec![list],
+            );
+            for list in lists {
+                for k in list {
+                    let (mut i, mut j, _) = self.edges[k];
+                    if self.in_blossom[j] == b {
+                        core::mem::swap(&mut i, &mut j);
+                    }
+                    let bj = self.in_blossom[j];
+                    if bj != b
+                        && self.label[bj] == OUTER
+                        && (best_to[bj] == NONE || self.slack(k) < self.slack(best_to[bj]))
+                    {
+                        best_to[bj] = k;
+                    }
+                }
+            }
+            self.best_edge[bv] = NONE;
+        }
+
+        let best: Vec<_> = best_to.into_iter().filter(|k| *k != NONE).collect();
+        self.best_edge[b] = NONE;
+        for &k in &best {
+            if self.best_edge[b] == NONE || self.slack(k) < self.slack(self.best_edge[b]) {
+                self.best_edge[b] = k;
+            }
+        }
+        self.best_edges[b] = Some(best);
+    }
+    /// Splits blossom `b` back into its children, relabelling them if
+    /// it's an inner blossom expanded mid-search
+    fn expand_blossom(&mut self, b: usize, end_stage: bool) {
+        for s in self.children[b].clone() {
+            self.parent[s] = NONE;
+            if s < self.vertices {
+                self.in_blossom[s] = s;
+            } else if end_stage && self.dual[s] == 0 {
+                self.expand_blossom(s, end_stage);
+            } else {
+                for v in self.leaves(s) {
+                    self.in_blossom[v] = s;
+                }
+            }
+        }
+
+        if !end_stage && self.label[b] == INNER {
+            // Relabels the children along the even length path from
+            // the child it was entered through to its base
+            let entry = self.in_blossom[self.endpoint[self.label_end[b] ^ 1]];
+            let len = self.children[b].len();
+            let child = |j: isize| wrap(j, len);
+            let mut j = self.children[b]
+                .iter()
+                .position(|c| *c == entry)
+                .map_or(0, to_isize);
+            let (step, trick) = if j & 1 == 1 {
+                j -= to_isize(len);
+                (1, 0)
+            } else {
+                (-1, 1)
+            };
+
+            let mut p = self.label_end[b];
+            while j != 0 {
+                self.label[self.endpoint[p ^ 1]] = FREE;
+                let end = self.ends[b][child(j - trick)] ^ to_usize(trick) ^ 1;
+                self.label[self.endpoint[end]] = FREE;
+                self.assign_label(self.endpoint[p ^ 1], INNER, p);
+                self.allowed[self.ends[b][child(j - trick)] / 2] = true;
+                j += step;
+                p = self.ends[b][child(j - trick)] ^ to_usize(trick);
+                self.allowed[p / 2] = true;
+                j += step;
+            }
+
+            let bv = self.children[b][child(j)];
+            let far = self.endpoint[p ^ 1];
+            self.label[far] = INNER;
+            self.label[bv] = INNER;
+            self.label_end[far] = p;
+            self.label_end[bv] = p;
+            self.best_edge[bv] = NONE;
+            j += step;
+
+            // The children along the odd length path are left free,
+            // unless reached from outside the blossom
+            while self.children[b][child(j)] != entry {
+                let bv = self.children[b][child(j)];
+                if self.label[bv] == OUTER {
+                    j += step;
+                    continue;
+                }
+                let reached = self.leaves(bv).into_iter().find(|v| self.label[*v] != FREE);
+                if let Some(v) = reached {
+                    self.label[v] = FREE;
+                    let mate = self.mate[self.base[bv]];
+                    self.label[self.endpoint[mate]] = FREE;
+                    self.assign_label(v, INNER, self.label_end[v]);
+                }
+                j += step;
+            }
+        }
+
+        self.label[b] = FREE;
+        self.label_end[b] = NONE;
+        self.children[b].clear();
+        self.ends[b].clear();
+        self.base[b] = NONE;
+        self.best_edges[b] = None;
+        self.best_edge[b] = NONE;
+        self.unused.push(b);
+    }
+    /// Swaps matched and unmatched edges along the even length path
+    /// around blossom `b` from vertex `v` to its base, making `v` the
+    /// new base
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t = v;
+        while self.parent[t] != b {
+            t = self.parent[t];
+        }
+        if t >= self.vertices {
+            self.augment_blossom(t, v);
+        }
+
+        let len = self.children[b].len();
+        let child = |j: isize| wrap(j, len);
+        let i = self.children[b].iter().position(|c| *c == t).unwrap_or(0);
+        let mut j = to_isize(i);
+        let (step, trick) = if j & 1 == 1 {
+            j -= to_isize(len);
+            (1, 0)
+        } else {
+            (-1, 1)
+        };
+
+        while j != 0 {
+            j += step;
+            let t = self.children[b][child(j)];
+            let p = self.ends[b][child(j - trick)] ^ to_usize(trick);
+            if t >= self.vertices {
+                self.augment_blossom(t, self.endpoint[p]);
+            }
+            j += step;
+            let t = self.children[b][child(j)];
+            if t >= self.vertices {
+                self.augment_blossom(t, self.endpoint[p ^ 1]);
+            }
+            self.mate[self.endpoint[p]] = p ^ 1;
+            self.mate[self.endpoint[p ^ 1]] = p;
+        }
+
+        self.children[b].rotate_left(i);
+        self.ends[b].rotate_left(i);
+        self.base[b] = self.base[self.children[b][0]];
+    }
+    /// Swaps matched and unmatched edges along the augmenting path
+    /// through edge `k`, between the roots of two trees
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k];
+        for (mut s, mut p) in [(v, 2 * k + 1), (w, 2 * k)] {
+            loop {
+                let bs = self.in_blossom[s];
+                if bs >= self.vertices {
+                    self.augment_blossom(bs, s);
+                }
+                self.mate[s] = p;
+                if self.label_end[bs] == NONE {
+                    // Reached the root of the tree
+                    break;
+                }
+
+                let t = self.endpoint[self.label_end[bs]];
+                let bt = self.in_blossom[t];
+                s = self.endpoint[self.label_end[bt]];
+                let j = self.endpoint[self.label_end[bt] ^ 1];
+                if bt >= self.vertices {
+                    self.augment_blossom(bt, j);
+                }
+                self.mate[j] = self.label_end[bt];
+                p = self.label_end[bt] ^ 1;
+            }
+        }
+    }
+    /// Scans the queue of outer vertices, growing trees and shrinking
+    /// blossoms, returning whether the matching was augmented
+    fn scan(&mut self) -> bool {
+        while let Some(v) = self.queue.pop() {
+            for p in self.far_ends[v].clone() {
+                let k = p / 2;
+                let w = self.endpoint[p];
+                if self.in_blossom[v] == self.in_blossom[w] {
+                    continue;
+                }
+
+                let mut slack = 0;
+                if !self.allowed[k] {
+                    slack = self.slack(k);
+                    if slack <= 0 {
+                        self.allowed[k] = true;
+                    }
+                }
+
+                let bw = self.in_blossom[w];
+                if self.allowed[k] {
+                    if self.label[bw] == FREE {
+                        self.assign_label(w, INNER, p ^ 1);
+                    } else if self.label[bw] == OUTER {
+                        let base = self.scan_blossom(v, w);
+                        if base == NONE {
+                            self.augment_matching(k);
+                            return true;
+                        }
+                        self.add_blossom(base, k);
+                    } else if self.label[w] == FREE {
+                        // Reached an inner blossom's vertex, but not
+                        // through its base
+                        self.label[w] = INNER;
+                        self.label_end[w] = p ^ 1;
+                    }
+                } else if self.label[bw] == OUTER {
+                    let b = self.in_blossom[v];
+                    if self.best_edge[b] == NONE || slack < self.slack(self.best_edge[b]) {
+                        self.best_edge[b] = k;
+                    }
+                } else if self.label[w] == FREE
+                    && (self.best_edge[w] == NONE || slack < self.slack(self.best_edge[w]))
+                {
+                    self.best_edge[w] = k;
+                }
+            }
+        }
+        false
+    }
+    /// Changes the dual variables by as much as possible without
+    /// making any slack negative, returning `false` if the search is
+    /// over, as no augmenting path can be found
+    fn adjust_duals(&mut self, max_cardinality: bool) -> bool {
+        enum Delta {
+            Finished,
+            Edge(usize),
+            Blossom(usize),
+            Expand(usize),
+        }
+
+        let n = self.vertices;
+        let mut best: Option<(i128, Delta)> = None;
+        let mut consider = |delta: i128, kind: Delta| {
+            if best.as_ref().is_none_or(|(least, _)| delta < *least) {
+                best = Some((delta, kind));
+            }
+        };
+
+        // Leaving every vertex free, when a vertex's dual reaches zero
+        if !max_cardinality {
+            let least = self.dual[..n].iter().copied().min().unwrap_or(0);
+            consider(least, Delta::Finished);
+        }
+        // An edge from a free vertex to an outer blossom losing its
+        // slack
+        for v in 0..n {
+            if self.label[self.in_blossom[v]] == FREE && self.best_edge[v] != NONE {
+                consider(
+                    self.slack(self.best_edge[v]),
+                    Delta::Edge(self.best_edge[v]),
+                );
+            }
+        }
+        // An edge between two outer blossoms losing its slack
+        for b in 0..2 * n {
+            if self.parent[b] == NONE && self.label[b] == OUTER && self.best_edge[b] != NONE {
+                consider(
+                    self.slack(self.best_edge[b]) / 2,
+                    Delta::Blossom(self.best_edge[b]),
+                );
+            }
+        }
+        // An inner blossom's dual reaching zero
+        for b in n..2 * n {
+            if self.base[b] != NONE && self.parent[b] == NONE && self.label[b] == INNER {
+                consider(self.dual[b], Delta::Expand(b));
+            }
+        }
+
+        let (delta, kind) = best.unwrap_or_else(|| {
+            // Only when looking for the most edges, with no more to be
+            // found
+            let least = self.dual[..n].iter().copied().min().unwrap_or(0);
+            (least.max(0), Delta::Finished)
+        });
+
+        for v in 0..n {
+            match self.label[self.in_blossom[v]] {
+                OUTER => self.dual[v] -= delta,
+                INNER => self.dual[v] += delta,
+                _ => {}
+            }
+        }
+        for b in n..2 * n {
+            if self.base[b] != NONE && self.parent[b] == NONE {
+                match self.label[b] {
+                    OUTER => self.dual[b] += delta,
+                    INNER => self.dual[b] -= delta,
+                    _ => {}
+                }
+            }
+        }
+
+        match kind {
+            Delta::Finished => return false,
+            Delta::Edge(k) => {
+                self.allowed[k] = true;
+                let (i, j, _) = self.edges[k];
+                let outer = if self.label[self.in_blossom[i]] == FREE {
+                    j
+                } else {
+                    i
+                };
+                self.queue.push(outer);
+            }
+            Delta::Blossom(k) => {
+                self.allowed[k] = true;
+                self.queue.push(self.edges[k].0);
+            }
+            Delta::Expand(b) => self.expand_blossom(b, false),
+        }
+        true
+    }
+    /// Runs stages of the search, each augmenting the matching by one
+    /// edge, until no augmenting path is left
+    fn solve(&mut self, max_cardinality: bool) {
+        let n = self.vertices;
+        for _ in 0..n {
+            self.label.fill(FREE);
+            self.best_edge.fill(NONE);
+            for list in &mut self.best_edges[n..] {
+                *list = None;
+            }
+            self.allowed.fill(false);
+            self.queue.clear();
+
+            for v in 0..n {
+                if self.mate[v] == NONE && self.label[self.in_blossom[v]] == FREE {
+                    self.assign_label(v, OUTER, NONE);
+                }
+            }
+
+            let augmented = loop {
+                if self.scan() {
+                    break true;
+                }
+                if !self.adjust_duals(max_cardinality) {
+                    break false;
+                }
+            };
+            if !augmented {
+                return;
+            }
+
+            // Expands outer blossoms with a dual of zero, which could
+            // otherwise hide a better matching
+            for b in n..2 * n {
+                if self.parent[b] == NONE
+                    && self.base[b] != NONE
+                    && self.label[b] == OUTER
+                    && self.dual[b] == 0
+                {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+    }
+}
+
+/// The index of child `j` of a blossom with `len` children, where a
+/// negative `j` counts back from the end
+const fn wrap(j: isize, len: usize) -> usize {
+    if j < 0 {
+        len - j.unsigned_abs()
+    } else {
+        to_usize(j)
+    }
+}
+
+#[allow(clippy::cast_possible_wrap)]
+const fn to_isize(i: usize) -> isize {
+    i as isize
+}
+
+#[allow(clippy::cast_sign_loss)]
+const fn to_usize(i: isize) -> usize {
+    i as usize
+}
@@ -0,0 +1,185 @@
+mod acyclic;
+mod biconnected;
+/// Measures of how central each vertex is within a graph
+pub mod centrality;
+mod cliques;
+mod compare;
+mod components;
+mod connectivity;
+mod constrained;
+mod contraction;
+mod cut;
+mod degrees;
+mod dfs;
+mod distances;
+mod ear;
+mod ego;
+mod girvan_newman;
+mod incremental;
+mod isomorphism;
+mod matching;
+/// Parallel versions of algorithms that run over a
+/// [`FrozenGraph`](crate::FrozenGraph) snapshot, using rayon
+#[cfg(feature = "rayon")]
+pub mod parallel;
+mod partition;
+mod path;
+mod path_cache;
+mod registry;
+mod reliability;
+mod shortest_path;
+mod sparsify;
+mod time_dependent;
+mod topological_order;
+mod tours;
+mod traversal;
+/// Rooted trees, with parents, children and lowest common ancestors
+pub mod tree;
+mod triangles;
+mod trim;
+
+use alloc::vec::Vec;
+use core::hash::{BuildHasher, Hash};
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, id::EdgeId, Allocator, Graph, VertexId};
+
+pub use acyclic::{topological_sort, Acyclic};
+pub use biconnected::{
+    articulation_points, biconnected_components, block_cut_tree, Block, BlockCutNode,
+};
+pub use cliques::MaximalCliques;
+pub use compare::{compare_snapshots, ComponentChange, SnapshotComparison};
+pub use components::{connected_components, strongly_connected_components, Components};
+pub use connectivity::DynamicConnectivity;
+pub use constrained::{resource_constrained_path, ConstrainedPath};
+pub use contraction::ContractionHierarchy;
+pub use cut::MinCut;
+pub use dfs::{DfsTree, EdgeClass};
+pub use ear::{ear_decomposition, open_ear_decomposition, Ear};
+pub use girvan_newman::edge_betweenness;
+pub use incremental::IncrementalShortestPaths;
+pub use isomorphism::{find_subgraph_isomorphisms, SubgraphIsomorphisms};
+pub use matching::Matching;
+pub use partition::partition;
+pub use path::{AllSimplePaths, Path};
+pub use path_cache::PathCache;
+pub use registry::{Algorithm, Outcome, Registry};
+pub use reliability::reliability;
+pub use shortest_path::{
+    dial, dijkstra, k_shortest_paths, nearest_matching, ShortestPath, ShortestPaths,
+};
+pub use sparsify::Sparsified;
+pub use time_dependent::{earliest_arrival, temporal_bfs, EarliestArrival};
+pub use topological_order::TopologicalOrder;
+pub use tours::Trail;
+pub use traversal::{Bfs, Dfs, TraversalOptions};
+
+/// Collects the edges of the vertex `id` along with the vertex
+/// on the other side of each edge, ignoring edge direction
+///
+/// Returns an empty [`Vec`] if `id` isn't within the graph
+pub(crate) fn neighbors<
+    'id,
+    Item,
+    Weight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    id: VertexId<'id>,
+    token: &GhostToken<'id>,
+) -> Vec<(EdgeId<'id>, VertexId<'id>)> {
+    graph.get_vertex(id).map_or_else(Vec::new, |vertex| {
+        vertex
+            .borrow(token)
+            .edges()
+            .filter_map(|(e_id, edge)| edge.borrow(token).other(id).map(|other| (*e_id, other)))
+            .collect()
+    })
+}
+
+/// Collects the vertices sharing an edge with the vertex `id`,
+/// ignoring edge direction and self-loops
+pub(crate) fn neighbor_set<
+    'id,
+    Item,
+    Weight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    id: VertexId<'id>,
+    token: &GhostToken<'id>,
+) -> HashSet<VertexId<'id>> {
+    neighbors(graph, id, token)
+        .into_iter()
+        .map(|(_, other)| other)
+        .filter(|other| *other != id)
+        .collect()
+}
+
+/// Collects the [`neighbor_set`] of every vertex in the graph
+pub(crate) fn adjacency<
+    'id,
+    Item,
+    Weight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    token: &GhostToken<'id>,
+) -> HashMap<VertexId<'id>, HashSet<VertexId<'id>>> {
+    graph
+        .vertex_ids()
+        .map(|id| (id, neighbor_set(graph, id, token)))
+        .collect()
+}
+
+/// The number of vertices in both `one` and `two`, found by looking
+/// up each vertex of the smaller set in the larger
+pub(crate) fn common_len<T: Hash + Eq>(one: &HashSet<T>, two: &HashSet<T>) -> usize {
+    let (smaller, larger) = if one.len() <= two.len() {
+        (one, two)
+    } else {
+        (two, one)
+    };
+    smaller.iter().filter(|v| larger.contains(v)).count()
+}
+
+/// Iterates over the edges that can be followed from the vertex
+/// `id`, respecting edge direction, along with the vertex each
+/// edge leads to and its weight
+///
+/// The iterator is empty if `id` isn't within the graph
+pub(crate) fn successors<
+    'a,
+    'id,
+    Item,
+    Weight: 'a,
+    Edge: EdgeTrait<'id, Item, Weight> + 'a,
+    S: BuildHasher + 'a,
+    A: Allocator,
+>(
+    graph: &'a Graph<'id, Item, Weight, Edge, S, A>,
+    id: VertexId<'id>,
+    token: &'a GhostToken<'id>,
+) -> impl Iterator<Item = (EdgeId<'id>, VertexId<'id>, &'a Weight)> + 'a
+where
+    'id: 'a,
+    Item: 'a,
+{
+    graph
+        .get_vertex(id)
+        .into_iter()
+        .flat_map(move |vertex| vertex.borrow(token).edges())
+        .filter_map(move |(e_id, edge)| {
+            let edge = edge.borrow(token);
+            let other = edge.traverse(id)?;
+            Some((*e_id, other, edge.get_weight()))
+        })
+}
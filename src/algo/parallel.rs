@@ -0,0 +1,178 @@
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use rayon::prelude::*;
+
+use crate::{FrozenGraph, VertexId};
+
+/// The adjacency of a [`FrozenGraph`] by index, which is cheaper
+/// to share between threads than [`VertexId`]s
+#[derive(Clone, Copy)]
+struct Csr<'a> {
+    offsets: &'a [usize],
+    targets: &'a [usize],
+}
+
+impl<'a> Csr<'a> {
+    fn new<Weight>(frozen: &'a FrozenGraph<'_, Weight>) -> Self {
+        let (offsets, targets) = frozen.adjacency();
+        Self { offsets, targets }
+    }
+    const fn len(self) -> usize {
+        self.offsets.len() - 1
+    }
+    fn neighbors(self, index: usize) -> &'a [usize] {
+        &self.targets[self.offsets[index]..self.offsets[index + 1]]
+    }
+}
+
+/// Groups the vertices of `frozen` into connected components in
+/// parallel, treating every edge as undirected
+///
+/// Each component is ordered by [`VertexId`], and the components are
+/// ordered by their smallest [`VertexId`]
+#[must_use]
+pub fn connected_components<'id, Weight>(
+    frozen: &FrozenGraph<'id, Weight>,
+) -> Vec<Vec<VertexId<'id>>> {
+    let csr = Csr::new(frozen);
+    let labels: Vec<_> = (0..csr.len()).map(AtomicUsize::new).collect();
+
+    // Every vertex's label falls to the smallest index it's
+    // connected to, one edge at a time
+    loop {
+        let changed = AtomicBool::new(false);
+
+        (0..csr.len()).into_par_iter().for_each(|index| {
+            for &other in csr.neighbors(index) {
+                let lowest = labels[index]
+                    .load(Ordering::Relaxed)
+                    .min(labels[other].load(Ordering::Relaxed));
+
+                for label in [&labels[index], &labels[other]] {
+                    if label.fetch_min(lowest, Ordering::Relaxed) > lowest {
+                        changed.store(true, Ordering::Relaxed);
+                    }
+                }
+            }
+        });
+
+        if !changed.load(Ordering::Relaxed) {
+            break;
+        }
+    }
+
+    let mut components: Vec<Vec<VertexId<'id>>> = Vec::new();
+    let mut component_of = alloc::vec![usize::MAX; frozen.vertex_len()];
+
+    for (index, label) in labels.into_iter().enumerate() {
+        let label = label.into_inner();
+
+        if component_of[label] == usize::MAX {
+            component_of[label] = components.len();
+            components.push(Vec::new());
+        }
+
+        components[component_of[label]].push(frozen.id_at(index));
+    }
+
+    components
+}
+
+/// Finds the vertices of `frozen` at each distance from the vertex at
+/// the dense index `start`, expanding each layer in parallel
+///
+/// The first layer only contains `start`. Edges are only followed in
+/// their direction, and each layer is ordered by [`VertexId`]
+///
+/// # Panics
+/// Panics if `start` is out of bounds
+#[must_use]
+pub fn bfs_layers<'id, Weight>(
+    frozen: &FrozenGraph<'id, Weight>,
+    start: usize,
+) -> Vec<Vec<VertexId<'id>>> {
+    let csr = Csr::new(frozen);
+    let visited: Vec<_> = (0..csr.len()).map(|_| AtomicBool::new(false)).collect();
+    visited[start].store(true, Ordering::Relaxed);
+
+    let mut layers = Vec::new();
+    let mut frontier = alloc::vec![start];
+
+    while !frontier.is_empty() {
+        let mut next: Vec<usize> = frontier
+            .par_iter()
+            .flat_map_iter(|index| csr.neighbors(*index).iter().copied())
+            .filter(|other| !visited[*other].swap(true, Ordering::Relaxed))
+            .collect();
+        next.par_sort_unstable();
+
+        layers.push(
+            frontier
+                .into_iter()
+                .map(|index| frozen.id_at(index))
+                .collect(),
+        );
+        frontier = next;
+    }
+
+    layers
+}
+
+/// Computes the `PageRank` of every vertex of `frozen` in parallel,
+/// ordered by dense index
+///
+/// `damping` is the chance of following an edge rather than jumping
+/// to a random vertex, usually `0.85`. The rank of vertices with no
+/// edges leaving them is spread evenly over every vertex
+#[must_use]
+#[allow(clippy::suboptimal_flops)]
+pub fn page_rank<Weight>(
+    frozen: &FrozenGraph<'_, Weight>,
+    damping: f64,
+    iterations: usize,
+) -> Vec<f64> {
+    let csr = Csr::new(frozen);
+    let len = csr.len();
+    if len == 0 {
+        return Vec::new();
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let share = 1.0 / len as f64;
+
+    let mut incoming = alloc::vec![Vec::new(); len];
+    for index in 0..len {
+        for &other in csr.neighbors(index) {
+            incoming[other].push(index);
+        }
+    }
+
+    let mut rank = alloc::vec![share; len];
+
+    for _ in 0..iterations {
+        #[allow(clippy::cast_precision_loss)]
+        let outgoing: Vec<f64> = (0..len)
+            .into_par_iter()
+            .map(|index| match csr.neighbors(index).len() {
+                0 => 0.0,
+                degree => rank[index] / degree as f64,
+            })
+            .collect();
+
+        let dangling: f64 = (0..len)
+            .into_par_iter()
+            .filter(|index| csr.neighbors(*index).is_empty())
+            .map(|index| rank[index])
+            .sum();
+
+        let base = damping * dangling * share + (1.0 - damping) * share;
+
+        rank = incoming
+            .par_iter()
+            .map(|sources| damping * sources.iter().map(|s| outgoing[*s]).sum::<f64>() + base)
+            .collect();
+    }
+
+    rank
+}
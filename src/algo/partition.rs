@@ -0,0 +1,253 @@
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use core::hash::BuildHasher;
+
+use hashbrown::HashMap;
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, Allocator, Graph, SecondaryMap, VertexId};
+
+/// The weight of the edges joining each vertex to the others, by
+/// index, ignoring edge direction
+type Adjacency = Vec<Vec<(usize, f64)>>;
+
+/// Splits the vertices of `graph` into `k` sets of as near to the
+/// same size as possible, with as little weight on the edges between
+/// different sets as it can find, ignoring edge direction
+///
+/// `weight` is given an edge's weight and returns how much cutting
+/// it costs. The graph is cut in two again and again, and each cut
+/// is improved with the Kernighan–Lin heuristic, swapping vertices
+/// between its two sides while that makes the cut lighter. The cut
+/// found isn't necessarily the lightest, but the sizes of the sets
+/// never differ by more than one. Fewer than `k` vertices leaves some
+/// sets empty, and `k` being zero gives no sets at all
+///
+/// ```rust
+/// use graph::{algo::partition, edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+///
+/// GhostToken::new(|mut token| {
+///     let mut graph: Graph<(), u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+///     let v: Vec<_> = (0..6).map(|_| graph.add_vertex(())).collect();
+///     // Two triangles joined by a single edge
+///     for (a, b) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+///         graph.add_edge(v[a], v[b], 1, &mut token).unwrap();
+///     }
+///
+///     let mut parts = partition(&graph, 2, |w| f64::from(*w), &token);
+///     parts.sort();
+///     assert_eq!(parts, vec![v[..3].to_vec(), v[3..].to_vec()]);
+/// })
+/// ```
+#[must_use]
+pub fn partition<'id, Item, Weight, Edge, S, A>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    k: usize,
+    weight: impl Fn(&Weight) -> f64,
+    token: &GhostToken<'id>,
+) -> Vec<Vec<VertexId<'id>>>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    let mut ids: Vec<_> = graph.vertex_ids().collect();
+    ids.sort_unstable();
+    let index: SecondaryMap<_, _> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+    let mut joined: Vec<HashMap<usize, f64>> = vec![HashMap::new(); ids.len()];
+    for (_, from, to, edge) in graph.unique_edges(token) {
+        let (from, to) = (index[from], index[to]);
+        if from != to {
+            *joined[from].entry(to).or_default() += weight(edge);
+            *joined[to].entry(from).or_default() += weight(edge);
+        }
+    }
+    let adjacency: Adjacency = joined
+        .into_iter()
+        .map(|others| {
+            let mut others: Vec<_> = others.into_iter().collect();
+            others.sort_unstable_by_key(|(other, _)| *other);
+            others
+        })
+        .collect();
+
+    let mut parts = Vec::with_capacity(k);
+    if k > 0 {
+        let mut side = vec![None; ids.len()];
+        split(
+            &adjacency,
+            (0..ids.len()).collect(),
+            k,
+            &mut side,
+            &mut parts,
+        );
+    }
+
+    parts
+        .into_iter()
+        .map(|part| part.into_iter().map(|i| ids[i]).collect())
+        .collect()
+}
+
+/// Splits `members` into `k` sets, adding them to `parts`
+fn split(
+    adjacency: &Adjacency,
+    members: Vec<usize>,
+    k: usize,
+    side: &mut [Option<bool>],
+    parts: &mut Vec<Vec<usize>>,
+) {
+    if k == 1 {
+        parts.push(members);
+        return;
+    }
+
+    let first_k = k / 2;
+    let first_len = members.len() * first_k / k;
+    let (first, second) = bisect(adjacency, &members, first_len, side);
+
+    split(adjacency, first, first_k, side, parts);
+    split(adjacency, second, k - first_k, side, parts);
+}
+
+/// Cuts `members` in two, with `first_len` of them on the first side
+fn bisect(
+    adjacency: &Adjacency,
+    members: &[usize],
+    first_len: usize,
+    side: &mut [Option<bool>],
+) -> (Vec<usize>, Vec<usize>) {
+    // Starting from a breadth first region keeps neighbours together,
+    // which leaves less for the refinement to do
+    for (n, i) in grown(adjacency, members, side).into_iter().enumerate() {
+        side[i] = Some(n < first_len);
+    }
+
+    while refine(adjacency, members, side) {}
+
+    let (first, second) = members.iter().partition(|i| side[**i] == Some(true));
+    for i in members {
+        side[*i] = None;
+    }
+    (first, second)
+}
+
+/// `members` in breadth first order through the edges between them,
+/// starting again from the lowest index left whenever a region runs
+/// out, using `side` to mark them as members
+fn grown(adjacency: &Adjacency, members: &[usize], side: &mut [Option<bool>]) -> Vec<usize> {
+    for i in members {
+        side[*i] = Some(false);
+    }
+
+    let mut order = Vec::with_capacity(members.len());
+    let mut queue = VecDeque::new();
+    for start in members {
+        if side[*start] != Some(false) {
+            continue;
+        }
+        side[*start] = Some(true);
+        queue.push_back(*start);
+
+        while let Some(i) = queue.pop_front() {
+            order.push(i);
+            for (other, _) in &adjacency[i] {
+                if side[*other] == Some(false) {
+                    side[*other] = Some(true);
+                    queue.push_back(*other);
+                }
+            }
+        }
+    }
+
+    order
+}
+
+/// Runs a single Kernighan–Lin pass over `members`, returning whether
+/// it made the cut lighter
+///
+/// Pairs of vertices from opposite sides are swapped one after
+/// another, each time choosing the swap that makes the cut lightest,
+/// even if that makes it heavier, and then every swap after the point
+/// where the cut was lightest is undone
+fn refine(adjacency: &Adjacency, members: &[usize], side: &mut [Option<bool>]) -> bool {
+    // How much lighter the cut would be if each vertex yet to be
+    // swapped changed side
+    let mut unlocked: HashMap<usize, f64> = members
+        .iter()
+        .map(|i| {
+            let gain = adjacency[*i]
+                .iter()
+                .filter_map(|(other, weight)| {
+                    let other = side[*other]?;
+                    Some(if Some(other) == side[*i] {
+                        -weight
+                    } else {
+                        *weight
+                    })
+                })
+                .sum();
+            (*i, gain)
+        })
+        .collect();
+
+    let mut swaps = Vec::new();
+    let (mut total, mut best, mut best_len) = (0.0, 0.0, 0);
+
+    while let Some(first) = most_gain(&unlocked, side, true) {
+        let moved = shift(adjacency, first, side, &mut unlocked);
+        // The second vertex is chosen knowing the first has moved
+        let Some(second) = most_gain(&unlocked, side, false) else {
+            side[first] = Some(true);
+            break;
+        };
+        total += moved;
+        total += shift(adjacency, second, side, &mut unlocked);
+
+        swaps.push([first, second]);
+        if total > best + f64::EPSILON * libm::fabs(best).max(1.0) {
+            best = total;
+            best_len = swaps.len();
+        }
+    }
+
+    for i in swaps.drain(best_len..).flatten() {
+        side[i] = side[i].map(|side| !side);
+    }
+    best_len > 0
+}
+
+/// The vertex on the side `from` that would make the cut lightest by
+/// changing side, with ties going to the lowest index
+fn most_gain(unlocked: &HashMap<usize, f64>, side: &[Option<bool>], from: bool) -> Option<usize> {
+    unlocked
+        .iter()
+        .filter(|(i, _)| side[**i] == Some(from))
+        .max_by(|(i, one), (j, two)| one.total_cmp(two).then(j.cmp(i)))
+        .map(|(i, _)| *i)
+}
+
+/// Moves `i` to the other side and locks it, returning how much
+/// lighter that made the cut
+fn shift(
+    adjacency: &Adjacency,
+    i: usize,
+    side: &mut [Option<bool>],
+    unlocked: &mut HashMap<usize, f64>,
+) -> f64 {
+    let gain = unlocked.remove(&i).unwrap_or_default();
+    let Some(from) = side[i] else {
+        return 0.0;
+    };
+    side[i] = Some(!from);
+
+    for (other, weight) in &adjacency[i] {
+        if let (Some(other_side), Some(other_gain)) = (side[*other], unlocked.get_mut(other)) {
+            // `i` used to be on the same side as `other` if they're
+            // now on opposite sides
+            let change = 2.0 * weight;
+            *other_gain += if other_side == from { change } else { -change };
+        }
+    }
+
+    gain
+}
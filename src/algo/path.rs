@@ -0,0 +1,363 @@
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+use hashbrown::HashSet;
+
+use crate::{
+    edge::EdgeTrait, ghost::GhostToken, id::EdgeId, Allocator, Graph, GraphError, Measure, VertexId,
+};
+
+use super::{successors, ConstrainedPath, ShortestPath};
+
+/// A walk through a graph, made of the vertices it visits and the
+/// edges it follows between them
+///
+/// Keeping the edges as well as the vertices tells apart paths that
+/// visit the same vertices along different parallel edges. A path
+/// always has at least one vertex, and one edge fewer than it has
+/// vertices, so that `edges()[i]` joins `vertices()[i]` and
+/// `vertices()[i + 1]`. A path doesn't borrow the graph, so it can
+/// outlive changes to it, which [`Path::is_valid`] checks for
+///
+/// ```rust
+/// use graph::{algo::Path, edge::DirectedWeightedEdge, ghost::GhostToken, Graph};
+///
+/// GhostToken::new(|mut token| {
+///     let mut graph: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+///     let a = graph.add_vertex(());
+///     let b = graph.add_vertex(());
+///     let c = graph.add_vertex(());
+///     let ab = graph.add_edge(a, b, 2, &mut token).unwrap();
+///     let bc = graph.add_edge(b, c, 3, &mut token).unwrap();
+///
+///     let first = Path::new(a).with_step(ab, b);
+///     let path = first.concat(Path::new(b).with_step(bc, c)).unwrap();
+///     assert_eq!(path.vertices(), &[a, b, c]);
+///     assert_eq!(path.cost(&graph, &token), Some(5));
+///
+///     graph.remove_edge_between(b, c, &mut token).unwrap();
+///     assert!(!path.is_valid(&graph, &token));
+/// })
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Path<'id> {
+    vertices: Vec<VertexId<'id>>,
+    edges: Vec<EdgeId<'id>>,
+}
+
+impl<'id> Path<'id> {
+    /// Constructs a path that starts and ends at `start`, without
+    /// following any edges
+    #[must_use]
+    pub fn new(start: VertexId<'id>) -> Self {
+        Self {
+            vertices: vec![start],
+            edges: Vec::new(),
+        }
+    }
+    /// Constructs a path from the vertices it visits and the edges it
+    /// follows between them, or `None` if there isn't exactly one
+    /// edge fewer than there are vertices
+    #[must_use]
+    pub fn from_parts(vertices: Vec<VertexId<'id>>, edges: Vec<EdgeId<'id>>) -> Option<Self> {
+        (edges.len() + 1 == vertices.len()).then_some(Self { vertices, edges })
+    }
+    /// Extends the path along `edge` to `vertex`
+    pub fn push(&mut self, edge: EdgeId<'id>, vertex: VertexId<'id>) {
+        self.edges.push(edge);
+        self.vertices.push(vertex);
+    }
+    /// Extends the path along `edge` to `vertex`, see [`Path::push`]
+    #[must_use]
+    pub fn with_step(mut self, edge: EdgeId<'id>, vertex: VertexId<'id>) -> Self {
+        self.push(edge, vertex);
+        self
+    }
+    /// Removes the last step of the path, returning the edge and
+    /// vertex it went along, or `None` if the path has no edges
+    pub fn pop(&mut self) -> Option<(EdgeId<'id>, VertexId<'id>)> {
+        let edge = self.edges.pop()?;
+        Some((edge, self.vertices.pop()?))
+    }
+    /// Joins `other` onto the end of `self`, or returns `None` if
+    /// `other` doesn't start where `self` ends
+    #[must_use]
+    pub fn concat(mut self, other: Self) -> Option<Self> {
+        if self.end() != other.start() {
+            return None;
+        }
+
+        self.vertices.extend_from_slice(&other.vertices[1..]);
+        self.edges.extend(other.edges);
+        Some(self)
+    }
+    /// The vertex the path starts at
+    #[must_use]
+    pub fn start(&self) -> VertexId<'id> {
+        self.vertices[0]
+    }
+    /// The vertex the path ends at
+    #[must_use]
+    pub fn end(&self) -> VertexId<'id> {
+        self.vertices[self.vertices.len() - 1]
+    }
+    /// The number of edges along the path
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.edges.len()
+    }
+    /// Whether the path doesn't follow any edges, and so starts and
+    /// ends at the same vertex
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+    /// The vertices along the path, in order
+    #[must_use]
+    pub fn vertices(&self) -> &[VertexId<'id>] {
+        &self.vertices
+    }
+    /// The edges along the path, in order
+    #[must_use]
+    pub fn edges(&self) -> &[EdgeId<'id>] {
+        &self.edges
+    }
+    /// Every step along the path, as the vertex it leaves, the edge
+    /// it follows and the vertex it reaches
+    pub fn iter_edges(
+        &self,
+    ) -> impl Iterator<Item = (VertexId<'id>, EdgeId<'id>, VertexId<'id>)> + '_ {
+        self.edges
+            .iter()
+            .zip(self.vertices.windows(2))
+            .map(|(edge, pair)| (pair[0], *edge, pair[1]))
+    }
+    /// Consumes the path, returning its vertices and edges
+    #[must_use]
+    pub fn into_parts(self) -> (Vec<VertexId<'id>>, Vec<EdgeId<'id>>) {
+        (self.vertices, self.edges)
+    }
+    /// Whether the path can still be walked in `graph`: every vertex
+    /// and edge along it is within the graph, and each edge can be
+    /// followed from the vertex before it to the vertex after it,
+    /// respecting edge direction
+    #[must_use]
+    pub fn is_valid<Item, Weight, Edge, S, A>(
+        &self,
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        token: &GhostToken<'id>,
+    ) -> bool
+    where
+        Edge: EdgeTrait<'id, Item, Weight>,
+        S: BuildHasher,
+        A: Allocator,
+    {
+        graph.get_vertex(self.start()).is_some()
+            && self.iter_edges().all(|(from, edge, to)| {
+                graph
+                    .get_edge(edge)
+                    .is_some_and(|edge| edge.borrow(token).traverse(from) == Some(to))
+            })
+    }
+    /// The total weight of every edge along the path, or `None` if
+    /// the path isn't valid in `graph`, see [`Path::is_valid`]
+    #[must_use]
+    pub fn cost<Item, Weight, Edge, S, A>(
+        &self,
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        token: &GhostToken<'id>,
+    ) -> Option<Weight>
+    where
+        Weight: Measure,
+        Edge: EdgeTrait<'id, Item, Weight>,
+        S: BuildHasher,
+        A: Allocator,
+    {
+        if !self.is_valid(graph, token) {
+            return None;
+        }
+
+        Some(self.edges.iter().fold(Weight::ZERO, |total, edge| {
+            total.saturating_add(*graph.edges[edge].borrow(token).get_weight())
+        }))
+    }
+}
+
+impl<'id, Distance> From<ShortestPath<'id, Distance>> for Path<'id> {
+    fn from(path: ShortestPath<'id, Distance>) -> Self {
+        Self {
+            vertices: path.vertices,
+            edges: path.edges,
+        }
+    }
+}
+
+impl<'id, Cost, Resource> From<ConstrainedPath<'id, Cost, Resource>> for Path<'id> {
+    fn from(path: ConstrainedPath<'id, Cost, Resource>) -> Self {
+        Self {
+            vertices: path.vertices,
+            edges: path.edges,
+        }
+    }
+}
+
+type SimplePathsBetween<'a, 'id, Item, Weight, Edge, S, A> =
+    Result<AllSimplePaths<'a, 'id, Item, Weight, Edge, S, A>, GraphError<'id, Item, Weight, Edge>>;
+
+/// An iterator over every simple path between two vertices, created
+/// by [`Graph::all_simple_paths`]
+pub struct AllSimplePaths<
+    'a,
+    'id,
+    Item,
+    Weight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S,
+    A: Allocator,
+> {
+    graph: &'a Graph<'id, Item, Weight, Edge, S, A>,
+    token: &'a GhostToken<'id>,
+    target: VertexId<'id>,
+    max_len: usize,
+    /// The path being extended, which never reaches the target
+    path: Path<'id>,
+    on_path: HashSet<VertexId<'id>>,
+    /// The edges leaving each vertex along the path, along with how
+    /// many of them have been followed
+    stack: Vec<(Vec<(EdgeId<'id>, VertexId<'id>)>, usize)>,
+    /// The path without any edges, given first when the source is
+    /// the target
+    trivial: Option<Path<'id>>,
+}
+
+impl<'a, 'id, Item, Weight, Edge, S, A> AllSimplePaths<'a, 'id, Item, Weight, Edge, S, A>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// The edges that can be followed from `id`, in order of the
+    /// vertex they lead to
+    fn leaving(&self, id: VertexId<'id>) -> Vec<(EdgeId<'id>, VertexId<'id>)> {
+        let mut leaving: Vec<_> = successors(self.graph, id, self.token)
+            .map(|(edge, other, _)| (edge, other))
+            .collect();
+        leaving.sort_unstable_by_key(|(edge, other)| (*other, *edge));
+        leaving
+    }
+}
+
+impl<'a, 'id, Item, Weight, Edge, S, A> Iterator
+    for AllSimplePaths<'a, 'id, Item, Weight, Edge, S, A>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    type Item = Path<'id>;
+
+    fn next(&mut self) -> Option<Path<'id>> {
+        if let Some(trivial) = self.trivial.take() {
+            return Some(trivial);
+        }
+
+        while let Some((leaving, next)) = self.stack.last_mut() {
+            let Some(&(edge, other)) = leaving.get(*next) else {
+                self.stack.pop();
+                if let Some((_, left)) = self.path.pop() {
+                    self.on_path.remove(&left);
+                }
+                continue;
+            };
+            *next += 1;
+
+            if self.on_path.contains(&other) {
+                continue;
+            }
+            if other == self.target {
+                return Some(self.path.clone().with_step(edge, other));
+            }
+            if self.path.len() + 1 < self.max_len {
+                self.path.push(edge, other);
+                self.on_path.insert(other);
+                let leaving = self.leaving(other);
+                self.stack.push((leaving, 0));
+            }
+        }
+
+        None
+    }
+}
+
+impl<'id, Item, Weight, Edge, S, A> Graph<'id, Item, Weight, Edge, S, A>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// Returns an iterator over every path from `from` to `to` that
+    /// doesn't visit any vertex twice and follows at most `max_len`
+    /// edges, respecting edge direction
+    ///
+    /// Paths are found one at a time by a depth first search, which
+    /// only holds the path being searched in memory, and are given
+    /// in order of the vertices they visit. If `from` is `to`, the
+    /// only path is the one without any edges. There can be
+    /// exponentially many paths, so `max_len` is best kept small on
+    /// dense graphs
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let square: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+    ///     for i in 0..4 {
+    ///         graph.add_edge(square[i], square[(i + 1) % 4], (), &mut token).unwrap();
+    ///     }
+    ///
+    ///     let paths = graph.all_simple_paths(square[0], square[2], 4, &token).unwrap();
+    ///     assert_eq!(paths.count(), 2);
+    ///     let paths = graph.all_simple_paths(square[0], square[1], 2, &token).unwrap();
+    ///     assert_eq!(paths.count(), 1);
+    /// })
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if either `from` or `to`
+    /// isn't within the graph
+    pub fn all_simple_paths<'a>(
+        &'a self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        max_len: usize,
+        token: &'a GhostToken<'id>,
+    ) -> SimplePathsBetween<'a, 'id, Item, Weight, Edge, S, A> {
+        for id in [from, to] {
+            if self.get_vertex(id).is_none() {
+                return Err(GraphError::VertexNotFound(id));
+            }
+        }
+
+        let mut paths = AllSimplePaths {
+            graph: self,
+            token,
+            target: to,
+            max_len,
+            path: Path::new(from),
+            on_path: HashSet::new(),
+            stack: Vec::new(),
+            trivial: None,
+        };
+        if from == to {
+            paths.trivial = Some(Path::new(from));
+        } else if max_len > 0 {
+            paths.on_path.insert(from);
+            let leaving = paths.leaving(from);
+            paths.stack.push((leaving, 0));
+        }
+
+        Ok(paths)
+    }
+}
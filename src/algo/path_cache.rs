@@ -0,0 +1,249 @@
+use alloc::collections::BTreeMap;
+use core::hash::BuildHasher;
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::{
+    edge::EdgeTrait, ghost::GhostToken, id::EdgeId, Allocator, Graph, GraphError, GraphObserver,
+    Measure, VertexId,
+};
+
+use super::{shortest_path::shortest_avoiding, ShortestPath};
+
+type Route<'id> = (VertexId<'id>, VertexId<'id>);
+
+/// A cached answer to a shortest path query
+#[derive(Debug, Clone)]
+struct Entry<'id, Distance> {
+    path: Option<ShortestPath<'id, Distance>>,
+    /// When the entry was last used, which orders the entries from
+    /// least to most recently used
+    used: u64,
+}
+
+/// Remembers the shortest paths between the pairs of vertices most
+/// recently asked about, up to a fixed number of them
+///
+/// The least recently used path is forgotten to make room for a new
+/// one. Given to [`Graph::set_observer`] through a
+/// [`SharedObserver`](crate::SharedObserver), the cache forgets paths
+/// as the graph changes: removing an edge only forgets the paths
+/// along it, but adding or changing an edge could make any path
+/// shorter, so forgets every path. Without an observer,
+/// [`PathCache::clear`] should be called after the graph changes.
+///
+/// `length` is given an edge's weight and returns the length of the
+/// edge, which must never be negative. The same `length` should be
+/// given to every query
+///
+/// ```rust
+/// use graph::{
+///     algo::PathCache, edge::DirectedWeightedEdge, ghost::GhostToken, Graph, SharedObserver,
+/// };
+///
+/// GhostToken::new(|mut token| {
+///     let mut graph: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+///     let a = graph.add_vertex(());
+///     let b = graph.add_vertex(());
+///     let c = graph.add_vertex(());
+///     graph.add_edge(a, b, 5, &mut token).unwrap();
+///     graph.add_edge(b, c, 5, &mut token).unwrap();
+///
+///     let cache = SharedObserver::new(PathCache::new(16));
+///     graph.set_observer(cache.clone());
+///
+///     let route = cache.with(|cache| {
+///         let route = cache.shortest_path(&graph, a, c, |w| *w, &token).unwrap();
+///         route.map(|route| route.length)
+///     });
+///     assert_eq!(route, Some(10));
+///
+///     // Adding an edge forgets the cached path
+///     graph.add_edge(a, c, 3, &mut token).unwrap();
+///     assert!(cache.with(|cache| cache.is_empty()));
+/// })
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathCache<'id, Distance> {
+    capacity: usize,
+    entries: HashMap<Route<'id>, Entry<'id, Distance>>,
+    /// Every entry, keyed by when it was last used
+    recency: BTreeMap<u64, Route<'id>>,
+    /// The entries whose paths follow each edge
+    along: HashMap<EdgeId<'id>, HashSet<Route<'id>>>,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl<'id, Distance: Measure> PathCache<'id, Distance> {
+    /// Constructs an empty cache that remembers at most `capacity`
+    /// paths, and always at least one
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            along: HashMap::new(),
+            clock: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+    /// The shortest path from `from` to `to`, or `None` if `to` can't
+    /// be reached, remembering it for next time
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if either `from` or `to`
+    /// isn't within the graph
+    pub fn shortest_path<Item, Weight, Edge, S, A>(
+        &mut self,
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        length: impl Fn(&Weight) -> Distance,
+        token: &GhostToken<'id>,
+    ) -> Result<Option<&ShortestPath<'id, Distance>>, GraphError<'id, Item, Weight, Edge>>
+    where
+        Edge: EdgeTrait<'id, Item, Weight>,
+        S: BuildHasher,
+        A: Allocator,
+    {
+        for id in [from, to] {
+            if graph.get_vertex(id).is_none() {
+                return Err(GraphError::VertexNotFound(id));
+            }
+        }
+
+        let route = (from, to);
+        self.clock += 1;
+
+        if let Some(entry) = self.entries.get_mut(&route) {
+            self.hits += 1;
+            self.recency.remove(&entry.used);
+            self.recency.insert(self.clock, route);
+            entry.used = self.clock;
+        } else {
+            self.misses += 1;
+            if self.entries.len() >= self.capacity {
+                if let Some((_, oldest)) = self.recency.pop_first() {
+                    self.forget(oldest);
+                }
+            }
+
+            let (vertices, edges) = (HashSet::new(), HashSet::new());
+            let path = shortest_avoiding(graph, from, to, &length, &vertices, &edges, token);
+            for edge in path.iter().flat_map(|path| &path.edges) {
+                self.along.entry(*edge).or_default().insert(route);
+            }
+            self.recency.insert(self.clock, route);
+            self.entries.insert(
+                route,
+                Entry {
+                    path,
+                    used: self.clock,
+                },
+            );
+        }
+
+        Ok(self.entries[&route].path.as_ref())
+    }
+    /// Forgets the path from `from` to `to`, returning whether it was
+    /// remembered
+    pub fn invalidate(&mut self, from: VertexId<'id>, to: VertexId<'id>) -> bool {
+        let route = (from, to);
+        let Some(used) = self.entries.get(&route).map(|entry| entry.used) else {
+            return false;
+        };
+        self.recency.remove(&used);
+        self.forget(route);
+        true
+    }
+    /// Removes the entry for `route`, and the edges of its path from
+    /// the index of paths along each edge
+    fn forget(&mut self, route: Route<'id>) {
+        let Some(entry) = self.entries.remove(&route) else {
+            return;
+        };
+
+        for edge in entry.path.iter().flat_map(|path| &path.edges) {
+            if let Some(routes) = self.along.get_mut(edge) {
+                routes.remove(&route);
+                if routes.is_empty() {
+                    self.along.remove(edge);
+                }
+            }
+        }
+    }
+}
+
+impl<'id, Distance> PathCache<'id, Distance> {
+    /// The most paths the cache remembers
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+    /// The number of paths the cache remembers
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// Whether the cache doesn't remember any paths
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// The number of queries answered from the cache
+    #[must_use]
+    pub const fn hits(&self) -> u64 {
+        self.hits
+    }
+    /// The number of queries that had to search the graph
+    #[must_use]
+    pub const fn misses(&self) -> u64 {
+        self.misses
+    }
+    /// Forgets every path
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+        self.along.clear();
+    }
+}
+
+impl<'id, Distance: Measure> GraphObserver<'id> for PathCache<'id, Distance> {
+    fn on_vertex_changed(&mut self, _: VertexId<'id>) {
+        self.clear();
+    }
+    fn on_vertex_removed(&mut self, id: VertexId<'id>) {
+        // Paths through the vertex were forgotten along with its
+        // edges, which leaves the ones that start or end there
+        let routes: alloc::vec::Vec<_> = self
+            .entries
+            .keys()
+            .filter(|(from, to)| *from == id || *to == id)
+            .copied()
+            .collect();
+        for (from, to) in routes {
+            self.invalidate(from, to);
+        }
+    }
+    fn on_edge_added(&mut self, _: EdgeId<'id>, _: VertexId<'id>, _: VertexId<'id>) {
+        self.clear();
+    }
+    fn on_edge_changed(&mut self, _: EdgeId<'id>) {
+        self.clear();
+    }
+    fn on_edge_removed(&mut self, id: EdgeId<'id>) {
+        let Some(routes) = self.along.remove(&id) else {
+            return;
+        };
+        for (from, to) in routes {
+            self.invalidate(from, to);
+        }
+    }
+    fn on_cleared(&mut self) {
+        self.clear();
+    }
+}
@@ -0,0 +1,178 @@
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+
+use hashbrown::HashMap;
+
+use crate::{
+    algo::{centrality, topological_sort},
+    ghost::GhostToken,
+    ops::GraphOps,
+    VertexId,
+};
+
+/// An analysis that can be run over an `Input`, such as a graph,
+/// producing an `Output`
+///
+/// Implementing this lets custom analyses be added to a
+/// [`Registry`] and run by name alongside the built-in ones
+pub trait Algorithm<'id, Input: ?Sized, Output> {
+    /// The name the algorithm is registered under
+    fn name(&self) -> &str;
+    /// A short description of what the algorithm does
+    fn description(&self) -> &str;
+    /// Runs the algorithm over `input`
+    fn run(&self, input: &Input, token: &GhostToken<'id>) -> Output;
+}
+
+/// An [`Algorithm`] made from a closure
+struct Named<F> {
+    name: String,
+    description: String,
+    run: F,
+}
+
+impl<'id, Input: ?Sized, Output, F> Algorithm<'id, Input, Output> for Named<F>
+where
+    F: Fn(&Input, &GhostToken<'id>) -> Output,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn description(&self) -> &str {
+        &self.description
+    }
+    fn run(&self, input: &Input, token: &GhostToken<'id>) -> Output {
+        (self.run)(input, token)
+    }
+}
+
+/// The result of one of the built-in algorithms in a [`Registry`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome<'id> {
+    /// A score for every vertex, such as its centrality
+    Scores(HashMap<VertexId<'id>, f64>),
+    /// An ordering of the vertices
+    Order(Vec<VertexId<'id>>),
+    /// The vertices an algorithm couldn't handle, such as those on
+    /// or after a cycle when ordering vertices topologically
+    Unordered(Vec<VertexId<'id>>),
+}
+
+/// A collection of [`Algorithm`]s that can be looked up and run
+/// by name
+pub struct Registry<'r, 'id, Input: ?Sized, Output> {
+    algorithms: BTreeMap<String, Box<dyn Algorithm<'id, Input, Output> + 'r>>,
+}
+
+impl<'r, 'id, Input: ?Sized, Output> Default for Registry<'r, 'id, Input, Output> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'r, 'id, Input: ?Sized, Output> Registry<'r, 'id, Input, Output> {
+    /// Constructs a new empty [`Registry`]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            algorithms: BTreeMap::new(),
+        }
+    }
+    /// Adds `algorithm` to the registry under its
+    /// [name](Algorithm::name), returning the algorithm it replaced
+    pub fn register(
+        &mut self,
+        algorithm: impl Algorithm<'id, Input, Output> + 'r,
+    ) -> Option<Box<dyn Algorithm<'id, Input, Output> + 'r>> {
+        self.algorithms
+            .insert(String::from(algorithm.name()), Box::new(algorithm))
+    }
+    /// Adds the closure `run` to the registry under `name`,
+    /// returning the algorithm it replaced
+    pub fn register_fn(
+        &mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        run: impl Fn(&Input, &GhostToken<'id>) -> Output + 'r,
+    ) -> Option<Box<dyn Algorithm<'id, Input, Output> + 'r>> {
+        self.register(Named {
+            name: name.into(),
+            description: description.into(),
+            run,
+        })
+    }
+    /// Removes the algorithm registered under `name`, returning it
+    pub fn unregister(
+        &mut self,
+        name: &str,
+    ) -> Option<Box<dyn Algorithm<'id, Input, Output> + 'r>> {
+        self.algorithms.remove(name)
+    }
+    /// The algorithm registered under `name`, if there is one
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&(dyn Algorithm<'id, Input, Output> + 'r)> {
+        self.algorithms.get(name).map(AsRef::as_ref)
+    }
+    /// Runs the algorithm registered under `name` over `input`,
+    /// returning `None` if there's no such algorithm
+    pub fn run(&self, name: &str, input: &Input, token: &GhostToken<'id>) -> Option<Output> {
+        self.get(name).map(|algorithm| algorithm.run(input, token))
+    }
+    /// The names of every registered algorithm, in alphabetical
+    /// order
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        self.algorithms.keys().map(String::as_str).collect()
+    }
+    /// The number of registered algorithms
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.algorithms.len()
+    }
+    /// Whether no algorithms are registered
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.algorithms.is_empty()
+    }
+}
+
+impl<'r, 'id, G: GraphOps<'id>> Registry<'r, 'id, G, Outcome<'id>> {
+    /// Constructs a [`Registry`] with the library's algorithms that
+    /// run over any [`GraphOps`] already registered
+    ///
+    /// Edges are treated as having a length of `1.0`, and `PageRank`
+    /// uses a damping factor of `0.85`
+    #[must_use]
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+
+        registry.register_fn(
+            "betweenness",
+            "The betweenness centrality of every vertex",
+            |graph, token| Outcome::Scores(centrality::betweenness(graph, |_| 1.0, token)),
+        );
+        registry.register_fn(
+            "closeness",
+            "The closeness centrality of every vertex",
+            |graph, token| Outcome::Scores(centrality::closeness(graph, |_| 1.0, token)),
+        );
+        registry.register_fn(
+            "degree",
+            "The degree centrality of every vertex",
+            |graph, token| Outcome::Scores(centrality::degree(graph, token)),
+        );
+        registry.register_fn(
+            "page_rank",
+            "The PageRank of every vertex",
+            |graph, token| Outcome::Scores(centrality::page_rank(graph, 0.85, 1e-10, 100, token)),
+        );
+        registry.register_fn(
+            "topological_sort",
+            "The vertices ordered so every edge leads forwards",
+            |graph, token| {
+                topological_sort(graph, token).map_or_else(Outcome::Unordered, Outcome::Order)
+            },
+        );
+
+        registry
+    }
+}
@@ -0,0 +1,114 @@
+use alloc::vec;
+use core::hash::BuildHasher;
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::{
+    edge::{EdgeTrait, ProbabilisticWeight},
+    generators::RandomSource,
+    ghost::GhostToken,
+    Allocator, Graph, GraphError, VertexId,
+};
+
+use super::successors;
+
+/// Estimates the probability that `target` can be reached from
+/// `source`, when every edge only exists with its own probability,
+/// independently of the others
+///
+/// Each of the `samples` draws which edges exist, and checks whether
+/// there's a path along them, respecting edge direction. Edges are
+/// only drawn when a search reaches them, so draws that reach
+/// `target` quickly take little time. The estimate is the fraction of
+/// draws with a path, so its error shrinks with the square root of
+/// `samples`. A vertex can always reach itself, and `0.0` is returned
+/// when `samples` is `0`
+///
+/// ```rust
+/// use graph::{
+///     algo::reliability,
+///     edge::{Probabilistic, UnDirectedProbabilisticEdge},
+///     generators::SplitMix64,
+///     ghost::GhostToken,
+///     Graph,
+/// };
+///
+/// GhostToken::new(|mut token| {
+///     let mut graph: Graph<(), Probabilistic, UnDirectedProbabilisticEdge<_>> = Graph::new();
+///     let a = graph.add_vertex(());
+///     let b = graph.add_vertex(());
+///     graph.add_edge(a, b, Probabilistic::new(0.9, ()), &mut token).unwrap();
+///
+///     let estimate = reliability(&graph, a, b, 10_000, &mut SplitMix64::new(1), &token).unwrap();
+///     assert!((estimate - 0.9).abs() < 0.02);
+/// })
+/// ```
+///
+/// # Errors
+/// Returns [`GraphError::VertexNotFound`] if either `source` or
+/// `target` isn't within the graph
+pub fn reliability<'id, Item, Weight, Edge, S, A>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    source: VertexId<'id>,
+    target: VertexId<'id>,
+    samples: usize,
+    rng: &mut impl RandomSource,
+    token: &GhostToken<'id>,
+) -> Result<f64, GraphError<'id, Item, Weight, Edge>>
+where
+    Weight: ProbabilisticWeight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    for id in [source, target] {
+        if graph.get_vertex(id).is_none() {
+            return Err(GraphError::VertexNotFound(id));
+        }
+    }
+    if samples == 0 {
+        return Ok(0.0);
+    }
+    if source == target {
+        return Ok(1.0);
+    }
+
+    let mut connected = 0_usize;
+    let mut drawn = HashMap::new();
+    let mut seen = HashSet::new();
+    let mut stack = vec![];
+
+    for _ in 0..samples {
+        drawn.clear();
+        seen.clear();
+        stack.clear();
+        seen.insert(source);
+        stack.push(source);
+
+        'search: while let Some(vertex) = stack.pop() {
+            for (edge, other, weight) in successors(graph, vertex, token) {
+                if seen.contains(&other) {
+                    continue;
+                }
+                // An undirected edge is drawn once, whichever end
+                // it's reached from
+                let exists = *drawn
+                    .entry(edge)
+                    .or_insert_with(|| rng.next_f64() < weight.probability());
+                if !exists {
+                    continue;
+                }
+
+                if other == target {
+                    connected += 1;
+                    break 'search;
+                }
+                seen.insert(other);
+                stack.push(other);
+            }
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    Ok(connected as f64 / samples as f64)
+}
@@ -0,0 +1,494 @@
+use alloc::{collections::BinaryHeap, vec::Vec};
+use core::{cmp::Reverse, hash::BuildHasher};
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::{
+    edge::EdgeTrait, ghost::GhostToken, id::EdgeId, measure::Total, AllocError, Allocator, Graph,
+    GraphError, IndexedHeap, Measure, VertexId,
+};
+
+use super::{successors, Path};
+
+/// The shortest distance to every vertex reachable from a source,
+/// as computed by [`dijkstra`] or [`dial`]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShortestPaths<'id, Distance> {
+    source: VertexId<'id>,
+    pub(super) distance: HashMap<VertexId<'id>, Distance>,
+    pub(super) previous: HashMap<VertexId<'id>, (VertexId<'id>, EdgeId<'id>)>,
+}
+
+impl<'id, Distance: Copy> ShortestPaths<'id, Distance> {
+    fn new(source: VertexId<'id>, zero: Distance) -> Self {
+        let mut paths = Self {
+            source,
+            distance: HashMap::new(),
+            previous: HashMap::new(),
+        };
+        paths.distance.insert(source, zero);
+        paths
+    }
+    /// The vertex the search started from
+    #[must_use]
+    pub const fn source(&self) -> VertexId<'id> {
+        self.source
+    }
+    /// The shortest distance from the source to `id`, or `None`
+    /// if it can't be reached
+    #[must_use]
+    pub fn distance(&self, id: VertexId<'id>) -> Option<Distance> {
+        self.distance.get(&id).copied()
+    }
+    /// The vertices along the shortest path to `id`, starting
+    /// with the source, or `None` if `id` can't be reached
+    #[must_use]
+    pub fn path_to(&self, id: VertexId<'id>) -> Option<Vec<VertexId<'id>>> {
+        if !self.distance.contains_key(&id) {
+            return None;
+        }
+
+        let mut path = alloc::vec![id];
+        let mut current = id;
+
+        while let Some((previous, _)) = self.previous.get(&current) {
+            path.push(*previous);
+            current = *previous;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+    /// The shortest path to `id`, along with the edges it follows, or
+    /// `None` if `id` can't be reached
+    #[must_use]
+    pub fn path(&self, id: VertexId<'id>) -> Option<Path<'id>> {
+        self.route_to(id).map(Path::from)
+    }
+    /// The shortest path to `id` along with its length, or `None`
+    /// if `id` can't be reached
+    fn route_to(&self, id: VertexId<'id>) -> Option<ShortestPath<'id, Distance>> {
+        let vertices = self.path_to(id)?;
+        let edges = vertices[1..]
+            .iter()
+            .filter_map(|id| self.previous(*id).map(|(_, edge)| edge))
+            .collect();
+
+        Some(ShortestPath {
+            length: self.distance[&id],
+            vertices,
+            edges,
+        })
+    }
+    /// Consumes the [`ShortestPaths`], returning the shortest distance
+    /// to every vertex that can be reached, including the source
+    #[must_use]
+    pub fn into_distances(self) -> HashMap<VertexId<'id>, Distance> {
+        self.distance
+    }
+    /// The edge used to reach `id` along its shortest path,
+    /// along with the vertex it was reached from
+    #[must_use]
+    pub fn previous(&self, id: VertexId<'id>) -> Option<(VertexId<'id>, EdgeId<'id>)> {
+        self.previous.get(&id).copied()
+    }
+}
+
+/// Finds the shortest distance from `source` to every vertex it
+/// can reach, using a binary heap
+///
+/// `length` is given an edge's weight and returns the length of
+/// the edge, which must never be negative
+///
+/// # Errors
+/// Returns [`GraphError::VertexNotFound`] if `source` isn't
+/// within the graph
+pub fn dijkstra<'id, Item, Weight, Edge, Distance, S, A: Allocator>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    source: VertexId<'id>,
+    length: impl Fn(&Weight) -> Distance,
+    token: &GhostToken<'id>,
+) -> Result<ShortestPaths<'id, Distance>, GraphError<'id, Item, Weight, Edge>>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    Distance: Measure,
+    S: BuildHasher,
+{
+    if graph.get_vertex(source).is_none() {
+        return Err(GraphError::VertexNotFound(source));
+    }
+
+    let mut paths = ShortestPaths::new(source, Distance::ZERO);
+
+    let mut queue = IndexedHeap::new();
+    queue.push(source, Distance::ZERO);
+    paths.settle(graph, queue, &length, token);
+
+    Ok(paths)
+}
+
+impl<'id, Distance: Measure> ShortestPaths<'id, Distance> {
+    /// Runs Dijkstra's algorithm from every vertex in `queue`, whose
+    /// distances have already been recorded
+    pub(super) fn settle<Item, Weight, Edge, S, A>(
+        &mut self,
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        mut queue: IndexedHeap<'id, Distance>,
+        length: impl Fn(&Weight) -> Distance,
+        token: &GhostToken<'id>,
+    ) where
+        Edge: EdgeTrait<'id, Item, Weight>,
+        S: BuildHasher,
+        A: Allocator,
+    {
+        let mut settled = graph.vertex_set();
+
+        while let Some((vertex, distance)) = queue.pop() {
+            settled.insert(vertex);
+
+            for (edge, other, weight) in successors(graph, vertex, token) {
+                if settled.contains(other) {
+                    continue;
+                }
+
+                let through = distance.saturating_add(length(weight));
+                if self.distance.get(&other).is_none_or(|best| through < *best) {
+                    self.distance.insert(other, through);
+                    self.previous.insert(other, (vertex, edge));
+                    queue.decrease_key(other, through);
+                }
+            }
+        }
+    }
+}
+
+/// Finds the shortest distance from `source` to every vertex it
+/// can reach, using Dial's bucket queue
+///
+/// `length` is given an edge's weight and returns the length of the
+/// edge. Only as many buckets as the longest edge are kept, so this
+/// is faster than [`dijkstra`] when every edge is short
+///
+/// # Errors
+/// Returns [`GraphError::VertexNotFound`] if `source` isn't
+/// within the graph, or [`GraphError::AllocationFailed`] if the
+/// longest edge is too long for its buckets to be allocated
+///
+/// # Panics
+/// Panics if the length of a path overflows a `usize`
+pub fn dial<'id, Item, Weight, Edge, S, A: Allocator>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    source: VertexId<'id>,
+    length: impl Fn(&Weight) -> usize,
+    token: &GhostToken<'id>,
+) -> Result<ShortestPaths<'id, usize>, GraphError<'id, Item, Weight, Edge>>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+{
+    if graph.get_vertex(source).is_none() {
+        return Err(GraphError::VertexNotFound(source));
+    }
+
+    let longest = graph
+        .edges
+        .values()
+        .map(|edge| length(edge.borrow(token).get_weight()))
+        .max()
+        .unwrap_or_default();
+
+    let mut paths = ShortestPaths::new(source, 0);
+
+    // One bucket for every length up to the longest edge's, which
+    // is asked for fallibly as a long edge could need any number
+    let mut buckets = Vec::new();
+    let len = longest
+        .checked_add(1)
+        .filter(|len| buckets.try_reserve_exact(*len).is_ok())
+        .ok_or(GraphError::AllocationFailed(AllocError))?;
+    buckets.resize_with(len, Vec::new);
+    buckets[0].push(source);
+
+    let mut pending = 1;
+    let mut current = 0;
+
+    while pending > 0 {
+        let Some(vertex) = buckets[current % len].pop() else {
+            current += 1;
+            continue;
+        };
+        pending -= 1;
+
+        if paths.distance[&vertex] != current {
+            continue;
+        }
+
+        for (edge, other, weight) in successors(graph, vertex, token) {
+            let through = current
+                .checked_add(length(weight))
+                .expect("path length overflowed usize");
+            if paths
+                .distance
+                .get(&other)
+                .is_none_or(|best| through < *best)
+            {
+                paths.distance.insert(other, through);
+                paths.previous.insert(other, (vertex, edge));
+                buckets[through % len].push(other);
+                pending += 1;
+            }
+        }
+    }
+
+    Ok(paths)
+}
+
+/// A path through a graph along with its total length, as found by
+/// [`k_shortest_paths`] and [`nearest_matching`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShortestPath<'id, Distance> {
+    /// The total length of every edge along the path
+    pub length: Distance,
+    /// The vertices along the path, starting with its source and
+    /// ending with its target
+    pub vertices: Vec<VertexId<'id>>,
+    /// The edges along the path, in order, so that `edges[i]` joins
+    /// `vertices[i]` and `vertices[i + 1]`
+    pub edges: Vec<EdgeId<'id>>,
+}
+
+/// Finds the shortest path from `source` to `target` which avoids
+/// every vertex in `banned_vertices` and edge in `banned_edges`,
+/// stopping as soon as `target` is reached
+#[allow(clippy::too_many_arguments)]
+pub(super) fn shortest_avoiding<'id, Item, Weight, Edge, Distance, S, A: Allocator>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    source: VertexId<'id>,
+    target: VertexId<'id>,
+    length: &impl Fn(&Weight) -> Distance,
+    banned_vertices: &HashSet<VertexId<'id>>,
+    banned_edges: &HashSet<EdgeId<'id>>,
+    token: &GhostToken<'id>,
+) -> Option<ShortestPath<'id, Distance>>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    Distance: Measure,
+    S: BuildHasher,
+{
+    let mut paths = ShortestPaths::new(source, Distance::ZERO);
+
+    let mut queue = IndexedHeap::new();
+    queue.push(source, Distance::ZERO);
+
+    while let Some((vertex, distance)) = queue.pop() {
+        if vertex == target {
+            break;
+        }
+
+        for (edge, other, weight) in successors(graph, vertex, token) {
+            if banned_edges.contains(&edge) || banned_vertices.contains(&other) {
+                continue;
+            }
+
+            let through = distance.saturating_add(length(weight));
+            if paths
+                .distance
+                .get(&other)
+                .is_none_or(|best| through < *best)
+            {
+                paths.distance.insert(other, through);
+                paths.previous.insert(other, (vertex, edge));
+                queue.decrease_key(other, through);
+            }
+        }
+    }
+
+    paths.route_to(target)
+}
+
+/// Finds the `k` shortest paths from `source` to `target` that
+/// don't visit any vertex twice, shortest first, using Yen's
+/// algorithm
+///
+/// `length` is given an edge's weight and returns the length of
+/// the edge, which must never be negative. Fewer than `k` paths are
+/// returned if there aren't `k` different paths, and none are
+/// returned if `target` can't be reached
+///
+/// # Errors
+/// Returns [`GraphError::VertexNotFound`] if either `source` or
+/// `target` isn't within the graph
+pub fn k_shortest_paths<'id, Item, Weight, Edge, Distance, S, A: Allocator>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    source: VertexId<'id>,
+    target: VertexId<'id>,
+    k: usize,
+    length: impl Fn(&Weight) -> Distance,
+    token: &GhostToken<'id>,
+) -> Result<Vec<ShortestPath<'id, Distance>>, GraphError<'id, Item, Weight, Edge>>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    Distance: Measure,
+    S: BuildHasher,
+{
+    for id in [source, target] {
+        if graph.get_vertex(id).is_none() {
+            return Err(GraphError::VertexNotFound(id));
+        }
+    }
+
+    let edge_length = |id: &EdgeId<'id>| {
+        graph.get_edge(*id).map_or(Distance::ZERO, |edge| {
+            length(edge.borrow(token).get_weight())
+        })
+    };
+
+    let mut found: Vec<ShortestPath<'id, Distance>> = Vec::new();
+    if k == 0 {
+        return Ok(found);
+    }
+    let Some(shortest) = shortest_avoiding(
+        graph,
+        source,
+        target,
+        &length,
+        &HashSet::new(),
+        &HashSet::new(),
+        token,
+    ) else {
+        return Ok(found);
+    };
+    found.push(shortest);
+
+    let mut candidates = BinaryHeap::new();
+    let mut seen: HashSet<Vec<EdgeId<'id>>> = HashSet::new();
+    seen.insert(found[0].edges.clone());
+
+    while found.len() < k {
+        let previous = &found[found.len() - 1];
+
+        for spur in 0..previous.edges.len() {
+            let root = &previous.vertices[..=spur];
+
+            // Stop every path found so far with the same root from
+            // being found again
+            let banned_edges: HashSet<_> = found
+                .iter()
+                .filter(|path| path.vertices.len() > spur + 1 && path.vertices[..=spur] == *root)
+                .map(|path| path.edges[spur])
+                .collect();
+            let banned_vertices: HashSet<_> = root[..spur].iter().copied().collect();
+
+            let Some(spur_path) = shortest_avoiding(
+                graph,
+                root[spur],
+                target,
+                &length,
+                &banned_vertices,
+                &banned_edges,
+                token,
+            ) else {
+                continue;
+            };
+
+            let mut edges = previous.edges[..spur].to_vec();
+            edges.extend_from_slice(&spur_path.edges);
+            if !seen.insert(edges.clone()) {
+                continue;
+            }
+
+            let length = previous.edges[..spur]
+                .iter()
+                .fold(spur_path.length, |total, edge| {
+                    total.saturating_add(edge_length(edge))
+                });
+            let mut vertices = root[..spur].to_vec();
+            vertices.extend_from_slice(&spur_path.vertices);
+
+            candidates.push(Reverse((Total(length), vertices, edges)));
+        }
+
+        let Some(Reverse((Total(length), vertices, edges))) = candidates.pop() else {
+            break;
+        };
+        found.push(ShortestPath {
+            length,
+            vertices,
+            edges,
+        });
+    }
+
+    Ok(found)
+}
+
+/// Finds the nearest vertex to `source` for which `goal` returns
+/// `true`, along with the shortest path to it, without searching
+/// any further than needed
+///
+/// `length` is given an edge's weight and returns the length of the
+/// edge, which must never be negative. If `max_length` is `Some`,
+/// vertices further away than it are never visited. `source` is
+/// itself returned if it matches `goal`
+///
+/// # Errors
+/// Returns [`GraphError::VertexNotFound`] if `source` isn't
+/// within the graph
+pub fn nearest_matching<'id, Item, Weight, Edge, Distance, S, A: Allocator>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    source: VertexId<'id>,
+    length: impl Fn(&Weight) -> Distance,
+    goal: impl Fn(VertexId<'id>, &Item) -> bool,
+    max_length: Option<Distance>,
+    token: &GhostToken<'id>,
+) -> Result<Option<ShortestPath<'id, Distance>>, GraphError<'id, Item, Weight, Edge>>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    Distance: Measure,
+    S: BuildHasher,
+{
+    if graph.get_vertex(source).is_none() {
+        return Err(GraphError::VertexNotFound(source));
+    }
+
+    let mut paths = ShortestPaths::new(source, Distance::ZERO);
+
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((Total(Distance::ZERO), source)));
+
+    while let Some(Reverse((Total(distance), vertex))) = queue.pop() {
+        if paths
+            .distance
+            .get(&vertex)
+            .is_some_and(|best| *best < distance)
+        {
+            continue;
+        }
+
+        let matches = graph
+            .get_vertex(vertex)
+            .is_some_and(|node| goal(vertex, node.borrow(token).get_item()));
+        if matches {
+            return Ok(paths.route_to(vertex));
+        }
+
+        for (edge, other, weight) in successors(graph, vertex, token) {
+            let through = distance.saturating_add(length(weight));
+            if max_length.is_some_and(|max| through > max) {
+                continue;
+            }
+            if paths
+                .distance
+                .get(&other)
+                .is_none_or(|best| through < *best)
+            {
+                paths.distance.insert(other, through);
+                paths.previous.insert(other, (vertex, edge));
+                queue.push(Reverse((Total(through), other)));
+            }
+        }
+    }
+
+    Ok(None)
+}
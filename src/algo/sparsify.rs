@@ -0,0 +1,234 @@
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+use crate::{
+    edge::EdgeTrait, generators::RandomSource, ghost::GhostToken, Allocator, EdgeId, Graph,
+    GraphError, SecondaryMap, VertexId,
+};
+
+/// A sparsified graph, along with the conductance each kept edge has
+/// within it, or the error that stopped it being built
+pub type Sparsified<'id, Item, Weight, Edge, S, A> = Result<
+    (
+        Graph<'id, Item, Weight, Edge, S, A>,
+        SecondaryMap<EdgeId<'id>, f64>,
+    ),
+    GraphError<'id, Item, Weight, Edge>,
+>;
+
+/// The edges of a graph by the index of their endpoints, with their
+/// conductance, for multiplying vectors by its Laplacian
+struct Laplacian {
+    edges: Vec<(usize, usize, f64)>,
+    vertices: usize,
+}
+
+impl Laplacian {
+    /// Sets `out` to the Laplacian multiplied by `x`
+    fn apply(&self, x: &[f64], out: &mut [f64]) {
+        out.fill(0.0);
+        for (one, two, conductance) in &self.edges {
+            let flow = conductance * (x[*one] - x[*two]);
+            out[*one] += flow;
+            out[*two] -= flow;
+        }
+    }
+    /// Solves `L x = b` using the conjugate gradient method, where `b`
+    /// sums to `0.0` over every connected component
+    ///
+    /// The Laplacian is singular, but conjugate gradient stays within
+    /// the space orthogonal to its null space when started from zero
+    fn solve(&self, b: &[f64]) -> Vec<f64> {
+        let dot =
+            |one: &[f64], two: &[f64]| -> f64 { one.iter().zip(two).map(|(a, b)| a * b).sum() };
+
+        let mut x = vec![0.0; self.vertices];
+        let mut residual = b.to_vec();
+        let mut direction = residual.clone();
+        let mut product = vec![0.0; self.vertices];
+        let mut norm = dot(&residual, &residual);
+        let tolerance = norm * 1e-20;
+
+        for _ in 0..10 * self.vertices {
+            if norm <= tolerance {
+                break;
+            }
+            self.apply(&direction, &mut product);
+            let curvature = dot(&direction, &product);
+            if curvature <= 0.0 {
+                break;
+            }
+
+            let step = norm / curvature;
+            for i in 0..self.vertices {
+                x[i] += step * direction[i];
+                residual[i] -= step * product[i];
+            }
+            let next = dot(&residual, &residual);
+            for i in 0..self.vertices {
+                direction[i] = residual[i] + next / norm * direction[i];
+            }
+            norm = next;
+        }
+        x
+    }
+    /// An estimate of the effective resistance of every edge, found by
+    /// projecting each vertex's row of `L⁺ Bᵀ W^½` onto `dimensions`
+    /// random directions, as in the Johnson–Lindenstrauss lemma
+    #[allow(clippy::cast_precision_loss)]
+    fn resistances(&self, dimensions: usize, rng: &mut impl RandomSource) -> Vec<f64> {
+        let mut resistances = vec![0.0; self.edges.len()];
+        let mut b = vec![0.0; self.vertices];
+
+        for _ in 0..dimensions {
+            b.fill(0.0);
+            for (one, two, conductance) in &self.edges {
+                let sign = if rng.next_u64() & 1 == 1 { 1.0 } else { -1.0 };
+                let share = sign * libm::sqrt(*conductance);
+                b[*one] += share;
+                b[*two] -= share;
+            }
+
+            let x = self.solve(&b);
+            for (resistance, (one, two, _)) in resistances.iter_mut().zip(&self.edges) {
+                let difference = x[*one] - x[*two];
+                *resistance += difference * difference;
+            }
+        }
+
+        for resistance in &mut resistances {
+            *resistance /= dimensions as f64;
+        }
+        resistances
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Constructs a spectral sparsifier of the graph, a copy with a
+    /// random sample of its edges that approximately preserves the
+    /// weight of every cut, using the Spielman–Srivastava algorithm
+    ///
+    /// `conductance` is given an edge's weight and returns how
+    /// strongly it joins its endpoints, with larger values for
+    /// stronger edges. Use `|_| 1.0` for unweighted graphs. Each edge
+    /// is kept with a probability that grows with its conductance
+    /// multiplied by its effective resistance, which is high for
+    /// edges that few other paths could stand in for, such as
+    /// bridges, and low for edges within dense clusters. Edges are
+    /// treated as undirected, and edges without a positive, finite,
+    /// conductance are never kept
+    ///
+    /// A smaller `epsilon` keeps more edges, for a closer
+    /// approximation, with about `4 n ln n / epsilon²` kept at most,
+    /// so only graphs with many more edges than vertices become much
+    /// smaller. Effective resistances are only estimated, so cuts are
+    /// preserved within about `epsilon` with high probability rather
+    /// than certainly
+    ///
+    /// The copy has every vertex of the graph, and every kept edge,
+    /// under the same ids, along with a [`SecondaryMap`] of the
+    /// conductance each kept edge has to have in the copy for cuts to
+    /// be preserved, which is its own conductance divided by the
+    /// probability it was kept with
+    ///
+    /// ```rust
+    /// use graph::{
+    ///     edge::UnDirectedWeightedEdge, generators::SplitMix64, ghost::GhostToken, Graph,
+    /// };
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<usize, (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let v: Vec<_> = (0..60).map(|i| graph.add_vertex(i)).collect();
+    ///     for a in 0..60 {
+    ///         for b in a + 1..60 {
+    ///             graph.add_edge(v[a], v[b], (), &mut token).unwrap();
+    ///         }
+    ///     }
+    ///
+    ///     let mut rng = SplitMix64::new(7);
+    ///     let (sparse, conductance) = graph.sparsify(1.0, |_| 1.0, &mut rng, &mut token).unwrap();
+    ///
+    ///     assert_eq!(sparse.vertex_len(), 60);
+    ///     assert!(sparse.edge_len() < graph.edge_len());
+    ///     assert_eq!(conductance.len(), sparse.edge_len());
+    /// })
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a [`GraphError`] if a kept edge can't be added to the
+    /// copy
+    ///
+    /// # Panics
+    /// Panics if `epsilon` isn't positive
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn sparsify(
+        &self,
+        epsilon: f64,
+        conductance: impl Fn(&Weight) -> f64,
+        rng: &mut impl RandomSource,
+        token: &mut GhostToken<'id>,
+    ) -> Sparsified<'id, Item, Weight, Edge, S, A>
+    where
+        Item: Clone,
+        Weight: Clone,
+        S: Clone + Default,
+        A: Clone + Default,
+    {
+        assert!(epsilon > 0.0, "epsilon must be positive");
+
+        let mut ids: Vec<_> = self.vertex_ids().collect();
+        ids.sort_unstable();
+        let index: SecondaryMap<_, _> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        let mut edges: Vec<(EdgeId<'id>, VertexId<'id>, VertexId<'id>, Weight, f64)> = self
+            .unique_edges(token)
+            .map(|(e_id, from, to, weight)| (e_id, from, to, weight.clone(), conductance(weight)))
+            .filter(|(.., conductance)| conductance.is_finite() && *conductance > 0.0)
+            .collect();
+        edges.sort_unstable_by_key(|(e_id, ..)| *e_id);
+
+        let laplacian = Laplacian {
+            edges: edges
+                .iter()
+                .map(|(_, from, to, _, conductance)| (index[*from], index[*to], *conductance))
+                .filter(|(one, two, _)| one != two)
+                .collect(),
+            vertices: ids.len(),
+        };
+        let log = libm::log(ids.len() as f64).max(1.0);
+        let resistances = laplacian.resistances(libm::ceil(4.0 * log) as usize, rng);
+        let oversampling = 4.0 * log / (epsilon * epsilon);
+
+        let mut sparse =
+            Self::with_capacity_and_hasher_in(ids.len(), 0, S::default(), A::default());
+        for id in &ids {
+            if let Some(vertex) = self.get_vertex(*id) {
+                sparse.insert_vertex(*id, vertex.borrow(token).get_item().clone());
+            }
+        }
+
+        let mut kept = SecondaryMap::new();
+        let mut resistances = resistances.into_iter();
+        for (e_id, from, to, weight, conductance) in edges {
+            // Self-loops cross no cut, so are never needed
+            if from == to {
+                continue;
+            }
+            let resistance = resistances.next().unwrap_or_default();
+            let probability = (oversampling * conductance * resistance).min(1.0);
+
+            if rng.next_f64() < probability {
+                sparse.insert_edge(e_id, from, to, weight, token)?;
+                kept.insert(e_id, conductance / probability);
+            }
+        }
+
+        Ok((sparse, kept))
+    }
+}
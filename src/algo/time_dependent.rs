@@ -0,0 +1,168 @@
+use alloc::{collections::BinaryHeap, vec::Vec};
+use core::{cmp::Reverse, hash::BuildHasher};
+
+use crate::{
+    edge::{EdgeTrait, TemporalWeight},
+    ghost::GhostToken,
+    id::EdgeId,
+    Allocator, Graph, GraphError, SecondaryMap, VertexId,
+};
+
+use super::successors;
+
+/// The earliest time every reachable vertex can be arrived at,
+/// as computed by [`earliest_arrival`]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EarliestArrival<'id, Time> {
+    source: VertexId<'id>,
+    arrival: SecondaryMap<VertexId<'id>, Time>,
+    previous: SecondaryMap<VertexId<'id>, (VertexId<'id>, EdgeId<'id>)>,
+}
+
+impl<'id, Time: Copy> EarliestArrival<'id, Time> {
+    /// The vertex the search started from
+    #[must_use]
+    pub const fn source(&self) -> VertexId<'id> {
+        self.source
+    }
+    /// The earliest time `id` can be arrived at, or `None`
+    /// if it can't be reached
+    #[must_use]
+    pub fn arrival(&self, id: VertexId<'id>) -> Option<Time> {
+        self.arrival.get(id).copied()
+    }
+    /// The vertices along the earliest route to `id`, starting
+    /// with the source, or `None` if `id` can't be reached
+    #[must_use]
+    pub fn path_to(&self, id: VertexId<'id>) -> Option<Vec<VertexId<'id>>> {
+        if !self.arrival.contains_key(id) {
+            return None;
+        }
+
+        let mut path = alloc::vec![id];
+        let mut current = id;
+
+        while let Some((previous, _)) = self.previous.get(current) {
+            path.push(*previous);
+            current = *previous;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+    /// The edge used to arrive at `id` along its earliest route,
+    /// along with the vertex it was left from
+    #[must_use]
+    pub fn previous(&self, id: VertexId<'id>) -> Option<(VertexId<'id>, EdgeId<'id>)> {
+        self.previous.get(id).copied()
+    }
+}
+
+/// Finds the earliest time every vertex can be reached when
+/// leaving `source` at `departure`, where the time taken to follow
+/// an edge depends on when it's started
+///
+/// `arrive` is given an edge's weight and the time the edge is
+/// started, and returns the time the other end is reached, or
+/// `None` if the edge can't be followed at that time. It must be
+/// FIFO, that is starting an edge later never arrives earlier, and
+/// never arrive before the time it's given.
+///
+/// # Errors
+/// Returns [`GraphError::VertexNotFound`] if `source` isn't
+/// within the graph
+pub fn earliest_arrival<'id, Item, Weight, Edge, Time, S, A: Allocator>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    source: VertexId<'id>,
+    departure: Time,
+    arrive: impl Fn(&Weight, Time) -> Option<Time>,
+    token: &GhostToken<'id>,
+) -> Result<EarliestArrival<'id, Time>, GraphError<'id, Item, Weight, Edge>>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    Time: Ord + Copy,
+    S: BuildHasher,
+{
+    if graph.get_vertex(source).is_none() {
+        return Err(GraphError::VertexNotFound(source));
+    }
+
+    let mut result = EarliestArrival {
+        source,
+        arrival: graph.vertex_map(),
+        previous: graph.vertex_map(),
+    };
+    result.arrival.insert(source, departure);
+
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((departure, source)));
+
+    while let Some(Reverse((time, vertex))) = queue.pop() {
+        if result.arrival.get(vertex).is_some_and(|best| *best < time) {
+            continue;
+        }
+
+        for (edge, other, weight) in successors(graph, vertex, token) {
+            if let Some(arrival) = arrive(weight, time) {
+                if result.arrival.get(other).is_none_or(|best| arrival < *best) {
+                    result.arrival.insert(other, arrival);
+                    result.previous.insert(other, (vertex, edge));
+                    queue.push(Reverse((arrival, other)));
+                }
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Finds the earliest time every vertex can be reached when leaving
+/// `source` at `departure`, along time-respecting paths over edges
+/// that only exist during certain periods
+///
+/// Following an edge takes no time, but can only be done while the
+/// edge exists, so a path may wait at a vertex for its next edge to
+/// appear. The times along a path never decrease, so a contact that
+/// ended before a vertex was reached can't be used to leave it
+///
+/// ```rust
+/// use graph::{
+///     algo::temporal_bfs,
+///     edge::{Temporal, UnDirectedTemporalEdge},
+///     ghost::GhostToken,
+///     Graph,
+/// };
+///
+/// GhostToken::new(|mut token| {
+///     let mut graph: Graph<(), Temporal<u32>, UnDirectedTemporalEdge<_, _>> = Graph::new();
+///     let a = graph.add_vertex(());
+///     let b = graph.add_vertex(());
+///     let c = graph.add_vertex(());
+///     graph.add_edge(a, b, Temporal::new(()).with_interval(5, 6), &mut token).unwrap();
+///     graph.add_edge(b, c, Temporal::new(()).with_interval(1, 3), &mut token).unwrap();
+///
+///     // `b` and `c` met before `a` and `b` did
+///     let reached = temporal_bfs(&graph, a, 0, &token).unwrap();
+///     assert_eq!(reached.arrival(b), Some(5));
+///     assert_eq!(reached.arrival(c), None);
+///     assert_eq!(reached.arrival(a), Some(0));
+/// })
+/// ```
+///
+/// # Errors
+/// Returns [`GraphError::VertexNotFound`] if `source` isn't
+/// within the graph
+pub fn temporal_bfs<'id, Item, Weight, Edge, S, A: Allocator>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    source: VertexId<'id>,
+    departure: Weight::Time,
+    token: &GhostToken<'id>,
+) -> Result<EarliestArrival<'id, Weight::Time>, GraphError<'id, Item, Weight, Edge>>
+where
+    Weight: TemporalWeight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+{
+    earliest_arrival(graph, source, departure, TemporalWeight::next_active, token)
+}
@@ -0,0 +1,208 @@
+use alloc::vec::Vec;
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::{ghost::GhostToken, ops::GraphOps, VertexId};
+
+use super::topological_sort;
+
+/// A topological order that's kept up to date as edges are inserted,
+/// using the Pearce–Kelly algorithm
+///
+/// Inserting an edge only reorders the vertices between its two
+/// endpoints within the current order, rather than sorting the whole
+/// graph again. The order tracks its own copy of the edges, so edges
+/// should be inserted here alongside the graph they describe
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TopologicalOrder<'id> {
+    order: Vec<VertexId<'id>>,
+    position: HashMap<VertexId<'id>, usize>,
+    successors: HashMap<VertexId<'id>, Vec<VertexId<'id>>>,
+    predecessors: HashMap<VertexId<'id>, Vec<VertexId<'id>>>,
+}
+
+impl<'id> TopologicalOrder<'id> {
+    /// Constructs a new empty order
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Constructs an order holding every vertex and edge of `graph`
+    ///
+    /// # Errors
+    /// Returns the vertices that couldn't be ordered if the graph
+    /// contains a cycle, see [`topological_sort`]
+    pub fn from_graph<G: GraphOps<'id>>(
+        graph: &G,
+        token: &GhostToken<'id>,
+    ) -> Result<Self, Vec<VertexId<'id>>> {
+        let order = topological_sort(graph, token)?;
+
+        let mut topo = Self::new();
+        for id in &order {
+            topo.add_vertex(*id);
+        }
+        for id in &order {
+            for (other, _) in graph.successors(*id, token) {
+                topo.successors.entry(*id).or_default().push(other);
+                topo.predecessors.entry(other).or_default().push(*id);
+            }
+        }
+
+        Ok(topo)
+    }
+    /// Adds `id` to the end of the order, returning `false` if it
+    /// was already within the order
+    pub fn add_vertex(&mut self, id: VertexId<'id>) -> bool {
+        if self.position.contains_key(&id) {
+            return false;
+        }
+
+        self.position.insert(id, self.order.len());
+        self.order.push(id);
+
+        true
+    }
+    /// Inserts an edge leading from `from` to `to`, moving vertices
+    /// as needed so that `from` stays before `to`
+    ///
+    /// Either vertex is added to the end of the order first if it
+    /// isn't already within it
+    ///
+    /// # Errors
+    /// Returns the path leading from `to` back to `from` if the edge
+    /// would create a cycle, in which case the edge isn't inserted
+    pub fn add_edge(
+        &mut self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+    ) -> Result<(), Vec<VertexId<'id>>> {
+        self.add_vertex(from);
+        self.add_vertex(to);
+
+        if from == to {
+            return Err(alloc::vec![from]);
+        }
+
+        let lower = self.position[&to];
+        let upper = self.position[&from];
+
+        if lower < upper {
+            let forward = self.forward(to, from, upper)?;
+            let backward = self.backward(from, lower);
+            self.reorder(backward, forward);
+        }
+
+        self.successors.entry(from).or_default().push(to);
+        self.predecessors.entry(to).or_default().push(from);
+
+        Ok(())
+    }
+    /// Returns the vertices in order, such that every edge leads from
+    /// an earlier vertex to a later one
+    #[must_use]
+    pub fn order(&self) -> &[VertexId<'id>] {
+        &self.order
+    }
+    /// Returns the position of `id` within the order, or `None` if
+    /// it isn't within the order
+    #[must_use]
+    pub fn position(&self, id: VertexId<'id>) -> Option<usize> {
+        self.position.get(&id).copied()
+    }
+    /// Returns if `id` is within the order
+    #[must_use]
+    pub fn contains(&self, id: VertexId<'id>) -> bool {
+        self.position.contains_key(&id)
+    }
+    /// Returns the number of vertices within the order
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.order.len()
+    }
+    /// Returns if the order contains no vertices
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+    /// Collects every vertex reachable from `start` that's placed
+    /// before `upper`, returning the path from `start` to `target`
+    /// if `target` can be reached
+    fn forward(
+        &self,
+        start: VertexId<'id>,
+        target: VertexId<'id>,
+        upper: usize,
+    ) -> Result<Vec<VertexId<'id>>, Vec<VertexId<'id>>> {
+        let mut parent = HashMap::new();
+        let mut visited = HashSet::new();
+        let mut stack = alloc::vec![start];
+        let mut found = Vec::new();
+
+        visited.insert(start);
+
+        while let Some(id) = stack.pop() {
+            found.push(id);
+
+            for next in self.successors.get(&id).into_iter().flatten() {
+                if *next == target {
+                    let mut path = alloc::vec![target, id];
+                    let mut current = id;
+                    while let Some(previous) = parent.get(&current) {
+                        path.push(*previous);
+                        current = *previous;
+                    }
+                    path.reverse();
+                    return Err(path);
+                }
+
+                if self.position[next] < upper && visited.insert(*next) {
+                    parent.insert(*next, id);
+                    stack.push(*next);
+                }
+            }
+        }
+
+        Ok(found)
+    }
+    /// Collects every vertex that reaches `start` and is placed
+    /// after `lower`
+    fn backward(&self, start: VertexId<'id>, lower: usize) -> Vec<VertexId<'id>> {
+        let mut visited = HashSet::new();
+        let mut stack = alloc::vec![start];
+        let mut found = Vec::new();
+
+        visited.insert(start);
+
+        while let Some(id) = stack.pop() {
+            found.push(id);
+
+            for next in self.predecessors.get(&id).into_iter().flatten() {
+                if self.position[next] > lower && visited.insert(*next) {
+                    stack.push(*next);
+                }
+            }
+        }
+
+        found
+    }
+    /// Places every vertex of `backward` before every vertex of
+    /// `forward`, reusing the positions they already held
+    fn reorder(&mut self, mut backward: Vec<VertexId<'id>>, mut forward: Vec<VertexId<'id>>) {
+        backward.sort_unstable_by_key(|id| self.position[id]);
+        forward.sort_unstable_by_key(|id| self.position[id]);
+
+        let mut positions: Vec<_> = backward
+            .iter()
+            .chain(&forward)
+            .map(|id| self.position[id])
+            .collect();
+        positions.sort_unstable();
+
+        for (id, position) in backward.into_iter().chain(forward).zip(positions) {
+            self.position.insert(id, position);
+            self.order[position] = id;
+        }
+    }
+}
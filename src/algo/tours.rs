@@ -0,0 +1,242 @@
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, id::EdgeId, Allocator, Graph, VertexId};
+
+use super::successors;
+
+/// A walk through a graph which uses no edge more than once, as found
+/// by [`Graph::eulerian_path`] and [`Graph::eulerian_circuit`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trail<'id> {
+    /// The vertices along the trail, in order, which may repeat
+    pub vertices: Vec<VertexId<'id>>,
+    /// The edges along the trail, in order, so that `edges[i]` joins
+    /// `vertices[i]` and `vertices[i + 1]`
+    pub edges: Vec<EdgeId<'id>>,
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Whether there's a trail through the graph that uses every edge
+    /// exactly once
+    #[must_use]
+    pub fn has_eulerian_path(&self, token: &GhostToken<'id>) -> bool {
+        self.eulerian_path(token).is_some()
+    }
+    /// Finds a trail through the graph that uses every edge exactly
+    /// once, following the direction of directed edges, using
+    /// Hierholzer's algorithm
+    ///
+    /// Returns `None` if there's no such trail, either because the
+    /// edges aren't all connected or because of the vertices'
+    /// degrees. A graph with no edges has an empty trail
+    #[must_use]
+    pub fn eulerian_path(&self, token: &GhostToken<'id>) -> Option<Trail<'id>> {
+        if self.edges.is_empty() {
+            return Some(Trail::default());
+        }
+
+        let mut ids: Vec<_> = self.vertex_ids().collect();
+        ids.sort_unstable();
+
+        // A trail has to start where there's one more edge leading
+        // out than in, or at an odd vertex if edges are undirected,
+        // and there can only be one such start and one such end
+        let mut starts = Vec::new();
+        let mut ends = 0;
+        for id in &ids {
+            let (outgoing, incoming) = self.degrees[id];
+            if Edge::DIRECTED {
+                if outgoing == incoming + 1 {
+                    starts.push(*id);
+                } else if incoming == outgoing + 1 {
+                    ends += 1;
+                } else if outgoing != incoming {
+                    return None;
+                }
+            } else if outgoing % 2 == 1 {
+                starts.push(*id);
+            }
+        }
+
+        let start = match (Edge::DIRECTED, starts.len(), ends) {
+            (true, 0, 0) | (false, 0, _) => self.first_with_edges(&ids)?,
+            (true, 1, 1) | (false, 2, _) => starts[0],
+            _ => return None,
+        };
+        self.hierholzer(start, token)
+    }
+    /// Finds a trail through the graph that uses every edge exactly
+    /// once and ends where it started, following the direction of
+    /// directed edges, using Hierholzer's algorithm
+    ///
+    /// Returns `None` if there's no such circuit, either because the
+    /// edges aren't all connected or because some vertex has an odd
+    /// degree, or a different in and out degree if edges are directed.
+    /// A graph with no edges has an empty circuit
+    #[must_use]
+    pub fn eulerian_circuit(&self, token: &GhostToken<'id>) -> Option<Trail<'id>> {
+        let balanced = |(outgoing, incoming): &(usize, usize)| {
+            if Edge::DIRECTED {
+                outgoing == incoming
+            } else {
+                outgoing % 2 == 0
+            }
+        };
+        if self.edges.is_empty() {
+            return Some(Trail::default());
+        }
+        if !self.degrees.values().all(balanced) {
+            return None;
+        }
+
+        let mut ids: Vec<_> = self.vertex_ids().collect();
+        ids.sort_unstable();
+        self.hierholzer(self.first_with_edges(&ids)?, token)
+    }
+    /// Finds a path through the graph that visits every vertex
+    /// exactly once, following the direction of directed edges
+    ///
+    /// Finding such a path is NP-complete, so the search backtracks
+    /// through at most `max_steps` vertices before giving up. It
+    /// tries the neighbors with the fewest unvisited neighbors of
+    /// their own first, which finds a path quickly on most graphs
+    /// that have one.
+    ///
+    /// Returns `None` if there's no such path, or none was found
+    /// within `max_steps`
+    #[must_use]
+    pub fn hamiltonian_path(
+        &self,
+        max_steps: usize,
+        token: &GhostToken<'id>,
+    ) -> Option<Vec<VertexId<'id>>> {
+        let mut ids: Vec<_> = self.vertex_ids().collect();
+        ids.sort_unstable();
+
+        let adjacent: HashMap<_, Vec<_>> = ids
+            .iter()
+            .map(|id| {
+                let mut others: Vec<_> = successors(self, *id, token)
+                    .map(|(_, other, _)| other)
+                    .filter(|other| other != id)
+                    .collect();
+                others.sort_unstable();
+                others.dedup();
+                (*id, others)
+            })
+            .collect();
+
+        let mut visited = HashSet::new();
+        // The neighbors of a vertex left to try, with the one to try
+        // next last
+        let options = |id: &VertexId<'id>, visited: &HashSet<VertexId<'id>>| {
+            let mut options: Vec<_> = adjacent[id]
+                .iter()
+                .filter(|other| !visited.contains(*other))
+                .map(|other| {
+                    let onward = adjacent[other]
+                        .iter()
+                        .filter(|v| !visited.contains(*v))
+                        .count();
+                    (onward, *other)
+                })
+                .collect();
+            options.sort_unstable_by(|a, b| b.cmp(a));
+            options
+                .into_iter()
+                .map(|(_, other)| other)
+                .collect::<Vec<_>>()
+        };
+
+        let mut steps = 0;
+        for start in &ids {
+            let mut path = vec![*start];
+            visited.clear();
+            visited.insert(*start);
+            let mut choices = vec![options(start, &visited)];
+
+            while let Some(next) = choices.last_mut().map(Vec::pop) {
+                if path.len() == ids.len() {
+                    return Some(path);
+                }
+
+                if let Some(next) = next {
+                    if visited.contains(&next) {
+                        continue;
+                    }
+                    steps += 1;
+                    if steps > max_steps {
+                        return None;
+                    }
+                    visited.insert(next);
+                    path.push(next);
+                    choices.push(options(&next, &visited));
+                } else {
+                    choices.pop();
+                    if let Some(last) = path.pop() {
+                        visited.remove(&last);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+    /// The first vertex in `ids` with any edges
+    fn first_with_edges(&self, ids: &[VertexId<'id>]) -> Option<VertexId<'id>> {
+        ids.iter().copied().find(|id| {
+            self.degrees
+                .get(id)
+                .is_some_and(|(outgoing, _)| *outgoing > 0)
+        })
+    }
+    /// Follows unused edges from `start` until stuck, splicing in
+    /// detours from every vertex along the way that still has unused
+    /// edges, returning `None` if some edge couldn't be reached
+    fn hierholzer(&self, start: VertexId<'id>, token: &GhostToken<'id>) -> Option<Trail<'id>> {
+        let mut remaining: HashMap<_, Vec<_>> = HashMap::new();
+        let mut used = HashSet::with_capacity(self.edges.len());
+        let mut stack = vec![(start, None)];
+        let mut trail = Vec::with_capacity(self.edges.len() + 1);
+
+        while let Some(&(id, _)) = stack.last() {
+            let options = remaining.entry(id).or_insert_with(|| {
+                let mut options: Vec<_> = successors(self, id, token)
+                    .map(|(e_id, other, _)| (e_id, other))
+                    .collect();
+                options.sort_unstable_by(|a, b| b.cmp(a));
+                options
+            });
+
+            let next = loop {
+                match options.pop() {
+                    Some((e_id, other)) if used.insert(e_id) => break Some((e_id, other)),
+                    Some(_) => {}
+                    None => break None,
+                }
+            };
+
+            match next {
+                Some((e_id, other)) => stack.push((other, Some(e_id))),
+                None => trail.extend(stack.pop()),
+            }
+        }
+
+        if used.len() != self.edges.len() {
+            return None;
+        }
+
+        trail.reverse();
+        let (vertices, edges) = trail.into_iter().unzip::<_, _, Vec<_>, Vec<_>>();
+        Some(Trail {
+            vertices,
+            edges: edges.into_iter().flatten().collect(),
+        })
+    }
+}
@@ -0,0 +1,573 @@
+use alloc::{vec, vec::Vec};
+use core::hash::BuildHasher;
+
+use crate::{
+    edge::EdgeTrait,
+    ghost::GhostToken,
+    ops::{AnyEdge, EdgePredicate},
+    Allocator, Graph, GraphError, VertexBitSet, VertexId,
+};
+
+use super::successors;
+
+type Traversal<'id, Item, Weight, Edge, T> = Result<T, GraphError<'id, Item, Weight, Edge>>;
+type BfsFrom<'a, 'id, Item, Weight, Edge, S, A, F> =
+    Traversal<'id, Item, Weight, Edge, Bfs<'a, 'id, Item, Weight, Edge, S, A, F>>;
+type DfsFrom<'a, 'id, Item, Weight, Edge, S, A, F> =
+    Traversal<'id, Item, Weight, Edge, Dfs<'a, 'id, Item, Weight, Edge, S, A, F>>;
+
+/// Limits on how far a traversal such as [`Graph::bfs`] or
+/// [`Graph::dfs`] goes, and which edges it follows
+///
+/// Traversing from a vertex with edges to most of a huge graph can
+/// take as long as traversing the whole graph, so limiting how deep
+/// a traversal goes or how many vertices it visits keeps it bounded.
+/// By default every edge is followed without limit
+///
+/// ```
+/// # use graph::{algo::TraversalOptions, edge::DirectedWeightedEdge, ghost::GhostToken, Graph};
+/// GhostToken::new(|mut token| {
+///     let mut graph: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+///     let hub = graph.add_vertex(());
+///     for weight in 0..10 {
+///         let other = graph.add_vertex(());
+///         graph.add_edge(hub, other, weight, &mut token).unwrap();
+///     }
+///
+///     let options = TraversalOptions::new()
+///         .max_visited(4)
+///         .edge_filter(|weight: &u32| *weight % 2 == 0);
+///     assert_eq!(graph.bfs(hub, &options, &token).unwrap().len(), 4);
+/// });
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TraversalOptions<F = AnyEdge> {
+    max_depth: Option<usize>,
+    max_visited: Option<usize>,
+    edge_filter: F,
+}
+
+impl TraversalOptions {
+    /// Constructs options that follow every edge without limit
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_depth: None,
+            max_visited: None,
+            edge_filter: AnyEdge,
+        }
+    }
+}
+
+impl Default for TraversalOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F> TraversalOptions<F> {
+    /// Only visits vertices at most `depth` edges away from where the
+    /// traversal starts
+    #[must_use]
+    pub const fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+    /// Stops the traversal once `visited` vertices have been visited,
+    /// including the one it starts from
+    #[must_use]
+    pub const fn max_visited(mut self, visited: usize) -> Self {
+        self.max_visited = Some(visited);
+        self
+    }
+    /// Only follows edges whose weight `edge_filter` keeps, which can
+    /// be a closure over the weight or any other [`EdgePredicate`]
+    #[must_use]
+    pub fn edge_filter<G>(self, edge_filter: G) -> TraversalOptions<G> {
+        TraversalOptions {
+            max_depth: self.max_depth,
+            max_visited: self.max_visited,
+            edge_filter,
+        }
+    }
+    /// Whether vertices `depth` edges away may be visited
+    fn within_depth(&self, depth: usize) -> bool {
+        self.max_depth.is_none_or(|max| depth <= max)
+    }
+    /// The most vertices that may be visited
+    fn visit_limit(&self) -> usize {
+        self.max_visited.unwrap_or(usize::MAX)
+    }
+}
+
+/// An iterator visiting vertices breadth first, created by
+/// [`Graph::bfs_iter`]
+pub struct Bfs<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator, F> {
+    graph: &'a Graph<'id, Item, Weight, Edge, S, A>,
+    token: &'a GhostToken<'id>,
+    options: &'a TraversalOptions<F>,
+    seen: VertexBitSet<'id>,
+    layer: Vec<VertexId<'id>>,
+    index: usize,
+    next: Vec<VertexId<'id>>,
+    depth: usize,
+}
+
+impl<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator, F>
+    Bfs<'a, 'id, Item, Weight, Edge, S, A, F>
+{
+    /// How many edges from the source the vertex last visited is
+    #[must_use]
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+}
+
+impl<'a, 'id, Item, Weight, Edge, S, A: Allocator, F> Iterator
+    for Bfs<'a, 'id, Item, Weight, Edge, S, A, F>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    F: EdgePredicate<Weight>,
+{
+    type Item = VertexId<'id>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(&id) = self.layer.get(self.index) {
+                self.index += 1;
+
+                if self.options.within_depth(self.depth + 1) {
+                    let limit = self.options.visit_limit();
+                    for other in self.graph.followed(id, self.options, self.token) {
+                        if self.seen.len() < limit && self.seen.insert(other) {
+                            self.next.push(other);
+                        }
+                    }
+                }
+
+                return Some(id);
+            }
+
+            if self.next.is_empty() {
+                return None;
+            }
+
+            self.next.sort_unstable();
+            self.layer = core::mem::take(&mut self.next);
+            self.index = 0;
+            self.depth += 1;
+        }
+    }
+}
+
+/// An iterator visiting vertices depth first, created by
+/// [`Graph::dfs_iter`]
+pub struct Dfs<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator, F> {
+    graph: &'a Graph<'id, Item, Weight, Edge, S, A>,
+    token: &'a GhostToken<'id>,
+    options: &'a TraversalOptions<F>,
+    seen: VertexBitSet<'id>,
+    /// The vertices each vertex along the current path leads to,
+    /// along with the index of the next to try
+    stack: Vec<(Vec<VertexId<'id>>, usize)>,
+    start: Option<VertexId<'id>>,
+}
+
+impl<'a, 'id, Item, Weight, Edge, S, A: Allocator, F> Iterator
+    for Dfs<'a, 'id, Item, Weight, Edge, S, A, F>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    F: EdgePredicate<Weight>,
+{
+    type Item = VertexId<'id>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(start) = self.start.take() {
+            return Some(start);
+        }
+
+        while let Some((others, next)) = self.stack.last_mut() {
+            if self.seen.len() >= self.options.visit_limit() {
+                return None;
+            }
+
+            let Some(&other) = others.get(*next) else {
+                self.stack.pop();
+                continue;
+            };
+            *next += 1;
+
+            if self.seen.insert(other) {
+                // `other` is as deep as the stack is tall, so the
+                // vertices its edges lead to are one deeper
+                if self.options.within_depth(self.stack.len() + 1) {
+                    let others = self.graph.followed(other, self.options, self.token);
+                    self.stack.push((others, 0));
+                }
+                return Some(other);
+            }
+        }
+
+        None
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Visits the vertices reachable from `source` breadth first,
+    /// following the direction of directed edges, within the limits
+    /// of `options`
+    ///
+    /// Returns the vertices in the order they were visited, starting
+    /// with `source`, which is the order of [`Graph::bfs_layers_with`].
+    /// This collects [`Graph::bfs_iter`]
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if `source` isn't
+    /// within the graph
+    pub fn bfs<F: EdgePredicate<Weight>>(
+        &self,
+        source: VertexId<'id>,
+        options: &TraversalOptions<F>,
+        token: &GhostToken<'id>,
+    ) -> Result<Vec<VertexId<'id>>, GraphError<'id, Item, Weight, Edge>> {
+        self.bfs_iter(source, options, token).map(Iterator::collect)
+    }
+    /// Returns an iterator which visits the vertices reachable from
+    /// `source` breadth first, as [`Graph::bfs`] does, one at a time
+    ///
+    /// Only the vertices already seen and those in the current and
+    /// next layers are held in memory
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if `source` isn't
+    /// within the graph
+    pub fn bfs_iter<'a, F: EdgePredicate<Weight>>(
+        &'a self,
+        source: VertexId<'id>,
+        options: &'a TraversalOptions<F>,
+        token: &'a GhostToken<'id>,
+    ) -> BfsFrom<'a, 'id, Item, Weight, Edge, S, A, F> {
+        if self.get_vertex(source).is_none() {
+            return Err(GraphError::VertexNotFound(source));
+        }
+
+        let mut bfs = Bfs {
+            graph: self,
+            token,
+            options,
+            seen: self.vertex_set(),
+            layer: Vec::new(),
+            index: 0,
+            next: Vec::new(),
+            depth: 0,
+        };
+        if options.visit_limit() > 0 {
+            bfs.seen.insert(source);
+            bfs.layer.push(source);
+        }
+
+        Ok(bfs)
+    }
+    /// Splits the vertices `source` can reach into layers by how many
+    /// edges need following to get to them, as [`Graph::bfs_layers`]
+    /// does, within the limits of `options`
+    ///
+    /// Where `options` limits how many vertices are visited, the
+    /// last layer may be missing some of the vertices it would hold
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if `source` isn't
+    /// within the graph
+    pub fn bfs_layers_with<F: EdgePredicate<Weight>>(
+        &self,
+        source: VertexId<'id>,
+        options: &TraversalOptions<F>,
+        token: &GhostToken<'id>,
+    ) -> Result<Vec<Vec<VertexId<'id>>>, GraphError<'id, Item, Weight, Edge>> {
+        let mut bfs = self.bfs_iter(source, options, token)?;
+        let mut layers: Vec<Vec<_>> = Vec::new();
+
+        while let Some(id) = bfs.next() {
+            if layers.len() <= bfs.depth() {
+                layers.push(Vec::new());
+            }
+            layers[bfs.depth()].push(id);
+        }
+
+        Ok(layers)
+    }
+    /// Visits the vertices reachable from `source` depth first,
+    /// following the direction of directed edges, within the limits
+    /// of `options`
+    ///
+    /// Returns the vertices in the order they were visited, starting
+    /// with `source`. Edges are followed in order of the [`VertexId`]
+    /// they lead to, and each vertex is only visited once, at the
+    /// depth it's first reached at, so a depth limit can leave out
+    /// vertices which are within it along a path that wasn't taken.
+    /// This collects [`Graph::dfs_iter`]
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if `source` isn't
+    /// within the graph
+    pub fn dfs<F: EdgePredicate<Weight>>(
+        &self,
+        source: VertexId<'id>,
+        options: &TraversalOptions<F>,
+        token: &GhostToken<'id>,
+    ) -> Result<Vec<VertexId<'id>>, GraphError<'id, Item, Weight, Edge>> {
+        self.dfs_iter(source, options, token).map(Iterator::collect)
+    }
+    /// Returns an iterator which visits the vertices reachable from
+    /// `source` depth first, as [`Graph::dfs`] does, one at a time
+    ///
+    /// Only the vertices already seen and the edges left to follow
+    /// along the current path are held in memory
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if `source` isn't
+    /// within the graph
+    pub fn dfs_iter<'a, F: EdgePredicate<Weight>>(
+        &'a self,
+        source: VertexId<'id>,
+        options: &'a TraversalOptions<F>,
+        token: &'a GhostToken<'id>,
+    ) -> DfsFrom<'a, 'id, Item, Weight, Edge, S, A, F> {
+        if self.get_vertex(source).is_none() {
+            return Err(GraphError::VertexNotFound(source));
+        }
+
+        let mut dfs = Dfs {
+            graph: self,
+            token,
+            options,
+            seen: self.vertex_set(),
+            stack: Vec::new(),
+            start: None,
+        };
+        if options.visit_limit() > 0 {
+            dfs.seen.insert(source);
+            dfs.start = Some(source);
+            if options.within_depth(1) {
+                dfs.stack.push((self.followed(source, options, token), 0));
+            }
+        }
+
+        Ok(dfs)
+    }
+    /// Searches for a vertex that `goal` returns `true` for by
+    /// iterative deepening, running depth first searches from
+    /// `source` that go one edge deeper each time, within the limits
+    /// of `options`
+    ///
+    /// Returns the vertices along the path to the first such vertex
+    /// found, which is as few edges from `source` as any, or `None`
+    /// if there isn't one within the limits of `options`. Vertices
+    /// visited again by deeper searches count towards the
+    /// [`TraversalOptions::max_visited`] limit again, in exchange for
+    /// only keeping the path being searched in memory
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if `source` isn't
+    /// within the graph
+    pub fn iterative_deepening<F: EdgePredicate<Weight>>(
+        &self,
+        source: VertexId<'id>,
+        mut goal: impl FnMut(VertexId<'id>) -> bool,
+        options: &TraversalOptions<F>,
+        token: &GhostToken<'id>,
+    ) -> Result<Option<Vec<VertexId<'id>>>, GraphError<'id, Item, Weight, Edge>> {
+        if self.get_vertex(source).is_none() {
+            return Err(GraphError::VertexNotFound(source));
+        }
+
+        let limit = options.visit_limit();
+        if limit == 0 {
+            return Ok(None);
+        }
+        if goal(source) {
+            return Ok(Some(vec![source]));
+        }
+
+        let mut visited = 1;
+        let mut depth = 1;
+
+        while options.within_depth(depth) {
+            // Whether any path was cut short by the depth, as
+            // otherwise searching deeper won't find anything new
+            let mut cut_short = false;
+            let mut path = vec![source];
+            let mut stack = vec![(self.followed(source, options, token), 0)];
+
+            while let Some((others, next)) = stack.last_mut() {
+                let Some(&other) = others.get(*next) else {
+                    stack.pop();
+                    path.pop();
+                    continue;
+                };
+                *next += 1;
+
+                if path.contains(&other) {
+                    continue;
+                }
+                visited += 1;
+                if visited > limit {
+                    return Ok(None);
+                }
+
+                // Shallower vertices were checked by earlier searches
+                if path.len() < depth {
+                    path.push(other);
+                    stack.push((self.followed(other, options, token), 0));
+                } else if goal(other) {
+                    path.push(other);
+                    return Ok(Some(path));
+                } else {
+                    cut_short = true;
+                }
+            }
+
+            if !cut_short {
+                break;
+            }
+            depth += 1;
+        }
+
+        Ok(None)
+    }
+    /// Returns the set of vertices `source` can reach, following the
+    /// direction of directed edges, including `source` itself
+    ///
+    /// ```rust
+    /// use graph::{edge::DirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let a = graph.add_vertex(());
+    ///     let b = graph.add_vertex(());
+    ///     let c = graph.add_vertex(());
+    ///     graph.add_edge(a, b, (), &mut token).unwrap();
+    ///     graph.add_edge(c, a, (), &mut token).unwrap();
+    ///
+    ///     let reached = graph.reachable_from(a, &token).unwrap();
+    ///     assert_eq!(reached.iter().collect::<Vec<_>>(), vec![a, b]);
+    /// })
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if `source` isn't
+    /// within the graph
+    pub fn reachable_from(
+        &self,
+        source: VertexId<'id>,
+        token: &GhostToken<'id>,
+    ) -> Traversal<'id, Item, Weight, Edge, VertexBitSet<'id>> {
+        self.reachable_from_with(source, &TraversalOptions::new(), |_, _| {}, token)
+    }
+    /// Returns the set of vertices `source` can reach within the
+    /// limits of `options`, spreading out one level at a time, with
+    /// `on_level` deciding which vertices of each level are reached
+    ///
+    /// `on_level` is given how many edges from `source` each level is,
+    /// starting from `0` for `source` alone, and the vertices the
+    /// vertices reached in the level before have edges to, ordered by
+    /// id. Only the vertices it leaves in the level are reached, and
+    /// spread to the next level. Those it removes can still be reached
+    /// later, from another vertex, so cascades such as the spread of
+    /// an epidemic, where each vertex only passes it on with some
+    /// probability, can be simulated by removing the vertices it
+    /// didn't pass to. Clearing the level stops the spread
+    ///
+    /// ```rust
+    /// use graph::{algo::TraversalOptions, edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let v: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+    ///     for i in 0..4 {
+    ///         graph.add_edge(v[i], v[i + 1], (), &mut token).unwrap();
+    ///     }
+    ///
+    ///     let mut levels = Vec::new();
+    ///     let reached = graph
+    ///         .reachable_from_with(
+    ///             v[2],
+    ///             &TraversalOptions::new(),
+    ///             |depth, level| {
+    ///                 // Nothing spreads past `v[1]`
+    ///                 level.retain(|id| *id != v[1]);
+    ///                 levels.push((depth, level.clone()));
+    ///             },
+    ///             &token,
+    ///         )
+    ///         .unwrap();
+    ///
+    ///     assert_eq!(levels, vec![(0, vec![v[2]]), (1, vec![v[3]]), (2, vec![v[4]])]);
+    ///     assert_eq!(reached.len(), 3);
+    /// })
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if `source` isn't
+    /// within the graph
+    pub fn reachable_from_with<F: EdgePredicate<Weight>>(
+        &self,
+        source: VertexId<'id>,
+        options: &TraversalOptions<F>,
+        mut on_level: impl FnMut(usize, &mut Vec<VertexId<'id>>),
+        token: &GhostToken<'id>,
+    ) -> Traversal<'id, Item, Weight, Edge, VertexBitSet<'id>> {
+        if self.get_vertex(source).is_none() {
+            return Err(GraphError::VertexNotFound(source));
+        }
+
+        let limit = options.visit_limit();
+        let mut reached = self.vertex_set();
+        let mut level = vec![source];
+        let mut depth = 0;
+
+        while options.within_depth(depth) {
+            level.truncate(limit.saturating_sub(reached.len()));
+            if level.is_empty() {
+                break;
+            }
+            on_level(depth, &mut level);
+            reached.extend(level.iter().copied());
+
+            let mut next: Vec<_> = level
+                .iter()
+                .flat_map(|id| self.followed(*id, options, token))
+                .filter(|other| !reached.contains(*other))
+                .collect();
+            if next.is_empty() {
+                break;
+            }
+            next.sort_unstable();
+            next.dedup();
+
+            level = next;
+            depth += 1;
+        }
+
+        Ok(reached)
+    }
+    /// The vertices the edges from `id` that `options` keeps lead to,
+    /// ordered by id
+    fn followed<F: EdgePredicate<Weight>>(
+        &self,
+        id: VertexId<'id>,
+        options: &TraversalOptions<F>,
+        token: &GhostToken<'id>,
+    ) -> Vec<VertexId<'id>> {
+        let mut others: Vec<_> = successors(self, id, token)
+            .filter(|(_, _, weight)| options.edge_filter.keep(weight))
+            .map(|(_, other, _)| other)
+            .collect();
+        others.sort_unstable();
+        others
+    }
+}
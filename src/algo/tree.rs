@@ -0,0 +1,282 @@
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use core::hash::BuildHasher;
+
+use hashbrown::HashMap;
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, Allocator, Graph, SecondaryMap, VertexId};
+
+use super::neighbors;
+
+/// Whether `graph` is a tree, with a single path between every pair
+/// of its vertices, ignoring the direction of edges
+///
+/// A graph without any vertices isn't a tree
+#[must_use]
+pub fn is_tree<'id, Item, Weight, Edge, S, A>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    token: &GhostToken<'id>,
+) -> bool
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    let Some(start) = graph.vertex_ids().next() else {
+        return false;
+    };
+
+    // A connected graph with one edge fewer than it has vertices
+    // can't have a cycle
+    graph.edge_len() + 1 == graph.vertex_len()
+        && bfs(graph, start, token).len() == graph.vertex_len()
+}
+
+/// Every vertex reachable from `start`, ignoring the direction of
+/// edges, in breadth-first order along with the vertex each was
+/// reached from
+fn bfs<'id, Item, Weight, Edge, S, A>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    start: VertexId<'id>,
+    token: &GhostToken<'id>,
+) -> Vec<(VertexId<'id>, Option<VertexId<'id>>)>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    let mut order = vec![(start, None)];
+    let mut seen: HashMap<_, ()> = HashMap::new();
+    seen.insert(start, ());
+    let mut queue = VecDeque::from(vec![start]);
+
+    while let Some(vertex) = queue.pop_front() {
+        let mut next = neighbors(graph, vertex, token);
+        next.sort_unstable_by_key(|(_, other)| *other);
+
+        for (_, other) in next {
+            if seen.insert(other, ()).is_none() {
+                order.push((other, Some(vertex)));
+                queue.push_back(other);
+            }
+        }
+    }
+
+    order
+}
+
+/// A tree hung from one of its vertices, built by [`RootedTree::new`]
+///
+/// Every vertex but the root has a parent, and the vertices it's the
+/// parent of are its children. Lowest common ancestors are found with
+/// binary lifting, which takes `O(n log n)` time and memory while
+/// building the tree, and `O(log n)` time for each query.
+///
+/// The rooted tree is a snapshot, so it doesn't see changes made to
+/// the graph afterwards, and doesn't need a [`GhostToken`] to query
+///
+/// ```rust
+/// use graph::{algo::tree::RootedTree, edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+///
+/// GhostToken::new(|mut token| {
+///     let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+///     let root = graph.add_vertex(());
+///     let left = graph.add_vertex(());
+///     let right = graph.add_vertex(());
+///     let leaf = graph.add_vertex(());
+///     graph.add_edge(root, left, (), &mut token).unwrap();
+///     graph.add_edge(root, right, (), &mut token).unwrap();
+///     graph.add_edge(left, leaf, (), &mut token).unwrap();
+///
+///     let tree = RootedTree::new(&graph, root, &token).unwrap();
+///     assert_eq!(tree.parent(leaf), Some(left));
+///     assert_eq!(tree.depth(leaf), Some(2));
+///     assert_eq!(tree.lowest_common_ancestor(leaf, right), Some(root));
+/// })
+/// ```
+#[derive(Debug, Clone)]
+pub struct RootedTree<'id> {
+    /// Every vertex in breadth-first order from the root
+    ids: Vec<VertexId<'id>>,
+    index: SecondaryMap<VertexId<'id>, usize>,
+    depth: Vec<usize>,
+    children: Vec<Vec<VertexId<'id>>>,
+    /// The index of the `2^i`th ancestor of every vertex, or the
+    /// root if it has fewer ancestors than that
+    ancestors: Vec<Vec<usize>>,
+}
+
+impl<'id> RootedTree<'id> {
+    /// Hangs `graph` from `root`, ignoring the direction of edges
+    ///
+    /// Returns `None` if `root` isn't within the graph, or the graph
+    /// isn't a tree, see [`is_tree`]
+    #[must_use]
+    pub fn new<Item, Weight, Edge, S, A>(
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        root: VertexId<'id>,
+        token: &GhostToken<'id>,
+    ) -> Option<Self>
+    where
+        Edge: EdgeTrait<'id, Item, Weight>,
+        S: BuildHasher,
+        A: Allocator,
+    {
+        graph.get_vertex(root)?;
+        if graph.edge_len() + 1 != graph.vertex_len() {
+            return None;
+        }
+
+        let order = bfs(graph, root, token);
+        if order.len() != graph.vertex_len() {
+            return None;
+        }
+
+        let n = order.len();
+        let ids: Vec<_> = order.iter().map(|(id, _)| *id).collect();
+        let index: SecondaryMap<_, _> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        let mut parent = vec![0; n];
+        let mut depth = vec![0; n];
+        let mut children = vec![Vec::new(); n];
+        for (i, (id, above)) in order.into_iter().enumerate() {
+            if let Some(above) = above {
+                let p = index[above];
+                parent[i] = p;
+                depth[i] = depth[p] + 1;
+                children[p].push(id);
+            }
+        }
+
+        let levels = (usize::BITS - n.leading_zeros()).max(1) as usize;
+        let mut ancestors = vec![parent];
+        for level in 1..levels {
+            let previous = &ancestors[level - 1];
+            let next = previous.iter().map(|p| previous[*p]).collect();
+            ancestors.push(next);
+        }
+
+        Some(Self {
+            ids,
+            index,
+            depth,
+            children,
+            ancestors,
+        })
+    }
+    /// The vertex the tree hangs from
+    #[must_use]
+    pub fn root(&self) -> VertexId<'id> {
+        self.ids[0]
+    }
+    /// The number of vertices in the tree
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.ids.len()
+    }
+    /// Whether the tree has no vertices, which is never the case
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+    /// Whether `id` is within the tree
+    #[must_use]
+    pub fn contains(&self, id: VertexId<'id>) -> bool {
+        self.index.contains_key(id)
+    }
+    /// The parent of `id`, or `None` if `id` is the root or isn't
+    /// within the tree
+    #[must_use]
+    pub fn parent(&self, id: VertexId<'id>) -> Option<VertexId<'id>> {
+        let i = *self.index.get(id)?;
+        (i != 0).then(|| self.ids[self.ancestors[0][i]])
+    }
+    /// The children of `id`, in order of their ids, which is empty if
+    /// `id` is a leaf or isn't within the tree
+    #[must_use]
+    pub fn children(&self, id: VertexId<'id>) -> &[VertexId<'id>] {
+        self.index
+            .get(id)
+            .map_or(&[], |i| self.children[*i].as_slice())
+    }
+    /// The number of edges between `id` and the root, or `None` if
+    /// `id` isn't within the tree
+    #[must_use]
+    pub fn depth(&self, id: VertexId<'id>) -> Option<usize> {
+        self.index.get(id).map(|i| self.depth[*i])
+    }
+    /// Every vertex in the tree, starting with the root, with each
+    /// vertex before its children
+    #[must_use]
+    pub fn vertices(&self) -> &[VertexId<'id>] {
+        &self.ids
+    }
+    /// The parent of every vertex but the root
+    #[must_use]
+    pub fn parents(&self) -> HashMap<VertexId<'id>, VertexId<'id>> {
+        self.ids
+            .iter()
+            .zip(&self.ancestors[0])
+            .skip(1)
+            .map(|(id, parent)| (*id, self.ids[*parent]))
+            .collect()
+    }
+    /// The ancestor `steps` edges above `id`, or `None` if `id` isn't
+    /// within the tree or is fewer than `steps` edges below the root
+    #[must_use]
+    pub fn ancestor(&self, id: VertexId<'id>, steps: usize) -> Option<VertexId<'id>> {
+        let i = *self.index.get(id)?;
+        if steps > self.depth[i] {
+            return None;
+        }
+        Some(self.ids[self.climb(i, steps)])
+    }
+    /// The index of the ancestor `steps` edges above the vertex at
+    /// index `i`
+    fn climb(&self, mut i: usize, steps: usize) -> usize {
+        for (level, ancestors) in self.ancestors.iter().enumerate() {
+            if steps >> level & 1 == 1 {
+                i = ancestors[i];
+            }
+        }
+        i
+    }
+    /// The deepest vertex that's an ancestor of both `a` and `b`, where
+    /// every vertex counts as its own ancestor, or `None` if either
+    /// isn't within the tree
+    #[must_use]
+    pub fn lowest_common_ancestor(
+        &self,
+        a: VertexId<'id>,
+        b: VertexId<'id>,
+    ) -> Option<VertexId<'id>> {
+        let (a, b) = (*self.index.get(a)?, *self.index.get(b)?);
+
+        let (mut a, mut b) = if self.depth[a] < self.depth[b] {
+            (self.climb(b, self.depth[b] - self.depth[a]), a)
+        } else {
+            (self.climb(a, self.depth[a] - self.depth[b]), b)
+        };
+        if a == b {
+            return Some(self.ids[a]);
+        }
+
+        // Both climb as far as they can without meeting, leaving them
+        // just beneath their lowest common ancestor
+        for ancestors in self.ancestors.iter().rev() {
+            if ancestors[a] != ancestors[b] {
+                a = ancestors[a];
+                b = ancestors[b];
+            }
+        }
+
+        Some(self.ids[self.ancestors[0][a]])
+    }
+    /// The number of edges along the path between `a` and `b`, or
+    /// `None` if either isn't within the tree
+    #[must_use]
+    pub fn distance(&self, a: VertexId<'id>, b: VertexId<'id>) -> Option<usize> {
+        let ancestor = self.lowest_common_ancestor(a, b)?;
+        let depth = |id| self.depth[self.index[id]];
+        Some(depth(a) + depth(b) - 2 * depth(ancestor))
+    }
+}
@@ -0,0 +1,89 @@
+use core::hash::BuildHasher;
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, Allocator, Graph, VertexId};
+
+use super::{adjacency, common_len, neighbor_set};
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// The number of triangles in the graph, sets of three vertices
+    /// which are all joined to each other, treating every edge as
+    /// undirected
+    #[must_use]
+    pub fn triangle_count(&self, token: &GhostToken<'id>) -> usize {
+        let adjacent = adjacency(self, token);
+
+        // Every triangle is seen once from each of its three edges
+        let seen: usize = adjacent
+            .iter()
+            .map(|(id, neighbors)| {
+                neighbors
+                    .iter()
+                    .filter(|other| id < *other)
+                    .map(|other| common_len(neighbors, &adjacent[other]))
+                    .sum::<usize>()
+            })
+            .sum();
+
+        seen / 3
+    }
+    /// The local clustering coefficient of the vertex `id`, the
+    /// fraction of pairs of its neighbors that are joined to each
+    /// other, treating every edge as undirected
+    ///
+    /// Vertices with fewer than two neighbors have a coefficient of
+    /// `0.0`. Returns `None` if `id` isn't within the graph
+    #[must_use]
+    pub fn clustering_coefficient(
+        &self,
+        id: VertexId<'id>,
+        token: &GhostToken<'id>,
+    ) -> Option<f64> {
+        self.get_vertex(id)?;
+
+        let neighbors = neighbor_set(self, id, token);
+        let links: usize = neighbors
+            .iter()
+            .map(|other| common_len(&neighbors, &neighbor_set(self, *other, token)))
+            .sum();
+
+        Some(coefficient(links, neighbors.len()))
+    }
+    /// The mean of every vertex's
+    /// [`clustering_coefficient`](Graph::clustering_coefficient), or
+    /// `0.0` if the graph is empty
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn average_clustering(&self, token: &GhostToken<'id>) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+
+        let adjacent = adjacency(self, token);
+        let total: f64 = adjacent
+            .values()
+            .map(|neighbors| {
+                let links = neighbors
+                    .iter()
+                    .map(|other| common_len(neighbors, &adjacent[other]))
+                    .sum();
+                coefficient(links, neighbors.len())
+            })
+            .sum();
+
+        total / adjacent.len() as f64
+    }
+}
+
+/// The clustering coefficient of a vertex with `degree` neighbors,
+/// given the sum over each neighbor of how many of the others it's
+/// joined to, which counts every link between them twice
+#[allow(clippy::cast_precision_loss)]
+fn coefficient(links: usize, degree: usize) -> f64 {
+    if degree < 2 {
+        0.0
+    } else {
+        links as f64 / (degree * (degree - 1)) as f64
+    }
+}
@@ -0,0 +1,95 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, Allocator, Graph};
+
+use super::neighbors;
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Removes every vertex without any edges, returning how many
+    /// were removed
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let a = graph.add_vertex(());
+    ///     let b = graph.add_vertex(());
+    ///     let c = graph.add_vertex(());
+    ///     graph.add_edge(a, b, (), &mut token).unwrap();
+    ///
+    ///     assert_eq!(graph.remove_isolated_vertices(&mut token), 1);
+    ///     assert!(graph.get_vertex(c).is_none());
+    ///     assert_eq!(graph.vertex_len(), 2);
+    /// })
+    /// ```
+    pub fn remove_isolated_vertices(&mut self, token: &mut GhostToken<'id>) -> usize {
+        let isolated: Vec<_> = self
+            .vertex_ids()
+            .filter(|id| self.degree(*id) == Some(0))
+            .collect();
+
+        for id in &isolated {
+            // Every id was just taken from the graph
+            let _ = self.remove(*id, token);
+        }
+        isolated.len()
+    }
+    /// Removes every vertex with fewer than `min_degree` edges, again
+    /// and again, as removing a vertex lowers the degree of its
+    /// neighbors, until every vertex left has at least `min_degree`
+    /// edges, returning how many were removed
+    ///
+    /// What's left is the graph's `min_degree`-core, the largest
+    /// subgraph in which every vertex has a degree of at least
+    /// `min_degree`, and is empty if there is none. Degrees are those
+    /// of [`Graph::degree`], so count edges in both directions for
+    /// directed graphs. A `min_degree` of `1` is the same as
+    /// [`Graph::remove_isolated_vertices`]
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let v: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+    ///     // A triangle with a path of two edges hanging off it
+    ///     for (a, b) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4)] {
+    ///         graph.add_edge(v[a], v[b], (), &mut token).unwrap();
+    ///     }
+    ///
+    ///     // Removing `v[4]` leaves `v[3]` with a single edge too
+    ///     assert_eq!(graph.prune(2, &mut token), 2);
+    ///     assert_eq!(graph.vertex_len(), 3);
+    ///     assert_eq!(graph.degree(v[2]), Some(2));
+    /// })
+    /// ```
+    pub fn prune(&mut self, min_degree: usize, token: &mut GhostToken<'id>) -> usize {
+        let mut queued = self.vertex_set();
+        let mut queue: Vec<_> = self
+            .vertex_ids()
+            .filter(|id| self.degree(*id).is_some_and(|degree| degree < min_degree))
+            .collect();
+        queued.extend(queue.iter().copied());
+
+        let mut removed = 0;
+        while let Some(id) = queue.pop() {
+            let around = neighbors(self, id, token);
+            if self.remove(id, token).is_err() {
+                continue;
+            }
+            removed += 1;
+
+            for (_, other) in around {
+                let low = self.degree(other).is_some_and(|degree| degree < min_degree);
+                if low && queued.insert(other) {
+                    queue.push(other);
+                }
+            }
+        }
+        removed
+    }
+}
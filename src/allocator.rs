@@ -0,0 +1,49 @@
+use core::{alloc::Layout, ptr::NonNull};
+
+/// A source of memory for the vertices and edges of a
+/// [`Graph`](crate::Graph), so that graphs can be stored somewhere
+/// other than the global heap, such as a fixed-size arena on an
+/// embedded device
+///
+/// This mirrors the unstable `core::alloc::Allocator`, so that it can
+/// be implemented on stable Rust. The maps indexing a graph's
+/// vertices and edges still use the global allocator, as hashbrown
+/// only accepts a custom allocator on nightly
+///
+/// # Safety
+/// Memory returned by [`Allocator::allocate`] must be valid for
+/// reads and writes of `layout.size()` bytes, aligned to
+/// `layout.align()`, and stay valid until it's passed to
+/// [`Allocator::deallocate`] on the same allocator or a clone of it
+pub unsafe trait Allocator {
+    /// Allocates memory fitting `layout`, which is never zero-sized,
+    /// or returns `None` if the memory can't be allocated
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>>;
+    /// Deallocates the memory `ptr` points to
+    ///
+    /// # Safety
+    /// `ptr` must have been returned by [`Allocator::allocate`] on this
+    /// allocator or a clone of it, with the same `layout`, and not
+    /// deallocated since
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+/// The error returned when memory can't be allocated, such as by
+/// [`Graph::try_add_vertex`](crate::Graph::try_add_vertex)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError;
+
+/// The global allocator, which graphs use by default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Global;
+
+unsafe impl Allocator for Global {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        // SAFETY: `layout` is never zero-sized
+        NonNull::new(unsafe { alloc::alloc::alloc(layout) })
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        alloc::alloc::dealloc(ptr.as_ptr(), layout);
+    }
+}
@@ -0,0 +1,280 @@
+use core::{alloc::Layout, mem::MaybeUninit, ptr::NonNull};
+
+use alloc::vec::Vec;
+
+use crate::{ghost::GhostCell, AllocError, Allocator, Global, Shared};
+
+/// The number of slots in an arena's first chunk
+const FIRST_CHUNK_LEN: usize = 16;
+/// The largest number of slots a single chunk can have
+const MAX_CHUNK_LEN: usize = 1 << 16;
+
+/// A block of slots allocated at once, the first `len` of which
+/// have been handed out
+struct Chunk<'id, T> {
+    ptr: NonNull<MaybeUninit<GhostCell<'id, T>>>,
+    len: usize,
+    capacity: usize,
+}
+
+impl<'id, T> Chunk<'id, T> {
+    /// Allocates a chunk of `capacity` slots from `alloc`
+    fn new<A: Allocator>(capacity: usize, alloc: &A) -> Self {
+        Self::try_new(capacity, alloc)
+            .unwrap_or_else(|AllocError| alloc::alloc::handle_alloc_error(Self::layout(capacity)))
+    }
+    /// Allocates a chunk of `capacity` slots from `alloc`, or returns
+    /// an error if the memory can't be allocated
+    fn try_new<A: Allocator>(capacity: usize, alloc: &A) -> Result<Self, AllocError> {
+        let layout =
+            Layout::array::<MaybeUninit<GhostCell<'id, T>>>(capacity).map_err(|_| AllocError)?;
+        let ptr = if layout.size() == 0 {
+            NonNull::dangling()
+        } else {
+            alloc.allocate(layout).ok_or(AllocError)?.cast()
+        };
+
+        Ok(Self {
+            ptr,
+            len: 0,
+            capacity,
+        })
+    }
+    /// Gives the chunk's memory back to `alloc`
+    ///
+    /// # Safety
+    /// The chunk must have been allocated by `alloc`, and nothing
+    /// within it may be used afterwards
+    unsafe fn free<A: Allocator>(self, alloc: &A) {
+        let layout = Self::layout(self.capacity);
+        if layout.size() != 0 {
+            alloc.deallocate(self.ptr.cast(), layout);
+        }
+    }
+    /// The layout of a chunk of `capacity` slots
+    fn layout(capacity: usize) -> Layout {
+        Layout::array::<MaybeUninit<GhostCell<'id, T>>>(capacity)
+            .unwrap_or_else(|_| panic!("arena chunk too large"))
+    }
+}
+
+/// A typed slab that [`Shared`]s within a [`Graph`](crate::Graph)
+/// are allocated from
+///
+/// Memory is handed out from a list of chunks that never move once
+/// allocated, so the pointers within each [`Shared`] remain valid
+/// until the arena itself is dropped. Slots freed by removing a
+/// vertex or edge are reused before any new memory is requested,
+/// and [`Arena::clear`] makes every slot available again without
+/// giving any memory back.
+///
+/// Slots are handed out as pointers rather than indices. Every
+/// vertex and edge holds [`Shared`]s to its neighbours, which only
+/// need a [`GhostToken`](crate::ghost::GhostToken) to be read, where
+/// an index would need the arena at every step of a traversal. There
+/// is no generation to tell a reused slot apart, so the graph takes
+/// a [`Shared`] out of its maps before its slot is freed.
+///
+/// Chunks are allocated from `A`, while the lists keeping track of
+/// them use the global allocator.
+///
+/// Dropping the arena frees every chunk at once, but does **not**
+/// drop the values still within it, see [`Arena::drop_live`]. Values
+/// that don't need dropping, such as edges with plain weights, are
+/// never visited when the arena is cleared or dropped
+pub struct Arena<'id, T, A: Allocator = Global> {
+    chunks: Vec<Chunk<'id, T>>,
+    /// The index of the first chunk that may have space left
+    current: usize,
+    free: Vec<NonNull<GhostCell<'id, T>>>,
+    alloc: A,
+}
+
+// SAFETY: The arena owns every value within its chunks, as a
+// `Vec<T>` would
+#[allow(clippy::non_send_fields_in_send_ty)]
+unsafe impl<'id, T: Send, A: Allocator + Send> Send for Arena<'id, T, A> {}
+// SAFETY: Nothing within the chunks can be reached through a `&Arena`
+unsafe impl<'id, T: Sync, A: Allocator + Sync> Sync for Arena<'id, T, A> {}
+
+impl<'id, T, A: Allocator> Drop for Arena<'id, T, A> {
+    fn drop(&mut self) {
+        for chunk in self.chunks.drain(..) {
+            // SAFETY: Every chunk was allocated by `self.alloc`
+            unsafe { chunk.free(&self.alloc) };
+        }
+    }
+}
+
+impl<'id, T, A: Allocator> Arena<'id, T, A> {
+    /// Constructs a new empty arena which allocates from `alloc`,
+    /// without allocating
+    pub const fn new_in(alloc: A) -> Self {
+        Self {
+            chunks: Vec::new(),
+            current: 0,
+            free: Vec::new(),
+            alloc,
+        }
+    }
+    /// The allocator chunks are allocated from
+    pub const fn allocator(&self) -> &A {
+        &self.alloc
+    }
+    /// Moves `item` into the arena, returning a [`Shared`]
+    /// pointing to it
+    pub fn alloc(&mut self, item: T) -> Shared<'id, T> {
+        let cell = GhostCell::new(item);
+
+        if let Some(slot) = self.free.pop() {
+            // SAFETY: Slots within `free` are allocated but hold
+            // no value, so can be written to without dropping
+            unsafe { slot.as_ptr().write(cell) };
+            return Shared::from_ptr(slot);
+        }
+
+        while self
+            .chunks
+            .get(self.current)
+            .is_some_and(|chunk| chunk.len == chunk.capacity)
+        {
+            self.current += 1;
+        }
+
+        if self.current == self.chunks.len() {
+            let len = self.capacity().clamp(FIRST_CHUNK_LEN, MAX_CHUNK_LEN);
+            self.chunks.push(Chunk::new(len, &self.alloc));
+        }
+
+        let chunk = &mut self.chunks[self.current];
+
+        // SAFETY: `len` is less than the chunk's capacity, so the
+        // write is in bounds, and chunks never move. No reference
+        // to the chunk's other slots is ever created
+        unsafe {
+            let slot = chunk.ptr.as_ptr().add(chunk.len);
+            slot.write(MaybeUninit::new(cell));
+            chunk.len += 1;
+            Shared::from_ptr(NonNull::new_unchecked(slot.cast()))
+        }
+    }
+    /// Drops the value `shared` points to, leaving its slot
+    /// free to be reused
+    ///
+    /// # Safety
+    /// `shared` must have been allocated by `self`, and there can
+    /// be no other pointers to its contents that are dereferenced
+    /// afterwards
+    #[allow(clippy::needless_pass_by_value)]
+    pub unsafe fn free(&mut self, shared: Shared<'id, T>) {
+        let slot = shared.as_ptr();
+        core::ptr::drop_in_place(slot.as_ptr());
+        self.free.push(slot);
+    }
+    /// Moves the value `shared` points to out of the arena,
+    /// leaving its slot free to be reused
+    ///
+    /// # Safety
+    /// `shared` must have been allocated by `self`, and there can
+    /// be no other pointers to its contents that are dereferenced
+    /// afterwards
+    #[allow(clippy::needless_pass_by_value)]
+    pub unsafe fn take(&mut self, shared: Shared<'id, T>) -> T {
+        let slot = shared.as_ptr();
+        let cell = slot.as_ptr().read();
+        self.free.push(slot);
+        cell.into_inner()
+    }
+    /// Drops every value in `live` in place, without freeing any
+    /// memory. This should be called just before the arena the
+    /// values are in is dropped, as an arena can't tell which of
+    /// its slots are in use
+    ///
+    /// # Safety
+    /// `live` must contain every value still within the arena
+    /// exactly once, and nothing within the arena may be used
+    /// afterwards
+    pub unsafe fn drop_live<'a>(live: impl Iterator<Item = &'a Shared<'id, T>>)
+    where
+        'id: 'a,
+        T: 'a,
+    {
+        if core::mem::needs_drop::<T>() {
+            live.for_each(|shared| core::ptr::drop_in_place(shared.as_ptr().as_ptr()));
+        }
+    }
+    /// Drops every value in `live`, and then makes every slot
+    /// within the arena available again, keeping its memory
+    ///
+    /// If `T` doesn't need dropping, `live` is never iterated
+    ///
+    /// # Safety
+    /// `live` must contain every value still within the arena
+    /// exactly once, and no [`Shared`] from the arena may be
+    /// used afterwards
+    pub unsafe fn clear(&mut self, live: impl Iterator<Item = Shared<'id, T>>) {
+        if core::mem::needs_drop::<T>() {
+            live.for_each(|shared| core::ptr::drop_in_place(shared.as_ptr().as_ptr()));
+        }
+
+        // The slots are `MaybeUninit`, so nothing is dropped
+        self.chunks.iter_mut().for_each(|chunk| chunk.len = 0);
+        self.current = 0;
+        self.free.clear();
+    }
+    /// Makes sure at least `additional` more values can be
+    /// allocated without the arena allocating any more memory
+    pub fn reserve(&mut self, additional: usize) {
+        if let Some(len) = self.missing(additional) {
+            self.chunks.push(Chunk::new(len, &self.alloc));
+        }
+    }
+    /// Makes sure at least `additional` more values can be
+    /// allocated without the arena allocating any more memory, or
+    /// returns an error if the memory can't be allocated
+    ///
+    /// # Errors
+    /// Returns an [`AllocError`] if the arena's allocator fails
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+        if let Some(len) = self.missing(additional) {
+            self.chunks.try_reserve(1).map_err(|_| AllocError)?;
+            self.chunks.push(Chunk::try_new(len, &self.alloc)?);
+        }
+        Ok(())
+    }
+    /// The length of the chunk needed to hold `additional` more
+    /// values, if the arena doesn't have room for them already
+    fn missing(&self, additional: usize) -> Option<usize> {
+        let available = self.free.len()
+            + self.chunks[self.current.min(self.chunks.len())..]
+                .iter()
+                .map(|chunk| chunk.capacity - chunk.len)
+                .sum::<usize>();
+
+        (available < additional).then(|| (additional - available).max(FIRST_CHUNK_LEN))
+    }
+    /// Frees every chunk that holds no values
+    ///
+    /// Slots freed by removing values can't be given back, as
+    /// other values within their chunk can't be moved
+    pub fn shrink_to_fit(&mut self) {
+        // Chunks are filled in order, so every chunk after the
+        // current one is empty
+        let kept = self.current
+            + usize::from(
+                self.chunks
+                    .get(self.current)
+                    .is_some_and(|chunk| chunk.len != 0),
+            );
+        for chunk in self.chunks.drain(kept..) {
+            // SAFETY: Every chunk was allocated by `self.alloc`,
+            // and empty chunks hold nothing that could be used
+            unsafe { chunk.free(&self.alloc) };
+        }
+        self.free.shrink_to_fit();
+    }
+    /// The total number of slots the arena has allocated
+    pub fn capacity(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.capacity).sum()
+    }
+}
@@ -0,0 +1,125 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, Allocator, EdgeId, Graph, GraphError, VertexId};
+
+/// What a batch operation should do when one of its items fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnError {
+    /// Stop at the first failure, returning it as an error. Items
+    /// before the failure are still applied
+    #[default]
+    FailFast,
+    /// Carry on past failures, discarding them
+    Skip,
+    /// Carry on past failures, recording each of them in the
+    /// [`BatchReport`]
+    Collect,
+}
+
+/// The outcome of a batch operation that didn't fail fast
+pub struct BatchReport<'id, Output, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> {
+    /// The output of every item that succeeded, along with the
+    /// item's index within the batch
+    pub succeeded: Vec<(usize, Output)>,
+    /// The index of every item that failed along with the reason, if
+    /// the batch was run with [`OnError::Collect`]
+    pub failed: Vec<(usize, GraphError<'id, Item, Weight, Edge>)>,
+}
+
+impl<'id, Output, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> core::fmt::Debug
+    for BatchReport<'id, Output, Item, Weight, Edge>
+where
+    Output: core::fmt::Debug,
+    GraphError<'id, Item, Weight, Edge>: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BatchReport")
+            .field("succeeded", &self.succeeded)
+            .field("failed", &self.failed)
+            .finish()
+    }
+}
+
+impl<'id, Output, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>>
+    BatchReport<'id, Output, Item, Weight, Edge>
+{
+    /// Runs `apply` over every item in `items`, handling failures
+    /// according to `on_error`
+    ///
+    /// # Errors
+    /// Returns the index and error of the first failure if
+    /// `on_error` is [`OnError::FailFast`]
+    pub fn run<T>(
+        items: impl IntoIterator<Item = T>,
+        on_error: OnError,
+        mut apply: impl FnMut(T) -> Result<Output, GraphError<'id, Item, Weight, Edge>>,
+    ) -> Result<Self, (usize, GraphError<'id, Item, Weight, Edge>)> {
+        let mut report = Self {
+            succeeded: Vec::new(),
+            failed: Vec::new(),
+        };
+
+        for (index, item) in items.into_iter().enumerate() {
+            match apply(item) {
+                Ok(output) => report.succeeded.push((index, output)),
+                Err(error) => match on_error {
+                    OnError::FailFast => return Err((index, error)),
+                    OnError::Skip => {}
+                    OnError::Collect => report.failed.push((index, error)),
+                },
+            }
+        }
+
+        Ok(report)
+    }
+    /// If every item in the batch succeeded
+    ///
+    /// Always `true` for batches run with [`OnError::Skip`]
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Adds an edge for every `(first, second, weight)` in `edges`,
+    /// see [`Graph::add_edge`]
+    ///
+    /// # Errors
+    /// Returns the index and error of the first edge that couldn't be
+    /// added if `on_error` is [`OnError::FailFast`]
+    #[allow(clippy::type_complexity)]
+    pub fn add_edges(
+        &mut self,
+        edges: impl IntoIterator<Item = (VertexId<'id>, VertexId<'id>, Weight)>,
+        on_error: OnError,
+        token: &mut GhostToken<'id>,
+    ) -> Result<
+        BatchReport<'id, EdgeId<'id>, Item, Weight, Edge>,
+        (usize, GraphError<'id, Item, Weight, Edge>),
+    > {
+        BatchReport::run(edges, on_error, |(first, second, weight)| {
+            self.add_edge(first, second, weight, token)
+        })
+    }
+    /// Removes every vertex in `ids`, see [`Graph::remove`]
+    ///
+    /// # Errors
+    /// Returns the index and error of the first vertex that couldn't
+    /// be removed if `on_error` is [`OnError::FailFast`]
+    #[allow(clippy::type_complexity)]
+    pub fn remove_vertices(
+        &mut self,
+        ids: impl IntoIterator<Item = VertexId<'id>>,
+        on_error: OnError,
+        token: &mut GhostToken<'id>,
+    ) -> Result<
+        BatchReport<'id, Item, Item, Weight, Edge>,
+        (usize, GraphError<'id, Item, Weight, Edge>),
+    > {
+        BatchReport::run(ids, on_error, |id| self.remove_take(id, token))
+    }
+}
@@ -0,0 +1,408 @@
+use alloc::{string::String, vec::Vec};
+use core::{
+    convert::{TryFrom, TryInto},
+    hash::BuildHasher,
+};
+
+use hashbrown::HashMap;
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, Allocator, EdgeId, Graph, VertexId};
+
+/// The bytes every snapshot starts with
+const MAGIC: [u8; 4] = *b"GRPH";
+/// The version of the format written by [`Graph::to_bytes`]
+const VERSION: u8 = 1;
+
+/// A value that can be written into a binary snapshot, see
+/// [`Graph::to_bytes`]
+///
+/// Numbers are written in little-endian order, and lengths and
+/// `usize`s as `u64`s, so the bytes are the same on every platform
+pub trait Encode {
+    /// Appends the bytes of `self` to `out`
+    fn encode(&self, out: &mut Vec<u8>);
+}
+
+/// A value that can be read back out of a binary snapshot, see
+/// [`Graph::from_bytes`]
+pub trait Decode: Sized {
+    /// Reads a value from the start of `input`, advancing `input`
+    /// past the bytes that were read
+    ///
+    /// # Errors
+    /// Returns a [`DecodeError`] if `input` doesn't start with a
+    /// valid value
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError>;
+}
+
+/// An error returned when a binary snapshot can't be read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The bytes ended part way through a value
+    UnexpectedEnd,
+    /// The bytes don't start with the snapshot header
+    BadMagic,
+    /// The snapshot was written by an unknown version of the format
+    UnsupportedVersion(u8),
+    /// The snapshot is of a directed graph being read as an
+    /// undirected one, or the other way around
+    DirectionMismatch,
+    /// A value was malformed, such as a `bool` that isn't 0 or 1, or
+    /// a string that isn't valid UTF-8
+    InvalidValue,
+    /// A number didn't fit in the type it was read as, such as a
+    /// `usize` on a 32-bit platform
+    Overflow,
+    /// The edge at this index couldn't be added to the graph, because
+    /// one of its vertices is missing or it duplicates another edge
+    InvalidEdge(usize),
+    /// There were bytes left over after the graph was read
+    TrailingBytes(usize),
+}
+
+/// Splits `len` bytes off the start of `input`
+const fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], DecodeError> {
+    if input.len() < len {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    let (taken, rest) = input.split_at(len);
+    *input = rest;
+    Ok(taken)
+}
+
+macro_rules! impl_number {
+    ($($t:ty),*) => {$(
+        impl Encode for $t {
+            fn encode(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+        }
+
+        impl Decode for $t {
+            fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+                let bytes = take(input, core::mem::size_of::<$t>())?;
+                let mut array = [0; core::mem::size_of::<$t>()];
+                array.copy_from_slice(bytes);
+                Ok(<$t>::from_le_bytes(array))
+            }
+        }
+    )*};
+}
+
+impl_number!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl Encode for usize {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as u64).encode(out);
+    }
+}
+
+impl Decode for usize {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        Self::try_from(u64::decode(input)?).map_err(|_| DecodeError::Overflow)
+    }
+}
+
+impl Encode for isize {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as i64).encode(out);
+    }
+}
+
+impl Decode for isize {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        Self::try_from(i64::decode(input)?).map_err(|_| DecodeError::Overflow)
+    }
+}
+
+impl Encode for () {
+    fn encode(&self, _: &mut Vec<u8>) {}
+}
+
+impl Decode for () {
+    fn decode(_: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(())
+    }
+}
+
+impl Encode for bool {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(u8::from(*self));
+    }
+}
+
+impl Decode for bool {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(input)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(DecodeError::InvalidValue),
+        }
+    }
+}
+
+impl Encode for char {
+    fn encode(&self, out: &mut Vec<u8>) {
+        u32::from(*self).encode(out);
+    }
+}
+
+impl Decode for char {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        Self::from_u32(u32::decode(input)?).ok_or(DecodeError::InvalidValue)
+    }
+}
+
+impl Encode for str {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.len().encode(out);
+        out.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl Encode for String {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.as_str().encode(out);
+    }
+}
+
+impl Decode for String {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let len = usize::decode(input)?;
+        let bytes = take(input, len)?;
+        core::str::from_utf8(bytes)
+            .map(Self::from)
+            .map_err(|_| DecodeError::InvalidValue)
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Some(value) => {
+                out.push(1);
+                value.encode(out);
+            }
+            None => out.push(0),
+        }
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        if bool::decode(input)? {
+            T::decode(input).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<T: Encode> Encode for [T] {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.len().encode(out);
+        for value in self {
+            value.encode(out);
+        }
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.as_slice().encode(out);
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let len = usize::decode(input)?;
+        let mut values = Self::with_capacity(len.min(input.len()));
+        for _ in 0..len {
+            values.push(T::decode(input)?);
+        }
+        Ok(values)
+    }
+}
+
+impl<T: Encode, const N: usize> Encode for [T; N] {
+    fn encode(&self, out: &mut Vec<u8>) {
+        for value in self {
+            value.encode(out);
+        }
+    }
+}
+
+impl<T: Decode, const N: usize> Decode for [T; N] {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let mut values = Vec::with_capacity(N);
+        for _ in 0..N {
+            values.push(T::decode(input)?);
+        }
+        values.try_into().map_err(|_| DecodeError::InvalidValue)
+    }
+}
+
+impl<T: Encode + ?Sized> Encode for &T {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (**self).encode(out);
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident),*) => {
+        impl<$($name: Encode),*> Encode for ($($name,)*) {
+            #[allow(non_snake_case)]
+            fn encode(&self, out: &mut Vec<u8>) {
+                let ($($name,)*) = self;
+                $($name.encode(out);)*
+            }
+        }
+
+        impl<$($name: Decode),*> Decode for ($($name,)*) {
+            fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+                Ok(($($name::decode(input)?,)*))
+            }
+        }
+    };
+}
+
+impl_tuple!(T1);
+impl_tuple!(T1, T2);
+impl_tuple!(T1, T2, T3);
+impl_tuple!(T1, T2, T3, T4);
+
+impl<'id, Item, Weight, Edge, S, A> Graph<'id, Item, Weight, Edge, S, A>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// Writes the graph into a compact binary snapshot, which can be
+    /// read back with [`Graph::from_bytes`]
+    ///
+    /// The snapshot doesn't depend on serde, and is the same on every
+    /// platform, with vertices and edges written in order of their
+    /// ids. [`Graph::from_bytes`] numbers them from zero in that
+    /// order, so ids are only kept if they had no gaps
+    ///
+    /// ```rust
+    /// use graph::{edge::DirectedWeightedEdge, ghost::GhostToken, ops::GraphOps, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<String, u32, DirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let a = graph.add_vertex("a".to_string());
+    ///     let b = graph.add_vertex("b".to_string());
+    ///     graph.add_edge(a, b, 7, &mut token).unwrap();
+    ///
+    ///     let bytes = graph.to_bytes(&token);
+    ///     let copy: Graph<String, u32, DirectedWeightedEdge<_, _>> =
+    ///         Graph::from_bytes(&bytes, &mut token).unwrap();
+    ///     assert_eq!(copy.weight_between(a, b, &token), Some(&7));
+    /// })
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self, token: &GhostToken<'id>) -> Vec<u8>
+    where
+        Item: Encode,
+        Weight: Encode,
+    {
+        let mut out = Vec::new();
+        out.extend_from_slice(&MAGIC);
+        out.push(VERSION);
+        Edge::DIRECTED.encode(&mut out);
+
+        let mut items: Vec<_> = self.items(token).collect();
+        items.sort_unstable_by_key(|(id, _)| *id);
+        items.len().encode(&mut out);
+        for (id, item) in items {
+            id.id().encode(&mut out);
+            item.encode(&mut out);
+        }
+
+        let mut edges: Vec<_> = self.unique_edges(token).collect();
+        edges.sort_unstable_by_key(|(id, ..)| *id);
+        edges.len().encode(&mut out);
+        for (id, from, to, weight) in edges {
+            id.id.encode(&mut out);
+            from.id().encode(&mut out);
+            to.id().encode(&mut out);
+            weight.encode(&mut out);
+        }
+
+        out
+    }
+    /// Reads a graph back from a snapshot written by
+    /// [`Graph::to_bytes`]
+    ///
+    /// Vertices and edges are numbered from zero in the order they
+    /// were written, rather than keeping the ids in the snapshot, so
+    /// however high those ids are the graph only needs room for as
+    /// many as it holds
+    ///
+    /// # Errors
+    /// Returns a [`DecodeError`] if `bytes` isn't a valid snapshot of
+    /// a graph with the same kind of edges
+    pub fn from_bytes(bytes: &[u8], token: &mut GhostToken<'id>) -> Result<Self, DecodeError>
+    where
+        Item: Decode,
+        Weight: Decode,
+        S: Clone + Default,
+        A: Clone + Default,
+    {
+        let mut input = bytes;
+        if take(&mut input, MAGIC.len())? != MAGIC {
+            return Err(DecodeError::BadMagic);
+        }
+        let version = u8::decode(&mut input)?;
+        if version != VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+        if bool::decode(&mut input)? != Edge::DIRECTED {
+            return Err(DecodeError::DirectionMismatch);
+        }
+
+        let vertices = usize::decode(&mut input)?;
+        let mut graph = Self::with_capacity_and_hasher_in(
+            vertices.min(input.len()),
+            0,
+            S::default(),
+            A::default(),
+        );
+        // The ids written for each vertex, to find the ends of edges by
+        let mut ids = HashMap::with_capacity(vertices.min(input.len()));
+        for i in 0..vertices {
+            let written = usize::decode(&mut input)?;
+            let item = Item::decode(&mut input)?;
+            let id = VertexId::new(i);
+            if ids.insert(written, id).is_some() {
+                return Err(DecodeError::InvalidValue);
+            }
+            graph.insert_vertex(id, item);
+        }
+
+        // Counts are checked against the bytes left, so a corrupt
+        // count can't reserve more memory than the snapshot could fill
+        let edges = usize::decode(&mut input)?;
+        graph.reserve_edges(edges.min(input.len()));
+        for i in 0..edges {
+            // Edge ids are only written to keep the order of edges
+            usize::decode(&mut input)?;
+            let from = usize::decode(&mut input)?;
+            let to = usize::decode(&mut input)?;
+            let weight = Weight::decode(&mut input)?;
+            let (from, to) = match (ids.get(&from), ids.get(&to)) {
+                (Some(from), Some(to)) => (*from, *to),
+                _ => return Err(DecodeError::InvalidEdge(i)),
+            };
+            graph
+                .insert_edge(EdgeId::new(i), from, to, weight, token)
+                .map_err(|_| DecodeError::InvalidEdge(i))?;
+        }
+
+        if input.is_empty() {
+            Ok(graph)
+        } else {
+            Err(DecodeError::TrailingBytes(input.len()))
+        }
+    }
+}
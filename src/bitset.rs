@@ -0,0 +1,230 @@
+use alloc::{collections::BTreeSet, vec, vec::Vec};
+use core::{fmt, iter::FromIterator};
+
+use crate::{edge::EdgeTrait, ghost::InvariantLifetime, Allocator, Graph, VertexId};
+
+const BITS: usize = u64::BITS as usize;
+
+/// The number of words a [`VertexBitSet`] can always have, however
+/// few ids it holds
+const MIN_WORDS: usize = 64;
+
+/// The number of words a [`VertexBitSet`] holding `len` ids, with
+/// space for `capacity` words, can grow to, which is at most one word
+/// for each id on top of [`MIN_WORDS`]
+const fn dense_limit(capacity: usize, len: usize) -> usize {
+    let limit = len.saturating_add(MIN_WORDS);
+    if capacity > limit {
+        capacity
+    } else {
+        limit
+    }
+}
+
+/// A set of [`VertexId`]s stored as one bit per id, for marking
+/// vertices as visited without hashing
+///
+/// Inserting, removing and checking for an id are each a single bit
+/// operation. As ids are never reused, the set only ever grows as
+/// large as the highest id put into it, and [`Graph::vertex_set`]
+/// constructs a set with space for every vertex in the graph already.
+/// Ids far beyond the number of ids the set holds are kept in an
+/// ordered set instead, so a few high ids can't make the set take up
+/// more space than the ids it holds need
+///
+/// ```rust
+/// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+///
+/// GhostToken::new(|mut token| {
+///     let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+///     let a = graph.add_vertex(());
+///     let b = graph.add_vertex(());
+///
+///     let mut visited = graph.vertex_set();
+///     assert!(visited.insert(b));
+///     assert!(!visited.insert(b));
+///
+///     assert!(visited.contains(b));
+///     assert!(!visited.contains(a));
+///     assert_eq!(visited.len(), 1);
+/// })
+/// ```
+#[derive(Clone)]
+pub struct VertexBitSet<'id> {
+    words: Vec<u64>,
+    /// Ids too high to keep in `words`, each of which is beyond the
+    /// end of `words`
+    sparse: BTreeSet<usize>,
+    len: usize,
+    _marker: InvariantLifetime<'id>,
+}
+
+impl<'id> VertexBitSet<'id> {
+    /// Constructs an empty set
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            words: Vec::new(),
+            sparse: BTreeSet::new(),
+            len: 0,
+            _marker: InvariantLifetime::new(),
+        }
+    }
+    /// Constructs an empty set with space for ids below `capacity`
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            words: vec![0; capacity.div_ceil(BITS)],
+            sparse: BTreeSet::new(),
+            len: 0,
+            _marker: InvariantLifetime::new(),
+        }
+    }
+    /// Returns the number of ids in the set
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns `true` if the set holds no ids
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Returns `true` if `id` is in the set
+    #[must_use]
+    pub fn contains(&self, id: VertexId<'id>) -> bool {
+        let (word, bit) = Self::position(id);
+        self.words
+            .get(word)
+            .map_or_else(|| self.sparse.contains(&id.id()), |word| word & bit != 0)
+    }
+    /// Adds `id` to the set, returning `true` if it wasn't already
+    /// in it
+    pub fn insert(&mut self, id: VertexId<'id>) -> bool {
+        let (word, bit) = Self::position(id);
+        let added = if self.make_dense(word) {
+            let added = self.words[word] & bit == 0;
+            self.words[word] |= bit;
+            added
+        } else {
+            self.sparse.insert(id.id())
+        };
+
+        self.len += usize::from(added);
+        added
+    }
+    /// Removes `id` from the set, returning `true` if it was in it
+    pub fn remove(&mut self, id: VertexId<'id>) -> bool {
+        let (word, bit) = Self::position(id);
+        let removed = match self.words.get_mut(word) {
+            Some(word) => {
+                let removed = *word & bit != 0;
+                *word &= !bit;
+                removed
+            }
+            None => self.sparse.remove(&id.id()),
+        };
+
+        self.len -= usize::from(removed);
+        removed
+    }
+    /// Removes every id, keeping the space they took
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|word| *word = 0);
+        self.sparse.clear();
+        self.len = 0;
+    }
+    /// Returns an iterator over the ids in the set, in order
+    pub fn iter(&self) -> impl Iterator<Item = VertexId<'id>> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(|(index, word)| {
+                let mut word = *word;
+                core::iter::from_fn(move || {
+                    if word == 0 {
+                        return None;
+                    }
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Some(VertexId::new(index * BITS + bit))
+                })
+            })
+            .chain(self.sparse.iter().map(|id| VertexId::new(*id)))
+    }
+    /// Grows `words` to hold `word` if it's not too far beyond the
+    /// number of ids held, moving any ids for the new words out of
+    /// `sparse`, and returns whether `word` is held
+    fn make_dense(&mut self, word: usize) -> bool {
+        if word < self.words.len() {
+            return true;
+        }
+        if word >= dense_limit(self.words.capacity(), self.len) {
+            return false;
+        }
+
+        self.words.resize(word + 1, 0);
+        let sparse = self.sparse.split_off(&(self.words.len() * BITS));
+        for id in core::mem::replace(&mut self.sparse, sparse) {
+            let (word, bit) = Self::position(VertexId::new(id));
+            self.words[word] |= bit;
+        }
+        true
+    }
+    /// The word `id` is stored in, and its bit within the word
+    const fn position(id: VertexId<'id>) -> (usize, u64) {
+        (id.id() / BITS, 1 << (id.id() % BITS))
+    }
+}
+
+impl<'id> Default for VertexBitSet<'id> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'id> PartialEq for VertexBitSet<'id> {
+    fn eq(&self, other: &Self) -> bool {
+        // Sets may have space for different ids without holding them,
+        // or keep the same id densely in one and sparsely in the other
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<'id> Eq for VertexBitSet<'id> {}
+
+impl<'id> fmt::Debug for VertexBitSet<'id> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<'id> Extend<VertexId<'id>> for VertexBitSet<'id> {
+    fn extend<I: IntoIterator<Item = VertexId<'id>>>(&mut self, iter: I) {
+        for id in iter {
+            self.insert(id);
+        }
+    }
+}
+
+impl<'id> FromIterator<VertexId<'id>> for VertexBitSet<'id> {
+    fn from_iter<I: IntoIterator<Item = VertexId<'id>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Constructs an empty [`VertexBitSet`] with space for every
+    /// vertex in the graph
+    #[must_use]
+    pub fn vertex_set(&self) -> VertexBitSet<'id> {
+        VertexBitSet::with_capacity(
+            self.current_vertex_id
+                .min(dense_limit(0, self.vertices.len()) * BITS),
+        )
+    }
+}
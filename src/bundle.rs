@@ -0,0 +1,211 @@
+use core::{hash::BuildHasher, ptr};
+
+use hashbrown::hash_map::DefaultHashBuilder;
+
+use crate::{edge::EdgeKind, ghost::GhostToken, Allocator, Global, Graph};
+
+/// The graph a [`GraphWithToken`] holds, given the brand `'id`
+type Branded<'id, Item, Weight, Edge, S, A> =
+    Graph<'id, Item, Weight, <Edge as EdgeKind<Item, Weight>>::Edge<'id>, S, A>;
+
+/// A [`Graph`] bundled with its [`GhostToken`], which can be stored
+/// in a struct or returned from a function, unlike a graph made
+/// within [`GhostToken::new`]
+///
+/// The graph is reached through [`GraphWithToken::with`] and
+/// [`GraphWithToken::with_mut`], which brand it afresh for the
+/// length of a closure, so nothing branded can escape it. Items
+/// and weights are kept across calls, but [`VertexId`](crate::VertexId)s
+/// have to be stored as plain indices, and rebuilt with
+/// [`VertexId::new`](crate::VertexId::new).
+///
+/// `Edge` names a family of edges, see [`EdgeKind`]
+///
+/// ```rust
+/// use graph::{edge::UnDirectedWeightedEdge, GraphWithToken};
+///
+/// fn path(n: usize) -> GraphWithToken<usize, (), UnDirectedWeightedEdge<'static, usize, ()>> {
+///     let mut bundle = GraphWithToken::new();
+///     bundle.with_mut(|graph, token| {
+///         let ids: Vec<_> = (0..n).map(|i| graph.add_vertex(i)).collect();
+///         for pair in ids.windows(2) {
+///             graph.add_edge(pair[0], pair[1], (), token).unwrap();
+///         }
+///     });
+///     bundle
+/// }
+///
+/// let bundle = path(4);
+/// assert_eq!(bundle.with(|graph, _| graph.edge_len()), 3);
+/// ```
+pub struct GraphWithToken<Item, Weight, Edge, S = DefaultHashBuilder, A: Allocator = Global>
+where
+    Item: 'static,
+    Weight: 'static,
+    Edge: EdgeKind<Item, Weight>,
+{
+    graph: Branded<'static, Item, Weight, Edge, S, A>,
+    token: GhostToken<'static>,
+}
+
+impl<Item, Weight, Edge> Default for GraphWithToken<Item, Weight, Edge>
+where
+    Item: 'static,
+    Weight: 'static,
+    Edge: EdgeKind<Item, Weight>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Item, Weight, Edge> GraphWithToken<Item, Weight, Edge>
+where
+    Item: 'static,
+    Weight: 'static,
+    Edge: EdgeKind<Item, Weight>,
+{
+    /// Constructs a new empty graph along with its token
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::new(),
+            // SAFETY: The token is only ever reached rebranded, with a
+            // brand nothing else has, see `with`
+            token: unsafe { GhostToken::new_unchecked() },
+        }
+    }
+}
+
+impl<Item, Weight, Edge, S, A: Allocator> GraphWithToken<Item, Weight, Edge, S, A>
+where
+    Item: 'static,
+    Weight: 'static,
+    Edge: EdgeKind<Item, Weight>,
+{
+    /// Constructs a new empty graph which uses `hash_builder` and
+    /// allocates from `alloc`, along with its token
+    #[must_use]
+    pub fn with_hasher_in(hash_builder: S, alloc: A) -> Self
+    where
+        S: BuildHasher + Clone,
+        A: Clone,
+    {
+        Self {
+            graph: Graph::with_hasher_in(hash_builder, alloc),
+            // SAFETY: The token is only ever reached rebranded, with a
+            // brand nothing else has, see `with`
+            token: unsafe { GhostToken::new_unchecked() },
+        }
+    }
+    /// Calls `f` with the graph and its token, branded with a
+    /// lifetime that only lasts as long as the call
+    pub fn with<R>(
+        &self,
+        f: impl for<'id> FnOnce(&Branded<'id, Item, Weight, Edge, S, A>, &GhostToken<'id>) -> R,
+    ) -> R {
+        // SAFETY: Brands don't change a graph's layout, and `f` works
+        // for any brand, so can't mix this graph or its token with
+        // anything branded elsewhere
+        unsafe {
+            f(
+                rebrand::<Item, Weight, Edge, S, A>(&self.graph),
+                &*ptr::from_ref(&self.token).cast(),
+            )
+        }
+    }
+    /// Calls `f` with the graph and its token mutably, branded with
+    /// a lifetime that only lasts as long as the call
+    pub fn with_mut<R>(
+        &mut self,
+        f: impl for<'id> FnOnce(&mut Branded<'id, Item, Weight, Edge, S, A>, &mut GhostToken<'id>) -> R,
+    ) -> R {
+        // SAFETY: As above
+        unsafe {
+            f(
+                rebrand_mut::<Item, Weight, Edge, S, A>(&mut self.graph),
+                &mut *ptr::from_mut(&mut self.token).cast(),
+            )
+        }
+    }
+}
+
+/// Gives `graph` the brand `'id`
+///
+/// # Safety
+/// Nothing else may be branded with `'id`
+const unsafe fn rebrand<'a, 'id, Item, Weight, Edge, S, A>(
+    graph: &'a Branded<'static, Item, Weight, Edge, S, A>,
+) -> &'a Branded<'id, Item, Weight, Edge, S, A>
+where
+    Item: 'static,
+    Weight: 'static,
+    Edge: EdgeKind<Item, Weight>,
+    A: Allocator,
+{
+    &*ptr::from_ref(graph).cast()
+}
+
+/// Gives `graph` the brand `'id`
+///
+/// # Safety
+/// Nothing else may be branded with `'id`
+unsafe fn rebrand_mut<'a, 'id, Item, Weight, Edge, S, A>(
+    graph: &'a mut Branded<'static, Item, Weight, Edge, S, A>,
+) -> &'a mut Branded<'id, Item, Weight, Edge, S, A>
+where
+    Item: 'static,
+    Weight: 'static,
+    Edge: EdgeKind<Item, Weight>,
+    A: Allocator,
+{
+    &mut *ptr::from_mut(graph).cast()
+}
+
+impl<'id> GhostToken<'id> {
+    /// Calls `f` with `N` new empty graphs sharing one brand, along
+    /// with their token, so that every graph can be used with the
+    /// same token and vertices can be moved between them with
+    /// [`Graph::move_vertex`]
+    ///
+    /// As the brand can't tell the graphs apart, they take turns
+    /// handing out [`VertexId`](crate::VertexId)s and
+    /// [`EdgeId`](crate::EdgeId)s, so no two ever hand out the same
+    /// id, and an id of one graph is never found within another
+    ///
+    /// `Edge` names a family of edges, see [`EdgeKind`]
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken};
+    ///
+    /// GhostToken::with_graphs::<u32, (), UnDirectedWeightedEdge<u32, ()>, _, 2>(
+    ///     |[mut first, mut second], mut token| {
+    ///         let id = first.add_vertex(1);
+    ///         let moved = first.move_vertex(id, &mut second, &mut token).unwrap();
+    ///
+    ///         assert!(first.is_empty());
+    ///         assert_eq!(second.vertex_len(), 1);
+    ///         assert_ne!(moved, id);
+    ///         assert!(second.get_vertex(id).is_none());
+    ///     },
+    /// );
+    /// ```
+    pub fn with_graphs<Item, Weight, Edge, R, const N: usize>(
+        f: impl for<'new_id> FnOnce(
+            [Branded<'new_id, Item, Weight, Edge, DefaultHashBuilder, Global>; N],
+            GhostToken<'new_id>,
+        ) -> R,
+    ) -> R
+    where
+        Item: 'static,
+        Weight: 'static,
+        Edge: EdgeKind<Item, Weight>,
+    {
+        f(
+            core::array::from_fn(|i| Graph::new().into_sibling(i, N)),
+            // SAFETY: As in `GhostToken::new`, `f` has to work for any
+            // `'new_id`, which makes the brand unique to this call
+            unsafe { GhostToken::new_unchecked() },
+        )
+    }
+}
@@ -0,0 +1,98 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use crate::{edge::EdgeTrait, Allocator, Graph, VertexId};
+
+/// An iterator over the [`VertexId`]s of a [`Graph`] in chunks,
+/// ordered by id
+///
+/// Created by [`Graph::vertices_chunks`]
+pub struct VertexChunks<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator>
+{
+    graph: &'a Graph<'id, Item, Weight, Edge, S, A>,
+    next: usize,
+    chunk_size: usize,
+}
+
+impl<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator>
+    VertexChunks<'a, 'id, Item, Weight, Edge, S, A>
+{
+    /// The [`VertexId`] the next chunk starts from, which can be
+    /// passed to [`Graph::vertices_chunks_from`] to carry on from
+    /// the same place later, even if the graph has been changed
+    #[must_use]
+    pub const fn resume_from(&self) -> VertexId<'id> {
+        VertexId::new(self.next)
+    }
+}
+
+impl<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Iterator for VertexChunks<'a, 'id, Item, Weight, Edge, S, A>
+{
+    type Item = Vec<VertexId<'id>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = Vec::with_capacity(self.chunk_size);
+
+        while chunk.len() < self.chunk_size && self.next < self.graph.current_vertex_id {
+            let id = VertexId::new(self.next);
+            if self.graph.vertices.contains_key(&id) {
+                chunk.push(id);
+            }
+            self.next += 1;
+        }
+
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(chunk)
+        }
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Returns an iterator over the [`VertexId`]s of every vertex in
+    /// chunks of at most `chunk_size`, ordered by id
+    ///
+    /// Ids are found as the chunks are taken, so a large graph can
+    /// be processed a little at a time without collecting every id
+    /// first. See [`VertexChunks::resume_from`] to carry on later
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is `0`
+    #[must_use]
+    pub fn vertices_chunks(
+        &self,
+        chunk_size: usize,
+    ) -> VertexChunks<'_, 'id, Item, Weight, Edge, S, A> {
+        self.vertices_chunks_from(VertexId::new(0), chunk_size)
+    }
+    /// Returns an iterator over the [`VertexId`]s of every vertex
+    /// with an id of at least `from` in chunks of at most
+    /// `chunk_size`, ordered by id
+    ///
+    /// New vertices always have greater ids than every vertex before
+    /// them, so carrying on from [`VertexChunks::resume_from`] visits
+    /// every vertex added since, and skips any that were removed.
+    /// Clearing the graph starts its ids again from `0`, so it
+    /// shouldn't be resumed afterwards
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is `0`
+    #[must_use]
+    pub fn vertices_chunks_from(
+        &self,
+        from: VertexId<'id>,
+        chunk_size: usize,
+    ) -> VertexChunks<'_, 'id, Item, Weight, Edge, S, A> {
+        assert!(chunk_size > 0, "chunks must hold at least one vertex");
+
+        VertexChunks {
+            graph: self,
+            next: from.id(),
+            chunk_size,
+        }
+    }
+}
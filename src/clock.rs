@@ -0,0 +1,173 @@
+use core::hash::BuildHasher;
+
+use hashbrown::HashMap;
+
+use crate::{edge::EdgeTrait, observer::Change, AllocError, Allocator, EdgeId, Graph, VertexId};
+
+/// When an element of a [`Graph`] was created and last modified,
+/// measured by the graph's logical clock
+///
+/// The clock ticks once for every change the graph records, so
+/// elements added after timestamps started being recorded have
+/// unique creation times that can be used to break ties
+/// deterministically. Timestamps are ordered by when the element
+/// was created
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timestamp {
+    /// The time the element was added to the graph
+    pub created: u64,
+    /// The time the element was last changed, which is the same as
+    /// `created` if it's never been changed
+    pub modified: u64,
+}
+
+/// The logical clock of a [`Graph`], along with the [`Timestamp`]
+/// of every element within it
+pub struct Clock<'id, S> {
+    now: u64,
+    vertices: HashMap<VertexId<'id>, Timestamp, S>,
+    edges: HashMap<EdgeId<'id>, Timestamp, S>,
+}
+
+impl<'id, S: BuildHasher> Clock<'id, S> {
+    const fn tick(&mut self) -> u64 {
+        self.now += 1;
+        self.now
+    }
+    pub fn create_vertex(&mut self, id: VertexId<'id>) {
+        let now = self.tick();
+        self.vertices.insert(
+            id,
+            Timestamp {
+                created: now,
+                modified: now,
+            },
+        );
+    }
+    pub fn create_edge(&mut self, id: EdgeId<'id>) {
+        let now = self.tick();
+        self.edges.insert(
+            id,
+            Timestamp {
+                created: now,
+                modified: now,
+            },
+        );
+    }
+    pub fn modify_vertex(&mut self, id: VertexId<'id>) -> bool {
+        let Some(stamp) = self.vertices.get_mut(&id) else {
+            return false;
+        };
+        self.now += 1;
+        stamp.modified = self.now;
+        true
+    }
+    pub fn modify_edge(&mut self, id: EdgeId<'id>) -> bool {
+        let Some(stamp) = self.edges.get_mut(&id) else {
+            return false;
+        };
+        self.now += 1;
+        stamp.modified = self.now;
+        true
+    }
+    pub fn forget_vertex(&mut self, id: VertexId<'id>) {
+        self.vertices.remove(&id);
+    }
+    pub fn forget_edge(&mut self, id: EdgeId<'id>) {
+        self.edges.remove(&id);
+    }
+    pub fn try_reserve(&mut self, vertices: usize, edges: usize) -> Result<(), AllocError> {
+        self.vertices
+            .try_reserve(vertices)
+            .and_then(|()| self.edges.try_reserve(edges))
+            .map_err(|_| AllocError)
+    }
+    pub fn forget_all(&mut self) {
+        self.vertices.clear();
+        self.edges.clear();
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Starts recording a [`Timestamp`] for every vertex and edge
+    ///
+    /// Elements already in the graph are all given the same
+    /// timestamp. Does nothing if timestamps are already recorded
+    pub fn track_timestamps(&mut self)
+    where
+        S: Clone,
+    {
+        if self.clock.is_some() {
+            return;
+        }
+
+        let hasher = self.hasher().clone();
+        let mut clock = Clock {
+            now: 1,
+            vertices: HashMap::with_capacity_and_hasher(self.vertices.len(), hasher.clone()),
+            edges: HashMap::with_capacity_and_hasher(self.edges.len(), hasher),
+        };
+
+        let start = Timestamp {
+            created: 1,
+            modified: 1,
+        };
+        clock
+            .vertices
+            .extend(self.vertices.keys().map(|id| (*id, start)));
+        clock.edges.extend(self.edges.keys().map(|id| (*id, start)));
+
+        self.clock = Some(clock);
+    }
+    /// Stops recording timestamps, discarding the ones recorded
+    pub fn untrack_timestamps(&mut self) {
+        self.clock = None;
+    }
+    /// The current time of the graph's logical clock, or `None` if
+    /// timestamps aren't being recorded
+    #[must_use]
+    pub fn logical_time(&self) -> Option<u64> {
+        self.clock.as_ref().map(|clock| clock.now)
+    }
+    /// The [`Timestamp`] of the vertex `id`, or `None` if `id` isn't
+    /// within the graph or timestamps aren't being recorded
+    #[must_use]
+    pub fn vertex_timestamp(&self, id: VertexId<'id>) -> Option<Timestamp> {
+        self.clock.as_ref()?.vertices.get(&id).copied()
+    }
+    /// The [`Timestamp`] of the edge `id`, or `None` if `id` isn't
+    /// within the graph or timestamps aren't being recorded
+    #[must_use]
+    pub fn edge_timestamp(&self, id: EdgeId<'id>) -> Option<Timestamp> {
+        self.clock.as_ref()?.edges.get(&id).copied()
+    }
+    /// Records that the vertex `id` has just been modified
+    ///
+    /// The graph can't see changes made to a vertex's item through
+    /// a [`GhostToken`](crate::ghost::GhostToken), so they should be
+    /// recorded with this, which also tells the graph's
+    /// [`GraphObserver`](crate::GraphObserver). Returns `false` if
+    /// `id` isn't within the graph or timestamps aren't being
+    /// recorded
+    pub fn touch_vertex(&mut self, id: VertexId<'id>) -> bool {
+        let known = self.vertices.contains_key(&id);
+        if known {
+            self.notify(Change::VertexChanged(id));
+        }
+        known && self.clock.is_some()
+    }
+    /// Records that the edge `id` has just been modified
+    ///
+    /// Returns `false` if `id` isn't within the graph or timestamps
+    /// aren't being recorded
+    pub fn touch_edge(&mut self, id: EdgeId<'id>) -> bool {
+        let known = self.edges.contains_key(&id);
+        if known {
+            self.notify(Change::EdgeChanged(id));
+        }
+        known && self.clock.is_some()
+    }
+}
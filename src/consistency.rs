@@ -0,0 +1,295 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::{
+    edge::EdgeTrait, ghost::GhostToken, observer::Change, Allocator, EdgeId, Graph, VertexId,
+};
+
+/// A way in which a [`Graph`]'s internal bookkeeping disagrees
+/// with itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InvariantViolation<'id> {
+    /// `vertex` refers to `edge`, but `edge` isn't in the graph
+    DanglingEdgeReference {
+        vertex: VertexId<'id>,
+        edge: EdgeId<'id>,
+    },
+    /// `vertex` refers to `edge`, but `edge` doesn't connect to `vertex`
+    MisplacedEdgeReference {
+        vertex: VertexId<'id>,
+        edge: EdgeId<'id>,
+    },
+    /// `edge` connects to `vertex`, but `vertex` doesn't refer to `edge`
+    MissingEdgeReference {
+        vertex: VertexId<'id>,
+        edge: EdgeId<'id>,
+    },
+    /// The edge connects to a vertex that isn't in the graph
+    DetachedEdge(EdgeId<'id>),
+    /// The ids the edge stores for its endpoints aren't the ids of
+    /// the vertices it connects
+    MismatchedEndpointIds(EdgeId<'id>),
+    /// The vertex is stored under a different id to its own
+    MismatchedVertexId {
+        key: VertexId<'id>,
+        stored: VertexId<'id>,
+    },
+    /// The cached degrees of the vertex don't match its edges
+    DegreeMismatch(VertexId<'id>),
+    /// The next vertex id to be handed out is already in use
+    StaleVertexIdCounter,
+    /// The next edge id to be handed out is already in use
+    StaleEdgeIdCounter,
+}
+
+impl<'id> InvariantViolation<'id> {
+    /// Whether [`Graph::repair`] can fix `self`
+    #[must_use]
+    pub const fn is_recoverable(&self) -> bool {
+        !matches!(self, Self::MismatchedVertexId { .. })
+    }
+}
+
+/// What [`Graph::repair`] found
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepairReport<'id> {
+    /// Every violation that was fixed
+    pub repaired: Vec<InvariantViolation<'id>>,
+    /// Every violation that couldn't be fixed, and is still
+    /// present in the graph
+    pub unrecoverable: Vec<InvariantViolation<'id>>,
+}
+
+impl<'id> RepairReport<'id> {
+    /// Whether the graph was already consistent
+    #[must_use]
+    pub const fn is_clean(&self) -> bool {
+        self.repaired.is_empty() && self.unrecoverable.is_empty()
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Finds every [`InvariantViolation`] in `self`, without changing it
+    ///
+    /// Edges that are no longer in the graph are never dereferenced,
+    /// so this is safe to call on a graph in any state
+    pub(crate) fn violations(&self, token: &GhostToken<'id>) -> Vec<InvariantViolation<'id>> {
+        let mut found = Vec::new();
+
+        let live: HashMap<_, _> = self
+            .vertices
+            .iter()
+            .map(|(id, vertex)| (vertex.as_ptr(), *id))
+            .collect();
+
+        for (key, vertex) in &self.vertices {
+            let inner = vertex.borrow(token);
+
+            if inner.id() != *key {
+                found.push(InvariantViolation::MismatchedVertexId {
+                    key: *key,
+                    stored: inner.id(),
+                });
+            }
+
+            for (edge_id, edge) in &inner.edges {
+                match self.edges.get(edge_id) {
+                    Some(stored) if stored.as_ptr() == edge.as_ptr() => {
+                        let (first, second) = stored.borrow(token).endpoint_nodes();
+                        if first != vertex && second != vertex {
+                            found.push(InvariantViolation::MisplacedEdgeReference {
+                                vertex: *key,
+                                edge: *edge_id,
+                            });
+                        }
+                    }
+                    _ => found.push(InvariantViolation::DanglingEdgeReference {
+                        vertex: *key,
+                        edge: *edge_id,
+                    }),
+                }
+            }
+        }
+
+        let mut detached = HashSet::new();
+
+        for (edge_id, edge) in &self.edges {
+            let (first, second) = edge.borrow(token).endpoint_nodes();
+            let (Some(first), Some(second)) =
+                (live.get(&first.as_ptr()), live.get(&second.as_ptr()))
+            else {
+                detached.insert(*edge_id);
+                found.push(InvariantViolation::DetachedEdge(*edge_id));
+                continue;
+            };
+
+            if edge.borrow(token).endpoints() != (*first, *second) {
+                found.push(InvariantViolation::MismatchedEndpointIds(*edge_id));
+            }
+
+            for vertex in [*first, *second] {
+                if !self.vertices[&vertex]
+                    .borrow(token)
+                    .edges
+                    .contains_key(edge_id)
+                {
+                    found.push(InvariantViolation::MissingEdgeReference {
+                        vertex,
+                        edge: *edge_id,
+                    });
+                }
+            }
+        }
+
+        // Degrees are counted from the edges the graph knows about,
+        // from their own view of which vertices they connect
+        let mut degrees: HashMap<VertexId<'id>, (usize, usize)> =
+            self.vertices.keys().map(|id| (*id, (0, 0))).collect();
+        for (edge_id, edge) in &self.edges {
+            if detached.contains(edge_id) {
+                continue;
+            }
+            let (first, second) = edge.borrow(token).endpoint_nodes();
+            for (end, outgoing) in [(first, true), (second, false)] {
+                if let Some((out, incoming)) = degrees.get_mut(&live[&end.as_ptr()]) {
+                    if !Edge::DIRECTED || outgoing {
+                        *out += 1;
+                    }
+                    if !Edge::DIRECTED || !outgoing {
+                        *incoming += 1;
+                    }
+                }
+            }
+        }
+        let mut wrong_degrees: Vec<_> = degrees
+            .into_iter()
+            .filter(|(id, counted)| self.degrees.get(id) != Some(counted))
+            .map(|(id, _)| id)
+            .collect();
+        wrong_degrees.sort_unstable();
+        found.extend(
+            wrong_degrees
+                .into_iter()
+                .map(InvariantViolation::DegreeMismatch),
+        );
+
+        if self
+            .vertices
+            .keys()
+            .any(|id| id.id() >= self.current_vertex_id)
+        {
+            found.push(InvariantViolation::StaleVertexIdCounter);
+        }
+        if self.edges.keys().any(|id| id.id >= self.current_edge_id) {
+            found.push(InvariantViolation::StaleEdgeIdCounter);
+        }
+
+        found
+    }
+    /// Checks that the graph's internal bookkeeping is consistent:
+    /// every edge a vertex refers to is within the graph and connects
+    /// to that vertex, both endpoints of every edge refer to it, and
+    /// the cached degrees and id counters all match the graph's
+    /// contents
+    ///
+    /// # Errors
+    /// Returns every [`InvariantViolation`] found, see
+    /// [`Graph::repair`] to fix them
+    pub fn validate(&self, token: &GhostToken<'id>) -> Result<(), Vec<InvariantViolation<'id>>> {
+        let violations = self.violations(token);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+    /// Fixes every recoverable [`InvariantViolation`] in `self`, and
+    /// reports the ones it couldn't fix
+    ///
+    /// References to edges that aren't in the graph are dropped,
+    /// edges that connect to vertices that aren't in the graph are
+    /// removed, missing references are restored, the endpoint ids
+    /// stored in edges are corrected, and degrees and id
+    /// counters are recalculated from the graph's contents
+    pub fn repair(&mut self, token: &mut GhostToken<'id>) -> RepairReport<'id> {
+        let mut report = RepairReport::default();
+
+        for violation in self.violations(token) {
+            match violation {
+                InvariantViolation::DanglingEdgeReference { vertex, edge }
+                | InvariantViolation::MisplacedEdgeReference { vertex, edge } => {
+                    self.vertices[&vertex].borrow_mut(token).edges.remove(&edge);
+                }
+                InvariantViolation::MissingEdgeReference { vertex, edge } => {
+                    let shared = self.edges[&edge].clone_shared();
+                    self.vertices[&vertex]
+                        .borrow_mut(token)
+                        .edges
+                        .insert(edge, shared);
+                }
+                InvariantViolation::DetachedEdge(edge_id) => {
+                    let Some(edge) = self.edges.remove(&edge_id) else {
+                        continue;
+                    };
+                    for vertex in self.vertices.values() {
+                        let vertex = vertex.borrow_mut(token);
+                        if vertex
+                            .edges
+                            .get(&edge_id)
+                            .is_some_and(|other| other.as_ptr() == edge.as_ptr())
+                        {
+                            vertex.edges.remove(&edge_id);
+                        }
+                    }
+
+                    self.notify(Change::EdgeRemoved(edge_id));
+
+                    // SAFETY: No pointers to the edge remain in the graph
+                    unsafe { self.edge_arena.free(edge) };
+                }
+                InvariantViolation::MismatchedEndpointIds(edge_id) => {
+                    let edge = self.edges[&edge_id].clone_shared();
+                    let (first, second) = edge.borrow(token).endpoint_nodes();
+                    let (first, second) = (first.clone_shared(), second.clone_shared());
+                    let (first_id, second_id) =
+                        (first.borrow(token).id(), second.borrow(token).id());
+
+                    // Reconnecting an endpoint to itself only
+                    // replaces the id it's stored under
+                    let edge = edge.borrow_mut(token);
+                    edge.reconnect(&first, first.clone_shared(), first_id);
+                    if first != second {
+                        edge.reconnect(&second, second.clone_shared(), second_id);
+                    }
+                }
+                InvariantViolation::MismatchedVertexId { .. } => {
+                    report.unrecoverable.push(violation);
+                    continue;
+                }
+                InvariantViolation::DegreeMismatch(_)
+                | InvariantViolation::StaleVertexIdCounter
+                | InvariantViolation::StaleEdgeIdCounter => {}
+            }
+            report.repaired.push(violation);
+        }
+
+        // Removing detached edges can change the degrees found
+        // above, so they're all recalculated last
+        let ids: Vec<_> = self.vertices.keys().copied().collect();
+        for id in ids {
+            self.recount_degrees(id, token);
+        }
+        if let Some(highest) = self.vertices.keys().map(|id| id.id()).max() {
+            self.current_vertex_id = self.current_vertex_id.max(self.id_after(highest));
+        }
+        if let Some(highest) = self.edges.keys().map(|id| id.id).max() {
+            self.current_edge_id = self.current_edge_id.max(self.id_after(highest));
+        }
+
+        report
+    }
+}
@@ -0,0 +1,160 @@
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Debug, Display},
+    hash::BuildHasher,
+};
+
+use crate::{
+    algo::successors, edge::EdgeTrait, ghost::GhostToken, Allocator, EdgeId, Graph, VertexId,
+};
+
+/// The edges leading from a vertex, as the vertex each leads to and
+/// its weight
+type Adjacent<'a, 'id, Weight> = Vec<(VertexId<'id>, &'a Weight)>;
+
+/// A [`Graph`] borrowed along with its [`GhostToken`], so that it can
+/// be printed as an adjacency listing, created by [`Graph::debug`]
+///
+/// [`Debug`] prints a map from every vertex to its item and the
+/// weights of the edges leading from it, while [`Display`] prints
+/// one line per vertex. Vertices and edges are listed in order of
+/// their ids, so the output is the same every time
+///
+/// ```rust
+/// use graph::{edge::DirectedWeightedEdge, ghost::GhostToken, Graph};
+///
+/// GhostToken::new(|mut token| {
+///     let mut graph: Graph<char, u32, DirectedWeightedEdge<_, _>> = Graph::new();
+///     let a = graph.add_vertex('a');
+///     let b = graph.add_vertex('b');
+///     graph.add_edge(a, b, 5, &mut token).unwrap();
+///
+///     assert_eq!(
+///         format!("{:?}", graph.debug(&token)),
+///         "{0: Vertex { item: 'a', edges: {1: 5} }, 1: Vertex { item: 'b', edges: {} }}"
+///     );
+///     assert_eq!(graph.debug(&token).to_string(), "0 (a) -> 1 [5]\n1 (b)\n");
+/// })
+/// ```
+pub struct GraphDebug<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator> {
+    graph: &'a Graph<'id, Item, Weight, Edge, S, A>,
+    token: &'a GhostToken<'id>,
+}
+
+impl<'a, 'id, Item, Weight, Edge, S, A> GraphDebug<'a, 'id, Item, Weight, Edge, S, A>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// Every vertex in order, along with its item and the edges
+    /// leading from it in order
+    fn listing(&self) -> Vec<(VertexId<'id>, &'a Item, Adjacent<'a, 'id, Weight>)> {
+        let mut ids: Vec<_> = self.graph.vertex_ids().collect();
+        ids.sort_unstable();
+
+        ids.into_iter()
+            .filter_map(|id| {
+                let item = self.graph.get_vertex(id)?.borrow(self.token).get_item();
+                let mut edges: Vec<(EdgeId<'id>, _, _)> =
+                    successors(self.graph, id, self.token).collect();
+                edges.sort_unstable_by_key(|(edge, other, _)| (*other, *edge));
+                let edges = edges
+                    .into_iter()
+                    .map(|(_, other, weight)| (other, weight))
+                    .collect();
+                Some((id, item, edges))
+            })
+            .collect()
+    }
+}
+
+/// A vertex's item and edges, as printed by [`GraphDebug`]'s
+/// [`Debug`] implementation
+struct VertexDebug<'a, 'id, Item, Weight> {
+    item: &'a Item,
+    edges: &'a [(VertexId<'id>, &'a Weight)],
+}
+
+impl<'a, 'id, Item: Debug, Weight: Debug> Debug for VertexDebug<'a, 'id, Item, Weight> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Vertex")
+            .field("item", self.item)
+            .field("edges", &DebugEdges(self.edges))
+            .finish()
+    }
+}
+
+/// The edges leading from a vertex, printed as a map from the
+/// vertex each leads to to its weight
+struct DebugEdges<'a, 'id, Weight>(&'a [(VertexId<'id>, &'a Weight)]);
+
+impl<'a, 'id, Weight: Debug> Debug for DebugEdges<'a, 'id, Weight> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(other, weight)| (other, weight)))
+            .finish()
+    }
+}
+
+impl<'a, 'id, Item, Weight, Edge, S, A> Debug for GraphDebug<'a, 'id, Item, Weight, Edge, S, A>
+where
+    Item: Debug,
+    Weight: Debug,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let listing = self.listing();
+
+        f.debug_map()
+            .entries(
+                listing
+                    .iter()
+                    .map(|(id, item, edges)| (id, VertexDebug { item: *item, edges })),
+            )
+            .finish()
+    }
+}
+
+impl<'a, 'id, Item, Weight, Edge, S, A> Display for GraphDebug<'a, 'id, Item, Weight, Edge, S, A>
+where
+    Item: Display,
+    Weight: Display,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arrow = if Edge::DIRECTED { " ->" } else { " --" };
+
+        for (id, item, edges) in self.listing() {
+            write!(f, "{id:?} ({item})")?;
+            for (i, (other, weight)) in edges.iter().enumerate() {
+                let separator = if i == 0 { arrow } else { "," };
+                write!(f, "{separator} {other:?} [{weight}]")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Borrows the graph along with `token`, returning a
+    /// [`GraphDebug`] which prints every vertex's item and edges
+    ///
+    /// The graph can't implement [`Debug`] itself, as its contents
+    /// can't be read without the token
+    #[must_use]
+    pub const fn debug<'a>(
+        &'a self,
+        token: &'a GhostToken<'id>,
+    ) -> GraphDebug<'a, 'id, Item, Weight, Edge, S, A> {
+        GraphDebug { graph: self, token }
+    }
+}
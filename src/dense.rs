@@ -0,0 +1,389 @@
+use core::hash::BuildHasher;
+
+use alloc::vec::Vec;
+
+use crate::{
+    edge::EdgeTrait,
+    ghost::{GhostToken, InvariantLifetime},
+    ops::GraphOps,
+    Allocator, Graph, GraphError, Measure, VertexId,
+};
+
+/// A graph backed by an adjacency matrix, suited to small and dense
+/// graphs
+///
+/// Checking whether two vertices are adjacent, or getting the weight
+/// between them, is `O(1)`, at the cost of `O(n²)` memory. Vertices
+/// can't be removed, and their [`VertexId`]s are simply their index
+/// in the order they were added.
+///
+/// Unlike [`Graph`], the contents of a [`DenseGraph`] are owned
+/// directly, so no [`GhostToken`] is needed to use it
+pub struct DenseGraph<'id, Item, Weight> {
+    items: Vec<Item>,
+    /// The weights, stored row-major with `stride` columns
+    matrix: Vec<Option<Weight>>,
+    stride: usize,
+    directed: bool,
+    edge_len: usize,
+    _marker: InvariantLifetime<'id>,
+}
+
+impl<'id, Item, Weight> DenseGraph<'id, Item, Weight> {
+    /// Constructs a new empty graph, with either directed or
+    /// undirected edges
+    #[must_use]
+    pub const fn new(directed: bool) -> Self {
+        Self {
+            items: Vec::new(),
+            matrix: Vec::new(),
+            stride: 0,
+            directed,
+            edge_len: 0,
+            _marker: InvariantLifetime::new(),
+        }
+    }
+    /// Adds a vertex with no edges, and returns the [`VertexId`] of the
+    /// created vertex
+    pub fn add_vertex(&mut self, item: Item) -> VertexId<'id> {
+        let id = VertexId::new(self.items.len());
+
+        if self.items.len() == self.stride {
+            self.grow();
+        }
+
+        self.items.push(item);
+        id
+    }
+    /// Doubles the number of vertices the matrix has space for
+    fn grow(&mut self) {
+        let stride = (self.stride * 2).max(4);
+        let mut matrix: Vec<Option<Weight>> = core::iter::repeat_with(|| None)
+            .take(stride * stride)
+            .collect();
+
+        for row in 0..self.stride {
+            for column in 0..self.stride {
+                matrix[row * stride + column] = self.matrix[row * self.stride + column].take();
+            }
+        }
+
+        self.matrix = matrix;
+        self.stride = stride;
+    }
+    /// The index into the matrix of the edge from `from` to `to`, or
+    /// `None` if either isn't within the graph
+    const fn cell(&self, from: VertexId<'id>, to: VertexId<'id>) -> Option<usize> {
+        let (mut row, mut column) = (from.id(), to.id());
+
+        if row >= self.items.len() || column >= self.items.len() {
+            return None;
+        }
+
+        if !self.directed && row > column {
+            core::mem::swap(&mut row, &mut column);
+        }
+
+        Some(row * self.stride + column)
+    }
+    /// Adds an edge from `from` to `to` with the given weight, or
+    /// replaces the weight of the existing edge, returning the
+    /// previous weight
+    ///
+    /// # Panics
+    /// Panics if either `from` or `to` isn't within the graph
+    pub fn insert_edge(
+        &mut self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        weight: Weight,
+    ) -> Option<Weight> {
+        let cell = self
+            .cell(from, to)
+            .expect("vertex not found within the graph");
+
+        let previous = self.matrix[cell].replace(weight);
+        if previous.is_none() {
+            self.edge_len += 1;
+        }
+        previous
+    }
+    /// Removes the edge from `from` to `to`, returning its weight
+    pub fn remove_edge(&mut self, from: VertexId<'id>, to: VertexId<'id>) -> Option<Weight> {
+        let cell = self.cell(from, to)?;
+
+        let previous = self.matrix[cell].take();
+        if previous.is_some() {
+            self.edge_len -= 1;
+        }
+        previous
+    }
+    /// The weight of the edge from `from` to `to`, or `None` if
+    /// there's no such edge
+    #[must_use]
+    pub fn weight(&self, from: VertexId<'id>, to: VertexId<'id>) -> Option<&Weight> {
+        self.matrix[self.cell(from, to)?].as_ref()
+    }
+    /// A mutable reference to the weight of the edge from `from`
+    /// to `to`, or `None` if there's no such edge
+    pub fn weight_mut(&mut self, from: VertexId<'id>, to: VertexId<'id>) -> Option<&mut Weight> {
+        let cell = self.cell(from, to)?;
+        self.matrix[cell].as_mut()
+    }
+    /// Whether there's an edge from `from` to `to`
+    #[must_use]
+    pub fn adjacent(&self, from: VertexId<'id>, to: VertexId<'id>) -> bool {
+        self.weight(from, to).is_some()
+    }
+    /// Gets a reference to the item within `id`
+    #[must_use]
+    pub fn get_item(&self, id: VertexId<'id>) -> Option<&Item> {
+        self.items.get(id.id())
+    }
+    /// Gets a mutable reference to the item within `id`
+    pub fn get_item_mut(&mut self, id: VertexId<'id>) -> Option<&mut Item> {
+        self.items.get_mut(id.id())
+    }
+    /// The number of vertices in the graph
+    #[must_use]
+    pub const fn vertex_len(&self) -> usize {
+        self.items.len()
+    }
+    /// The number of edges in the graph
+    #[must_use]
+    pub const fn edge_len(&self) -> usize {
+        self.edge_len
+    }
+    /// If there are no vertices in the graph
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+    /// Whether the graph's edges are directed
+    #[must_use]
+    pub const fn is_directed(&self) -> bool {
+        self.directed
+    }
+    /// Computes the length of the shortest path between every pair
+    /// of vertices using the Floyd–Warshall algorithm
+    ///
+    /// `distances[i][j]` is the length of the shortest path from the
+    /// vertex with id `i` to the vertex with id `j`, or `None` if
+    /// there's no such path. The distance from a vertex to itself is
+    /// [`Measure::ZERO`], unless a negative cycle passes through it.
+    #[must_use]
+    pub fn floyd_warshall(&self) -> Vec<Vec<Option<Weight>>>
+    where
+        Weight: Measure,
+    {
+        let len = self.items.len();
+
+        let mut distances: Vec<Vec<Option<Weight>>> = (0..len)
+            .map(|from| {
+                (0..len)
+                    .map(|to| {
+                        let weight = self.weight(VertexId::new(from), VertexId::new(to)).copied();
+                        if from == to {
+                            Some(weight.map_or(Weight::ZERO, |w| {
+                                if w < Weight::ZERO {
+                                    w
+                                } else {
+                                    Weight::ZERO
+                                }
+                            }))
+                        } else {
+                            weight
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        for via in 0..len {
+            let from_via = distances[via].clone();
+
+            for row in &mut distances {
+                let Some(to_via) = row[via] else { continue };
+
+                for (current, from_via) in row.iter_mut().zip(&from_via) {
+                    if let Some(from_via) = from_via {
+                        let through = to_via.saturating_add(*from_via);
+                        if current.is_none_or(|current| through < current) {
+                            *current = Some(through);
+                        }
+                    }
+                }
+            }
+        }
+
+        distances
+    }
+    /// Builds a [`DenseGraph`] with the same vertices and edges as
+    /// `graph`, cloning every item and weight
+    ///
+    /// The vertices are added in order of their [`VertexId`]s within
+    /// `graph`, so the `n`th smallest id in `graph` becomes id `n`
+    #[must_use]
+    pub fn from_graph<Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>(
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        token: &GhostToken<'id>,
+    ) -> Self
+    where
+        Item: Clone,
+        Weight: Clone,
+    {
+        let mut ids: Vec<_> = graph.get_all_vertices().copied().collect();
+        ids.sort_unstable();
+
+        let mut dense = Self::new(Edge::DIRECTED);
+        let mut index = hashbrown::HashMap::with_capacity(ids.len());
+
+        for id in &ids {
+            if let Some(item) = GraphOps::item(graph, *id, token) {
+                index.insert(*id, dense.add_vertex(item.clone()));
+            }
+        }
+
+        for id in &ids {
+            for (other, weight) in graph.successors(*id, token) {
+                dense.insert_edge(index[id], index[&other], weight.clone());
+            }
+        }
+
+        dense
+    }
+    /// Converts `self` into a pointer-based [`Graph`], with the
+    /// vertices added in order of their [`VertexId`]s
+    ///
+    /// # Errors
+    /// Returns a [`GraphError`] if any edge can't be added to the
+    /// graph, such as an edge from a vertex to itself
+    pub fn into_graph<Edge: EdgeTrait<'id, Item, Weight>>(
+        self,
+        token: &mut GhostToken<'id>,
+    ) -> Result<Graph<'id, Item, Weight, Edge>, GraphError<'id, Item, Weight, Edge>> {
+        let mut graph = Graph::new();
+
+        let ids: Vec<_> = self
+            .items
+            .into_iter()
+            .map(|item| graph.add_vertex(item))
+            .collect();
+
+        let (stride, len) = (self.stride, ids.len());
+
+        for (cell, weight) in self.matrix.into_iter().enumerate() {
+            let (row, column) = (cell / stride, cell % stride);
+
+            if let Some(weight) = weight {
+                if row < len && column < len {
+                    graph.add_edge(ids[row], ids[column], weight, token)?;
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+/// The iterator over a [`DenseGraph`]'s neighboring vertices,
+/// returned by [`GraphOps::successors`] and [`GraphOps::predecessors`]
+pub struct DenseNeighbors<'a, 'id, Item, Weight> {
+    graph: &'a DenseGraph<'id, Item, Weight>,
+    id: VertexId<'id>,
+    next: usize,
+    incoming: bool,
+}
+
+impl<'a, 'id, Item, Weight> Iterator for DenseNeighbors<'a, 'id, Item, Weight> {
+    type Item = (VertexId<'id>, &'a Weight);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < self.graph.vertex_len() {
+            let other = VertexId::new(self.next);
+            self.next += 1;
+
+            let weight = if self.incoming {
+                self.graph.weight(other, self.id)
+            } else {
+                self.graph.weight(self.id, other)
+            };
+
+            if let Some(weight) = weight {
+                return Some((other, weight));
+            }
+        }
+
+        None
+    }
+}
+
+impl<'id, Item, Weight> GraphOps<'id> for DenseGraph<'id, Item, Weight> {
+    type Item = Item;
+    type Weight = Weight;
+    type Successors<'a>
+        = DenseNeighbors<'a, 'id, Item, Weight>
+    where
+        Self: 'a,
+        'id: 'a;
+    type Predecessors<'a>
+        = DenseNeighbors<'a, 'id, Item, Weight>
+    where
+        Self: 'a,
+        'id: 'a;
+
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    fn vertex_len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn contains(&self, id: VertexId<'id>) -> bool {
+        id.id() < self.items.len()
+    }
+
+    fn vertex_ids(&self) -> Vec<VertexId<'id>> {
+        (0..self.items.len()).map(VertexId::new).collect()
+    }
+
+    fn item<'a>(&'a self, id: VertexId<'id>, _token: &'a GhostToken<'id>) -> Option<&'a Item> {
+        self.get_item(id)
+    }
+
+    fn weight_between<'a>(
+        &'a self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        _token: &'a GhostToken<'id>,
+    ) -> Option<&'a Weight> {
+        self.weight(from, to)
+    }
+
+    fn successors<'a>(
+        &'a self,
+        id: VertexId<'id>,
+        _token: &'a GhostToken<'id>,
+    ) -> Self::Successors<'a> {
+        DenseNeighbors {
+            graph: self,
+            id,
+            next: 0,
+            incoming: false,
+        }
+    }
+
+    fn predecessors<'a>(
+        &'a self,
+        id: VertexId<'id>,
+        _token: &'a GhostToken<'id>,
+    ) -> Self::Predecessors<'a> {
+        DenseNeighbors {
+            graph: self,
+            id,
+            next: 0,
+            incoming: true,
+        }
+    }
+}
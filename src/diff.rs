@@ -0,0 +1,201 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, Allocator, EdgeId, Graph, GraphError, VertexId};
+
+/// The changes that turn one [`Graph`] into another, as found by
+/// [`Graph::diff`] and applied by [`Graph::apply`]
+///
+/// Vertices and edges are matched by their ids, so a diff can be
+/// sent to another copy of a graph to bring it up to date without
+/// sending the whole graph. Every list is ordered by id
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GraphDiff<'id, Item, Weight> {
+    /// Vertices that were added, along with their items
+    pub added_vertices: Vec<(VertexId<'id>, Item)>,
+    /// Vertices that were removed
+    pub removed_vertices: Vec<VertexId<'id>>,
+    /// Vertices whose item changed, along with their new item
+    pub changed_items: Vec<(VertexId<'id>, Item)>,
+    /// Edges that were added, along with the vertices they join,
+    /// in the order [`Graph::endpoints`] returns them, and their
+    /// weights
+    pub added_edges: Vec<(EdgeId<'id>, VertexId<'id>, VertexId<'id>, Weight)>,
+    /// Edges that were removed
+    pub removed_edges: Vec<EdgeId<'id>>,
+    /// Edges whose weight changed, along with their new weight
+    pub changed_weights: Vec<(EdgeId<'id>, Weight)>,
+}
+
+impl<'id, Item, Weight> Default for GraphDiff<'id, Item, Weight> {
+    fn default() -> Self {
+        Self {
+            added_vertices: Vec::new(),
+            removed_vertices: Vec::new(),
+            changed_items: Vec::new(),
+            added_edges: Vec::new(),
+            removed_edges: Vec::new(),
+            changed_weights: Vec::new(),
+        }
+    }
+}
+
+impl<'id, Item, Weight> GraphDiff<'id, Item, Weight> {
+    /// Whether the diff contains no changes at all
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.added_vertices.is_empty()
+            && self.removed_vertices.is_empty()
+            && self.changed_items.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_weights.is_empty()
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Finds the changes that turn `self` into `other`
+    ///
+    /// An edge that joins different vertices in `other` than in
+    /// `self` is treated as being removed and added again
+    #[must_use]
+    pub fn diff<S2: BuildHasher>(
+        &self,
+        other: &Graph<'id, Item, Weight, Edge, S2>,
+        token: &GhostToken<'id>,
+    ) -> GraphDiff<'id, Item, Weight>
+    where
+        Item: Clone + PartialEq,
+        Weight: Clone + PartialEq,
+    {
+        let mut diff = GraphDiff::default();
+
+        for (id, vertex) in &self.vertices {
+            match other.get_vertex(*id) {
+                None => diff.removed_vertices.push(*id),
+                Some(new) => {
+                    let new = new.borrow(token).get_item();
+                    if vertex.borrow(token).get_item() != new {
+                        diff.changed_items.push((*id, new.clone()));
+                    }
+                }
+            }
+        }
+        for (id, vertex) in &other.vertices {
+            if !self.vertices.contains_key(id) {
+                let item = vertex.borrow(token).get_item().clone();
+                diff.added_vertices.push((*id, item));
+            }
+        }
+
+        // Undirected edges join the same vertices whichever way
+        // around they were added
+        let moved = |id: EdgeId<'id>| {
+            let order = |ends: Option<(VertexId<'id>, VertexId<'id>)>| {
+                ends.map(|(from, to)| {
+                    if Edge::DIRECTED || from < to {
+                        (from, to)
+                    } else {
+                        (to, from)
+                    }
+                })
+            };
+            order(self.endpoints(id, token)) != order(other.endpoints(id, token))
+        };
+
+        for (id, edge) in &self.edges {
+            match other.get_edge(*id) {
+                Some(new) if !moved(*id) => {
+                    let new = new.borrow(token).get_weight();
+                    if edge.borrow(token).get_weight() != new {
+                        diff.changed_weights.push((*id, new.clone()));
+                    }
+                }
+                _ => diff.removed_edges.push(*id),
+            }
+        }
+        for (id, edge) in &other.edges {
+            if !self.edges.contains_key(id) || moved(*id) {
+                if let Some((from, to)) = other.endpoints(*id, token) {
+                    let weight = edge.borrow(token).get_weight().clone();
+                    diff.added_edges.push((*id, from, to, weight));
+                }
+            }
+        }
+
+        diff.added_vertices.sort_unstable_by_key(|(id, _)| *id);
+        diff.removed_vertices.sort_unstable();
+        diff.changed_items.sort_unstable_by_key(|(id, _)| *id);
+        diff.added_edges.sort_unstable_by_key(|(id, ..)| *id);
+        diff.removed_edges.sort_unstable();
+        diff.changed_weights.sort_unstable_by_key(|(id, _)| *id);
+
+        diff
+    }
+    /// Applies the changes in `diff` to `self`, keeping the ids of
+    /// every vertex and edge it adds
+    ///
+    /// Removals are applied first, then additions, then changes. A
+    /// vertex or edge that's added when its id is already within the
+    /// graph replaces the one that was there
+    ///
+    /// # Errors
+    /// Returns a [`GraphError`] if a vertex or edge that should be
+    /// removed or changed isn't within the graph, or an edge can't be
+    /// added. Every change before the failing one is kept
+    pub fn apply(
+        &mut self,
+        diff: GraphDiff<'id, Item, Weight>,
+        token: &mut GhostToken<'id>,
+    ) -> Result<(), GraphError<'id, Item, Weight, Edge>> {
+        for id in diff.removed_edges {
+            self.remove_edge(id, token)?;
+        }
+        for id in diff.removed_vertices {
+            self.remove(id, token)?;
+        }
+
+        for (id, item) in diff.added_vertices {
+            if self.vertices.contains_key(&id) {
+                self.remove(id, token)?;
+            }
+            self.insert_vertex(id, item);
+        }
+        for (id, from, to, weight) in diff.added_edges {
+            if self.edges.contains_key(&id) {
+                self.remove_edge(id, token)?;
+            }
+            self.insert_edge(id, from, to, weight, token)?;
+        }
+
+        for (id, item) in diff.changed_items {
+            let vertex = self
+                .vertices
+                .get(&id)
+                .ok_or(GraphError::VertexNotFound(id))?;
+            *vertex.borrow_mut(token).get_item_mut() = item;
+            self.touch_vertex(id);
+        }
+        for (id, weight) in diff.changed_weights {
+            let edge = self.edges.get(&id).ok_or(GraphError::EdgeNotFound(id))?;
+            *edge.borrow_mut(token).get_weight_mut() = weight;
+            self.touch_edge(id);
+        }
+
+        Ok(())
+    }
+    /// Removes the edge `id` by finding the vertices it joins
+    pub(crate) fn remove_edge(
+        &mut self,
+        id: EdgeId<'id>,
+        token: &mut GhostToken<'id>,
+    ) -> Result<(), GraphError<'id, Item, Weight, Edge>> {
+        let (from, to) = self
+            .endpoints(id, token)
+            .ok_or(GraphError::EdgeNotFound(id))?;
+        self.remove_edge_between(from, to, token)
+    }
+}
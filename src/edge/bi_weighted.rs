@@ -0,0 +1,195 @@
+use core::hash::BuildHasher;
+
+use crate::{
+    edge::{register_edge, EdgeKind, EdgeTrait, RegisterError},
+    ghost::GhostToken,
+    id::EdgeId,
+    Allocator, Graph, SharedNode, VertexId,
+};
+
+/// The weights of a [`BiWeightedEdge`], one for each direction it can
+/// be followed in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BiWeight<Weight> {
+    /// The weight of following the edge from its first vertex to its
+    /// second
+    pub forward: Weight,
+    /// The weight of following the edge from its second vertex to its
+    /// first
+    pub backward: Weight,
+}
+
+impl<Weight> BiWeight<Weight> {
+    /// Constructs a new [`BiWeight`]
+    #[must_use]
+    pub const fn new(forward: Weight, backward: Weight) -> Self {
+        Self { forward, backward }
+    }
+    /// Constructs a [`BiWeight`] with the same weight both ways
+    #[must_use]
+    pub fn symmetric(weight: Weight) -> Self
+    where
+        Weight: Clone,
+    {
+        Self::new(weight.clone(), weight)
+    }
+    /// The weight of the direction given, where `forward` is from the
+    /// edge's first vertex to its second
+    #[must_use]
+    pub const fn get(&self, forward: bool) -> &Weight {
+        if forward {
+            &self.forward
+        } else {
+            &self.backward
+        }
+    }
+    /// The weight of the direction given, mutably, see
+    /// [`BiWeight::get`]
+    #[must_use]
+    pub const fn get_mut(&mut self, forward: bool) -> &mut Weight {
+        if forward {
+            &mut self.forward
+        } else {
+            &mut self.backward
+        }
+    }
+}
+
+/// An edge between two [vertices](crate::Vertex) that can be followed
+/// either way, with a separate weight for each direction
+///
+/// A single edge stores both weights, where two
+/// [`DirectedWeightedEdge`](crate::edge::DirectedWeightedEdge)s would
+/// each need their own allocation and entry in both vertices, so
+/// graphs where most edges can be followed both ways, such as road
+/// networks, take half the memory. The edge's first vertex is the
+/// one given first to [`Graph::add_edge`]
+#[derive(Debug)]
+pub struct BiWeightedEdge<'id, Item, Weight>(
+    pub BiWeight<Weight>,
+    SharedNode<'id, Item, BiWeight<Weight>, Self>,
+    SharedNode<'id, Item, BiWeight<Weight>, Self>,
+    (VertexId<'id>, VertexId<'id>),
+);
+
+impl<Item, Weight> EdgeKind<Item, BiWeight<Weight>> for BiWeightedEdge<'static, Item, Weight> {
+    type Edge<'id>
+        = BiWeightedEdge<'id, Item, Weight>
+    where
+        Item: 'id,
+        Weight: 'id;
+}
+
+impl<'id, Item, Weight> BiWeightedEdge<'id, Item, Weight> {
+    /// Whether following the edge from `id` goes forward, from its
+    /// first vertex to its second, or `None` if `id` isn't either of
+    /// its vertices
+    #[must_use]
+    pub fn is_forward_from(&self, id: VertexId<'id>) -> Option<bool> {
+        if id == self.3 .0 {
+            Some(true)
+        } else if id == self.3 .1 {
+            Some(false)
+        } else {
+            None
+        }
+    }
+    /// The weight of following the edge from `id` to its other
+    /// vertex, or `None` if `id` isn't either of its vertices
+    #[must_use]
+    pub fn weight_from(&self, id: VertexId<'id>) -> Option<&Weight> {
+        self.is_forward_from(id).map(|forward| self.0.get(forward))
+    }
+}
+
+// SAFETY: The nodes are stored by `register_edge` as given, and
+// `reconnect` replaces whichever equals `old`, which is at most one
+// as the graph never joins a vertex to itself
+unsafe impl<'id, Item, Weight> EdgeTrait<'id, Item, BiWeight<Weight>>
+    for BiWeightedEdge<'id, Item, Weight>
+{
+    type Error = RegisterError<'id>;
+
+    fn add_edge<'new_id, S: BuildHasher, A: Allocator>(
+        weight: BiWeight<Weight>,
+        first: &SharedNode<'id, Item, BiWeight<Weight>, Self>,
+        second: &SharedNode<'id, Item, BiWeight<Weight>, Self>,
+        id: EdgeId<'id>,
+        graph: &mut Graph<'id, Item, BiWeight<Weight>, Self, S, A>,
+        token: &'new_id mut GhostToken<'id>,
+    ) -> Result<(), Self::Error> {
+        let edge = |first, second, ids| Self(weight, first, second, ids);
+        register_edge(edge, id, first, second, graph, token)
+    }
+
+    fn get_weight(&self) -> &BiWeight<Weight> {
+        &self.0
+    }
+
+    fn get_weight_mut(&mut self) -> &mut BiWeight<Weight> {
+        &mut self.0
+    }
+
+    fn reconnect(
+        &mut self,
+        old: &SharedNode<'id, Item, BiWeight<Weight>, Self>,
+        new: SharedNode<'id, Item, BiWeight<Weight>, Self>,
+        new_id: VertexId<'id>,
+    ) -> bool {
+        if self.1 == *old {
+            self.1 = new;
+            self.3 .0 = new_id;
+            true
+        } else if self.2 == *old {
+            self.2 = new;
+            self.3 .1 = new_id;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn endpoints(&self) -> (VertexId<'id>, VertexId<'id>) {
+        self.3
+    }
+
+    fn endpoint_nodes(
+        &self,
+    ) -> (
+        &SharedNode<'id, Item, BiWeight<Weight>, Self>,
+        &SharedNode<'id, Item, BiWeight<Weight>, Self>,
+    ) {
+        (&self.1, &self.2)
+    }
+}
+
+impl<'id, Item, Weight, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, BiWeight<Weight>, BiWeightedEdge<'id, Item, Weight>, S, A>
+{
+    /// The weight of following the edge between `from` and `to` from
+    /// `from`, or `None` if they aren't joined by an edge
+    #[must_use]
+    pub fn directed_weight<'a>(
+        &'a self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Option<&'a Weight> {
+        let id = self.edge_id_between(from, to, false, token).ok()??;
+        self.edges.get(&id)?.borrow(token).weight_from(from)
+    }
+    /// The weight of following the edge between `from` and `to` from
+    /// `from`, mutably, see [`Graph::directed_weight`]
+    pub fn directed_weight_mut<'a>(
+        &'a mut self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        token: &'a mut GhostToken<'id>,
+    ) -> Option<&'a mut Weight> {
+        let id = self.edge_id_between(from, to, false, token).ok()??;
+        let edge = self.edges.get(&id)?;
+        let forward = edge.borrow(token).is_forward_from(from)?;
+        Some(edge.borrow_mut(token).0.get_mut(forward))
+    }
+}
@@ -0,0 +1,162 @@
+use core::ptr;
+
+use crate::{
+    edge::{EdgeKind, EdgeTrait},
+    GraphWithToken,
+};
+
+/// Checks that the family of edges `Kind` behaves the way [`Graph`]
+/// relies on, panicking with a description of the first thing that
+/// doesn't
+///
+/// Every item and weight needed is made with `item` and `weight`.
+/// The edges are added, followed, reconnected by contracting one of
+/// them and removed, checking [`EdgeTrait`]'s methods and the graph's
+/// own bookkeeping along the way. Usually called through
+/// [`assert_edge_impl!`](crate::assert_edge_impl)
+///
+/// [`Graph`]: crate::Graph
+///
+/// # Panics
+/// Panics if `Kind`'s edges don't uphold the contract of
+/// [`EdgeTrait`], or can't be added to a graph
+pub fn check_edge_impl<Kind, Item, Weight>(
+    mut item: impl FnMut() -> Item,
+    mut weight: impl FnMut() -> Weight,
+) where
+    Kind: EdgeKind<Item, Weight>,
+    Item: 'static,
+    Weight: 'static,
+{
+    let mut bundle: GraphWithToken<Item, Weight, Kind> = GraphWithToken::new();
+    bundle.with_mut(|graph, token| {
+        let directed = <Kind::Edge<'_> as EdgeTrait<'_, Item, Weight>>::DIRECTED;
+
+        let a = graph.add_vertex(item());
+        let b = graph.add_vertex(item());
+        let c = graph.add_vertex(item());
+
+        let Ok(ab) = graph.add_edge(a, b, weight(), token) else {
+            panic!("adding an edge failed");
+        };
+        let Ok(bc) = graph.add_edge(b, c, weight(), token) else {
+            panic!("adding a second edge failed");
+        };
+        assert_eq!(graph.edge_len(), 2, "both edges should be in the graph");
+
+        let edge = graph.get_edge(ab).expect("the edge should be in the graph");
+        let edge = edge.borrow(token);
+
+        assert_eq!(
+            edge.endpoints(),
+            (a, b),
+            "`endpoints` should give the vertices in the order they were added",
+        );
+        let (first, second) = edge.endpoint_nodes();
+        assert_eq!(
+            (first.borrow(token).id(), second.borrow(token).id()),
+            (a, b),
+            "`endpoint_nodes` should agree with `endpoints`",
+        );
+        assert_eq!(graph.endpoints(ab, token), Some((a, b)));
+
+        assert_eq!(
+            edge.other(a),
+            Some(b),
+            "`other` should lead from `a` to `b`"
+        );
+        assert_eq!(
+            edge.other(b),
+            Some(a),
+            "`other` should lead from `b` to `a`"
+        );
+        assert_eq!(edge.other(c), None, "`other` should reject other vertices");
+        assert_eq!(
+            edge.traverse(a),
+            Some(b),
+            "`traverse` should follow the edge"
+        );
+        assert_eq!(
+            edge.traverse(b),
+            (!directed).then_some(a),
+            "`traverse` should only follow directed edges forwards",
+        );
+
+        assert!(
+            edge.connects(first, second),
+            "`connects` should see its vertices"
+        );
+        assert!(
+            edge.connects(second, first),
+            "`connects` should ignore order"
+        );
+        let third = graph
+            .get_vertex(c)
+            .expect("the vertex should be in the graph");
+        assert!(
+            !edge.connects(first, third),
+            "`connects` should reject other vertices"
+        );
+
+        // Undirected edges count towards both degrees of each vertex
+        let per_edge = if directed { 1 } else { 2 };
+        assert_eq!(graph.out_degree(a), Some(1));
+        assert_eq!(graph.in_degree(a), Some(per_edge - 1));
+        assert_eq!(graph.in_degree(b), Some(per_edge));
+        assert_eq!(graph.out_degree(b), Some(per_edge));
+
+        let edge = graph.get_edge(ab).expect("the edge should be in the graph");
+        let edge = edge.borrow_mut(token);
+        let stored = ptr::from_ref(edge.get_weight());
+        assert!(
+            ptr::eq(stored, edge.get_weight_mut()),
+            "`get_weight` and `get_weight_mut` should give the same weight",
+        );
+
+        assert_eq!(graph.validate(token), Ok(()));
+
+        // Contracting `ab` moves `bc` onto `a`, which needs `reconnect`
+        let Ok(kept) = graph.contract_edge(ab, |_, _| {}, |_, _| {}, token) else {
+            panic!("contracting an edge failed");
+        };
+        assert_eq!(kept, a, "contracting should keep the first vertex");
+        assert_eq!(
+            graph.endpoints(bc, token),
+            Some((a, c)),
+            "`reconnect` should replace the endpoint and its id",
+        );
+        let edge = graph.get_edge(bc).expect("the edge should be in the graph");
+        let (first, _) = edge.borrow(token).endpoint_nodes();
+        assert_eq!(first.borrow(token).id(), a);
+        assert_eq!(graph.validate(token), Ok(()));
+
+        assert!(
+            graph.remove_edge_between(a, c, token).is_ok(),
+            "removing an edge failed",
+        );
+        assert_eq!(graph.edge_len(), 0);
+        assert_eq!(graph.degree(a), Some(0));
+        assert_eq!(graph.validate(token), Ok(()));
+    });
+}
+
+/// Checks that a family of edges behaves the way a
+/// [`Graph`](crate::Graph) relies on, see
+/// [`check_edge_impl`](crate::edge::check_edge_impl)
+///
+/// Takes the family of edges, named as for
+/// [`GraphWithToken`](crate::GraphWithToken), then an expression
+/// giving an item and one giving a weight, which are evaluated as
+/// many times as needed
+///
+/// ```rust
+/// use graph::{assert_edge_impl, edge::DirectedWeightedEdge};
+///
+/// assert_edge_impl!(DirectedWeightedEdge<'static, u32, i64>, 0, -1);
+/// ```
+#[macro_export]
+macro_rules! assert_edge_impl {
+    ($kind:ty, $item:expr, $weight:expr $(,)?) => {
+        $crate::edge::check_edge_impl::<$kind, _, _>(|| $item, || $weight)
+    };
+}
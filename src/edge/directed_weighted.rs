@@ -1,6 +1,11 @@
-use core::convert::Infallible;
+use core::hash::BuildHasher;
 
-use crate::{edge::EdgeTrait, ghost::GhostToken, id::EdgeId, Graph, Shared, SharedNode, VertexId};
+use crate::{
+    edge::{register_edge, EdgeKind, EdgeTrait, RegisterError},
+    ghost::GhostToken,
+    id::EdgeId,
+    Allocator, Graph, GraphError, SharedNode, VertexId,
+};
 
 /// A directed edge between two [vertices](crate::Vertex), with a given weight
 #[derive(Debug)]
@@ -8,8 +13,17 @@ pub struct DirectedWeightedEdge<'id, Item, Weight>(
     pub Weight,
     SharedNode<'id, Item, Weight, Self>,
     SharedNode<'id, Item, Weight, Self>,
+    (VertexId<'id>, VertexId<'id>),
 );
 
+impl<Item, Weight> EdgeKind<Item, Weight> for DirectedWeightedEdge<'static, Item, Weight> {
+    type Edge<'id>
+        = DirectedWeightedEdge<'id, Item, Weight>
+    where
+        Item: 'id,
+        Weight: 'id;
+}
+
 impl<'id, Item, Weight> DirectedWeightedEdge<'id, Item, Weight> {
     /// Returns the 'sender' in the edge
     pub const fn sender(&self) -> &SharedNode<'id, Item, Weight, Self> {
@@ -19,46 +33,68 @@ impl<'id, Item, Weight> DirectedWeightedEdge<'id, Item, Weight> {
     pub const fn receiver(&self) -> &SharedNode<'id, Item, Weight, Self> {
         &self.2
     }
+    /// Swaps the 'sender' and 'receiver' of the edge
+    ///
+    /// The graph counts the edges leading to and from each vertex, so
+    /// an edge within a graph should be reversed with
+    /// [`Graph::reverse_edge`], which keeps the counts up to date
+    pub const fn reverse(&mut self) {
+        core::mem::swap(&mut self.1, &mut self.2);
+        self.3 = (self.3 .1, self.3 .0);
+    }
 }
 
-impl<'id, Item, Weight> EdgeTrait<'id, Item, Weight> for DirectedWeightedEdge<'id, Item, Weight> {
-    type Error = Infallible;
+impl<'id, Item, Weight, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, DirectedWeightedEdge<'id, Item, Weight>, S, A>
+{
+    /// Reverses the edge `id` in place, so that it leads the other
+    /// way, keeping its id and weight
+    ///
+    /// # Errors
+    /// Returns [`GraphError::EdgeNotFound`] if `id` isn't within the
+    /// graph, or [`GraphError::AlreadyEdgeBetween`] if there's already
+    /// an edge leading the other way
+    pub fn reverse_edge(
+        &mut self,
+        id: EdgeId<'id>,
+        token: &mut GhostToken<'id>,
+    ) -> Result<(), GraphError<'id, Item, Weight, DirectedWeightedEdge<'id, Item, Weight>>> {
+        let (from, to) = self
+            .endpoints(id, token)
+            .ok_or(GraphError::EdgeNotFound(id))?;
+        if self.edge_id_between(to, from, true, token)?.is_some() {
+            return Err(GraphError::AlreadyEdgeBetween);
+        }
+
+        self.edges[&id].borrow_mut(token).reverse();
+        self.unlink_degrees(from, to);
+        self.link_degrees(to, from);
+        self.touch_edge(id);
 
-    fn add_edge<'new_id>(
+        Ok(())
+    }
+}
+
+// SAFETY: The nodes are stored by `register_edge` as given, and
+// `reconnect` replaces whichever equals `old`, which is at most one
+// as the graph never joins a vertex to itself
+unsafe impl<'id, Item, Weight> EdgeTrait<'id, Item, Weight>
+    for DirectedWeightedEdge<'id, Item, Weight>
+{
+    type Error = RegisterError<'id>;
+
+    const DIRECTED: bool = true;
+
+    fn add_edge<'new_id, S: BuildHasher, A: Allocator>(
         weight: Weight,
         first: &SharedNode<'id, Item, Weight, Self>,
         second: &SharedNode<'id, Item, Weight, Self>,
         id: EdgeId<'id>,
-        graph: &mut Graph<'id, Item, Weight, Self>,
+        graph: &mut Graph<'id, Item, Weight, Self, S, A>,
         token: &'new_id mut GhostToken<'id>,
     ) -> Result<(), Self::Error> {
-        let edge = Shared::new(Self(weight, first.clone_shared(), second.clone_shared()));
-
-        first
-            .borrow_mut(token)
-            .edges
-            .insert(id, edge.clone_shared());
-        second
-            .borrow_mut(token)
-            .edges
-            .insert(id, edge.clone_shared());
-        graph.edges.insert(id, edge);
-
-        Ok(())
-    }
-
-    fn other<'new_id>(
-        &'new_id self,
-        id: VertexId<'id>,
-        token: &'new_id GhostToken<'id>,
-    ) -> Option<&SharedNode<'id, Item, Weight, Self>> {
-        if id == self.1.borrow(token).id() {
-            Some(&self.2)
-        } else if id == self.2.borrow(token).id() {
-            Some(&self.1)
-        } else {
-            None
-        }
+        let edge = |first, second, ids| Self(weight, first, second, ids);
+        register_edge(edge, id, first, second, graph, token)
     }
 
     fn get_weight(&self) -> &Weight {
@@ -69,11 +105,35 @@ impl<'id, Item, Weight> EdgeTrait<'id, Item, Weight> for DirectedWeightedEdge<'i
         &mut self.0
     }
 
-    fn connects(
-        &self,
-        first: &SharedNode<'id, Item, Weight, Self>,
-        second: &SharedNode<'id, Item, Weight, Self>,
+    fn reconnect(
+        &mut self,
+        old: &SharedNode<'id, Item, Weight, Self>,
+        new: SharedNode<'id, Item, Weight, Self>,
+        new_id: VertexId<'id>,
     ) -> bool {
-        (self.1 == *first && self.2 == *second) || (self.1 == *second && self.2 == *first)
+        if self.1 == *old {
+            self.1 = new;
+            self.3 .0 = new_id;
+            true
+        } else if self.2 == *old {
+            self.2 = new;
+            self.3 .1 = new_id;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn endpoints(&self) -> (VertexId<'id>, VertexId<'id>) {
+        self.3
+    }
+
+    fn endpoint_nodes(
+        &self,
+    ) -> (
+        &SharedNode<'id, Item, Weight, Self>,
+        &SharedNode<'id, Item, Weight, Self>,
+    ) {
+        (&self.1, &self.2)
     }
 }
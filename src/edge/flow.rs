@@ -0,0 +1,146 @@
+use core::hash::BuildHasher;
+
+use crate::{
+    edge::{DirectedWeightedEdge, EdgeTrait},
+    ghost::GhostToken,
+    Allocator, Graph, Measure, VertexId,
+};
+
+/// The weight of an edge in a flow network, with how much can flow
+/// along it and how much currently does
+///
+/// The flow should never be negative or exceed the capacity, which
+/// [`Flow::augment`] and [`Flow::cancel`] keep to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Flow<Capacity> {
+    /// The most that can flow along the edge
+    pub capacity: Capacity,
+    /// How much currently flows along the edge
+    pub flow: Capacity,
+}
+
+impl<Capacity: Measure> Flow<Capacity> {
+    /// Constructs a new [`Flow`] with nothing flowing yet
+    #[must_use]
+    pub const fn new(capacity: Capacity) -> Self {
+        Self {
+            capacity,
+            flow: Capacity::ZERO,
+        }
+    }
+    /// How much more can flow along the edge
+    #[must_use]
+    pub fn residual_capacity(&self) -> Capacity {
+        self.capacity.saturating_sub(self.flow)
+    }
+    /// Whether no more can flow along the edge
+    #[must_use]
+    pub fn is_saturated(&self) -> bool {
+        self.residual_capacity() <= Capacity::ZERO
+    }
+    /// Adds `amount` to the flow, returning `false` and leaving the
+    /// flow as it was if that would exceed the capacity
+    pub fn augment(&mut self, amount: Capacity) -> bool {
+        if amount < Capacity::ZERO || amount > self.residual_capacity() {
+            return false;
+        }
+        self.flow = self.flow.saturating_add(amount);
+        true
+    }
+    /// Takes `amount` away from the flow, returning `false` and
+    /// leaving the flow as it was if that would make it negative
+    pub fn cancel(&mut self, amount: Capacity) -> bool {
+        if amount < Capacity::ZERO || amount > self.flow {
+            return false;
+        }
+        self.flow = self.flow.saturating_sub(amount);
+        true
+    }
+}
+
+impl<'id, Item, Capacity: Measure, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Flow<Capacity>, DirectedWeightedEdge<'id, Item, Flow<Capacity>>, S, A>
+{
+    /// How much more can be sent from `from` to `to` directly, which
+    /// is the residual capacity of the edge from `from` to `to`, plus
+    /// the flow along the edge from `to` to `from` that can be
+    /// cancelled
+    ///
+    /// Returns zero if neither edge exists
+    #[must_use]
+    pub fn residual_capacity(
+        &self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        token: &GhostToken<'id>,
+    ) -> Capacity {
+        let forward = self
+            .flow_between(from, to, token)
+            .map_or(Capacity::ZERO, Flow::residual_capacity);
+        let backward = self
+            .flow_between(to, from, token)
+            .map_or(Capacity::ZERO, |flow| flow.flow);
+
+        forward.saturating_add(backward)
+    }
+    /// Sends `amount` from `from` to `to`, first cancelling any flow
+    /// along the edge from `to` to `from`, then adding the rest to the
+    /// edge from `from` to `to`
+    ///
+    /// Returns `false` and leaves the flow as it was if `amount` is
+    /// more than the [residual capacity](Graph::residual_capacity)
+    pub fn augment(
+        &mut self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        amount: Capacity,
+        token: &mut GhostToken<'id>,
+    ) -> bool {
+        if amount < Capacity::ZERO || amount > self.residual_capacity(from, to, token) {
+            return false;
+        }
+
+        let mut remaining = amount;
+        if let Ok(Some(id)) = self.edge_id_between(to, from, true, token) {
+            let flow = self.edges[&id].borrow_mut(token).get_weight_mut();
+            let cancelled = if flow.flow < remaining {
+                flow.flow
+            } else {
+                remaining
+            };
+            if cancelled > Capacity::ZERO {
+                flow.cancel(cancelled);
+                remaining = remaining.saturating_sub(cancelled);
+                self.touch_edge(id);
+            }
+        }
+        if remaining > Capacity::ZERO {
+            if let Ok(Some(id)) = self.edge_id_between(from, to, true, token) {
+                self.edges[&id]
+                    .borrow_mut(token)
+                    .get_weight_mut()
+                    .augment(remaining);
+                self.touch_edge(id);
+            }
+        }
+
+        true
+    }
+    /// Sets the flow along every edge back to zero
+    pub fn clear_flow(&mut self, token: &mut GhostToken<'id>) {
+        for edge in self.edges.values() {
+            edge.borrow_mut(token).get_weight_mut().flow = Capacity::ZERO;
+        }
+    }
+    /// The flow along the edge from `from` to `to`
+    fn flow_between<'a>(
+        &'a self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Option<&'a Flow<Capacity>> {
+        let id = self.edge_id_between(from, to, true, token).ok()??;
+        Some(self.edges.get(&id)?.borrow(token).get_weight())
+    }
+}
@@ -0,0 +1,39 @@
+/// The weight of an edge that carries a label, such as the kind of
+/// relationship it represents, as well as a weight for algorithms
+/// to use
+///
+/// Edges weighted with [`Labeled`] can be filtered by their label
+/// with [`EdgeFilter::by_label`](crate::ops::EdgeFilter::by_label)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Labeled<Label, Weight = ()> {
+    /// What the edge represents
+    pub label: Label,
+    /// The weight of the edge
+    pub weight: Weight,
+}
+
+impl<Label, Weight> Labeled<Label, Weight> {
+    /// Constructs a new [`Labeled`] weight
+    #[must_use]
+    pub const fn new(label: Label, weight: Weight) -> Self {
+        Self { label, weight }
+    }
+}
+
+/// A weight that carries a label distinct from the weight itself
+pub trait LabeledWeight {
+    /// The type of the label
+    type Label;
+
+    /// Returns the label
+    fn label(&self) -> &Self::Label;
+}
+
+impl<Label, Weight> LabeledWeight for Labeled<Label, Weight> {
+    type Label = Label;
+
+    fn label(&self) -> &Label {
+        &self.label
+    }
+}
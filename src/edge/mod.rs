@@ -1,11 +1,25 @@
 #![allow(clippy::module_name_repetitions)]
 
+mod bi_weighted;
+mod conformance;
 mod directed_weighted;
+mod flow;
+mod labeled;
+mod probabilistic;
+mod temporal;
 mod undirected_weighted;
 
-use crate::{ghost::GhostToken, id::EdgeId, Graph, SharedNode, VertexId};
+use core::hash::BuildHasher;
 
+use crate::{ghost::GhostToken, id::EdgeId, Allocator, Graph, SharedNode, VertexId};
+
+pub use bi_weighted::{BiWeight, BiWeightedEdge};
+pub use conformance::check_edge_impl;
 pub use directed_weighted::DirectedWeightedEdge;
+pub use flow::Flow;
+pub use labeled::{Labeled, LabeledWeight};
+pub use probabilistic::{Probabilistic, ProbabilisticWeight};
+pub use temporal::{Temporal, TemporalWeight};
 
 pub use undirected_weighted::UnDirectedWeightedEdge;
 
@@ -13,43 +27,321 @@ pub use undirected_weighted::UnDirectedWeightedEdge;
 /// no weight
 pub type UnDirectedUnWeightedEdge<'id, Item> = UnDirectedWeightedEdge<'id, Item, ()>;
 
+/// A directed edge between two [vertices](crate::Vertex) with a
+/// label, and a weight that defaults to none
+pub type DirectedLabeledEdge<'id, Item, Label, Weight = ()> =
+    DirectedWeightedEdge<'id, Item, Labeled<Label, Weight>>;
+
+/// An undirected edge between two [vertices](crate::Vertex) with a
+/// label, and a weight that defaults to none
+pub type UnDirectedLabeledEdge<'id, Item, Label, Weight = ()> =
+    UnDirectedWeightedEdge<'id, Item, Labeled<Label, Weight>>;
+
+/// A directed edge of a flow network, with a capacity and the flow
+/// along it, see [`Flow`]
+pub type FlowEdge<'id, Item, Capacity> = DirectedWeightedEdge<'id, Item, Flow<Capacity>>;
+
+/// A directed edge between two [vertices](crate::Vertex) that only
+/// exists with some probability, with a weight that defaults to none
+pub type DirectedProbabilisticEdge<'id, Item, Weight = ()> =
+    DirectedWeightedEdge<'id, Item, Probabilistic<Weight>>;
+
+/// An undirected edge between two [vertices](crate::Vertex) that only
+/// exists with some probability, with a weight that defaults to none
+pub type UnDirectedProbabilisticEdge<'id, Item, Weight = ()> =
+    UnDirectedWeightedEdge<'id, Item, Probabilistic<Weight>>;
+
+/// A directed edge between two [vertices](crate::Vertex) that only
+/// exists during certain periods, with a weight that defaults to none
+pub type DirectedTemporalEdge<'id, Item, Time, Weight = ()> =
+    DirectedWeightedEdge<'id, Item, Temporal<Time, Weight>>;
+
+/// An undirected edge between two [vertices](crate::Vertex) that only
+/// exists during certain periods, with a weight that defaults to none
+pub type UnDirectedTemporalEdge<'id, Item, Time, Weight = ()> =
+    UnDirectedWeightedEdge<'id, Item, Temporal<Time, Weight>>;
+
+/// An edge type that can be named for any brand `'id`, so that a
+/// graph of it can be stored without a brand, as by
+/// [`GraphWithToken`](crate::GraphWithToken)
+///
+/// Implemented for every edge with the brand `'static`, so
+/// `UnDirectedWeightedEdge<'static, Item, Weight>` names the family
+/// of [`UnDirectedWeightedEdge`]s
+pub trait EdgeKind<Item, Weight> {
+    /// The edge with the brand `'id`
+    type Edge<'id>: EdgeTrait<'id, Item, Weight>
+    where
+        Item: 'id,
+        Weight: 'id;
+}
+
+/// The reason [`register_edge`] refused to store an edge, in which
+/// case the graph is left unchanged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisterError<'id> {
+    /// Another edge of the graph already has the id
+    EdgeIdInUse(EdgeId<'id>),
+    /// The vertex with the id isn't one of the graph's own, such as a
+    /// vertex of another graph with the same brand
+    ForeignVertex(VertexId<'id>),
+}
+
+/// Builds an edge joining `first` and `second` with `edge`, and
+/// stores it in `graph` under `id` and in the edges of both vertices
+///
+/// `edge` is given both vertices along with their ids, in the same
+/// order, to keep in the edge it returns. This is what an
+/// implementation of [`EdgeTrait::add_edge`] usually comes down to,
+/// as an edge missing from any of the three places it's stored
+/// leaves the graph inconsistent, see [`Graph::validate`]. The graph
+/// keeps track of the degrees of `first` and `second` itself
+///
+/// ```rust
+/// use core::hash::BuildHasher;
+///
+/// use graph::{
+///     edge::{register_edge, EdgeTrait, RegisterError},
+///     ghost::GhostToken,
+///     Allocator, EdgeId, Graph, SharedNode, VertexId,
+/// };
+///
+/// /// An undirected edge that remembers when it was added
+/// #[derive(Debug)]
+/// struct StampedEdge<'id> {
+///     added: u64,
+///     nodes: (SharedNode<'id, (), u64, Self>, SharedNode<'id, (), u64, Self>),
+///     ids: (VertexId<'id>, VertexId<'id>),
+/// }
+///
+/// // SAFETY: The nodes are stored as given, and `reconnect` below
+/// // replaces whichever equals `old`
+/// unsafe impl<'id> EdgeTrait<'id, (), u64> for StampedEdge<'id> {
+///     type Error = RegisterError<'id>;
+///
+///     fn add_edge<'new_id, S: BuildHasher, A: Allocator>(
+///         added: u64,
+///         first: &SharedNode<'id, (), u64, Self>,
+///         second: &SharedNode<'id, (), u64, Self>,
+///         id: EdgeId<'id>,
+///         graph: &mut Graph<'id, (), u64, Self, S, A>,
+///         token: &'new_id mut GhostToken<'id>,
+///     ) -> Result<(), RegisterError<'id>> {
+///         let edge = |one, two, ids| Self {
+///             added,
+///             nodes: (one, two),
+///             ids,
+///         };
+///         register_edge(edge, id, first, second, graph, token)
+///     }
+///     // ...
+///     # fn get_weight(&self) -> &u64 { &self.added }
+///     # fn get_weight_mut(&mut self) -> &mut u64 { &mut self.added }
+///     # fn reconnect(
+///     #     &mut self,
+///     #     old: &SharedNode<'id, (), u64, Self>,
+///     #     new: SharedNode<'id, (), u64, Self>,
+///     #     new_id: VertexId<'id>,
+///     # ) -> bool {
+///     #     if self.nodes.0 == *old {
+///     #         self.nodes.0 = new;
+///     #         self.ids.0 = new_id;
+///     #     } else if self.nodes.1 == *old {
+///     #         self.nodes.1 = new;
+///     #         self.ids.1 = new_id;
+///     #     } else {
+///     #         return false;
+///     #     }
+///     #     true
+///     # }
+///     # fn endpoints(&self) -> (VertexId<'id>, VertexId<'id>) { self.ids }
+///     # fn endpoint_nodes(
+///     #     &self,
+///     # ) -> (&SharedNode<'id, (), u64, Self>, &SharedNode<'id, (), u64, Self>) {
+///     #     (&self.nodes.0, &self.nodes.1)
+///     # }
+/// }
+///
+/// GhostToken::new(|mut token| {
+///     let mut graph: Graph<(), u64, StampedEdge> = Graph::new();
+///     let a = graph.add_vertex(());
+///     let b = graph.add_vertex(());
+///     graph.add_edge(a, b, 1_700_000_000, &mut token).unwrap();
+///     assert_eq!(graph.validate(&token), Ok(()));
+/// })
+/// ```
+///
+/// # Errors
+/// Returns [`RegisterError::EdgeIdInUse`] if `id` is already in use
+/// within `graph`, or [`RegisterError::ForeignVertex`] if `first` or
+/// `second` isn't a vertex of `graph`, leaving everything unchanged
+pub fn register_edge<'id, Item, Weight, Edge, S, A>(
+    edge: impl FnOnce(
+        SharedNode<'id, Item, Weight, Edge>,
+        SharedNode<'id, Item, Weight, Edge>,
+        (VertexId<'id>, VertexId<'id>),
+    ) -> Edge,
+    id: EdgeId<'id>,
+    first: &SharedNode<'id, Item, Weight, Edge>,
+    second: &SharedNode<'id, Item, Weight, Edge>,
+    graph: &mut Graph<'id, Item, Weight, Edge, S, A>,
+    token: &mut GhostToken<'id>,
+) -> Result<(), RegisterError<'id>>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    if graph.edges.contains_key(&id) {
+        return Err(RegisterError::EdgeIdInUse(id));
+    }
+    let ids = (first.borrow(token).id(), second.borrow(token).id());
+    for (node, id) in [(first, ids.0), (second, ids.1)] {
+        if graph.vertices.get(&id) != Some(node) {
+            return Err(RegisterError::ForeignVertex(id));
+        }
+    }
+
+    let edge = edge(first.clone_shared(), second.clone_shared(), ids);
+    let edge = graph.edge_arena.alloc(edge);
+
+    first
+        .borrow_mut(token)
+        .edges
+        .insert(id, edge.clone_shared());
+    second
+        .borrow_mut(token)
+        .edges
+        .insert(id, edge.clone_shared());
+    graph.edges.insert(id, edge);
+
+    Ok(())
+}
+
 /// A graph can add edges between [`Vertices`](crate::Vertex) of any
 /// type that implements [`EdgeTrait`]
-pub trait EdgeTrait<'id, Item, Weight>: Sized {
+///
+/// # Safety
+/// The graph frees and reuses the memory of vertices based on what
+/// edges report, so implementations have to keep track of their
+/// endpoints faithfully:
+/// - [`EdgeTrait::endpoint_nodes`] returns the [`SharedNode`]s the
+///   edge was given when it was added, or in place of them by
+///   [`EdgeTrait::reconnect`], and [`EdgeTrait::endpoints`] returns
+///   their ids
+/// - [`EdgeTrait::reconnect`] replaces every endpoint equal to `old`
+///   with `new`, so that `old` is no longer reachable through the
+///   edge afterwards, and returns `true` if and only if it did
+///
+/// [`register_edge`] builds edges that keep to the first point when
+/// their [`SharedNode`]s and ids are stored as given
+pub unsafe trait EdgeTrait<'id, Item, Weight>: Sized {
     type Error;
 
+    /// Whether the edge can only be followed from its first
+    /// [`Vertex`](crate::Vertex) to its second
+    const DIRECTED: bool = false;
+
     /// Adds an edge between `first`, `second`
     /// and the graph, with the given weight
     ///
     /// # Errors
     /// Returns [`Self::Error`] if adding an edge
     /// fails
-    fn add_edge<'new_id>(
+    fn add_edge<'new_id, S: BuildHasher, A: Allocator>(
         weight: Weight,
         first: &SharedNode<'id, Item, Weight, Self>,
         second: &SharedNode<'id, Item, Weight, Self>,
         id: EdgeId<'id>,
-        graph: &mut Graph<'id, Item, Weight, Self>,
+        graph: &mut Graph<'id, Item, Weight, Self, S, A>,
         token: &'new_id mut GhostToken<'id>,
     ) -> Result<(), Self::Error>;
-    /// Returns the other [`Vertex`](crate::Vertex) in `self`
+    /// Returns the [`VertexId`] of the other [`Vertex`](crate::Vertex)
+    /// in `self`
     ///
     /// # Errors
     /// Returns `None` if the provided [`VertexId`] doesn't
     /// relate to either [`Vertex`](crate::Vertex) in `self`
-    fn other<'new_id>(
-        &'new_id self,
-        id: VertexId<'id>,
-        token: &'new_id GhostToken<'id>,
-    ) -> Option<&SharedNode<'id, Item, Weight, Self>>;
+    fn other(&self, id: VertexId<'id>) -> Option<VertexId<'id>> {
+        let (first, second) = self.endpoints();
+        if id == first {
+            Some(second)
+        } else if id == second {
+            Some(first)
+        } else {
+            None
+        }
+    }
+    /// Returns the [`VertexId`] of the [`Vertex`](crate::Vertex)
+    /// reached by following `self` from `id`, respecting the edge's
+    /// direction
+    ///
+    /// # Errors
+    /// Returns `None` if `self` can't be followed from `id`
+    fn traverse(&self, id: VertexId<'id>) -> Option<VertexId<'id>> {
+        if Self::DIRECTED {
+            let (first, second) = self.endpoints();
+            (id == first).then_some(second)
+        } else {
+            self.other(id)
+        }
+    }
 
     fn get_weight(&self) -> &Weight;
 
     fn get_weight_mut(&mut self) -> &mut Weight;
 
+    /// Replaces the endpoint `old` with `new`, whose id is `new_id`,
+    /// returning `false` if `old` isn't an endpoint of `self`
+    ///
+    /// `new` is handed over to the edge, as nothing outside the
+    /// graph can make copies of a [`SharedNode`]
+    ///
+    /// `old` is only compared by address, so it's fine for
+    /// it to have already been deallocated
+    ///
+    /// # Breaking change
+    /// Added in 0.2.0 without a default, as only the edge knows where
+    /// it keeps its endpoints, so edge types written for 0.1 have to
+    /// implement it. Replacing whichever stored [`SharedNode`] equals
+    /// `old`, along with the matching id returned by
+    /// [`EdgeTrait::endpoints`], is all that's needed, as the graph
+    /// updates the vertices' own edges itself. The graph relies on
+    /// this to free vertices safely, which is why implementing
+    /// [`EdgeTrait`] became `unsafe` in 0.2.0 as well.
+    /// [`check_edge_impl`] checks an implementation
+    fn reconnect(
+        &mut self,
+        old: &SharedNode<'id, Item, Weight, Self>,
+        new: SharedNode<'id, Item, Weight, Self>,
+        new_id: VertexId<'id>,
+    ) -> bool;
+
+    /// Returns the [`VertexId`]s of both of the
+    /// [vertices](crate::Vertex) in `self`, with the one the edge
+    /// leads from first if it's directed
+    ///
+    /// The ids are stored in the edge itself, so unlike
+    /// [`EdgeTrait::endpoint_nodes`] they can be read without
+    /// borrowing either vertex
+    fn endpoints(&self) -> (VertexId<'id>, VertexId<'id>);
+
+    /// Returns both of the [vertices](crate::Vertex) in `self`,
+    /// with the one the edge leads from first if it's directed
+    fn endpoint_nodes(
+        &self,
+    ) -> (
+        &SharedNode<'id, Item, Weight, Self>,
+        &SharedNode<'id, Item, Weight, Self>,
+    );
+
+    /// Whether `self` joins `first` and `second`, in either order
     fn connects(
         &self,
         first: &SharedNode<'id, Item, Weight, Self>,
         second: &SharedNode<'id, Item, Weight, Self>,
-    ) -> bool;
+    ) -> bool {
+        let (one, two) = self.endpoint_nodes();
+        (one == first && two == second) || (one == second && two == first)
+    }
 }
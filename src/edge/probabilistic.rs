@@ -0,0 +1,39 @@
+/// The weight of an edge that only exists with some probability, such
+/// as a link in a network that may fail, as well as a weight for
+/// algorithms to use
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Probabilistic<Weight = ()> {
+    /// The probability the edge exists, from `0.0` to `1.0`
+    pub probability: f64,
+    /// The weight of the edge
+    pub weight: Weight,
+}
+
+impl<Weight> Probabilistic<Weight> {
+    /// Constructs a new [`Probabilistic`] weight, clamping
+    /// `probability` to `0.0..=1.0`
+    #[must_use]
+    pub const fn new(probability: f64, weight: Weight) -> Self {
+        Self {
+            probability: if probability.is_nan() {
+                0.0
+            } else {
+                probability.clamp(0.0, 1.0)
+            },
+            weight,
+        }
+    }
+}
+
+/// A weight that only exists with some probability
+pub trait ProbabilisticWeight {
+    /// The probability the edge exists, from `0.0` to `1.0`
+    fn probability(&self) -> f64;
+}
+
+impl<Weight> ProbabilisticWeight for Probabilistic<Weight> {
+    fn probability(&self) -> f64 {
+        self.probability
+    }
+}
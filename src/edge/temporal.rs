@@ -0,0 +1,94 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use crate::{algo::successors, edge::EdgeTrait, ghost::GhostToken, Allocator, Graph, VertexId};
+
+/// The weight of an edge that only exists during certain periods,
+/// such as a contact between two people, as well as a weight for
+/// algorithms to use
+///
+/// Each period is an inclusive range of times, `(start, end)`, and
+/// periods may overlap
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Temporal<Time, Weight = ()> {
+    /// The periods the edge exists during
+    pub intervals: Vec<(Time, Time)>,
+    /// The weight of the edge
+    pub weight: Weight,
+}
+
+impl<Time, Weight> Temporal<Time, Weight> {
+    /// Constructs a new [`Temporal`] weight that never exists, until
+    /// periods are added
+    #[must_use]
+    pub const fn new(weight: Weight) -> Self {
+        Self {
+            intervals: Vec::new(),
+            weight,
+        }
+    }
+    /// Adds a period the edge exists during, from `start` to `end`
+    /// inclusive
+    #[must_use]
+    pub fn with_interval(mut self, start: Time, end: Time) -> Self {
+        self.intervals.push((start, end));
+        self
+    }
+}
+
+/// A weight that only exists during certain periods
+pub trait TemporalWeight {
+    /// The type of the times the periods are measured in
+    type Time: Ord + Copy;
+
+    /// Whether the edge exists at `time`
+    fn active_at(&self, time: Self::Time) -> bool;
+    /// The earliest time the edge exists at, no earlier than `time`,
+    /// or `None` if it never exists again
+    fn next_active(&self, time: Self::Time) -> Option<Self::Time>;
+}
+
+impl<Time: Ord + Copy, Weight> TemporalWeight for Temporal<Time, Weight> {
+    type Time = Time;
+
+    fn active_at(&self, time: Time) -> bool {
+        self.intervals
+            .iter()
+            .any(|(start, end)| *start <= time && time <= *end)
+    }
+
+    fn next_active(&self, time: Time) -> Option<Time> {
+        self.intervals
+            .iter()
+            .filter(|(start, end)| start <= end && time <= *end)
+            .map(|(start, _)| time.max(*start))
+            .min()
+    }
+}
+
+impl<'id, Item, Weight, Edge, S, A> Graph<'id, Item, Weight, Edge, S, A>
+where
+    Weight: TemporalWeight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// The vertices that can be reached from `id` along a single edge
+    /// that exists at `time`, respecting edge direction, in no
+    /// particular order
+    ///
+    /// Returns an empty list if `id` isn't within the graph
+    #[must_use]
+    pub fn neighbors_at(
+        &self,
+        id: VertexId<'id>,
+        time: Weight::Time,
+        token: &GhostToken<'id>,
+    ) -> Vec<VertexId<'id>> {
+        successors(self, id, token)
+            .filter(|(_, _, weight)| weight.active_at(time))
+            .map(|(_, other, _)| other)
+            .collect()
+    }
+}
@@ -1,6 +1,11 @@
-use core::{convert::Infallible, fmt::Debug};
+use core::{fmt::Debug, hash::BuildHasher};
 
-use crate::{edge::EdgeTrait, ghost::GhostToken, id::EdgeId, Graph, Shared, SharedNode, VertexId};
+use crate::{
+    edge::{register_edge, EdgeKind, EdgeTrait, RegisterError},
+    ghost::GhostToken,
+    id::EdgeId,
+    Allocator, Graph, SharedNode, VertexId,
+};
 
 /// An undirected edge between two [vertices](crate::Vertex), with a given weight
 #[derive(Debug)]
@@ -8,49 +13,36 @@ pub struct UnDirectedWeightedEdge<'id, Item: Debug, Weight>(
     pub Weight,
     SharedNode<'id, Item, Weight, Self>,
     SharedNode<'id, Item, Weight, Self>,
+    (VertexId<'id>, VertexId<'id>),
 );
 
-impl<'id, Item: Debug, Weight> EdgeTrait<'id, Item, Weight>
+impl<Item: Debug, Weight> EdgeKind<Item, Weight> for UnDirectedWeightedEdge<'static, Item, Weight> {
+    type Edge<'id>
+        = UnDirectedWeightedEdge<'id, Item, Weight>
+    where
+        Item: 'id,
+        Weight: 'id;
+}
+
+// SAFETY: The nodes are stored by `register_edge` as given, and
+// `reconnect` replaces whichever equals `old`, which is at most one
+// as the graph never joins a vertex to itself
+unsafe impl<'id, Item: Debug, Weight> EdgeTrait<'id, Item, Weight>
     for UnDirectedWeightedEdge<'id, Item, Weight>
 {
-    type Error = Infallible;
+    type Error = RegisterError<'id>;
 
-    fn add_edge<'new_id>(
+    fn add_edge<'new_id, S: BuildHasher, A: Allocator>(
         weight: Weight,
         first: &SharedNode<'id, Item, Weight, Self>,
         second: &SharedNode<'id, Item, Weight, Self>,
         id: EdgeId<'id>,
-        graph: &mut Graph<'id, Item, Weight, Self>,
+        graph: &mut Graph<'id, Item, Weight, Self, S, A>,
         token: &'new_id mut GhostToken<'id>,
     ) -> Result<(), Self::Error> {
-        let edge = Shared::new(Self(weight, first.clone_shared(), second.clone_shared()));
-
-        first
-            .borrow_mut(token)
-            .edges
-            .insert(id, edge.clone_shared());
-        second
-            .borrow_mut(token)
-            .edges
-            .insert(id, edge.clone_shared());
-        graph.edges.insert(id, edge);
-
-        Ok(())
+        let edge = |first, second, ids| Self(weight, first, second, ids);
+        register_edge(edge, id, first, second, graph, token)
     }
-    fn other<'new_id>(
-        &'new_id self,
-        id: VertexId<'id>,
-        token: &'new_id GhostToken<'id>,
-    ) -> Option<&SharedNode<'id, Item, Weight, Self>> {
-        if id == self.1.borrow(token).id() {
-            Some(&self.2)
-        } else if id == self.2.borrow(token).id() {
-            Some(&self.1)
-        } else {
-            None
-        }
-    }
-
     fn get_weight(&self) -> &Weight {
         &self.0
     }
@@ -59,11 +51,35 @@ impl<'id, Item: Debug, Weight> EdgeTrait<'id, Item, Weight>
         &mut self.0
     }
 
-    fn connects(
-        &self,
-        first: &SharedNode<'id, Item, Weight, Self>,
-        second: &SharedNode<'id, Item, Weight, Self>,
+    fn reconnect(
+        &mut self,
+        old: &SharedNode<'id, Item, Weight, Self>,
+        new: SharedNode<'id, Item, Weight, Self>,
+        new_id: VertexId<'id>,
     ) -> bool {
-        (self.1 == *first && self.2 == *second) || (self.1 == *second && self.2 == *first)
+        if self.1 == *old {
+            self.1 = new;
+            self.3 .0 = new_id;
+            true
+        } else if self.2 == *old {
+            self.2 = new;
+            self.3 .1 = new_id;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn endpoints(&self) -> (VertexId<'id>, VertexId<'id>) {
+        self.3
+    }
+
+    fn endpoint_nodes(
+        &self,
+    ) -> (
+        &SharedNode<'id, Item, Weight, Self>,
+        &SharedNode<'id, Item, Weight, Self>,
+    ) {
+        (&self.1, &self.2)
     }
 }
@@ -0,0 +1,70 @@
+use alloc::{string::String, vec::Vec};
+
+/// A record of where an exported graph or result came from, such as
+/// the pipeline run that produced it
+///
+/// The library never reads a [`Provenance`], it's only carried
+/// alongside exported data by [`Watermarked`] so that it can be read
+/// back on import
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Provenance {
+    /// Where the data came from
+    pub source: String,
+    /// When the data was produced, in whatever unit the caller uses
+    pub timestamp: u64,
+    /// The parameters used to generate the data, as key value pairs
+    pub parameters: Vec<(String, String)>,
+}
+
+impl Provenance {
+    /// Constructs a new [`Provenance`] with no parameters
+    #[must_use]
+    pub fn new(source: impl Into<String>, timestamp: u64) -> Self {
+        Self {
+            source: source.into(),
+            timestamp,
+            parameters: Vec::new(),
+        }
+    }
+    /// Adds a generator parameter to the record
+    #[must_use]
+    pub fn with_parameter(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.parameters.push((key.into(), value.into()));
+        self
+    }
+    /// Returns the value of the parameter `key`, or `None` if
+    /// there's no such parameter
+    #[must_use]
+    pub fn parameter(&self, key: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Exported data, such as a [`FrozenGraph`](crate::FrozenGraph) or
+/// an algorithm's result, along with the [`Provenance`] of the data
+///
+/// With the `serde` feature enabled, the provenance is written out
+/// and read back in alongside the data in every serde format
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Watermarked<T> {
+    /// Where `data` came from
+    pub provenance: Provenance,
+    /// The exported data
+    pub data: T,
+}
+
+impl<T> Watermarked<T> {
+    /// Pairs `data` with its provenance
+    pub const fn new(provenance: Provenance, data: T) -> Self {
+        Self { provenance, data }
+    }
+    /// Splits `self` back into its provenance and data
+    pub fn into_parts(self) -> (Provenance, T) {
+        (self.provenance, self.data)
+    }
+}
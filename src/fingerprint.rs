@@ -0,0 +1,88 @@
+use core::hash::{BuildHasher, Hash, Hasher};
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, Allocator, Graph};
+
+/// A 64 bit FNV-1a hasher, which unlike the graph's own hasher gives
+/// the same hashes in every process
+///
+/// See <http://www.isthe.com/chongo/tech/comp/fnv/>
+struct Fnv(u64);
+
+impl Fnv {
+    const OFFSET: u64 = 0xCBF2_9CE4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01B3;
+
+    /// Hashes `value` on its own
+    fn hash(value: &impl Hash) -> u64 {
+        let mut hasher = Self(Self::OFFSET);
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        // FNV's low bits are weak, so they're mixed as SplitMix64's
+        // output is before the hashes are summed
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+}
+
+impl<'id, Item, Weight, Edge, S, A> Graph<'id, Item, Weight, Edge, S, A>
+where
+    Item: Hash,
+    Weight: Hash,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// A hash of every vertex and edge in the graph, along with their
+    /// items, weights and ids, which doesn't depend on the order
+    /// they're stored in
+    ///
+    /// Graphs with the same fingerprint are almost certainly the
+    /// same, so it's a cheap way to tell whether a graph has changed
+    /// since the fingerprint was taken. The fingerprint is the same
+    /// in every process and on every platform with the same pointer
+    /// width, but graphs with the same shape and different ids have
+    /// different fingerprints
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<u32, u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let a = graph.add_vertex(1);
+    ///     let b = graph.add_vertex(2);
+    ///     let before = graph.fingerprint(&token);
+    ///
+    ///     graph.add_edge(a, b, 3, &mut token).unwrap();
+    ///     assert_ne!(graph.fingerprint(&token), before);
+    ///
+    ///     graph.remove_edge_between(a, b, &mut token).unwrap();
+    ///     assert_eq!(graph.fingerprint(&token), before);
+    /// })
+    /// ```
+    #[must_use]
+    pub fn fingerprint(&self, token: &GhostToken<'id>) -> u64 {
+        let vertices = self
+            .items(token)
+            .map(|(id, item)| Fnv::hash(&(0_u8, id.id(), item)))
+            .fold(0, u64::wrapping_add);
+        let edges = self
+            .unique_edges(token)
+            .map(|(id, from, to, weight)| Fnv::hash(&(1_u8, id.id, from.id(), to.id(), weight)))
+            .fold(0, u64::wrapping_add);
+
+        Fnv::hash(&(self.vertex_len(), self.edge_len(), vertices, edges))
+    }
+}
@@ -0,0 +1,186 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use crate::{
+    edge::EdgeTrait, ghost::GhostToken, id::EdgeId, Allocator, Graph, SecondaryMap, VertexId,
+};
+
+/// A read-only snapshot of a [`Graph`]'s structure, stored in
+/// compressed sparse row form
+///
+/// Every vertex is given a dense index, in order of [`VertexId`],
+/// and the vertices reachable from each vertex are stored next to
+/// each other in a single array. Traversing a [`FrozenGraph`]
+/// never touches the graph's vertices, and so doesn't need a
+/// [`GhostToken`], which makes it much friendlier to the cache
+/// for analytics that run many times over the same graph.
+///
+/// Directed edges only appear in their sender's adjacency, while
+/// undirected edges appear in both of their vertices'
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrozenGraph<'id, Weight> {
+    ids: Vec<VertexId<'id>>,
+    index: SecondaryMap<VertexId<'id>, usize>,
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    edges: Vec<EdgeId<'id>>,
+    weights: Vec<Weight>,
+}
+
+impl<'id, Weight> FrozenGraph<'id, Weight> {
+    /// Takes a snapshot of `graph`, cloning every edge's weight
+    #[must_use]
+    pub fn new<Item, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>(
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        token: &GhostToken<'id>,
+    ) -> Self
+    where
+        Weight: Clone,
+    {
+        let mut ids: Vec<_> = graph.get_all_vertices().copied().collect();
+        ids.sort_unstable_by_key(|id| id.id());
+
+        let index: SecondaryMap<_, _> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        let adjacency_len = if Edge::DIRECTED {
+            graph.edge_len()
+        } else {
+            graph.edge_len() * 2
+        };
+
+        let mut offsets = Vec::with_capacity(ids.len() + 1);
+        let mut targets = Vec::with_capacity(adjacency_len);
+        let mut edges = Vec::with_capacity(adjacency_len);
+        let mut weights = Vec::with_capacity(adjacency_len);
+
+        let mut adjacent = Vec::new();
+
+        for id in &ids {
+            offsets.push(targets.len());
+
+            if let Some(vertex) = graph.get_vertex(*id) {
+                adjacent.extend(vertex.borrow(token).edges().filter_map(|(e_id, edge)| {
+                    let edge = edge.borrow(token);
+                    let other = edge.traverse(*id)?;
+                    Some((index[other], *e_id, edge.get_weight()))
+                }));
+            }
+
+            adjacent.sort_unstable_by_key(|(target, _, _)| *target);
+
+            for &(target, e_id, weight) in &adjacent {
+                targets.push(target);
+                edges.push(e_id);
+                weights.push(weight.clone());
+            }
+
+            adjacent.clear();
+        }
+
+        offsets.push(targets.len());
+
+        Self {
+            ids,
+            index,
+            offsets,
+            targets,
+            edges,
+            weights,
+        }
+    }
+    /// The number of vertices within the snapshot
+    #[must_use]
+    pub const fn vertex_len(&self) -> usize {
+        self.ids.len()
+    }
+    /// The number of entries within the adjacency array, which for
+    /// undirected graphs is twice the number of edges
+    #[must_use]
+    pub const fn adjacency_len(&self) -> usize {
+        self.targets.len()
+    }
+    /// If there are no vertices within the snapshot
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+    /// The dense index of `id`, or `None` if `id` wasn't
+    /// in the graph when it was frozen
+    #[must_use]
+    pub fn index_of(&self, id: VertexId<'id>) -> Option<usize> {
+        self.index.get(id).copied()
+    }
+    /// The [`VertexId`] at the dense `index`
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds
+    #[must_use]
+    pub fn id_at(&self, index: usize) -> VertexId<'id> {
+        self.ids[index]
+    }
+    /// Every [`VertexId`], ordered by dense index
+    #[must_use]
+    pub fn ids(&self) -> &[VertexId<'id>] {
+        &self.ids
+    }
+    /// The dense indices of the vertices reachable from `index`
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds
+    #[must_use]
+    pub fn neighbors(&self, index: usize) -> &[usize] {
+        &self.targets[self.range(index)]
+    }
+    /// The [`EdgeId`]s of the edges leaving `index`, in the same
+    /// order as [`FrozenGraph::neighbors`]
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds
+    #[must_use]
+    pub fn edge_ids(&self, index: usize) -> &[EdgeId<'id>] {
+        &self.edges[self.range(index)]
+    }
+    /// The weights of the edges leaving `index`, in the same
+    /// order as [`FrozenGraph::neighbors`]
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds
+    #[must_use]
+    pub fn weights(&self, index: usize) -> &[Weight] {
+        &self.weights[self.range(index)]
+    }
+    /// The number of edges leaving `index`
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds
+    #[must_use]
+    pub fn degree(&self, index: usize) -> usize {
+        self.offsets[index + 1] - self.offsets[index]
+    }
+    /// The offsets and targets arrays, without the items and weights
+    #[cfg(feature = "rayon")]
+    pub(crate) fn adjacency(&self) -> (&[usize], &[usize]) {
+        (&self.offsets, &self.targets)
+    }
+    fn range(&self, index: usize) -> core::ops::Range<usize> {
+        self.offsets[index]..self.offsets[index + 1]
+    }
+}
+
+impl<
+        'id,
+        Item,
+        Weight: Clone,
+        Edge: EdgeTrait<'id, Item, Weight>,
+        S: BuildHasher,
+        A: Allocator,
+    > Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Takes a read-only [`FrozenGraph`] snapshot of the graph's
+    /// structure, see [`FrozenGraph::new`]
+    #[must_use]
+    pub fn freeze(&self, token: &GhostToken<'id>) -> FrozenGraph<'id, Weight> {
+        FrozenGraph::new(self, token)
+    }
+}
@@ -0,0 +1,357 @@
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, Allocator, Graph, GraphError, VertexId};
+
+/// A source of random numbers for the random graph generators
+///
+/// Only [`RandomSource::next_u64`] needs implementing, so any random
+/// number generator can be used without the library depending on one
+pub trait RandomSource {
+    /// Returns the next uniformly distributed `u64`
+    fn next_u64(&mut self) -> u64;
+
+    /// Returns a uniformly distributed `f64` in `0.0..1.0`
+    #[allow(clippy::cast_precision_loss)]
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Returns a uniformly distributed `usize` in `0..bound`
+    ///
+    /// # Panics
+    /// Panics if `bound` is `0`
+    #[allow(clippy::cast_possible_truncation)]
+    fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "the bound must be positive");
+        ((u128::from(self.next_u64()) * bound as u128) >> 64) as usize
+    }
+}
+
+/// A small and fast [`RandomSource`], which isn't suitable for
+/// anything needing unpredictable numbers
+///
+/// See <https://prng.di.unimi.it/splitmix64.c>
+#[derive(Debug, Clone)]
+pub struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// Constructs a new [`SplitMix64`], which always produces the
+    /// same numbers for the same `seed`
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+}
+
+impl RandomSource for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+type Generated<'id, Item, Weight, Edge, S, A> =
+    Result<Graph<'id, Item, Weight, Edge, S, A>, GraphError<'id, Item, Weight, Edge>>;
+
+/// Constructs a graph of `n` vertices, the `i`th of which holds
+/// `item(i)`
+fn with_vertices<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A>(
+    n: usize,
+    edges: usize,
+    item: impl FnMut(usize) -> Item,
+) -> (Graph<'id, Item, Weight, Edge, S, A>, Vec<VertexId<'id>>)
+where
+    S: BuildHasher + Clone + Default,
+    A: Allocator + Clone + Default,
+{
+    let mut graph = Graph::with_capacity_and_hasher_in(n, edges, S::default(), A::default());
+    let ids = (0..n)
+        .map(item)
+        .map(|item| graph.add_vertex(item))
+        .collect();
+    (graph, ids)
+}
+
+/// Constructs the complete graph of `n` vertices, with an edge
+/// between every pair of vertices
+///
+/// The `i`th vertex holds `item(i)`, and the edge from the `i`th
+/// vertex to the `j`th has the weight `weight(i, j)`, where `i < j`
+///
+/// # Errors
+/// Returns a [`GraphError`] if any edge can't be added to the graph
+pub fn complete<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A>(
+    n: usize,
+    item: impl FnMut(usize) -> Item,
+    mut weight: impl FnMut(usize, usize) -> Weight,
+    token: &mut GhostToken<'id>,
+) -> Generated<'id, Item, Weight, Edge, S, A>
+where
+    S: BuildHasher + Clone + Default,
+    A: Allocator + Clone + Default,
+{
+    let (mut graph, ids) = with_vertices(n, n * n.saturating_sub(1) / 2, item);
+
+    for i in 0..n {
+        for j in i + 1..n {
+            graph.add_edge(ids[i], ids[j], weight(i, j), token)?;
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Constructs the cycle of `n` vertices, with an edge from every
+/// vertex to the next and from the last vertex back to the first
+///
+/// The `i`th vertex holds `item(i)`, and the edge from the `i`th
+/// vertex to the `j`th has the weight `weight(i, j)`. Fewer than
+/// three vertices can't form a cycle, so two vertices are joined by
+/// a single edge and one vertex has no edges
+///
+/// # Errors
+/// Returns a [`GraphError`] if any edge can't be added to the graph
+pub fn cycle<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A>(
+    n: usize,
+    item: impl FnMut(usize) -> Item,
+    mut weight: impl FnMut(usize, usize) -> Weight,
+    token: &mut GhostToken<'id>,
+) -> Generated<'id, Item, Weight, Edge, S, A>
+where
+    S: BuildHasher + Clone + Default,
+    A: Allocator + Clone + Default,
+{
+    let (mut graph, ids) = with_vertices(n, n, item);
+
+    let edges = match n {
+        0 | 1 => 0,
+        2 => 1,
+        _ => n,
+    };
+    for i in 0..edges {
+        let j = (i + 1) % n;
+        graph.add_edge(ids[i], ids[j], weight(i, j), token)?;
+    }
+
+    Ok(graph)
+}
+
+/// Constructs a `width` by `height` grid, with an edge between every
+/// vertex and the vertices directly to its right and below it
+///
+/// The vertex at column `x` and row `y` holds `item(x, y)` and is the
+/// `y * width + x`th vertex. The edge from the `i`th vertex to the
+/// `j`th has the weight `weight(i, j)`
+///
+/// # Errors
+/// Returns a [`GraphError`] if any edge can't be added to the graph
+pub fn grid<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A>(
+    width: usize,
+    height: usize,
+    mut item: impl FnMut(usize, usize) -> Item,
+    mut weight: impl FnMut(usize, usize) -> Weight,
+    token: &mut GhostToken<'id>,
+) -> Generated<'id, Item, Weight, Edge, S, A>
+where
+    S: BuildHasher + Clone + Default,
+    A: Allocator + Clone + Default,
+{
+    let edges = 2 * width * height;
+    let (mut graph, ids) = with_vertices(width * height, edges, |i| item(i % width, i / width));
+
+    for y in 0..height {
+        for x in 0..width {
+            let i = y * width + x;
+            if x + 1 < width {
+                graph.add_edge(ids[i], ids[i + 1], weight(i, i + 1), token)?;
+            }
+            if y + 1 < height {
+                graph.add_edge(ids[i], ids[i + width], weight(i, i + width), token)?;
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+impl<'id, Item, Weight, Edge, S, A> Graph<'id, Item, Weight, Edge, S, A>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher + Clone + Default,
+    A: Allocator + Clone + Default,
+{
+    /// Constructs a `width` by `height` grid, with an edge between
+    /// every cell and each of its four neighbors, or eight if
+    /// `diagonal` is `true`
+    ///
+    /// The vertex at column `x` and row `y` holds `item(x, y)` and has
+    /// the [`VertexId`] `y * width + x`. The edge from `(x, y)` to
+    /// another cell has the weight `weight((x, y), other)`. Neighbors
+    /// of a directed grid are joined in both directions, so that it
+    /// can be crossed either way
+    ///
+    /// ```rust
+    /// use graph::{algo::dijkstra, edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph, VertexId};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let grid: Graph<(usize, usize), u32, UnDirectedWeightedEdge<_, _>> = Graph::grid(
+    ///         4,
+    ///         4,
+    ///         true,
+    ///         |x, y| (x, y),
+    ///         |(x, y), (other_x, other_y)| if x != other_x && y != other_y { 14 } else { 10 },
+    ///         &mut token,
+    ///     )
+    ///     .unwrap();
+    ///
+    ///     let paths = dijkstra(&grid, VertexId::new(0), |w| *w, &token).unwrap();
+    ///     assert_eq!(paths.distance(VertexId::new(15)), Some(42));
+    /// })
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a [`GraphError`] if any edge can't be added to the graph
+    pub fn grid(
+        width: usize,
+        height: usize,
+        diagonal: bool,
+        mut item: impl FnMut(usize, usize) -> Item,
+        mut weight: impl FnMut((usize, usize), (usize, usize)) -> Weight,
+        token: &mut GhostToken<'id>,
+    ) -> Generated<'id, Item, Weight, Edge, S, A> {
+        let per_cell = if diagonal { 4 } else { 2 };
+        let edges = per_cell * width * height * if Edge::DIRECTED { 2 } else { 1 };
+        let (mut graph, ids) = with_vertices(width * height, edges, |i| item(i % width, i / width));
+
+        for y in 0..height {
+            for x in 0..width {
+                let mut neighbors = [None; 4];
+                if x + 1 < width {
+                    neighbors[0] = Some((x + 1, y));
+                }
+                if y + 1 < height {
+                    neighbors[1] = Some((x, y + 1));
+                    if diagonal && x + 1 < width {
+                        neighbors[2] = Some((x + 1, y + 1));
+                    }
+                    if diagonal && x > 0 {
+                        neighbors[3] = Some((x - 1, y + 1));
+                    }
+                }
+
+                for other in neighbors.iter().flatten().copied() {
+                    let (from, to) = (ids[y * width + x], ids[other.1 * width + other.0]);
+                    if Edge::DIRECTED {
+                        let there = weight((x, y), other);
+                        let back = weight(other, (x, y));
+                        graph.add_edge_bidirectional(from, to, there, back, token)?;
+                    } else {
+                        graph.add_edge(from, to, weight((x, y), other), token)?;
+                    }
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+/// Constructs an Erdős–Rényi random graph `G(n, p)`, where every pair
+/// of `n` vertices is joined by an edge with probability `p`
+///
+/// The `i`th vertex holds `item(i)`, and the edge from the `i`th
+/// vertex to the `j`th has the weight `weight(i, j)`. Each pair of
+/// vertices is only joined once, so directed edges are given a
+/// random direction
+///
+/// # Errors
+/// Returns a [`GraphError`] if any edge can't be added to the graph
+pub fn erdos_renyi<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A>(
+    n: usize,
+    p: f64,
+    rng: &mut impl RandomSource,
+    item: impl FnMut(usize) -> Item,
+    mut weight: impl FnMut(usize, usize) -> Weight,
+    token: &mut GhostToken<'id>,
+) -> Generated<'id, Item, Weight, Edge, S, A>
+where
+    S: BuildHasher + Clone + Default,
+    A: Allocator + Clone + Default,
+{
+    let (mut graph, ids) = with_vertices(n, 0, item);
+
+    for i in 0..n {
+        for j in i + 1..n {
+            if rng.next_f64() < p {
+                let (from, to) = if Edge::DIRECTED && rng.next_u64() & 1 == 1 {
+                    (j, i)
+                } else {
+                    (i, j)
+                };
+                graph.add_edge(ids[from], ids[to], weight(from, to), token)?;
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Constructs a Barabási–Albert random graph of `n` vertices by
+/// preferential attachment
+///
+/// The graph starts with `m` vertices and no edges. Every vertex
+/// added afterwards gets edges to `m` different vertices already in
+/// the graph, chosen with probability proportional to their degree.
+///
+/// The `i`th vertex holds `item(i)`, and the edge from the `i`th
+/// vertex to the `j`th has the weight `weight(i, j)`. Directed edges
+/// lead from the newer vertex to the older one
+///
+/// # Errors
+/// Returns a [`GraphError`] if any edge can't be added to the graph
+///
+/// # Panics
+/// Panics if `m` is `0`, or isn't less than `n`
+pub fn barabasi_albert<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A>(
+    n: usize,
+    m: usize,
+    rng: &mut impl RandomSource,
+    item: impl FnMut(usize) -> Item,
+    mut weight: impl FnMut(usize, usize) -> Weight,
+    token: &mut GhostToken<'id>,
+) -> Generated<'id, Item, Weight, Edge, S, A>
+where
+    S: BuildHasher + Clone + Default,
+    A: Allocator + Clone + Default,
+{
+    assert!(0 < m && m < n, "`m` must be between 0 and `n`");
+
+    let (mut graph, ids) = with_vertices(n, (n - m) * m, item);
+
+    // Every vertex appears once for each edge it has, so picking
+    // from this uniformly is weighted by degree
+    let mut ends: Vec<usize> = Vec::with_capacity(2 * (n - m) * m);
+    let mut targets: Vec<usize> = (0..m).collect();
+
+    for new in m..n {
+        for &old in &targets {
+            graph.add_edge(ids[new], ids[old], weight(new, old), token)?;
+            ends.push(new);
+            ends.push(old);
+        }
+
+        targets.clear();
+        while targets.len() < m {
+            let picked = ends[rng.below(ends.len())];
+            if !targets.contains(&picked) {
+                targets.push(picked);
+            }
+        }
+    }
+
+    Ok(graph)
+}
@@ -0,0 +1,324 @@
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+use core::{cmp::Reverse, hash::BuildHasher};
+
+use crate::{
+    algo::{successors, ShortestPath},
+    edge::EdgeTrait,
+    ghost::GhostToken,
+    measure::Total,
+    Allocator, EdgeId, Graph, GraphError, SecondaryMap, VertexId,
+};
+
+/// An item with a position in `N` dimensional space
+pub trait Position<const N: usize> {
+    /// The coordinates of the item
+    fn coords(&self) -> [f64; N];
+}
+
+impl<const N: usize> Position<N> for [f64; N] {
+    fn coords(&self) -> [f64; N] {
+        *self
+    }
+}
+
+impl Position<2> for (f64, f64) {
+    fn coords(&self) -> [f64; 2] {
+        [self.0, self.1]
+    }
+}
+
+impl Position<3> for (f64, f64, f64) {
+    fn coords(&self) -> [f64; 3] {
+        [self.0, self.1, self.2]
+    }
+}
+
+/// The straight line distance between `a` and `b`
+#[must_use]
+pub fn euclidean<const N: usize>(a: &[f64; N], b: &[f64; N]) -> f64 {
+    libm::sqrt(squared(a, b))
+}
+
+/// The square of the straight line distance between `a` and `b`
+fn squared<const N: usize>(a: &[f64; N], b: &[f64; N]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum()
+}
+
+impl<'id, Item, Weight, Edge, S, A> Graph<'id, Item, Weight, Edge, S, A>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// Adds an edge between `id_one` and `id_two`, weighted by the
+    /// straight line distance between their items
+    ///
+    /// # Errors
+    /// Returns a [`GraphError`] if either vertex isn't within the
+    /// graph, or if the edge can't be added, as [`Graph::add_edge`]
+    /// does
+    pub fn add_edge_with_euclidean_weight<const N: usize>(
+        &mut self,
+        id_one: VertexId<'id>,
+        id_two: VertexId<'id>,
+        token: &mut GhostToken<'id>,
+    ) -> Result<EdgeId<'id>, GraphError<'id, Item, Weight, Edge>>
+    where
+        Item: Position<N>,
+        Weight: From<f64>,
+    {
+        let coords = |id| {
+            self.get_vertex(id)
+                .map(|vertex| vertex.borrow(token).get_item().coords())
+                .ok_or(GraphError::VertexNotFound(id))
+        };
+        let distance = euclidean(&coords(id_one)?, &coords(id_two)?);
+
+        self.add_edge(id_one, id_two, Weight::from(distance), token)
+    }
+}
+
+/// A k-d tree of the positions of every vertex in a graph, for
+/// finding the vertices nearest to a point, built by
+/// [`SpatialIndex::build`]
+///
+/// The index is a snapshot, so it doesn't see vertices added to the
+/// graph afterwards, or changes to their positions
+#[derive(Debug, Clone)]
+pub struct SpatialIndex<'id, const N: usize> {
+    /// The points, ordered so that the median of every range is the
+    /// split point of the subtree the range holds
+    points: Vec<(VertexId<'id>, [f64; N])>,
+}
+
+impl<'id, const N: usize> SpatialIndex<'id, N> {
+    /// Builds an index of the position of every vertex in `graph`
+    #[must_use]
+    pub fn build<Item, Weight, Edge, S, A>(
+        graph: &Graph<'id, Item, Weight, Edge, S, A>,
+        token: &GhostToken<'id>,
+    ) -> Self
+    where
+        Item: Position<N>,
+        Edge: EdgeTrait<'id, Item, Weight>,
+        S: BuildHasher,
+        A: Allocator,
+    {
+        let mut points: Vec<_> = graph
+            .items(token)
+            .map(|(id, item)| (id, item.coords()))
+            .collect();
+        points.sort_unstable_by_key(|(id, _)| *id);
+
+        Self::arrange(&mut points, 0);
+        Self { points }
+    }
+    /// Orders `points` into a subtree splitting on the `depth`th axis
+    fn arrange(points: &mut [(VertexId<'id>, [f64; N])], depth: usize) {
+        if points.len() <= 1 || N == 0 {
+            return;
+        }
+
+        let axis = depth % N;
+        let middle = points.len() / 2;
+        points.select_nth_unstable_by(middle, |(_, a), (_, b)| a[axis].total_cmp(&b[axis]));
+
+        let (before, after) = points.split_at_mut(middle);
+        Self::arrange(before, depth + 1);
+        Self::arrange(&mut after[1..], depth + 1);
+    }
+    /// The number of vertices in the index
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.points.len()
+    }
+    /// Whether the index has no vertices
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+    /// The vertex nearest to `point`, along with its distance from
+    /// `point`, or `None` if the index is empty
+    #[must_use]
+    pub fn nearest(&self, point: &[f64; N]) -> Option<(VertexId<'id>, f64)> {
+        self.k_nearest(point, 1).into_iter().next()
+    }
+    /// The `k` vertices nearest to `point`, nearest first, along with
+    /// their distances from `point`
+    ///
+    /// Returns every vertex if there are fewer than `k`
+    #[must_use]
+    pub fn k_nearest(&self, point: &[f64; N], k: usize) -> Vec<(VertexId<'id>, f64)> {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        // The furthest of the nearest points found so far is on top
+        let mut nearest = BinaryHeap::with_capacity(k + 1);
+        let mut stack = vec![(0, self.points.len(), 0)];
+
+        while let Some((start, end, depth)) = stack.pop() {
+            if start >= end {
+                continue;
+            }
+
+            let middle = start + (end - start) / 2;
+            let (id, coords) = self.points[middle];
+            let distance = squared(point, &coords);
+            if nearest.len() < k {
+                nearest.push((Total(distance), id));
+            } else if nearest
+                .peek()
+                .is_some_and(|(furthest, _)| Total(distance) < *furthest)
+            {
+                nearest.pop();
+                nearest.push((Total(distance), id));
+            }
+
+            if N == 0 {
+                continue;
+            }
+            let axis = depth % N;
+            let offset = point[axis] - coords[axis];
+            let (near, far) = if offset < 0.0 {
+                ((start, middle), (middle + 1, end))
+            } else {
+                ((middle + 1, end), (start, middle))
+            };
+
+            // The far side can only hold nearer points if the
+            // splitting plane is nearer than the furthest found
+            if nearest.len() < k
+                || nearest
+                    .peek()
+                    .is_some_and(|(Total(furthest), _)| offset * offset < *furthest)
+            {
+                stack.push((far.0, far.1, depth + 1));
+            }
+            stack.push((near.0, near.1, depth + 1));
+        }
+
+        nearest
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(Total(distance), id)| (id, libm::sqrt(distance)))
+            .collect()
+    }
+    /// Every vertex within `radius` of `point`, in no particular order,
+    /// along with their distances from `point`
+    #[must_use]
+    pub fn within(&self, point: &[f64; N], radius: f64) -> Vec<(VertexId<'id>, f64)> {
+        let mut found = Vec::new();
+        let mut stack = vec![(0, self.points.len(), 0)];
+        let limit = radius * radius;
+
+        while let Some((start, end, depth)) = stack.pop() {
+            if start >= end {
+                continue;
+            }
+
+            let middle = start + (end - start) / 2;
+            let (id, coords) = self.points[middle];
+            let distance = squared(point, &coords);
+            if distance <= limit {
+                found.push((id, libm::sqrt(distance)));
+            }
+
+            if N == 0 {
+                continue;
+            }
+            let axis = depth % N;
+            let offset = point[axis] - coords[axis];
+            if offset <= radius {
+                stack.push((start, middle, depth + 1));
+            }
+            if -offset <= radius {
+                stack.push((middle + 1, end, depth + 1));
+            }
+        }
+
+        found
+    }
+}
+
+/// Finds the shortest path from `source` to `target` using A*, guided
+/// by the straight line distance from each vertex to `target`
+///
+/// `length` is given an edge's weight and returns the length of the
+/// edge, which must never be less than the straight line distance
+/// between the vertices it joins, or a shorter path may be missed.
+/// Returns `None` if there's no path from `source` to `target`
+///
+/// # Errors
+/// Returns [`GraphError::VertexNotFound`] if either `source` or
+/// `target` isn't within the graph
+pub fn astar_euclidean<'id, Item, Weight, Edge, S, A, const N: usize>(
+    graph: &Graph<'id, Item, Weight, Edge, S, A>,
+    source: VertexId<'id>,
+    target: VertexId<'id>,
+    length: impl Fn(&Weight) -> f64,
+    token: &GhostToken<'id>,
+) -> Result<Option<ShortestPath<'id, f64>>, GraphError<'id, Item, Weight, Edge>>
+where
+    Item: Position<N>,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    let coords = |id| {
+        graph
+            .get_vertex(id)
+            .map(|vertex| vertex.borrow(token).get_item().coords())
+            .ok_or(GraphError::VertexNotFound(id))
+    };
+    let (start, goal) = (coords(source)?, coords(target)?);
+
+    let mut distance = graph.vertex_map();
+    let mut previous: SecondaryMap<VertexId<'id>, (VertexId<'id>, EdgeId<'id>)> =
+        graph.vertex_map();
+    distance.insert(source, 0.0);
+
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((
+        Total(euclidean(&start, &goal)),
+        Total(0.0),
+        source,
+    )));
+
+    while let Some(Reverse((_, Total(travelled), vertex))) = queue.pop() {
+        if vertex == target {
+            let mut vertices = vec![target];
+            let mut edges = Vec::new();
+            let mut current = target;
+            while let Some((before, edge)) = previous.get(current) {
+                vertices.push(*before);
+                edges.push(*edge);
+                current = *before;
+            }
+            vertices.reverse();
+            edges.reverse();
+
+            return Ok(Some(ShortestPath {
+                length: travelled,
+                vertices,
+                edges,
+            }));
+        }
+        if distance.get(vertex).is_some_and(|best| *best < travelled) {
+            continue;
+        }
+
+        for (edge, other, weight) in successors(graph, vertex, token) {
+            let through = travelled + length(weight);
+            if distance.get(other).is_none_or(|best| through < *best) {
+                distance.insert(other, through);
+                previous.insert(other, (vertex, edge));
+
+                let remaining = coords(other).map_or(0.0, |at| euclidean(&at, &goal));
+                queue.push(Reverse((Total(through + remaining), Total(through), other)));
+            }
+        }
+    }
+
+    Ok(None)
+}
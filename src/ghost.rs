@@ -3,7 +3,7 @@
 use core::{cell::UnsafeCell, marker::PhantomData};
 
 #[derive(Clone, Copy, Default, Debug, Hash)]
-pub(crate) struct InvariantLifetime<'id>(PhantomData<*mut &'id ()>);
+pub(crate) struct InvariantLifetime<'id>(PhantomData<fn(&'id ()) -> &'id ()>);
 
 impl<'id> InvariantLifetime<'id> {
     pub const fn new() -> Self {
@@ -32,20 +32,50 @@ impl<'id> InvariantLifetime<'id> {
 ///     // ...
 /// })
 /// ```
-#[derive(Default)]
+///
+/// Only one token can exist for each brand, which is what lets tokens
+/// be sent and shared between threads, so there's no way to make one
+/// other than [`GhostToken::new`]
+///
+/// ```compile_fail
+/// use graph::ghost::GhostToken;
+///
+/// let second: GhostToken<'static> = GhostToken::default();
+/// ```
+///
+/// Nor can a token escape the closure given to [`GhostToken::new`],
+/// as the brand it's given is only known within it
+///
+/// ```compile_fail
+/// use graph::ghost::GhostToken;
+///
+/// let first = GhostToken::new(|token| token);
+/// let second = GhostToken::new(|token| token);
+/// ```
 pub struct GhostToken<'id> {
     _marker: InvariantLifetime<'id>,
 }
 
 impl<'id> GhostToken<'id> {
     #[allow(clippy::new_ret_no_self)]
-    pub fn new<'new_id, F, R>(f: F) -> R
-    where
-        F: FnOnce(GhostToken<'new_id>) -> R + 'new_id,
-    {
-        let token = GhostToken::default();
+    pub fn new<R>(f: impl for<'new_id> FnOnce(GhostToken<'new_id>) -> R) -> R {
+        // SAFETY: `f` has to work for any `'new_id`, so it can't name
+        // it, return the token, or mix it with any other brand, which
+        // makes `'new_id` unique to this call
+        let token = unsafe { GhostToken::new_unchecked() };
         f(token)
     }
+    /// Makes a token with the brand `'id`
+    ///
+    /// # Safety
+    /// No other token with the brand `'id` may exist, or ever be made
+    /// while this one is alive, or two threads could each borrow the
+    /// same [`GhostCell`] mutably
+    pub(crate) const unsafe fn new_unchecked() -> Self {
+        Self {
+            _marker: InvariantLifetime::new(),
+        }
+    }
 }
 
 /// A 'lock' that can be unlocked using [`GhostToken`]
@@ -58,6 +88,12 @@ pub struct GhostCell<'id, T> {
     _marker: InvariantLifetime<'id>,
 }
 
+// SAFETY: Sharing a `GhostCell` between threads allows its contents
+// to be borrowed immutably from each of them with a shared token, or
+// mutably from whichever holds the token uniquely, as for `RwLock`.
+// This relies on there only ever being one token for each brand
+unsafe impl<'id, T: Send + Sync> Sync for GhostCell<'id, T> {}
+
 impl<'id, T> GhostCell<'id, T> {
     /// Creates a new [`GhostCell`] from a
     /// given value
@@ -68,7 +104,7 @@ impl<'id, T> GhostCell<'id, T> {
         }
     }
     /// Gets a mutable value
-    pub fn get_mut(&mut self) -> &mut T {
+    pub const fn get_mut(&mut self) -> &mut T {
         self.value.get_mut()
     }
     /// Unwraps the value
@@ -79,14 +115,14 @@ impl<'id, T> GhostCell<'id, T> {
     /// with the guarantee it's not being accessed mutably
     /// elsewhere by the fact the token must be immutably borrowed
     /// for the entirety of the time it's contents is borrowed
-    pub const fn g_borrow<'a>(&'a self, _token: &'a GhostToken<'id>) -> &'a T {
+    pub fn g_borrow<'a>(&'a self, _token: &'a GhostToken<'id>) -> &'a T {
         unsafe { &*self.value.get() }
     }
     /// Mutably borrows the [`GhostCell`]'s contents,
     /// guaranteeing unique mutably access to it's contents
     /// by the way that a mutable reference to the token
     /// is required
-    pub const fn g_borrow_mut<'a>(&'a self, _token: &'a mut GhostToken<'id>) -> &'a mut T {
+    pub fn g_borrow_mut<'a>(&'a self, _token: &'a mut GhostToken<'id>) -> &'a mut T {
         unsafe { &mut *self.value.get() }
     }
 }
@@ -1,46 +1,102 @@
 use crate::{
-    edge::EdgeTrait, ghost::GhostToken, id::EdgeId, GraphError, Node, Shared, SharedNode, Vertex,
-    VertexId,
+    arena::Arena,
+    clock::Clock,
+    edge::EdgeTrait,
+    ghost::GhostToken,
+    id::EdgeId,
+    observer::{Change, Observer},
+    AllocError, Allocator, Global, GraphError, Node, Shared, SharedNode, Vertex, VertexId,
 };
 
-use hashbrown::HashMap;
+use core::hash::BuildHasher;
+
+use hashbrown::{hash_map::DefaultHashBuilder, HashMap};
 
 /// The overall graph, just a container for [vertices](Vertex)
 ///
 /// # Types
 /// * `'id` - The marker lifetime to indicate which [`GhostToken`] works
-/// with the specific graph's [`GhostToken`]s
+///   with the specific graph's [`GhostToken`]s
 ///
 /// * `Item` - The type that each [`Vertex`] contains
 ///
 /// * `Weight` - The type that each edge between vertices contains
 ///
 /// * `Edge` - The type of edge being used, examples of which are
-/// [`UnDirectedUnWeightedEdge`](crate::edge::UnDirectedUnWeightedEdge)
-/// and [`UnDirectedWeightedEdge`](crate::edge::UnDirectedWeightedEdge)
-pub struct Graph<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> {
-    vertices: HashMap<VertexId<'id>, SharedNode<'id, Item, Weight, Edge>>,
-    pub(crate) edges: HashMap<EdgeId<'id>, Shared<'id, Edge>>,
-    current_vertex_id: usize,
-    current_edge_id: usize,
-    vertex_len: usize,
-    edge_len: usize,
+///   [`UnDirectedUnWeightedEdge`](crate::edge::UnDirectedUnWeightedEdge)
+///   and [`UnDirectedWeightedEdge`](crate::edge::UnDirectedWeightedEdge)
+///
+/// * `S` - The [`BuildHasher`] used by the graph's maps of vertices
+///   and edges, defaulting to hashbrown's
+///
+/// * `A` - The [`Allocator`] the vertices and edges themselves are
+///   allocated from, defaulting to the global allocator
+pub struct Graph<
+    'id,
+    Item,
+    Weight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S = DefaultHashBuilder,
+    A: Allocator = Global,
+> {
+    pub(crate) vertices: HashMap<VertexId<'id>, SharedNode<'id, Item, Weight, Edge>, S>,
+    pub(crate) edges: HashMap<EdgeId<'id>, Shared<'id, Edge>, S>,
+    pub(crate) degrees: HashMap<VertexId<'id>, (usize, usize), S>,
+    vertex_arena: Arena<'id, Vertex<'id, Item, Weight, Edge>, A>,
+    pub(crate) edge_arena: Arena<'id, Edge, A>,
+    pub(crate) current_vertex_id: usize,
+    pub(crate) current_edge_id: usize,
+    /// The graph's place among the graphs made together by
+    /// [`GhostToken::with_graphs`], and how many there are, as each
+    /// only hands out every `siblings`th id
+    sibling: usize,
+    siblings: usize,
+    pub(crate) clock: Option<Clock<'id, S>>,
+    pub(crate) observer: Option<Observer<'id>>,
 }
 
-impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Default
-    for Graph<'id, Item, Weight, Edge>
+impl<
+        'id,
+        Item,
+        Weight,
+        Edge: EdgeTrait<'id, Item, Weight>,
+        S: BuildHasher + Clone + Default,
+        A: Allocator + Clone + Default,
+    > Default for Graph<'id, Item, Weight, Edge, S, A>
 {
     fn default() -> Self {
-        Self::new()
+        Self::with_hasher_in(S::default(), A::default())
     }
 }
 
-impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Drop
-    for Graph<'id, Item, Weight, Edge>
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator> Drop
+    for Graph<'id, Item, Weight, Edge, S, A>
 {
     fn drop(&mut self) {
-        self.vertices.drain().for_each(|(_, s)| unsafe { s.drop() });
-        self.edges.drain().for_each(|(_, s)| unsafe { s.drop() });
+        // SAFETY: Every vertex and edge within the graph is in
+        // exactly one of the maps, and the arenas free all of
+        // their memory in bulk when they're dropped afterwards
+        unsafe {
+            Arena::<_, A>::drop_live(self.vertices.values());
+            Arena::<_, A>::drop_live(self.edges.values());
+        }
+    }
+}
+
+impl<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator> IntoIterator
+    for &'a Graph<'id, Item, Weight, Edge, S, A>
+{
+    type Item = (VertexId<'id>, &'a SharedNode<'id, Item, Weight, Edge>);
+    #[allow(clippy::type_complexity)]
+    type IntoIter = core::iter::Map<
+        hashbrown::hash_map::Iter<'a, VertexId<'id>, SharedNode<'id, Item, Weight, Edge>>,
+        fn(
+            (&'a VertexId<'id>, &'a SharedNode<'id, Item, Weight, Edge>),
+        ) -> (VertexId<'id>, &'a SharedNode<'id, Item, Weight, Edge>),
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vertices.iter().map(|(id, vertex)| (*id, vertex))
     }
 }
 
@@ -48,36 +104,282 @@ impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Graph<'id, Item, Wei
     /// Constructs a new empty graph
     #[must_use]
     pub fn new() -> Self {
+        Self::with_hasher(DefaultHashBuilder::default())
+    }
+    /// Constructs a new empty graph with space for at least
+    /// `vertices` vertices and `edges` edges without reallocating
+    #[must_use]
+    pub fn with_capacity(vertices: usize, edges: usize) -> Self {
+        Self::with_capacity_and_hasher(vertices, edges, DefaultHashBuilder::default())
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher>
+    Graph<'id, Item, Weight, Edge, S>
+{
+    /// Constructs a new empty graph which uses `hash_builder`
+    /// to hash its [`VertexId`]s and [`EdgeId`]s
+    #[must_use]
+    pub fn with_hasher(hash_builder: S) -> Self
+    where
+        S: Clone,
+    {
+        Self::with_hasher_in(hash_builder, Global)
+    }
+    /// Constructs a new empty graph which uses `hash_builder`, with
+    /// space for at least `vertices` vertices and `edges` edges
+    #[must_use]
+    pub fn with_capacity_and_hasher(vertices: usize, edges: usize, hash_builder: S) -> Self
+    where
+        S: Clone,
+    {
+        Self::with_capacity_and_hasher_in(vertices, edges, hash_builder, Global)
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, A: Allocator + Clone>
+    Graph<'id, Item, Weight, Edge, DefaultHashBuilder, A>
+{
+    /// Constructs a new empty graph whose vertices and edges are
+    /// allocated from `alloc`
+    #[must_use]
+    pub fn new_in(alloc: A) -> Self {
+        Self::with_hasher_in(DefaultHashBuilder::default(), alloc)
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Constructs a new empty graph which uses `hash_builder` to
+    /// hash its [`VertexId`]s and [`EdgeId`]s, and whose vertices
+    /// and edges are allocated from `alloc`
+    #[must_use]
+    pub fn with_hasher_in(hash_builder: S, alloc: A) -> Self
+    where
+        S: Clone,
+        A: Clone,
+    {
         Self {
-            vertices: HashMap::new(),
-            edges: HashMap::new(),
+            vertices: HashMap::with_hasher(hash_builder.clone()),
+            edges: HashMap::with_hasher(hash_builder.clone()),
+            degrees: HashMap::with_hasher(hash_builder),
+            vertex_arena: Arena::new_in(alloc.clone()),
+            edge_arena: Arena::new_in(alloc),
             current_vertex_id: 0,
             current_edge_id: 0,
-            vertex_len: 0,
-            edge_len: 0,
+            sibling: 0,
+            siblings: 1,
+            clock: None,
+            observer: None,
         }
     }
+    /// Constructs a new empty graph which uses `hash_builder` and
+    /// allocates from `alloc`, with space for at least `vertices`
+    /// vertices and `edges` edges
+    #[must_use]
+    pub fn with_capacity_and_hasher_in(
+        vertices: usize,
+        edges: usize,
+        hash_builder: S,
+        alloc: A,
+    ) -> Self
+    where
+        S: Clone,
+        A: Clone,
+    {
+        let mut graph = Self::with_hasher_in(hash_builder, alloc);
+        graph.reserve_vertices(vertices);
+        graph.reserve_edges(edges);
+        graph
+    }
+    /// Returns a reference to the allocator the graph's vertices
+    /// and edges are allocated from
+    #[must_use]
+    pub const fn allocator(&self) -> &A {
+        self.vertex_arena.allocator()
+    }
+    /// Returns a reference to the graph's [`BuildHasher`]
+    #[must_use]
+    pub fn hasher(&self) -> &S {
+        self.vertices.hasher()
+    }
+    /// Reserves space for at least `additional` more vertices
+    pub fn reserve_vertices(&mut self, additional: usize) {
+        self.vertices.reserve(additional);
+        self.degrees.reserve(additional);
+        self.vertex_arena.reserve(additional);
+    }
+    /// Reserves space for at least `additional` more edges
+    pub fn reserve_edges(&mut self, additional: usize) {
+        self.edges.reserve(additional);
+        self.edge_arena.reserve(additional);
+    }
+    /// Reserves space for at least `additional` more vertices, or
+    /// returns an error if the memory can't be allocated
+    ///
+    /// # Errors
+    /// Returns an [`AllocError`] if any allocation fails, in which
+    /// case some of the space may still have been reserved
+    pub fn try_reserve_vertices(&mut self, additional: usize) -> Result<(), AllocError> {
+        self.vertices
+            .try_reserve(additional)
+            .and_then(|()| self.degrees.try_reserve(additional))
+            .map_err(|_| AllocError)?;
+        self.vertex_arena.try_reserve(additional)?;
+        self.clock
+            .as_mut()
+            .map_or(Ok(()), |clock| clock.try_reserve(additional, 0))
+    }
+    /// Reserves space for at least `additional` more edges, or
+    /// returns an error if the memory can't be allocated
+    ///
+    /// # Errors
+    /// Returns an [`AllocError`] if any allocation fails, in which
+    /// case some of the space may still have been reserved
+    pub fn try_reserve_edges(&mut self, additional: usize) -> Result<(), AllocError> {
+        self.edges.try_reserve(additional).map_err(|_| AllocError)?;
+        self.edge_arena.try_reserve(additional)?;
+        self.clock
+            .as_mut()
+            .map_or(Ok(()), |clock| clock.try_reserve(0, additional))
+    }
+    /// Shrinks the memory used by the graph as much as possible
+    ///
+    /// Memory used by removed vertices and edges may still be
+    /// kept, if other vertices or edges were allocated alongside
+    /// them
+    pub fn shrink_to_fit(&mut self) {
+        self.vertices.shrink_to_fit();
+        self.edges.shrink_to_fit();
+        self.degrees.shrink_to_fit();
+        self.vertex_arena.shrink_to_fit();
+        self.edge_arena.shrink_to_fit();
+    }
+    /// The number of vertices the graph can hold without
+    /// reallocating
+    #[must_use]
+    pub fn vertex_capacity(&self) -> usize {
+        self.vertices.capacity().min(self.vertex_arena.capacity())
+    }
+    /// The number of edges the graph can hold without
+    /// reallocating
+    #[must_use]
+    pub fn edge_capacity(&self) -> usize {
+        self.edges.capacity().min(self.edge_arena.capacity())
+    }
     /// Adds a vertex with no edges, and returns the [`VertexId`] of the
     /// created vertex
     pub fn add_vertex(&mut self, item: Item) -> VertexId<'id> {
         let id = self.new_vertex_id();
-        let vertex = Vertex::new(id, item);
-        self.vertex_len += 1;
-        self.vertices.insert(id, Shared::new(vertex));
+        self.insert_vertex(id, item);
         id
     }
+    /// Adds a vertex with no edges, and returns the [`VertexId`] of the
+    /// created vertex, without aborting if memory runs out
+    ///
+    /// Any memory the vertex needs is reserved before the graph is
+    /// changed. A [`GraphObserver`](crate::GraphObserver) watching the graph may still
+    /// allocate when it's told about the vertex
+    ///
+    /// # Errors
+    /// Returns an [`AllocError`] if the memory for the vertex can't
+    /// be allocated, in which case the graph is unchanged
+    pub fn try_add_vertex(&mut self, item: Item) -> Result<VertexId<'id>, AllocError> {
+        self.try_reserve_vertices(1)?;
+        Ok(self.add_vertex(item))
+    }
+    /// Adds a vertex with no edges under the given `id`, which
+    /// mustn't already be within the graph
+    pub(crate) fn insert_vertex(&mut self, id: VertexId<'id>, item: Item) {
+        debug_assert!(!self.vertices.contains_key(&id));
+
+        let vertex = Vertex::new(id, item);
+        self.vertices.insert(id, self.vertex_arena.alloc(vertex));
+        self.degrees.insert(id, (0, 0));
+        self.current_vertex_id = self.current_vertex_id.max(self.id_after(id.id()));
+        self.notify(Change::VertexAdded(id));
+    }
     /// Adds all the vertices in the iterator provided
     pub fn add_vertices(&mut self, vertices: impl Iterator<Item = Item>) {
         vertices.for_each(|v| {
             self.add_vertex(v);
         });
     }
+    /// Constructs a graph from an adjacency matrix, where `matrix[i][j]`
+    /// holds the weight of the edge from vertex `i` to vertex `j`, if
+    /// there is one
+    ///
+    /// The vertex for row `i` holds the `i`th item and has the
+    /// [`VertexId`] `i`. For undirected edges only the upper triangle
+    /// of the matrix is read
+    ///
+    /// # Errors
+    /// Returns a [`GraphError`] if any edge can't be added to the
+    /// graph, such as an edge from a vertex to itself
+    pub fn from_adjacency_matrix<const N: usize>(
+        matrix: &[[Option<Weight>; N]; N],
+        items: [Item; N],
+        token: &mut GhostToken<'id>,
+    ) -> Result<Self, GraphError<'id, Item, Weight, Edge>>
+    where
+        Weight: Clone,
+        S: Clone + Default,
+        A: Clone + Default,
+    {
+        Self::from_adjacency_slice(matrix, items, token)
+    }
+    /// Constructs a graph from an adjacency matrix of any size, see
+    /// [`Graph::from_adjacency_matrix`]
+    ///
+    /// # Errors
+    /// Returns a [`GraphError`] if any edge can't be added to the
+    /// graph, such as an edge from a vertex to itself
+    ///
+    /// # Panics
+    /// Panics if `matrix` isn't square, or there isn't exactly one
+    /// item for every row
+    pub fn from_adjacency_slice<Row: AsRef<[Option<Weight>]>>(
+        matrix: &[Row],
+        items: impl IntoIterator<Item = Item>,
+        token: &mut GhostToken<'id>,
+    ) -> Result<Self, GraphError<'id, Item, Weight, Edge>>
+    where
+        Weight: Clone,
+        S: Clone + Default,
+        A: Clone + Default,
+    {
+        let mut graph =
+            Self::with_capacity_and_hasher_in(matrix.len(), 0, S::default(), A::default());
+
+        graph.add_vertices(items.into_iter());
+        assert_eq!(
+            graph.vertex_len(),
+            matrix.len(),
+            "one item is needed per row"
+        );
+
+        for (row, weights) in matrix.iter().enumerate() {
+            let weights = weights.as_ref();
+            assert_eq!(weights.len(), matrix.len(), "the matrix must be square");
+
+            let start = if Edge::DIRECTED { 0 } else { row };
+            for (column, weight) in weights.iter().enumerate().skip(start) {
+                if let Some(weight) = weight {
+                    let (from, to) = (VertexId::new(row), VertexId::new(column));
+                    graph.add_edge(from, to, weight.clone(), token)?;
+                }
+            }
+        }
+
+        Ok(graph)
+    }
     /// Adds an edge between the `id_one` and the `id_two`
     /// with the given weight
     ///
-    /// The internal edge count will still be incremented,
-    /// even if the method fails
-    /// 
+    /// Two vertices can only be joined by one undirected edge, or
+    /// one directed edge in each direction
+    ///
     /// # Errors
     /// If `id_one` is the same as `id_two`, or either
     /// id doesn't exist within the graph, a [`GraphError`] will
@@ -89,11 +391,61 @@ impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Graph<'id, Item, Wei
         weight: Weight,
         token: &mut GhostToken<'id>,
     ) -> Result<EdgeId<'id>, GraphError<'id, Item, Weight, Edge>> {
-        let id = self.new_edge_id();
+        self.insert_edge(
+            EdgeId::new(self.current_edge_id),
+            id_one,
+            id_two,
+            weight,
+            token,
+        )
+    }
+    /// Adds an edge between `id_one` and `id_two`, as
+    /// [`Graph::add_edge`] does, without aborting if memory runs out
+    ///
+    /// Any memory the edge needs is reserved before the graph is
+    /// changed. A [`GraphObserver`](crate::GraphObserver) watching the graph may still
+    /// allocate when it's told about the edge
+    ///
+    /// # Errors
+    /// Returns [`GraphError::AllocationFailed`] if the memory for the
+    /// edge can't be allocated, or any error [`Graph::add_edge`]
+    /// does, in which case no edge is added
+    pub fn try_add_edge(
+        &mut self,
+        id_one: VertexId<'id>,
+        id_two: VertexId<'id>,
+        weight: Weight,
+        token: &mut GhostToken<'id>,
+    ) -> Result<EdgeId<'id>, GraphError<'id, Item, Weight, Edge>> {
+        self.try_reserve_edges(1)
+            .map_err(GraphError::AllocationFailed)?;
+        for id in [id_one, id_two] {
+            if let Some(vertex) = self.vertices.get(&id) {
+                vertex
+                    .borrow_mut(token)
+                    .edges
+                    .try_reserve(1)
+                    .map_err(|_| GraphError::AllocationFailed(AllocError))?;
+            }
+        }
+
+        self.add_edge(id_one, id_two, weight, token)
+    }
+    /// Adds an edge between `id_one` and `id_two` under the given
+    /// `id`, which mustn't already be within the graph
+    pub(crate) fn insert_edge(
+        &mut self,
+        id: EdgeId<'id>,
+        id_one: VertexId<'id>,
+        id_two: VertexId<'id>,
+        weight: Weight,
+        token: &mut GhostToken<'id>,
+    ) -> Result<EdgeId<'id>, GraphError<'id, Item, Weight, Edge>> {
+        debug_assert!(!self.edges.contains_key(&id));
 
         if id_one == id_two {
             Err(GraphError::IdenticalVertex(id_one))
-        } else if self.adjacent(id_one, id_two, token)? {
+        } else if self.edge_id_between(id_one, id_two, true, token)?.is_some() {
             Err(GraphError::AlreadyEdgeBetween)
         } else {
             let first = self
@@ -110,10 +462,53 @@ impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Graph<'id, Item, Wei
 
             Edge::add_edge(weight, &first, &second, id, self, token)
                 .map_err(GraphError::AddEdgeError)?;
-            self.edge_len += 1;
+            self.current_edge_id = self.current_edge_id.max(self.id_after(id.id));
+            self.link_degrees(id_one, id_two);
+            self.notify(Change::EdgeAdded(id, id_one, id_two));
             Ok(id)
         }
     }
+    /// Adds a directed edge from `id_one` to `id_two` with the weight
+    /// `weight_one_two`, and one from `id_two` to `id_one` with the
+    /// weight `weight_two_one`, returning both of their [`EdgeId`]s
+    ///
+    /// Either both edges are added or neither is. If adding the second
+    /// edge fails, the first is removed again, so an observer sees it
+    /// being added and then removed, and its id isn't handed out again
+    ///
+    /// # Errors
+    /// If the graph's edges are undirected,
+    /// [`GraphError::RequiresDirectedEdges`] is returned. If `id_one`
+    /// is the same as `id_two`, either id doesn't exist within the
+    /// graph, or there's already an edge in either direction, a
+    /// [`GraphError`] will be returned
+    pub fn add_edge_bidirectional(
+        &mut self,
+        id_one: VertexId<'id>,
+        id_two: VertexId<'id>,
+        weight_one_two: Weight,
+        weight_two_one: Weight,
+        token: &mut GhostToken<'id>,
+    ) -> Result<(EdgeId<'id>, EdgeId<'id>), GraphError<'id, Item, Weight, Edge>> {
+        if id_one == id_two {
+            return Err(GraphError::IdenticalVertex(id_one));
+        }
+        if !Edge::DIRECTED {
+            return Err(GraphError::RequiresDirectedEdges);
+        }
+        if self.edge_id_between(id_two, id_one, true, token)?.is_some() {
+            return Err(GraphError::AlreadyEdgeBetween);
+        }
+
+        let first = self.add_edge(id_one, id_two, weight_one_two, token)?;
+        match self.add_edge(id_two, id_one, weight_two_one, token) {
+            Ok(second) => Ok((first, second)),
+            Err(error) => {
+                self.remove_edge_between(id_one, id_two, token)?;
+                Err(error)
+            }
+        }
+    }
     /// Creates an edge between `id_one` and `id_two`
     /// if it doesn't already exist, and if it does,
     /// changes it to the result of `weight`
@@ -139,7 +534,7 @@ impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Graph<'id, Item, Wei
         ) -> Weight,
         token: &mut GhostToken<'id>,
     ) -> Result<EdgeId<'id>, GraphError<'id, Item, Weight, Edge>> {
-        use GraphError::{AddEdgeError, IdenticalVertex, VertexNotFound};
+        use GraphError::{IdenticalVertex, VertexNotFound};
 
         if id_one == id_two {
             Err(IdenticalVertex(id_one))
@@ -156,14 +551,7 @@ impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Graph<'id, Item, Wei
                 .ok_or(VertexNotFound(id_two))?
                 .clone_shared();
 
-            let mut edge_id = None;
-
-            for id in vertex_one.borrow(token).edges.keys() {
-                if vertex_two.borrow(token).edges.contains_key(id) {
-                    edge_id = Some(*id);
-                    break;
-                }
-            }
+            let edge_id = self.edge_id_between(id_one, id_two, true, token)?;
 
             let ghost_one = vertex_one.ghost();
             let ghost_two = vertex_two.ghost();
@@ -174,47 +562,387 @@ impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Graph<'id, Item, Wei
                 // SAFETY: It's guranteed that the id is within vertex_one's edges
                 let vertex_one = unsafe { vertex_one.unwrap_unchecked() }.clone_shared();
                 *vertex_one.borrow_mut(token).get_weight_mut() = weight;
+                self.notify(Change::EdgeChanged(id));
 
                 Ok(id)
             } else {
-                let id = self.new_edge_id();
-                Edge::add_edge(weight, &vertex_one, &vertex_two, id, self, token)
-                    .map_err(AddEdgeError)?;
-                self.edge_len += 1;
-                Ok(id)
+                self.insert_edge(
+                    EdgeId::new(self.current_edge_id),
+                    id_one,
+                    id_two,
+                    weight,
+                    token,
+                )
+            }
+        }
+    }
+    /// Splits the vertex `id` into two vertices, dividing its
+    /// edges between them
+    ///
+    /// Edges for which `partition` returns `true` stay with the
+    /// first vertex, which keeps `id`, and the rest are moved to
+    /// the second, newly created, vertex. `make_items` is given the
+    /// original vertex's item, and returns the items for the first
+    /// and second vertices respectively.
+    ///
+    /// If `connection` is `Some`, an edge with the given weight is
+    /// added from the first vertex to the second.
+    ///
+    /// Returns the [`VertexId`]s of the first and second vertices
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if `id` is not found
+    /// within the graph, or a [`GraphError`] if adding the connecting
+    /// edge fails. The connecting edge is added last, so if it fails
+    /// the vertex stays split, without the edge between its halves
+    pub fn split_vertex(
+        &mut self,
+        id: VertexId<'id>,
+        partition: impl Fn(&Edge) -> bool,
+        make_items: impl FnOnce(Item) -> (Item, Item),
+        connection: Option<Weight>,
+        token: &mut GhostToken<'id>,
+    ) -> Result<(VertexId<'id>, VertexId<'id>), GraphError<'id, Item, Weight, Edge>> {
+        use GraphError::VertexNotFound;
+
+        let old = self.vertices.get(&id).ok_or(VertexNotFound(id))?;
+
+        // Decide where every edge goes whilst the original
+        // vertex is still intact
+        let stays: alloc::vec::Vec<(EdgeId<'id>, bool)> = old
+            .borrow(token)
+            .edges
+            .iter()
+            .map(|(e_id, edge)| (*e_id, partition(edge.borrow(token))))
+            .collect();
+
+        let old = self.vertices.remove(&id).ok_or(VertexNotFound(id))?;
+        let old_ref = old.clone_shared();
+        // SAFETY: `old_ref` is only used for address comparisons
+        // after this point, and every edge pointing to it is
+        // reconnected below, which by `EdgeTrait`'s safety contract
+        // leaves none of them pointing to it
+        let Vertex {
+            mut edges, item, ..
+        } = unsafe { self.vertex_arena.take(old) };
+
+        let (item_one, item_two) = make_items(item);
+
+        let second_id = self.new_vertex_id();
+        let first = self.vertex_arena.alloc(Vertex::new(id, item_one));
+        let second = self.vertex_arena.alloc(Vertex::new(second_id, item_two));
+
+        for (e_id, stays) in stays {
+            // The ids were gotten from the vertex's own edges
+            if let Some(edge) = edges.remove(&e_id) {
+                let target = if stays { &first } else { &second };
+                let target_id = if stays { id } else { second_id };
+                edge.borrow_mut(token)
+                    .reconnect(&old_ref, target.clone_shared(), target_id);
+                target.borrow_mut(token).edges.insert(e_id, edge);
+                if !stays {
+                    self.notify(Change::EdgeChanged(e_id));
+                }
             }
         }
+
+        self.vertices.insert(id, first);
+        self.vertices.insert(second_id, second);
+        self.recount_degrees(id, token);
+        self.recount_degrees(second_id, token);
+        self.notify(Change::VertexChanged(id));
+        self.notify(Change::VertexAdded(second_id));
+
+        if let Some(weight) = connection {
+            self.insert_edge(
+                EdgeId::new(self.current_edge_id),
+                id,
+                second_id,
+                weight,
+                token,
+            )?;
+        }
+
+        Ok((id, second_id))
+    }
+    /// Contracts the edge `id`, merging the two vertices it joins
+    /// into one and returning its [`VertexId`]
+    ///
+    /// The vertex the edge leads from, or its first vertex if it's
+    /// undirected, is kept, and `merge_items` is given its item along
+    /// with the other vertex's item to merge into it. Every edge of
+    /// the other vertex is moved to the kept vertex, unless the kept
+    /// vertex already has an edge to the same vertex in the same
+    /// direction, in which case `merge_weights` is given that edge's
+    /// weight along with the moved edge's weight to merge into it,
+    /// and the moved edge is removed. A directed edge leading the
+    /// opposite way to `id` is removed along with it.
+    ///
+    /// # Errors
+    /// Returns [`GraphError::EdgeNotFound`] if `id` is not found
+    /// within the graph
+    pub fn contract_edge(
+        &mut self,
+        id: EdgeId<'id>,
+        merge_items: impl FnOnce(&mut Item, Item),
+        mut merge_weights: impl FnMut(&mut Weight, &Weight),
+        token: &mut GhostToken<'id>,
+    ) -> Result<VertexId<'id>, GraphError<'id, Item, Weight, Edge>> {
+        use GraphError::{EdgeNotFound, VertexNotFound};
+
+        let (keep, gone) = self.endpoints(id, token).ok_or(EdgeNotFound(id))?;
+        self.remove_edge(id, token)?;
+        if let Some(back) = self.edge_id_between(gone, keep, false, token)? {
+            self.remove_edge(back, token)?;
+        }
+
+        let kept = self.vertices.get(&keep).ok_or(VertexNotFound(keep))?;
+        let kept = kept.clone_shared();
+        let merged = self.vertices.get(&gone).ok_or(VertexNotFound(gone))?;
+        let merged = merged.clone_shared();
+
+        // Whether each edge leads away from the merged vertex is
+        // decided whilst its edges are all intact
+        let moving: alloc::vec::Vec<(EdgeId<'id>, VertexId<'id>, bool)> = merged
+            .borrow(token)
+            .edges
+            .iter()
+            .filter_map(|(e_id, edge)| {
+                let edge = edge.borrow(token);
+                let other = edge.other(gone)?;
+                Some((*e_id, other, edge.traverse(gone).is_some()))
+            })
+            .collect();
+
+        for (e_id, other, outgoing) in moving {
+            let (from, to) = if outgoing {
+                (keep, other)
+            } else {
+                (other, keep)
+            };
+
+            // The ids were gotten from the vertex's own edges
+            let Some(edge) = merged.borrow_mut(token).edges.remove(&e_id) else {
+                continue;
+            };
+
+            if let Some(existing) = self.edge_id_between(from, to, true, token)? {
+                if let Some(other) = self.vertices.get(&other) {
+                    other.borrow_mut(token).edges.remove(&e_id);
+                }
+                self.unlink_degrees(from, to);
+                self.edges.remove(&e_id);
+
+                // SAFETY: No pointers to the edge remain in the graph
+                let duplicate = unsafe { self.edge_arena.take(edge) };
+                let existing_edge = self.edges[&existing].clone_shared();
+                merge_weights(
+                    existing_edge.borrow_mut(token).get_weight_mut(),
+                    duplicate.get_weight(),
+                );
+
+                self.notify(Change::EdgeRemoved(e_id));
+                self.notify(Change::EdgeChanged(existing));
+            } else {
+                edge.borrow_mut(token)
+                    .reconnect(&merged, kept.clone_shared(), keep);
+                kept.borrow_mut(token).edges.insert(e_id, edge);
+                self.notify(Change::EdgeChanged(e_id));
+            }
+        }
+
+        let merged = self.vertices.remove(&gone).ok_or(VertexNotFound(gone))?;
+        // SAFETY: Every edge of the vertex has been removed, or moved
+        // to the kept vertex with `reconnect`, which by `EdgeTrait`'s
+        // safety contract leaves no pointers to it
+        let Vertex { item, .. } = unsafe { self.vertex_arena.take(merged) };
+        merge_items(kept.borrow_mut(token).get_item_mut(), item);
+
+        self.degrees.remove(&gone);
+        self.recount_degrees(keep, token);
+        self.notify(Change::VertexRemoved(gone));
+        self.notify(Change::VertexChanged(keep));
+
+        Ok(keep)
     }
     /// Empties self
+    ///
+    /// The memory used by the graph is kept, so refilling the graph
+    /// with a similar number of vertices and edges won't allocate
+    ///
+    /// Edges whose weights don't need dropping are released in bulk,
+    /// without visiting each one. Every vertex owns a map of its
+    /// edges though, so clearing or dropping a graph still drops its
+    /// vertices one by one, freeing one map for each
     pub fn clear(&mut self) {
-        *self = Self::new();
+        // SAFETY: Every vertex and edge within the graph is in
+        // exactly one of the maps, which are emptied alongside
+        // the arenas
+        unsafe {
+            self.vertex_arena
+                .clear(self.vertices.drain().map(|(_, vertex)| vertex));
+            self.edge_arena
+                .clear(self.edges.drain().map(|(_, edge)| edge));
+        }
+        self.degrees.clear();
+        self.notify(Change::Cleared);
+
+        self.current_vertex_id = self.sibling;
+        self.current_edge_id = self.sibling;
+    }
+    /// Consumes the graph, returning the item of every vertex
+    /// along with its [`VertexId`], ordered by id
+    ///
+    /// Every edge is dropped, and all of the graph's memory
+    /// is deallocated
+    #[must_use]
+    pub fn into_items(
+        mut self,
+        _token: &mut GhostToken<'id>,
+    ) -> alloc::vec::Vec<(VertexId<'id>, Item)> {
+        let arena = &mut self.vertex_arena;
+        let mut items: alloc::vec::Vec<_> = self
+            .vertices
+            .drain()
+            .map(|(id, vertex)| {
+                // SAFETY: The graph is consumed, so nothing else can
+                // point to the vertex, and the vertex has been
+                // removed from the map so it won't be dropped again
+                let vertex = unsafe { arena.take(vertex) };
+                (id, vertex.item)
+            })
+            .collect();
+
+        // SAFETY: Every edge within the graph is within the map,
+        // which is emptied alongside the arena
+        unsafe {
+            self.edge_arena
+                .clear(self.edges.drain().map(|(_, edge)| edge));
+        }
+
+        items.sort_unstable_by_key(|(id, _)| *id);
+        items
     }
     /// The number of [`vertices`](Vertex) in the graph
     #[must_use]
     pub fn vertex_len(&self) -> usize {
-        self.vertex_len
+        self.vertices.len()
     }
     /// The number of `edges` in the graph
     #[must_use]
     pub fn edge_len(&self) -> usize {
-        self.edge_len
+        self.edges.len()
     }
     /// If there are no [`vertices`](Vertex) in the graph
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.vertex_len == 0
+        self.vertices.is_empty()
+    }
+    /// The number of edges connected to `id`, or `None` if `id`
+    /// does not exist within the graph
+    ///
+    /// Degrees are kept up to date as the graph changes, so no
+    /// [`GhostToken`] is needed to read them
+    #[must_use]
+    pub fn degree(&self, id: VertexId<'id>) -> Option<usize> {
+        let (outgoing, incoming) = self.degrees.get(&id)?;
+        Some(if Edge::DIRECTED {
+            outgoing + incoming
+        } else {
+            *outgoing
+        })
+    }
+    /// The number of edges leading to `id`, or `None` if `id` does
+    /// not exist within the graph
+    ///
+    /// For undirected edges this is the same as [`Graph::degree`]
+    #[must_use]
+    pub fn in_degree(&self, id: VertexId<'id>) -> Option<usize> {
+        self.degrees.get(&id).map(|(_, incoming)| *incoming)
+    }
+    /// The number of edges leading from `id`, or `None` if `id` does
+    /// not exist within the graph
+    ///
+    /// For undirected edges this is the same as [`Graph::degree`]
+    #[must_use]
+    pub fn out_degree(&self, id: VertexId<'id>) -> Option<usize> {
+        self.degrees.get(&id).map(|(outgoing, _)| *outgoing)
+    }
+    /// Counts a new edge leading from `from` to `to`
+    pub(crate) fn link_degrees(&mut self, from: VertexId<'id>, to: VertexId<'id>) {
+        self.shift_degrees(from, to, |count| *count += 1);
+    }
+    /// Uncounts a removed edge that lead from `from` to `to`
+    pub(crate) fn unlink_degrees(&mut self, from: VertexId<'id>, to: VertexId<'id>) {
+        self.shift_degrees(from, to, |count| *count -= 1);
+    }
+    /// Applies `shift` to the degrees an edge from `from` to `to` counts towards
+    fn shift_degrees(&mut self, from: VertexId<'id>, to: VertexId<'id>, shift: fn(&mut usize)) {
+        if let Some((outgoing, incoming)) = self.degrees.get_mut(&from) {
+            shift(outgoing);
+            if !Edge::DIRECTED {
+                shift(incoming);
+            }
+        }
+        if let Some((outgoing, incoming)) = self.degrees.get_mut(&to) {
+            shift(incoming);
+            if !Edge::DIRECTED {
+                shift(outgoing);
+            }
+        }
+    }
+    /// Counts the degrees of `id` again from its edges
+    pub(crate) fn recount_degrees(&mut self, id: VertexId<'id>, token: &GhostToken<'id>) {
+        let Some(vertex) = self.vertices.get(&id) else {
+            return;
+        };
+
+        let vertex = vertex.borrow(token);
+        let total = vertex.edges.len();
+        let outgoing = if Edge::DIRECTED {
+            vertex
+                .edges
+                .values()
+                .filter(|edge| edge.borrow(token).traverse(id).is_some())
+                .count()
+        } else {
+            total
+        };
+        let incoming = if Edge::DIRECTED {
+            total - outgoing
+        } else {
+            total
+        };
+
+        self.degrees.insert(id, (outgoing, incoming));
     }
     /// Gets a new id for a new [`Vertex`]
-    fn new_vertex_id(&mut self) -> VertexId<'id> {
+    const fn new_vertex_id(&mut self) -> VertexId<'id> {
         let id = VertexId::new(self.current_vertex_id);
-        self.current_vertex_id += 1;
+        self.current_vertex_id += self.siblings;
         id
     }
-    /// Gets a new id for a new [`Edge`]
-    fn new_edge_id(&mut self) -> EdgeId<'id> {
-        let id = EdgeId::new(self.current_edge_id);
-        self.current_edge_id += 1;
-        id
+    /// The first id after `id` the graph can hand out, which is
+    /// simply the next one unless it has siblings
+    ///
+    /// Saturates rather than overflowing, so that an id near
+    /// [`usize::MAX`] can't panic, though ids read from outside the
+    /// graph should be rejected before they get that high
+    pub(crate) const fn id_after(&self, id: usize) -> usize {
+        let next = id.saturating_add(1);
+        next.saturating_add((self.sibling + self.siblings - next % self.siblings) % self.siblings)
+    }
+    /// Makes the graph the `sibling`th of `siblings` graphs sharing a
+    /// brand, which take turns handing out ids so that an id of one
+    /// is never found within another
+    pub(crate) fn into_sibling(mut self, sibling: usize, siblings: usize) -> Self {
+        debug_assert!(self.is_empty() && sibling < siblings);
+
+        self.sibling = sibling;
+        self.siblings = siblings;
+        self.current_vertex_id = sibling;
+        self.current_edge_id = sibling;
+        self
     }
     /// Attempts to get a vertex using a given [`VertexId`]
     /// # Errors
@@ -235,6 +963,18 @@ impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Graph<'id, Item, Wei
     pub fn get_edge(&self, id: EdgeId<'id>) -> Option<&Shared<'id, Edge>> {
         self.edges.get(&id)
     }
+    /// Returns the [`VertexId`]s of both vertices the edge `id` connects,
+    /// with the vertex the edge leads from first if it's directed
+    ///
+    /// Returns `None` if `id` does not exist within the graph
+    #[must_use]
+    pub fn endpoints(
+        &self,
+        id: EdgeId<'id>,
+        token: &GhostToken<'id>,
+    ) -> Option<(VertexId<'id>, VertexId<'id>)> {
+        Some(self.edges.get(&id)?.borrow(token).endpoints())
+    }
     /// Returns an immutable iterator over the
     /// graph's nodes
     #[must_use]
@@ -243,6 +983,168 @@ impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Graph<'id, Item, Wei
     ) -> hashbrown::hash_map::Iter<'_, VertexId<'id>, SharedNode<'id, Item, Weight, Edge>> {
         self.vertices.iter()
     }
+    /// Returns an iterator over every vertex in the graph,
+    /// along with its [`VertexId`]
+    pub fn iter(&self) -> <&Self as IntoIterator>::IntoIter {
+        self.into_iter()
+    }
+    /// Returns an iterator over the [`VertexId`] of every
+    /// vertex in the graph
+    pub fn vertex_ids(&self) -> impl Iterator<Item = VertexId<'id>> + '_ {
+        self.vertices.keys().copied()
+    }
+    /// Returns an iterator over the [`EdgeId`] of every
+    /// edge in the graph
+    pub fn edge_ids(&self) -> impl Iterator<Item = EdgeId<'id>> + '_ {
+        self.edges.keys().copied()
+    }
+    /// Returns an iterator over the item of every vertex
+    /// in the graph, along with its [`VertexId`]
+    pub fn items<'a>(
+        &'a self,
+        token: &'a GhostToken<'id>,
+    ) -> impl Iterator<Item = (VertexId<'id>, &'a Item)> + 'a {
+        self.vertices
+            .iter()
+            .map(move |(id, vertex)| (*id, vertex.borrow(token).get_item()))
+    }
+    /// Returns an iterator over mutable references to the item
+    /// of every vertex in the graph, along with its [`VertexId`]
+    pub fn items_mut<'a>(
+        &'a self,
+        _token: &'a mut GhostToken<'id>,
+    ) -> impl Iterator<Item = (VertexId<'id>, &'a mut Item)> + 'a {
+        self.vertices.iter().map(|(id, vertex)| {
+            // SAFETY: The token is borrowed mutably for as long as the
+            // iterator lives, so nothing else can access the vertices,
+            // and each vertex is yielded at most once
+            let vertex = unsafe { &mut *vertex.as_ptr().as_ptr() };
+            (*id, vertex.get_mut().get_item_mut())
+        })
+    }
+    /// Returns an iterator over the weight of every edge in the
+    /// graph, along with its [`EdgeId`]
+    ///
+    /// Each edge is yielded once, even if it's undirected
+    pub fn weights<'a>(
+        &'a self,
+        token: &'a GhostToken<'id>,
+    ) -> impl Iterator<Item = (EdgeId<'id>, &'a Weight)> + 'a {
+        self.edges
+            .iter()
+            .map(move |(id, edge)| (*id, edge.borrow(token).get_weight()))
+    }
+    /// Returns an iterator over mutable references to the weight
+    /// of every edge in the graph, along with its [`EdgeId`]
+    ///
+    /// Each edge is yielded once, even if it's undirected, so every
+    /// weight can be updated without visiting it from both ends
+    pub fn weights_mut<'a>(
+        &'a self,
+        _token: &'a mut GhostToken<'id>,
+    ) -> impl Iterator<Item = (EdgeId<'id>, &'a mut Weight)> + 'a {
+        self.edges.iter().map(|(id, edge)| {
+            // SAFETY: The token is borrowed mutably for as long as the
+            // iterator lives, so nothing else can access the edges,
+            // and each edge is yielded at most once
+            let edge = unsafe { &mut *edge.as_ptr().as_ptr() };
+            (*id, edge.get_mut().get_weight_mut())
+        })
+    }
+    /// Returns an iterator over every edge in the graph, each
+    /// yielded exactly once along with the [`VertexId`]s of its
+    /// endpoints and its weight
+    ///
+    /// Directed edges give the vertex they lead from first. Walking
+    /// the edges of every vertex instead sees each undirected edge
+    /// from both of its ends
+    pub fn unique_edges<'a>(
+        &'a self,
+        token: &'a GhostToken<'id>,
+    ) -> impl Iterator<Item = (EdgeId<'id>, VertexId<'id>, VertexId<'id>, &'a Weight)> + 'a {
+        self.edges.iter().map(move |(id, edge)| {
+            let edge = edge.borrow(token);
+            let (first, second) = edge.endpoints();
+            (*id, first, second, edge.get_weight())
+        })
+    }
+    /// Returns the [`VertexId`] of the vertex whose item satisfies
+    /// `predicate`, or `None` if no item does
+    ///
+    /// Where several items do, the one with the lowest id is given
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<&str, u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let london = graph.add_vertex("London");
+    ///     let paris = graph.add_vertex("Paris");
+    ///     let tunnel = graph.add_edge(london, paris, 50, &mut token).unwrap();
+    ///
+    ///     assert_eq!(graph.find_vertex(|city| city.starts_with('P'), &token), Some(paris));
+    ///     assert_eq!(graph.find_vertex(|city| city.is_empty(), &token), None);
+    ///     assert_eq!(graph.find_edge(|length| *length > 10, &token), Some(tunnel));
+    /// })
+    /// ```
+    #[must_use]
+    pub fn find_vertex(
+        &self,
+        mut predicate: impl FnMut(&Item) -> bool,
+        token: &GhostToken<'id>,
+    ) -> Option<VertexId<'id>> {
+        self.items(token)
+            .filter(|(_, item)| predicate(item))
+            .map(|(id, _)| id)
+            .min()
+    }
+    /// Returns the [`VertexId`]s of every vertex whose item satisfies
+    /// `predicate`, in order
+    #[must_use]
+    pub fn vertex_positions(
+        &self,
+        mut predicate: impl FnMut(&Item) -> bool,
+        token: &GhostToken<'id>,
+    ) -> alloc::vec::Vec<VertexId<'id>> {
+        let mut found: alloc::vec::Vec<_> = self
+            .items(token)
+            .filter(|(_, item)| predicate(item))
+            .map(|(id, _)| id)
+            .collect();
+        found.sort_unstable();
+        found
+    }
+    /// Returns the [`EdgeId`] of the edge whose weight satisfies
+    /// `predicate`, or `None` if no weight does
+    ///
+    /// Where several weights do, the one with the lowest id is given
+    #[must_use]
+    pub fn find_edge(
+        &self,
+        mut predicate: impl FnMut(&Weight) -> bool,
+        token: &GhostToken<'id>,
+    ) -> Option<EdgeId<'id>> {
+        self.weights(token)
+            .filter(|(_, weight)| predicate(weight))
+            .map(|(id, _)| id)
+            .min()
+    }
+    /// Returns the [`EdgeId`]s of every edge whose weight satisfies
+    /// `predicate`, in order
+    #[must_use]
+    pub fn edge_positions(
+        &self,
+        mut predicate: impl FnMut(&Weight) -> bool,
+        token: &GhostToken<'id>,
+    ) -> alloc::vec::Vec<EdgeId<'id>> {
+        let mut found: alloc::vec::Vec<_> = self
+            .weights(token)
+            .filter(|(_, weight)| predicate(weight))
+            .map(|(id, _)| id)
+            .collect();
+        found.sort_unstable();
+        found
+    }
     /// Attempts to remove a [`Vertex`] from the graph, removing all edges to and
     /// from the [`Vertex`]
     /// # Errors
@@ -252,6 +1154,17 @@ impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Graph<'id, Item, Wei
         id: VertexId<'id>,
         token: &mut GhostToken<'id>,
     ) -> Result<(), GraphError<'id, Item, Weight, Edge>> {
+        self.remove_take(id, token).map(drop)
+    }
+    /// Attempts to remove a [`Vertex`] from the graph, removing all edges to and
+    /// from the [`Vertex`], and returns the item the [`Vertex`] contained
+    /// # Errors
+    /// Returns a [`GraphError`] if `id` is not found within the graph
+    pub fn remove_take(
+        &mut self,
+        id: VertexId<'id>,
+        token: &mut GhostToken<'id>,
+    ) -> Result<Item, GraphError<'id, Item, Weight, Edge>> {
         use GraphError::{EdgeNotFound, VertexNotFound};
 
         let to_remove = self.vertices.remove(&id).ok_or(VertexNotFound(id))?;
@@ -266,37 +1179,139 @@ impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Graph<'id, Item, Wei
             let one = to_remove.borrow_mut(token);
             let one = one.edges.remove(&e_id);
 
-            // SAFETY: Each key will be removed only once,
-            // and is guranteed to be within to_remove, as
-            // the keys were gotten from to_remove
-            // let one = unsafe { one.unwrap_unchecked() };
-            let one = one.unwrap();
+            // Each key will be removed only once, and is
+            // guranteed to be within to_remove, as the keys
+            // were gotten from to_remove
+            let one = one.ok_or(EdgeNotFound(e_id))?;
 
             // Finds the other vertex in the edge
-            let two = one
-                .borrow(token)
-                .other(id, token)
-                .ok_or(VertexNotFound(id))?
-                .clone_shared();
+            let (first, second) = one.borrow(token).endpoint_nodes();
+            let two = match one.borrow(token).endpoints() {
+                (first_id, _) if first_id == id => second,
+                (_, second_id) if second_id == id => first,
+                _ => return Err(VertexNotFound(id)),
+            }
+            .clone_shared();
             let two = two.borrow_mut(token);
 
             // Removes the edge from the other vertex's edges
             two.edges.remove(&e_id).ok_or(EdgeNotFound(e_id))?;
 
+            if let Some((from, to)) = self.endpoints(e_id, token) {
+                self.unlink_degrees(from, to);
+            }
+
             let edge = self.edges.remove(&e_id).ok_or(EdgeNotFound(e_id))?;
+            self.notify(Change::EdgeRemoved(e_id));
 
             // SAFETY: No pointers to the edge can exist any more
-            unsafe { edge.drop() };
+            unsafe { self.edge_arena.free(edge) };
         }
 
-        unsafe { to_remove.drop() }
+        // SAFETY: Every edge to the vertex has been removed, so
+        // no pointers to it remain
+        let vertex = unsafe { self.vertex_arena.take(to_remove) };
+        self.degrees.remove(&id);
+        self.notify(Change::VertexRemoved(id));
 
-        self.vertex_len -= 1;
+        Ok(vertex.item)
+    }
+    /// Moves the vertex `id` to the sibling graph `to`, which shares
+    /// the graph's brand, returning its [`VertexId`] within `to`
+    ///
+    /// The vertex's edges can't follow it, so are removed. Graphs made
+    /// by [`GhostToken::with_graphs`] never share ids, so passing an
+    /// id of `to`, or of any other sibling, is caught as a missing
+    /// vertex
+    ///
+    /// # Errors
+    /// Returns a [`GraphError`] if `id` is not found within the graph
+    pub fn move_vertex<S2: BuildHasher, A2: Allocator>(
+        &mut self,
+        id: VertexId<'id>,
+        to: &mut Graph<'id, Item, Weight, Edge, S2, A2>,
+        token: &mut GhostToken<'id>,
+    ) -> Result<VertexId<'id>, GraphError<'id, Item, Weight, Edge>> {
+        let item = self.remove_take(id, token)?;
+        Ok(to.add_vertex(item))
+    }
+    /// Moves every vertex in `ids` to the sibling graph `to`, which
+    /// shares the graph's brand, along with the edges between them,
+    /// returning the [`VertexId`] each has within `to` in the same
+    /// order
+    ///
+    /// Edges to vertices that aren't moved are removed. The weights
+    /// of the edges that are moved are cloned, as edges can't be
+    /// taken apart
+    ///
+    /// # Errors
+    /// Returns [`GraphError::VertexNotFound`] if any vertex in `ids`
+    /// isn't within the graph, or [`GraphError::IdenticalVertex`] if
+    /// any appears twice, in which case nothing is moved.
+    ///
+    /// Every edge is re-added between vertices new to `to`, so only
+    /// an edge type whose [`EdgeTrait::add_edge`] can fail by itself
+    /// can make it fail, with [`GraphError::AddEdgeError`]. By then
+    /// every vertex has been moved and has lost its edges within the
+    /// graph, and only the edges re-added before the one that failed
+    /// are within `to`
+    pub fn move_vertices<S2: BuildHasher, A2: Allocator>(
+        &mut self,
+        ids: &[VertexId<'id>],
+        to: &mut Graph<'id, Item, Weight, Edge, S2, A2>,
+        token: &mut GhostToken<'id>,
+    ) -> Result<alloc::vec::Vec<VertexId<'id>>, GraphError<'id, Item, Weight, Edge>>
+    where
+        Weight: Clone,
+    {
+        let mut moved: HashMap<VertexId<'id>, Option<VertexId<'id>>> =
+            HashMap::with_capacity(ids.len());
+        for id in ids {
+            if !self.vertices.contains_key(id) {
+                return Err(GraphError::VertexNotFound(*id));
+            }
+            if moved.insert(*id, None).is_some() {
+                return Err(GraphError::IdenticalVertex(*id));
+            }
+        }
+
+        let mut edges = alloc::vec::Vec::new();
+        let mut seen = hashbrown::HashSet::new();
+        for id in ids {
+            let vertex = self.vertices[id].borrow(token);
+            for (e_id, edge) in &vertex.edges {
+                if !seen.insert(*e_id) {
+                    continue;
+                }
+                let Some((from, to)) = self.endpoints(*e_id, token) else {
+                    continue;
+                };
+                if moved.contains_key(&from) && moved.contains_key(&to) {
+                    edges.push((from, to, edge.borrow(token).get_weight().clone()));
+                }
+            }
+        }
+
+        let mut new_ids = alloc::vec::Vec::with_capacity(ids.len());
+        for id in ids {
+            let new_id = self.move_vertex(*id, to, token)?;
+            moved.insert(*id, Some(new_id));
+            new_ids.push(new_id);
+        }
 
-        Ok(())
+        for (from, other, weight) in edges {
+            if let (Some(Some(from)), Some(Some(other))) = (moved.get(&from), moved.get(&other)) {
+                to.add_edge(*from, *other, weight, token)?;
+            }
+        }
+
+        Ok(new_ids)
     }
     /// Removes the edge between `id_one` and `id_two`
     ///
+    /// If they're joined by a directed edge in each direction, the
+    /// one from `id_one` to `id_two` is removed
+    ///
     /// # Errors
     /// If there is no edge between `id_one` and `id_two`,
     /// [`GraphError::NoEdgeBetween`] will be returned.
@@ -309,30 +1324,9 @@ impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Graph<'id, Item, Wei
         id_two: VertexId<'id>,
         token: &mut GhostToken<'id>,
     ) -> Result<(), GraphError<'id, Item, Weight, Edge>> {
-        use GraphError::{NoEdgeBetween, VertexNotFound};
-
-        // Finds the `edge_id` of the edge between
-        // `id_one` and `id_two` - remains `None` if
-        // there is no edge between them
-        let mut edge_id = None;
+        use GraphError::{EdgeNotFound, NoEdgeBetween, VertexNotFound};
 
-        let vertex_one = self
-            .vertices
-            .get(&id_one)
-            .ok_or(VertexNotFound(id_one))?
-            .borrow(token);
-
-        if let Some(vertex_two) = self.vertices.get(&id_two) {
-            let second = vertex_two.borrow(token);
-            for id in vertex_one.edges.keys() {
-                if second.edges.contains_key(id) {
-                    edge_id = Some(*id);
-                    break;
-                }
-            }
-        } else {
-            return Err(VertexNotFound(id_two));
-        }
+        let edge_id = self.edge_id_between(id_one, id_two, false, token)?;
 
         if let Some(edge_id) = edge_id {
             // Actually remove the edges
@@ -350,10 +1344,15 @@ impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Graph<'id, Item, Wei
                 .edges
                 .remove(&edge_id);
 
-            let edge = self.edges.remove(&edge_id).unwrap();
+            if let Some((from, to)) = self.endpoints(edge_id, token) {
+                self.unlink_degrees(from, to);
+            }
+
+            let edge = self.edges.remove(&edge_id).ok_or(EdgeNotFound(edge_id))?;
+            self.notify(Change::EdgeRemoved(edge_id));
 
             // SAFETY: No pointers to the edge can exist any more
-            unsafe { edge.drop() };
+            unsafe { self.edge_arena.free(edge) };
 
             Ok(())
         } else {
@@ -361,6 +1360,44 @@ impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Graph<'id, Item, Wei
             Err(NoEdgeBetween)
         }
     }
+    /// Finds the edge between `id_one` and `id_two`, preferring one
+    /// that leads from `id_one` to `id_two`
+    ///
+    /// If `exact`, a directed edge leading from `id_two` to `id_one`
+    /// isn't returned
+    pub(crate) fn edge_id_between(
+        &self,
+        id_one: VertexId<'id>,
+        id_two: VertexId<'id>,
+        exact: bool,
+        token: &GhostToken<'id>,
+    ) -> Result<Option<EdgeId<'id>>, GraphError<'id, Item, Weight, Edge>> {
+        use GraphError::VertexNotFound;
+
+        let vertex_one = self
+            .vertices
+            .get(&id_one)
+            .ok_or(VertexNotFound(id_one))?
+            .borrow(token);
+        let vertex_two = self
+            .vertices
+            .get(&id_two)
+            .ok_or(VertexNotFound(id_two))?
+            .borrow(token);
+
+        let mut reversed = None;
+
+        for (id, edge) in &vertex_one.edges {
+            if vertex_two.edges.contains_key(id) {
+                if edge.borrow(token).traverse(id_one).is_some() {
+                    return Ok(Some(*id));
+                }
+                reversed = Some(*id);
+            }
+        }
+
+        Ok(if exact { None } else { reversed })
+    }
     /// Returns whether `id_one` and `id_two` have an edge
     /// connecting them
     /// # Errors
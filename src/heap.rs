@@ -0,0 +1,201 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{measure::Total, SecondaryMap, VertexId};
+
+/// A binary min-heap of vertices, each with a priority, which can
+/// find any vertex within it to change its priority
+///
+/// Each vertex is in the heap at most once, so lowering the priority
+/// of a vertex with [`IndexedHeap::decrease_key`] moves it rather than
+/// adding it again, which keeps the heap no larger than the number of
+/// vertices, as Dijkstra's algorithm needs. Vertices with equal, or
+/// incomparable, priorities come out lowest id first.
+///
+/// Where each vertex is within the heap is kept in a [`SecondaryMap`],
+/// so vertices with ids far beyond the number of vertices in the heap
+/// don't make it set aside space for every id below them
+///
+/// ```rust
+/// use graph::{IndexedHeap, VertexId};
+///
+/// let (a, b, c) = (VertexId::new(0), VertexId::new(1), VertexId::new(2));
+///
+/// let mut heap = IndexedHeap::new();
+/// heap.push(a, 5);
+/// heap.push(b, 3);
+/// heap.push(c, 8);
+///
+/// assert!(heap.decrease_key(c, 1));
+/// assert!(!heap.decrease_key(b, 4));
+///
+/// assert_eq!(heap.pop(), Some((c, 1)));
+/// assert_eq!(heap.pop(), Some((b, 3)));
+/// assert_eq!(heap.pop(), Some((a, 5)));
+/// assert_eq!(heap.pop(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IndexedHeap<'id, P> {
+    heap: Vec<(VertexId<'id>, P)>,
+    /// Where each vertex is within `heap`
+    position: SecondaryMap<VertexId<'id>, usize>,
+}
+
+impl<'id, P: PartialOrd> IndexedHeap<'id, P> {
+    /// Constructs an empty heap
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            heap: Vec::new(),
+            position: SecondaryMap::new(),
+        }
+    }
+    /// Constructs an empty heap with space for `capacity` vertices
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            heap: Vec::with_capacity(capacity),
+            position: SecondaryMap::with_capacity(capacity),
+        }
+    }
+    /// Returns the number of vertices in the heap
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.heap.len()
+    }
+    /// Returns `true` if the heap holds no vertices
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+    /// Returns `true` if `id` is in the heap
+    #[must_use]
+    pub fn contains(&self, id: VertexId<'id>) -> bool {
+        self.position.contains_key(id)
+    }
+    /// Returns the priority of `id`, or `None` if it isn't in the heap
+    #[must_use]
+    pub fn priority(&self, id: VertexId<'id>) -> Option<&P> {
+        let i = *self.position.get(id)?;
+        Some(&self.heap[i].1)
+    }
+    /// Returns the vertex with the lowest priority, along with its
+    /// priority, without removing it
+    #[must_use]
+    pub fn peek(&self) -> Option<(VertexId<'id>, &P)> {
+        self.heap.first().map(|(id, priority)| (*id, priority))
+    }
+    /// Adds `id` with `priority`, or changes its priority to
+    /// `priority` if it's already in the heap, returning the priority
+    /// it had before
+    pub fn push(&mut self, id: VertexId<'id>, priority: P) -> Option<P> {
+        if let Some(&i) = self.position.get(id) {
+            let old = core::mem::replace(&mut self.heap[i].1, priority);
+            let i = self.sift_up(i);
+            self.sift_down(i);
+            return Some(old);
+        }
+
+        self.position.insert(id, self.heap.len());
+        self.heap.push((id, priority));
+        self.sift_up(self.heap.len() - 1);
+        None
+    }
+    /// Lowers the priority of `id` to `priority`, adding it if it
+    /// isn't in the heap, returning whether the heap changed
+    ///
+    /// A `priority` no lower than the priority `id` already has
+    /// leaves it as it is
+    pub fn decrease_key(&mut self, id: VertexId<'id>, priority: P) -> bool {
+        let Some(&i) = self.position.get(id) else {
+            self.push(id, priority);
+            return true;
+        };
+        if priority.partial_cmp(&self.heap[i].1) != Some(Ordering::Less) {
+            return false;
+        }
+
+        self.heap[i].1 = priority;
+        self.sift_up(i);
+        true
+    }
+    /// Removes the vertex with the lowest priority, returning it along
+    /// with its priority
+    pub fn pop(&mut self) -> Option<(VertexId<'id>, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        Some(self.take(0))
+    }
+    /// Removes `id`, returning its priority, or `None` if it isn't in
+    /// the heap
+    pub fn remove(&mut self, id: VertexId<'id>) -> Option<P> {
+        let i = *self.position.get(id)?;
+        Some(self.take(i).1)
+    }
+    /// Removes every vertex
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.position.clear();
+    }
+    /// Removes the entry at `i`, filling its place with the last entry
+    fn take(&mut self, i: usize) -> (VertexId<'id>, P) {
+        let last = self.heap.len() - 1;
+        self.swap(i, last);
+        let entry = self.heap.pop().expect("the heap isn't empty");
+        self.position.remove(entry.0);
+
+        if i < self.heap.len() {
+            let i = self.sift_up(i);
+            self.sift_down(i);
+        }
+        entry
+    }
+    /// Whether the entry at `i` should come out before the one at `j`
+    fn before(&self, i: usize, j: usize) -> bool {
+        let ((first, one), (second, two)) = (&self.heap[i], &self.heap[j]);
+        Total(one).cmp(&Total(two)).then(first.cmp(second)) == Ordering::Less
+    }
+    /// Moves the entry at `i` up until it's below an earlier entry,
+    /// returning where it ends up
+    fn sift_up(&mut self, mut i: usize) -> usize {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !self.before(i, parent) {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+        i
+    }
+    /// Moves the entry at `i` down until it's above later entries
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut first = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.heap.len() && self.before(child, first) {
+                    first = child;
+                }
+            }
+            if first == i {
+                break;
+            }
+            self.swap(i, first);
+            i = first;
+        }
+    }
+    /// Swaps the entries at `i` and `j`, keeping their positions
+    /// up to date
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.position.insert(self.heap[i].0, i);
+        self.position.insert(self.heap[j].0, j);
+    }
+}
+
+impl<'id, P: PartialOrd> Default for IndexedHeap<'id, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,345 @@
+use alloc::vec::Vec;
+use core::{hash::BuildHasher, slice};
+
+use hashbrown::{hash_map::DefaultHashBuilder, HashMap};
+
+use crate::{ghost::GhostToken, ops::GraphOps, EdgeId, VertexId};
+
+/// An edge of a [`Hypergraph`], which can join any number of
+/// vertices
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HyperEdge<'id, Weight> {
+    vertices: Vec<VertexId<'id>>,
+    weight: Weight,
+}
+
+impl<'id, Weight> HyperEdge<'id, Weight> {
+    /// The vertices the edge joins, in the order they were given
+    #[must_use]
+    pub fn vertices(&self) -> &[VertexId<'id>] {
+        &self.vertices
+    }
+    /// Whether the edge joins `id`
+    #[must_use]
+    pub fn contains(&self, id: VertexId<'id>) -> bool {
+        self.vertices.contains(&id)
+    }
+    /// Gets a reference to the weight of the edge
+    #[must_use]
+    pub const fn weight(&self) -> &Weight {
+        &self.weight
+    }
+    /// Gets a mutable reference to the weight of the edge
+    pub const fn weight_mut(&mut self) -> &mut Weight {
+        &mut self.weight
+    }
+}
+
+/// A vertex of a [`Hypergraph`], along with the edges that join it
+struct HyperVertex<'id, Item> {
+    item: Item,
+    edges: Vec<EdgeId<'id>>,
+}
+
+/// A graph whose edges can each join any number of vertices, such as
+/// the nets of an electrical circuit
+///
+/// Like [`DenseGraph`](crate::DenseGraph), the contents of a
+/// [`Hypergraph`] are owned directly, so no [`GhostToken`] is needed
+/// to use it. As a [`GraphOps`], every pair of vertices joined by a
+/// hyperedge are neighbors, so algorithms run over the graph's
+/// clique expansion; two vertices joined by several hyperedges are
+/// neighbors once for each of them
+pub struct Hypergraph<'id, Item, Weight, S = DefaultHashBuilder> {
+    vertices: HashMap<VertexId<'id>, HyperVertex<'id, Item>, S>,
+    edges: HashMap<EdgeId<'id>, HyperEdge<'id, Weight>, S>,
+    current_vertex_id: usize,
+    current_edge_id: usize,
+}
+
+impl<'id, Item, Weight> Default for Hypergraph<'id, Item, Weight> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'id, Item, Weight> Hypergraph<'id, Item, Weight> {
+    /// Constructs a new empty hypergraph
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_hasher(DefaultHashBuilder::default())
+    }
+}
+
+impl<'id, Item, Weight, S: BuildHasher> Hypergraph<'id, Item, Weight, S> {
+    /// Constructs a new empty hypergraph which uses `hash_builder`
+    /// to hash its [`VertexId`]s and [`EdgeId`]s
+    #[must_use]
+    pub fn with_hasher(hash_builder: S) -> Self
+    where
+        S: Clone,
+    {
+        Self {
+            vertices: HashMap::with_hasher(hash_builder.clone()),
+            edges: HashMap::with_hasher(hash_builder),
+            current_vertex_id: 0,
+            current_edge_id: 0,
+        }
+    }
+    /// Adds a vertex with no edges, and returns the [`VertexId`] of the
+    /// created vertex
+    pub fn add_vertex(&mut self, item: Item) -> VertexId<'id> {
+        let id = VertexId::new(self.current_vertex_id);
+        self.current_vertex_id += 1;
+
+        self.vertices.insert(
+            id,
+            HyperVertex {
+                item,
+                edges: Vec::new(),
+            },
+        );
+        id
+    }
+    /// Adds an edge joining every vertex in `vertices` with the given
+    /// weight, and returns the [`EdgeId`] of the created edge
+    ///
+    /// Vertices given more than once are only joined once
+    ///
+    /// # Errors
+    /// Returns the first [`VertexId`] in `vertices` that isn't within
+    /// the graph, in which case no edge is added
+    pub fn add_hyperedge(
+        &mut self,
+        vertices: &[VertexId<'id>],
+        weight: Weight,
+    ) -> Result<EdgeId<'id>, VertexId<'id>> {
+        if let Some(missing) = vertices.iter().find(|id| !self.vertices.contains_key(id)) {
+            return Err(*missing);
+        }
+
+        let mut joined = Vec::with_capacity(vertices.len());
+        for id in vertices {
+            if !joined.contains(id) {
+                joined.push(*id);
+            }
+        }
+
+        let id = EdgeId::new(self.current_edge_id);
+        self.current_edge_id += 1;
+
+        for vertex in &joined {
+            if let Some(vertex) = self.vertices.get_mut(vertex) {
+                vertex.edges.push(id);
+            }
+        }
+        self.edges.insert(
+            id,
+            HyperEdge {
+                vertices: joined,
+                weight,
+            },
+        );
+
+        Ok(id)
+    }
+    /// Removes the edge `id`, returning its weight
+    pub fn remove_hyperedge(&mut self, id: EdgeId<'id>) -> Option<Weight> {
+        let edge = self.edges.remove(&id)?;
+
+        for vertex in &edge.vertices {
+            if let Some(vertex) = self.vertices.get_mut(vertex) {
+                vertex.edges.retain(|e_id| *e_id != id);
+            }
+        }
+
+        Some(edge.weight)
+    }
+    /// Removes the vertex `id` from the graph and from every edge
+    /// joining it, returning its item
+    ///
+    /// Edges left joining no vertices are removed too
+    pub fn remove_vertex(&mut self, id: VertexId<'id>) -> Option<Item> {
+        let vertex = self.vertices.remove(&id)?;
+
+        for e_id in vertex.edges {
+            if let Some(edge) = self.edges.get_mut(&e_id) {
+                edge.vertices.retain(|v_id| *v_id != id);
+                if edge.vertices.is_empty() {
+                    self.edges.remove(&e_id);
+                }
+            }
+        }
+
+        Some(vertex.item)
+    }
+    /// Gets a reference to the item within `id`
+    #[must_use]
+    pub fn get_item(&self, id: VertexId<'id>) -> Option<&Item> {
+        self.vertices.get(&id).map(|vertex| &vertex.item)
+    }
+    /// Gets a mutable reference to the item within `id`
+    pub fn get_item_mut(&mut self, id: VertexId<'id>) -> Option<&mut Item> {
+        self.vertices.get_mut(&id).map(|vertex| &mut vertex.item)
+    }
+    /// Gets a reference to the edge `id`
+    #[must_use]
+    pub fn get_hyperedge(&self, id: EdgeId<'id>) -> Option<&HyperEdge<'id, Weight>> {
+        self.edges.get(&id)
+    }
+    /// Gets a mutable reference to the edge `id`
+    pub fn get_hyperedge_mut(&mut self, id: EdgeId<'id>) -> Option<&mut HyperEdge<'id, Weight>> {
+        self.edges.get_mut(&id)
+    }
+    /// The edges joining the vertex `id`, in the order they were
+    /// added, or `None` if `id` isn't within the graph
+    #[must_use]
+    pub fn incident(&self, id: VertexId<'id>) -> Option<&[EdgeId<'id>]> {
+        self.vertices.get(&id).map(|vertex| vertex.edges.as_slice())
+    }
+    /// The vertices sharing at least one edge with `id`, ordered by
+    /// id, or `None` if `id` isn't within the graph
+    #[must_use]
+    pub fn neighbors(&self, id: VertexId<'id>) -> Option<Vec<VertexId<'id>>> {
+        let vertex = self.vertices.get(&id)?;
+
+        let mut neighbors: Vec<_> = vertex
+            .edges
+            .iter()
+            .filter_map(|e_id| self.edges.get(e_id))
+            .flat_map(|edge| edge.vertices.iter().copied())
+            .filter(|other| *other != id)
+            .collect();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+
+        Some(neighbors)
+    }
+    /// Gets an iterator over the [`EdgeId`]s of every edge in the
+    /// graph, in no particular order
+    pub fn edge_ids(&self) -> impl Iterator<Item = EdgeId<'id>> + '_ {
+        self.edges.keys().copied()
+    }
+    /// The number of vertices in the graph
+    #[must_use]
+    pub fn vertex_len(&self) -> usize {
+        self.vertices.len()
+    }
+    /// The number of edges in the graph
+    #[must_use]
+    pub fn edge_len(&self) -> usize {
+        self.edges.len()
+    }
+    /// If there are no vertices in the graph
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+}
+
+/// The iterator over a [`Hypergraph`]'s neighboring vertices,
+/// returned by [`GraphOps::successors`] and [`GraphOps::predecessors`]
+pub struct HyperNeighbors<'a, 'id, Weight, S> {
+    edges: Option<slice::Iter<'a, EdgeId<'id>>>,
+    map: &'a HashMap<EdgeId<'id>, HyperEdge<'id, Weight>, S>,
+    current: Option<(slice::Iter<'a, VertexId<'id>>, &'a Weight)>,
+    id: VertexId<'id>,
+}
+
+impl<'a, 'id, Weight, S: BuildHasher> Iterator for HyperNeighbors<'a, 'id, Weight, S> {
+    type Item = (VertexId<'id>, &'a Weight);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.id;
+
+        loop {
+            if let Some((vertices, weight)) = &mut self.current {
+                if let Some(other) = vertices.find(|other| **other != id) {
+                    return Some((*other, *weight));
+                }
+            }
+
+            let e_id = self.edges.as_mut()?.next()?;
+            self.current = self
+                .map
+                .get(e_id)
+                .map(|edge| (edge.vertices.iter(), &edge.weight));
+        }
+    }
+}
+
+impl<'id, Item, Weight, S: BuildHasher> GraphOps<'id> for Hypergraph<'id, Item, Weight, S> {
+    type Item = Item;
+    type Weight = Weight;
+    type Successors<'a>
+        = HyperNeighbors<'a, 'id, Weight, S>
+    where
+        Self: 'a,
+        'id: 'a;
+    type Predecessors<'a>
+        = HyperNeighbors<'a, 'id, Weight, S>
+    where
+        Self: 'a,
+        'id: 'a;
+
+    fn is_directed(&self) -> bool {
+        false
+    }
+
+    fn vertex_len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn contains(&self, id: VertexId<'id>) -> bool {
+        self.vertices.contains_key(&id)
+    }
+
+    fn vertex_ids(&self) -> Vec<VertexId<'id>> {
+        self.vertices.keys().copied().collect()
+    }
+
+    fn item<'a>(&'a self, id: VertexId<'id>, _token: &'a GhostToken<'id>) -> Option<&'a Item> {
+        self.get_item(id)
+    }
+
+    fn weight_between<'a>(
+        &'a self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        _token: &'a GhostToken<'id>,
+    ) -> Option<&'a Weight> {
+        if from == to {
+            return None;
+        }
+
+        self.vertices
+            .get(&from)?
+            .edges
+            .iter()
+            .filter_map(|e_id| self.edges.get(e_id))
+            .find(|edge| edge.contains(to))
+            .map(|edge| &edge.weight)
+    }
+
+    fn successors<'a>(
+        &'a self,
+        id: VertexId<'id>,
+        _token: &'a GhostToken<'id>,
+    ) -> Self::Successors<'a> {
+        HyperNeighbors {
+            edges: self.vertices.get(&id).map(|vertex| vertex.edges.iter()),
+            map: &self.edges,
+            current: None,
+            id,
+        }
+    }
+
+    fn predecessors<'a>(
+        &'a self,
+        id: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Self::Predecessors<'a> {
+        self.successors(id, token)
+    }
+}
@@ -26,7 +26,7 @@ use crate::ghost::InvariantLifetime;
 ///     assert!(graph.get_vertex(VertexId::new(1)).is_none());
 /// })
 /// ```
-#[derive(Clone, Copy, Hash)]
+#[derive(Clone, Copy)]
 pub struct VertexId<'id> {
     id: usize,
     _marker: InvariantLifetime<'id>,
@@ -56,18 +56,50 @@ impl<'id> PartialEq for VertexId<'id> {
 
 impl<'id> Eq for VertexId<'id> {}
 
+impl<'id> PartialOrd for VertexId<'id> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'id> Ord for VertexId<'id> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl<'id> core::hash::Hash for VertexId<'id> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
 impl<'id> core::fmt::Debug for VertexId<'id> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.id)
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'id> serde::Serialize for VertexId<'id> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.id.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'id> serde::Deserialize<'de> for VertexId<'id> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        usize::deserialize(deserializer).map(Self::new)
+    }
+}
+
 /// The type describing a `Edge`'s index within a
 /// [`Graph`](crate::graph::Graph)
 ///
 /// Seperate from [`VertexId`] so a user doesn't have to
 /// think about the id of their vertices being out of order
-#[derive(Clone, Copy, Hash)]
+#[derive(Clone, Copy)]
 pub struct EdgeId<'id> {
     pub id: usize,
     _marker: InvariantLifetime<'id>,
@@ -92,8 +124,40 @@ impl<'id> PartialEq for EdgeId<'id> {
 
 impl<'id> Eq for EdgeId<'id> {}
 
+impl<'id> PartialOrd for EdgeId<'id> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'id> Ord for EdgeId<'id> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl<'id> core::hash::Hash for EdgeId<'id> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
 impl<'id> core::fmt::Debug for EdgeId<'id> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.id)
     }
 }
+
+#[cfg(feature = "serde")]
+impl<'id> serde::Serialize for EdgeId<'id> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.id.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'id> serde::Deserialize<'de> for EdgeId<'id> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        usize::deserialize(deserializer).map(Self::new)
+    }
+}
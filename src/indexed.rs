@@ -0,0 +1,234 @@
+use alloc::vec::Vec;
+use core::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash},
+    ops::Deref,
+};
+
+use hashbrown::{hash_map::DefaultHashBuilder, HashMap};
+
+use crate::{
+    edge::EdgeTrait, ghost::GhostToken, Allocator, EdgeId, Global, Graph, GraphError, VertexId,
+};
+
+/// A [`Graph`] along with an index from a key of each vertex's item,
+/// such as a name, to the vertex's [`VertexId`]
+///
+/// Created by [`Graph::index_by`], or empty by [`IndexedGraph::new`].
+/// Vertices added and removed through the [`IndexedGraph`] keep the
+/// index up to date, and every method of [`Graph`] that doesn't
+/// change it can be called through [`Deref`]
+///
+/// Only one vertex can be indexed under each key, so vertices whose
+/// items have the same key as one already indexed can't be added
+pub struct IndexedGraph<
+    'id,
+    Item,
+    Weight,
+    Edge,
+    K,
+    F,
+    S = DefaultHashBuilder,
+    A: Allocator = Global,
+> where
+    Edge: EdgeTrait<'id, Item, Weight>,
+{
+    graph: Graph<'id, Item, Weight, Edge, S, A>,
+    index: HashMap<K, VertexId<'id>, S>,
+    key: F,
+}
+
+impl<'id, Item, Weight, Edge, K, F> IndexedGraph<'id, Item, Weight, Edge, K, F>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    K: Hash + Eq,
+    F: Fn(&Item) -> K,
+{
+    /// Constructs a new empty graph, indexing its vertices by `key`
+    #[must_use]
+    pub fn new(key: F) -> Self {
+        Self {
+            graph: Graph::new(),
+            index: HashMap::new(),
+            key,
+        }
+    }
+}
+
+impl<'id, Item, Weight, Edge, K, F, S, A: Allocator> Deref
+    for IndexedGraph<'id, Item, Weight, Edge, K, F, S, A>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+{
+    type Target = Graph<'id, Item, Weight, Edge, S, A>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+impl<'id, Item, Weight, Edge, K, F, S, A: Allocator>
+    IndexedGraph<'id, Item, Weight, Edge, K, F, S, A>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    K: Hash + Eq,
+    F: Fn(&Item) -> K,
+    S: BuildHasher,
+{
+    /// The vertex indexed under `key`, if there is one
+    #[must_use]
+    pub fn find<Q>(&self, key: &Q) -> Option<VertexId<'id>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.get(key).copied()
+    }
+    /// Whether a vertex is indexed under `key`
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.contains_key(key)
+    }
+    /// Adds a vertex holding `item`, indexed under its key, and
+    /// returns the [`VertexId`] of the created vertex
+    ///
+    /// # Errors
+    /// Returns `item` back if a vertex is already indexed under
+    /// its key
+    pub fn add_vertex(&mut self, item: Item) -> Result<VertexId<'id>, Item> {
+        let key = (self.key)(&item);
+        if self.index.contains_key(&key) {
+            return Err(item);
+        }
+
+        let id = self.graph.add_vertex(item);
+        self.index.insert(key, id);
+        Ok(id)
+    }
+    /// Returns the vertex indexed under the key of `item`, adding a
+    /// vertex holding `item` if there isn't one
+    pub fn get_or_add_vertex(&mut self, item: Item) -> VertexId<'id> {
+        let key = (self.key)(&item);
+        if let Some(id) = self.index.get(&key) {
+            return *id;
+        }
+
+        let id = self.graph.add_vertex(item);
+        self.index.insert(key, id);
+        id
+    }
+    /// Adds an edge between `id_one` and `id_two`, as
+    /// [`Graph::add_edge`] does
+    ///
+    /// # Errors
+    /// Returns a [`GraphError`] if the edge can't be added, as
+    /// [`Graph::add_edge`] does
+    pub fn add_edge(
+        &mut self,
+        id_one: VertexId<'id>,
+        id_two: VertexId<'id>,
+        weight: Weight,
+        token: &mut GhostToken<'id>,
+    ) -> Result<EdgeId<'id>, GraphError<'id, Item, Weight, Edge>> {
+        self.graph.add_edge(id_one, id_two, weight, token)
+    }
+    /// Removes the vertex `id` and every edge to it from the graph
+    /// and the index
+    ///
+    /// # Errors
+    /// Returns a [`GraphError`] if `id` isn't within the graph
+    pub fn remove(
+        &mut self,
+        id: VertexId<'id>,
+        token: &mut GhostToken<'id>,
+    ) -> Result<(), GraphError<'id, Item, Weight, Edge>> {
+        self.remove_take(id, token).map(drop)
+    }
+    /// Removes the vertex `id` and every edge to it from the graph
+    /// and the index, returning the vertex's item
+    ///
+    /// # Errors
+    /// Returns a [`GraphError`] if `id` isn't within the graph
+    pub fn remove_take(
+        &mut self,
+        id: VertexId<'id>,
+        token: &mut GhostToken<'id>,
+    ) -> Result<Item, GraphError<'id, Item, Weight, Edge>> {
+        let item = self.graph.remove_take(id, token)?;
+
+        let key = (self.key)(&item);
+        if self.index.get(&key) == Some(&id) {
+            self.index.remove(&key);
+        }
+
+        Ok(item)
+    }
+    /// Rebuilds the index from every vertex's item
+    ///
+    /// The index can't see changes made to items through a
+    /// [`GhostToken`], or to the graph through
+    /// [`IndexedGraph::graph_mut`], so it should be rebuilt
+    /// afterwards. Where several items have the same key, the
+    /// vertex with the lowest id is indexed
+    pub fn reindex(&mut self, token: &GhostToken<'id>) {
+        self.index.clear();
+
+        let mut ids: Vec<_> = self.graph.get_all_vertices().copied().collect();
+        ids.sort_unstable();
+
+        for id in ids {
+            if let Some(vertex) = self.graph.get_vertex(id) {
+                let key = (self.key)(vertex.borrow(token).get_item());
+                self.index.entry(key).or_insert(id);
+            }
+        }
+    }
+    /// The number of keys in the index
+    #[must_use]
+    pub fn index_len(&self) -> usize {
+        self.index.len()
+    }
+    /// The graph, which can be changed freely, after which the
+    /// index should be rebuilt with [`IndexedGraph::reindex`]
+    pub const fn graph_mut(&mut self) -> &mut Graph<'id, Item, Weight, Edge, S, A> {
+        &mut self.graph
+    }
+    /// Consumes the [`IndexedGraph`], returning the graph and
+    /// discarding the index
+    pub fn into_inner(self) -> Graph<'id, Item, Weight, Edge, S, A> {
+        self.graph
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Consumes the graph, returning an [`IndexedGraph`] that can
+    /// find its vertices by the key `key` gives their items
+    ///
+    /// Where several items have the same key, the vertex with the
+    /// lowest id is indexed
+    #[must_use]
+    pub fn index_by<K, F>(
+        self,
+        key: F,
+        token: &GhostToken<'id>,
+    ) -> IndexedGraph<'id, Item, Weight, Edge, K, F, S, A>
+    where
+        K: Hash + Eq,
+        F: Fn(&Item) -> K,
+        S: Clone,
+    {
+        let mut indexed = IndexedGraph {
+            index: HashMap::with_capacity_and_hasher(self.vertex_len(), self.hasher().clone()),
+            graph: self,
+            key,
+        };
+        indexed.reindex(token);
+        indexed
+    }
+}
@@ -0,0 +1,610 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, hash::BuildHasher};
+
+use hashbrown::HashMap;
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, Allocator, Graph, VertexId};
+
+/// Arrays and objects nested any deeper than this are rejected, so
+/// that parsing can't overflow the stack
+const MAX_DEPTH: usize = 128;
+
+/// A JSON value, as written by [`Graph::to_json_node_link`] and read
+/// by [`Graph::from_json_node_link`]
+///
+/// Objects keep the order their fields were written in
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    /// `null`
+    Null,
+    /// `true` or `false`
+    Bool(bool),
+    /// A number, which is written as `null` if it isn't finite
+    Number(f64),
+    /// A string
+    String(String),
+    /// An array of values
+    Array(Vec<Self>),
+    /// An object, as its fields in order
+    Object(Vec<(String, Self)>),
+}
+
+/// An error returned when JSON, or a node-link graph within it,
+/// can't be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonError {
+    /// The text isn't valid JSON, at the byte offset given
+    Syntax(usize),
+    /// The graph is missing a field it needs, such as `nodes`, or the
+    /// `id` of a node
+    MissingField(&'static str),
+    /// The graph is of a directed graph being read as an undirected
+    /// one, or the other way around
+    DirectionMismatch,
+    /// Two nodes have the same id
+    DuplicateNode(usize),
+    /// The item of the node at this index couldn't be read
+    InvalidItem(usize),
+    /// The weight of the link at this index couldn't be read
+    InvalidWeight(usize),
+    /// The link at this index couldn't be added to the graph, because
+    /// one of its nodes is missing or it duplicates another link
+    InvalidLink(usize),
+}
+
+impl JsonValue {
+    /// Parses `text` as a single JSON value
+    ///
+    /// # Errors
+    /// Returns [`JsonError::Syntax`] if `text` isn't valid JSON
+    pub fn parse(text: &str) -> Result<Self, JsonError> {
+        let mut parser = Parser {
+            bytes: text.as_bytes(),
+            position: 0,
+        };
+        let value = parser.value(0)?;
+        parser.whitespace();
+        if parser.position == parser.bytes.len() {
+            Ok(value)
+        } else {
+            Err(JsonError::Syntax(parser.position))
+        }
+    }
+    /// The field `key` of an object, or `None` if `self` isn't an
+    /// object or has no such field
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            Self::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+    /// The value of a boolean
+    #[must_use]
+    pub const fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+    /// The value of a number
+    #[must_use]
+    pub const fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+    /// The value of a number that's a whole, non-negative integer
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::float_cmp
+    )]
+    pub fn as_u64(&self) -> Option<u64> {
+        // 2^64, the first whole number too large for a `u64`
+        const LIMIT: f64 = 18_446_744_073_709_551_616.0;
+
+        self.as_f64()
+            .filter(|n| *n >= 0.0 && *n < LIMIT && libm::trunc(*n) == *n)
+            .map(|n| n as u64)
+    }
+    /// The value of a string
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+    /// The elements of an array
+    #[must_use]
+    pub fn as_array(&self) -> Option<&[Self]> {
+        match self {
+            Self::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {$(
+        impl From<$t> for JsonValue {
+            #[allow(clippy::cast_precision_loss, clippy::cast_lossless)]
+            fn from(value: $t) -> Self {
+                Self::Number(value as f64)
+            }
+        }
+    )*};
+}
+
+impl_from_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+impl From<bool> for JsonValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl<T: Into<Self>> From<Option<T>> for JsonValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+impl<T: Into<Self>> From<Vec<T>> for JsonValue {
+    fn from(values: Vec<T>) -> Self {
+        Self::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Writes `text` as a quoted JSON string
+fn write_string(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in text.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    f.write_str("\"")
+}
+
+/// Writes compact JSON, without any whitespace
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Number(value) if value.is_finite() => write!(f, "{value}"),
+            Self::Null | Self::Number(_) => f.write_str("null"),
+            Self::String(value) => write_string(f, value),
+            Self::Array(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_str("]")
+            }
+            Self::Object(fields) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+/// A recursive descent parser over the bytes of a JSON document
+struct Parser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    const fn error(&self) -> JsonError {
+        JsonError::Syntax(self.position)
+    }
+    fn whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.position += 1;
+        }
+    }
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+    fn expect(&mut self, literal: &str) -> Result<(), JsonError> {
+        if self.bytes[self.position..].starts_with(literal.as_bytes()) {
+            self.position += literal.len();
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+    fn value(&mut self, depth: usize) -> Result<JsonValue, JsonError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error());
+        }
+
+        self.whitespace();
+        match self.peek().ok_or_else(|| self.error())? {
+            b'n' => self.expect("null").map(|()| JsonValue::Null),
+            b't' => self.expect("true").map(|()| JsonValue::Bool(true)),
+            b'f' => self.expect("false").map(|()| JsonValue::Bool(false)),
+            b'"' => self.string().map(JsonValue::String),
+            b'[' => {
+                self.position += 1;
+                let mut values = Vec::new();
+                self.whitespace();
+                if self.peek() == Some(b']') {
+                    self.position += 1;
+                    return Ok(JsonValue::Array(values));
+                }
+                loop {
+                    values.push(self.value(depth + 1)?);
+                    self.whitespace();
+                    match self.peek() {
+                        Some(b',') => self.position += 1,
+                        Some(b']') => {
+                            self.position += 1;
+                            return Ok(JsonValue::Array(values));
+                        }
+                        _ => return Err(self.error()),
+                    }
+                }
+            }
+            b'{' => {
+                self.position += 1;
+                let mut fields = Vec::new();
+                self.whitespace();
+                if self.peek() == Some(b'}') {
+                    self.position += 1;
+                    return Ok(JsonValue::Object(fields));
+                }
+                loop {
+                    self.whitespace();
+                    if self.peek() != Some(b'"') {
+                        return Err(self.error());
+                    }
+                    let key = self.string()?;
+                    self.whitespace();
+                    self.expect(":")?;
+                    fields.push((key, self.value(depth + 1)?));
+                    self.whitespace();
+                    match self.peek() {
+                        Some(b',') => self.position += 1,
+                        Some(b'}') => {
+                            self.position += 1;
+                            return Ok(JsonValue::Object(fields));
+                        }
+                        _ => return Err(self.error()),
+                    }
+                }
+            }
+            b'-' | b'0'..=b'9' => self.number(),
+            _ => Err(self.error()),
+        }
+    }
+    fn number(&mut self) -> Result<JsonValue, JsonError> {
+        let start = self.position;
+        let digits = |parser: &mut Self| {
+            let from = parser.position;
+            while matches!(parser.peek(), Some(b'0'..=b'9')) {
+                parser.position += 1;
+            }
+            parser.position > from
+        };
+
+        if self.peek() == Some(b'-') {
+            self.position += 1;
+        }
+        // Numbers can't start with a zero unless they're below one
+        if self.peek() == Some(b'0') {
+            self.position += 1;
+        } else if !digits(self) {
+            return Err(self.error());
+        }
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            if !digits(self) {
+                return Err(self.error());
+            }
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.position += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.position += 1;
+            }
+            if !digits(self) {
+                return Err(self.error());
+            }
+        }
+
+        core::str::from_utf8(&self.bytes[start..self.position])
+            .ok()
+            .and_then(|text| text.parse().ok())
+            .map(JsonValue::Number)
+            .ok_or(JsonError::Syntax(start))
+    }
+    fn hex(&mut self) -> Result<u32, JsonError> {
+        let digits = self
+            .bytes
+            .get(self.position..self.position + 4)
+            .and_then(|digits| core::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| self.error())?;
+        self.position += 4;
+        Ok(digits)
+    }
+    fn string(&mut self) -> Result<String, JsonError> {
+        // Skip the opening quote
+        self.position += 1;
+        let mut text = String::new();
+
+        loop {
+            let start = self.position;
+            while !matches!(self.peek(), Some(b'"' | b'\\') | None) {
+                if self.bytes[self.position] < 0x20 {
+                    return Err(self.error());
+                }
+                self.position += 1;
+            }
+            // The input is a `str`, and quotes and backslashes are
+            // never part of a longer character, so this can't fail
+            text.push_str(
+                core::str::from_utf8(&self.bytes[start..self.position])
+                    .map_err(|_| self.error())?,
+            );
+
+            match self.peek() {
+                Some(b'"') => {
+                    self.position += 1;
+                    return Ok(text);
+                }
+                Some(b'\\') => {
+                    self.position += 1;
+                    let escape = self.peek().ok_or_else(|| self.error())?;
+                    self.position += 1;
+                    let c = match escape {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let mut code = self.hex()?;
+                            // Characters outside the basic plane are
+                            // written as a pair of surrogates
+                            if (0xD800..0xDC00).contains(&code) {
+                                self.expect("\\u")?;
+                                let low = self.hex()?;
+                                if !(0xDC00..0xE000).contains(&low) {
+                                    return Err(self.error());
+                                }
+                                code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                            }
+                            char::from_u32(code).ok_or_else(|| self.error())?
+                        }
+                        _ => return Err(self.error()),
+                    };
+                    text.push(c);
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+}
+
+/// Moves the fields of `value` into `fields` if it's an object, or
+/// adds it under `key` otherwise
+fn merge(fields: &mut Vec<(String, JsonValue)>, key: &str, value: JsonValue) {
+    match value {
+        JsonValue::Object(more) => fields.extend(more),
+        value => fields.push((key.to_string(), value)),
+    }
+}
+
+/// The fields of `value` other than `skip`, as an object
+fn attributes(value: &JsonValue, skip: &[&str]) -> JsonValue {
+    match value {
+        JsonValue::Object(fields) => JsonValue::Object(
+            fields
+                .iter()
+                .filter(|(key, _)| !skip.contains(&key.as_str()))
+                .cloned()
+                .collect(),
+        ),
+        _ => JsonValue::Object(Vec::new()),
+    }
+}
+
+impl<'id, Item, Weight, Edge, S, A> Graph<'id, Item, Weight, Edge, S, A>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// Writes the graph as JSON in node-link form, as read by d3.js
+    /// and networkx's `node_link_graph`
+    ///
+    /// Every node is written with its [`VertexId`] as its `id`, and
+    /// every link with the ids of its vertices as its `source` and
+    /// `target`. `item` and `weight` turn each item and weight into
+    /// JSON. If they give an object its fields are added to the node
+    /// or link, otherwise the value is added as its `item` or
+    /// `weight` field
+    ///
+    /// ```rust
+    /// use graph::{edge::DirectedWeightedEdge, ghost::GhostToken, json::JsonValue, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<&str, u32, DirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let a = graph.add_vertex("a");
+    ///     let b = graph.add_vertex("b");
+    ///     graph.add_edge(a, b, 7, &mut token).unwrap();
+    ///
+    ///     let json = graph.to_json_node_link(
+    ///         |item| JsonValue::Object(vec![("label".to_string(), (*item).into())]),
+    ///         |weight| (*weight).into(),
+    ///         &token,
+    ///     );
+    ///     assert_eq!(
+    ///         json,
+    ///         r#"{"directed":true,"multigraph":false,"graph":{},"nodes":[{"id":0,"label":"a"},{"id":1,"label":"b"}],"links":[{"source":0,"target":1,"weight":7}]}"#
+    ///     );
+    /// })
+    /// ```
+    #[must_use]
+    pub fn to_json_node_link(
+        &self,
+        item: impl Fn(&Item) -> JsonValue,
+        weight: impl Fn(&Weight) -> JsonValue,
+        token: &GhostToken<'id>,
+    ) -> String {
+        let mut items: Vec<_> = self.items(token).collect();
+        items.sort_unstable_by_key(|(id, _)| *id);
+        let nodes = items
+            .into_iter()
+            .map(|(id, value)| {
+                let mut fields = alloc::vec![("id".to_string(), id.id().into())];
+                merge(&mut fields, "item", item(value));
+                JsonValue::Object(fields)
+            })
+            .collect();
+
+        let mut edges: Vec<_> = self.unique_edges(token).collect();
+        edges.sort_unstable_by_key(|(id, ..)| *id);
+        let links = edges
+            .into_iter()
+            .map(|(_, from, to, value)| {
+                let mut fields = alloc::vec![
+                    ("source".to_string(), from.id().into()),
+                    ("target".to_string(), to.id().into()),
+                ];
+                merge(&mut fields, "weight", weight(value));
+                JsonValue::Object(fields)
+            })
+            .collect();
+
+        JsonValue::Object(alloc::vec![
+            ("directed".to_string(), Edge::DIRECTED.into()),
+            ("multigraph".to_string(), false.into()),
+            ("graph".to_string(), JsonValue::Object(Vec::new())),
+            ("nodes".to_string(), JsonValue::Array(nodes)),
+            ("links".to_string(), JsonValue::Array(links)),
+        ])
+        .to_string()
+    }
+    /// Reads a graph from JSON in node-link form, such as that written
+    /// by [`Graph::to_json_node_link`] or networkx's `node_link_data`
+    ///
+    /// `item` and `weight` are given the fields of each node and link,
+    /// other than its `id`, or `source` and `target`, as an object,
+    /// and return its item or weight. Links may also be given under
+    /// `edges` rather than `links`.
+    ///
+    /// Whatever the nodes' ids are, numbers or names, the vertices are
+    /// numbered from zero in the order their nodes appear, so a graph
+    /// read back after [`Graph::to_json_node_link`] keeps its ids only
+    /// if they had no gaps
+    ///
+    /// # Errors
+    /// Returns a [`JsonError`] if `json` isn't valid JSON, isn't a
+    /// graph in node-link form, or is of a graph whose edges have a
+    /// different direction, or if an item or weight can't be read
+    pub fn from_json_node_link(
+        json: &str,
+        mut item: impl FnMut(&JsonValue) -> Option<Item>,
+        mut weight: impl FnMut(&JsonValue) -> Option<Weight>,
+        token: &mut GhostToken<'id>,
+    ) -> Result<Self, JsonError>
+    where
+        S: Clone + Default,
+        A: Clone + Default,
+    {
+        let json = JsonValue::parse(json)?;
+        if json
+            .get("directed")
+            .and_then(JsonValue::as_bool)
+            .is_some_and(|directed| directed != Edge::DIRECTED)
+        {
+            return Err(JsonError::DirectionMismatch);
+        }
+
+        let nodes = json
+            .get("nodes")
+            .and_then(JsonValue::as_array)
+            .ok_or(JsonError::MissingField("nodes"))?;
+        let links = json
+            .get("links")
+            .or_else(|| json.get("edges"))
+            .map_or(Some(&[][..]), JsonValue::as_array)
+            .ok_or(JsonError::MissingField("links"))?;
+
+        let mut graph =
+            Self::with_capacity_and_hasher_in(nodes.len(), links.len(), S::default(), A::default());
+        // Node ids are matched by the text of their JSON, since they
+        // could be any value
+        let mut ids = HashMap::new();
+        for (i, node) in nodes.iter().enumerate() {
+            let node_id = node.get("id").ok_or(JsonError::MissingField("id"))?;
+            let id = VertexId::new(i);
+            if ids.insert(node_id.to_string(), id).is_some() {
+                return Err(JsonError::DuplicateNode(i));
+            }
+
+            let value = item(&attributes(node, &["id"])).ok_or(JsonError::InvalidItem(i))?;
+            graph.insert_vertex(id, value);
+        }
+
+        for (i, link) in links.iter().enumerate() {
+            let end = |field| {
+                let id = link.get(field).ok_or(JsonError::MissingField(field))?;
+                ids.get(&id.to_string())
+                    .copied()
+                    .ok_or(JsonError::InvalidLink(i))
+            };
+            let (from, to) = (end("source")?, end("target")?);
+
+            let value = weight(&attributes(link, &["source", "target", "key"]))
+                .ok_or(JsonError::InvalidWeight(i))?;
+            graph
+                .add_edge(from, to, value, token)
+                .map_err(|_| JsonError::InvalidLink(i))?;
+        }
+
+        Ok(graph)
+    }
+}
@@ -1,10 +1,5 @@
 #![warn(clippy::pedantic, clippy::nursery, clippy::all)]
-#![feature(
-    const_raw_ptr_deref,
-    const_mut_refs,
-    option_result_unwrap_unchecked,
-    allocator_api
-)]
+#![allow(clippy::elidable_lifetime_names)]
 #![no_std]
 
 //! A simple and efficient graph-theory library written with a focus
@@ -21,26 +16,107 @@
 //! are part of what makes the library so fast.
 //!
 //! They are based on <http://plv.mpi-sws.org/rustbelt/ghostcell/paper.pdf>
+//!
+//! # Threads
+//!
+//! A [`Graph`] is [`Sync`] whenever its items, weights and edges
+//! are, and its [`GhostToken`](ghost::GhostToken) is [`Send`] and
+//! [`Sync`]. The usual borrowing rules then carry over between
+//! threads: any number of threads can read the graph with a shared
+//! reference to the token, while changing it needs the token
+//! uniquely. A graph isn't [`Send`] itself, as the observer given to
+//! it with [`Graph::set_observer`] needn't be.
+//!
+//! [`SyncGraph`] bundles a graph with its token behind a lock, for
+//! when threads need to take turns changing the graph as well, and
+//! can be sent between threads
 
 extern crate alloc;
 
+/// A module containing algorithms that run over a [`Graph`],
+/// such as depth-first search trees, ear decompositions and
+/// biconnected components
+pub mod algo;
+mod allocator;
+mod arena;
+/// A module containing [`OnError`](batch::OnError), the policy
+/// batch operations follow when some of their items fail
+pub mod batch;
+/// A module containing [`Encode`](binary::Encode) and
+/// [`Decode`](binary::Decode), for writing graphs into compact binary
+/// snapshots with [`Graph::to_bytes`]
+pub mod binary;
+mod bitset;
+mod bundle;
+mod chunks;
+mod clock;
+/// A module containing [`InvariantViolation`](consistency::InvariantViolation),
+/// found by [`Graph::validate`] and fixed by [`Graph::repair`]
+pub mod consistency;
+mod debug;
+mod dense;
+mod diff;
 /// A module containing the [`EdgeTrait`], which is the trait
 /// that must be implemented by any edge that can be used, and
 /// the edges that already implement [`EdgeTrait`]
 pub mod edge;
+/// A module containing [`Provenance`](export::Provenance), for
+/// recording where exported graphs and results came from
+pub mod export;
+mod fingerprint;
+mod frozen;
+/// A module containing generators of common and random graphs,
+/// such as complete graphs and Erdős–Rényi graphs
+pub mod generators;
+/// A module containing [`Position`](geo::Position), for graphs whose
+/// vertices have a position in space, along with nearest vertex
+/// queries and A* guided by straight line distance
+#[cfg(feature = "geo")]
+pub mod geo;
 /// A module containing the types outlined in
 /// <http://plv.mpi-sws.org/rustbelt/ghostcell/paper.pdf>,
 /// [`GhostToken`](ghost::GhostToken) and [`GhostCell`](ghost::GhostCell)
 pub mod ghost;
 mod graph;
+mod heap;
+mod hyper;
 mod id;
+mod indexed;
+/// A module containing [`JsonValue`](json::JsonValue), for writing
+/// graphs as node-link JSON with [`Graph::to_json_node_link`] and
+/// reading them back with [`Graph::from_json_node_link`]
+pub mod json;
+mod measure;
+mod observer;
+/// A module containing [`GraphOps`](ops::GraphOps), the read-only
+/// interface shared by every kind of graph in the library
+pub mod ops;
+mod secondary;
 mod shared;
+mod sync;
 mod vertex;
+mod weights;
 
 pub use crate::graph::Graph;
+pub use allocator::{AllocError, Allocator, Global};
+pub use bitset::VertexBitSet;
+pub use bundle::GraphWithToken;
+pub use chunks::VertexChunks;
+pub use clock::Timestamp;
+pub use debug::GraphDebug;
+pub use dense::DenseGraph;
+pub use diff::GraphDiff;
 use edge::EdgeTrait;
+pub use frozen::FrozenGraph;
+pub use heap::IndexedHeap;
+pub use hyper::{HyperEdge, Hypergraph};
 pub use id::{EdgeId, VertexId};
+pub use indexed::IndexedGraph;
+pub use measure::{FiniteWeight, Measure};
+pub use observer::{GraphObserver, SharedObserver};
+pub use secondary::{IdKey, SecondaryMap};
 pub use shared::Shared;
+pub use sync::{SyncGraph, SyncReadGuard, SyncWriteGuard};
 pub use vertex::Vertex;
 
 pub type SharedNode<'id, Item, Weight, Edge> = Shared<'id, Vertex<'id, Item, Weight, Edge>>;
@@ -71,4 +147,12 @@ pub enum GraphError<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> {
     /// An error for when there's already an edge between two
     /// [vertices](vertex::Vertex) when there shouldn't be
     AlreadyEdgeBetween,
+    /// An error for when an operation that only makes sense for
+    /// directed edges, such as [`Graph::add_edge_bidirectional`], is
+    /// used on a graph with undirected edges
+    RequiresDirectedEdges,
+    /// An error for when memory for a vertex or edge couldn't be
+    /// allocated, only returned by fallible methods such as
+    /// [`Graph::try_add_edge`]
+    AllocationFailed(AllocError),
 }
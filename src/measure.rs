@@ -0,0 +1,227 @@
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+/// A number that lengths, costs and distances can be measured in,
+/// such as by [`dijkstra`](crate::algo::dijkstra)
+///
+/// Implemented for every primitive integer and float, and can be
+/// implemented for fixed-point types too. Sums saturate rather than
+/// overflow, so an unreachably long path is [`Measure::MAX`] rather
+/// than a panic. Measures only need to be partially ordered, but
+/// algorithms treat incomparable measures, such as `NaN`, as equal
+pub trait Measure: Copy + PartialOrd {
+    /// The measure of nothing, such as a path with no edges
+    const ZERO: Self;
+    /// The largest measure there is
+    const MAX: Self;
+
+    /// Adds `self` and `other`, giving [`Measure::MAX`] rather than
+    /// overflowing
+    #[must_use]
+    fn saturating_add(self, other: Self) -> Self;
+    /// Subtracts `other` from `self`, giving the smallest measure
+    /// there is rather than overflowing
+    #[must_use]
+    fn saturating_sub(self, other: Self) -> Self;
+}
+
+macro_rules! integer_measure {
+    ($($t:ty),*) => {
+        $(
+            impl Measure for $t {
+                const ZERO: Self = 0;
+                const MAX: Self = <$t>::MAX;
+
+                fn saturating_add(self, other: Self) -> Self {
+                    <$t>::saturating_add(self, other)
+                }
+                fn saturating_sub(self, other: Self) -> Self {
+                    <$t>::saturating_sub(self, other)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! float_measure {
+    ($($t:ty),*) => {
+        $(
+            impl Measure for $t {
+                const ZERO: Self = 0.0;
+                const MAX: Self = <$t>::INFINITY;
+
+                fn saturating_add(self, other: Self) -> Self {
+                    self + other
+                }
+                fn saturating_sub(self, other: Self) -> Self {
+                    self - other
+                }
+            }
+        )*
+    };
+}
+
+integer_measure!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+float_measure!(f32, f64);
+
+/// A [`Measure`] made totally ordered, by treating incomparable
+/// measures as equal, so that it can be kept in a `BinaryHeap`
+#[derive(Debug, Clone, Copy)]
+pub struct Total<M>(pub M);
+
+impl<M: PartialOrd> PartialEq for Total<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<M: PartialOrd> Eq for Total<M> {}
+
+impl<M: PartialOrd> PartialOrd for Total<M> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<M: PartialOrd> Ord for Total<M> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.partial_cmp(&other.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// A float which is never `NaN` or infinite, so that it's totally
+/// ordered, and can be used where [`Ord`] or [`Hash`] is needed
+///
+/// Sums saturate at the largest finite float rather than reaching
+/// infinity. `-0.0` is stored as `0.0`, so equal weights hash equally
+///
+/// ```rust
+/// use graph::FiniteWeight;
+///
+/// let mut weights = vec![FiniteWeight::new(2.5).unwrap(), FiniteWeight::new(-1.0).unwrap()];
+/// weights.sort();
+/// assert_eq!(weights[0].get(), -1.0);
+///
+/// assert!(FiniteWeight::new(f64::NAN).is_none());
+/// ```
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct FiniteWeight<F>(F);
+
+/// A float that can be wrapped in a [`FiniteWeight`], which can't be
+/// implemented outside of the crate
+pub trait Float: Measure + Default {
+    /// The smallest finite float
+    const MIN: Self;
+    /// The largest finite float
+    const MAX: Self;
+
+    /// Whether the float is neither `NaN` nor infinite
+    fn is_finite(self) -> bool;
+    /// Orders two floats, as `total_cmp` does
+    fn total_cmp(self, other: Self) -> Ordering;
+    /// The float's bits, as `to_bits` gives them
+    fn bits(self) -> u64;
+}
+
+macro_rules! float {
+    ($($t:ty),*) => {
+        $(
+            impl Float for $t {
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn is_finite(self) -> bool {
+                    <$t>::is_finite(self)
+                }
+                fn total_cmp(self, other: Self) -> Ordering {
+                    <$t>::total_cmp(&self, &other)
+                }
+                fn bits(self) -> u64 {
+                    self.to_bits().into()
+                }
+            }
+
+            impl From<FiniteWeight<$t>> for $t {
+                fn from(weight: FiniteWeight<$t>) -> Self {
+                    weight.0
+                }
+            }
+        )*
+    };
+}
+
+float!(f32, f64);
+
+impl<F: Float> FiniteWeight<F> {
+    /// Wraps `value`, or returns `None` if it's `NaN` or infinite
+    #[must_use]
+    pub fn new(value: F) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        // Adding zero turns `-0.0` into `0.0`, and changes nothing else
+        Some(Self(value.saturating_add(F::ZERO)))
+    }
+    /// The wrapped float
+    #[must_use]
+    pub const fn get(self) -> F {
+        self.0
+    }
+    /// Clamps `value`, which is never `NaN`, to the finite floats
+    fn clamped(value: F) -> Self {
+        if value < F::MIN {
+            Self(F::MIN)
+        } else if value > <F as Float>::MAX {
+            Self(<F as Float>::MAX)
+        } else {
+            Self(value)
+        }
+    }
+}
+
+impl<F: Float> Eq for FiniteWeight<F> {}
+
+impl<F: Float> PartialOrd for FiniteWeight<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: Float> Ord for FiniteWeight<F> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(other.0)
+    }
+}
+
+impl<F: Float> Hash for FiniteWeight<F> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.bits().hash(state);
+    }
+}
+
+impl<F: Float> Measure for FiniteWeight<F> {
+    const ZERO: Self = Self(F::ZERO);
+    const MAX: Self = Self(<F as Float>::MAX);
+
+    fn saturating_add(self, other: Self) -> Self {
+        Self::clamped(self.0.saturating_add(other.0))
+    }
+    fn saturating_sub(self, other: Self) -> Self {
+        Self::clamped(self.0.saturating_sub(other.0))
+    }
+}
+
+impl<F: fmt::Debug> fmt::Debug for FiniteWeight<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<F: fmt::Display> fmt::Display for FiniteWeight<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
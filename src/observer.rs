@@ -0,0 +1,260 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    cell::UnsafeCell,
+    hash::BuildHasher,
+    hint,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{edge::EdgeTrait, Allocator, EdgeId, Graph, VertexId};
+
+/// Told about every change made to a [`Graph`] it's been given to
+/// with [`Graph::set_observer`]
+///
+/// Every method does nothing by default, so only the changes of
+/// interest need handling. Observers are told about a change after
+/// it's been made, and can be used to keep indexes, caches or logs
+/// up to date with the graph
+pub trait GraphObserver<'id> {
+    /// Called when the vertex `id` is added
+    fn on_vertex_added(&mut self, id: VertexId<'id>) {
+        let _ = id;
+    }
+    /// Called when the vertex `id` is changed, such as by
+    /// [`Graph::split_vertex`] or [`Graph::touch_vertex`]
+    fn on_vertex_changed(&mut self, id: VertexId<'id>) {
+        let _ = id;
+    }
+    /// Called when the vertex `id` is removed, after each of its
+    /// edges has been
+    fn on_vertex_removed(&mut self, id: VertexId<'id>) {
+        let _ = id;
+    }
+    /// Called when the edge `id` from `from` to `to` is added
+    fn on_edge_added(&mut self, id: EdgeId<'id>, from: VertexId<'id>, to: VertexId<'id>) {
+        let _ = (id, from, to);
+    }
+    /// Called when the weight of the edge `id`, or the vertices it
+    /// joins, are changed
+    fn on_edge_changed(&mut self, id: EdgeId<'id>) {
+        let _ = id;
+    }
+    /// Called when the edge `id` is removed
+    fn on_edge_removed(&mut self, id: EdgeId<'id>) {
+        let _ = id;
+    }
+    /// Called when every vertex and edge is removed at once by
+    /// [`Graph::clear`], instead of for each of them
+    fn on_cleared(&mut self) {}
+}
+
+/// A [`GraphObserver`] that can still be reached after it's been
+/// given to a [`Graph`], by keeping a clone of it
+///
+/// Every clone shares the same observer behind a spin lock, so one
+/// can be given to [`Graph::set_observer`] while another is used to
+/// read what the observer has seen
+///
+/// ```rust
+/// use graph::{
+///     algo::DynamicConnectivity, edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph,
+///     SharedObserver,
+/// };
+///
+/// GhostToken::new(|mut token| {
+///     let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+///     let connectivity = SharedObserver::new(DynamicConnectivity::new());
+///     graph.set_observer(connectivity.clone());
+///
+///     let a = graph.add_vertex(());
+///     let b = graph.add_vertex(());
+///     assert!(!connectivity.with(|c| c.connected(a, b)));
+///
+///     graph.add_edge(a, b, (), &mut token).unwrap();
+///     assert!(connectivity.with(|c| c.connected(a, b)));
+/// })
+/// ```
+pub struct SharedObserver<T>(Arc<Locked<T>>);
+
+/// A value behind a spin lock, shared by every clone of a
+/// [`SharedObserver`]
+struct Locked<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: The value is only reached by whichever thread holds the
+// lock, as with `Mutex`
+unsafe impl<T: Send> Sync for Locked<T> {}
+
+/// Releases the lock of a [`Locked`] when dropped, even if the
+/// closure holding it panics
+struct Unlock<'a>(&'a AtomicBool);
+
+impl Drop for Unlock<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl<T> SharedObserver<T> {
+    /// Shares `observer` between every clone of the returned
+    /// [`SharedObserver`]
+    #[must_use]
+    pub fn new(observer: T) -> Self {
+        Self(Arc::new(Locked {
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(observer),
+        }))
+    }
+    /// Calls `f` with the shared observer, spinning while another
+    /// clone is using it
+    ///
+    /// Calling [`SharedObserver::with`] again from within `f` never
+    /// returns
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        while self
+            .0
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+        let _unlock = Unlock(&self.0.locked);
+
+        // SAFETY: The lock is held until `_unlock` is dropped
+        f(unsafe { &mut *self.0.value.get() })
+    }
+}
+
+impl<T> Clone for SharedObserver<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<'id, T: GraphObserver<'id>> GraphObserver<'id> for SharedObserver<T> {
+    fn on_vertex_added(&mut self, id: VertexId<'id>) {
+        self.with(|observer| observer.on_vertex_added(id));
+    }
+    fn on_vertex_changed(&mut self, id: VertexId<'id>) {
+        self.with(|observer| observer.on_vertex_changed(id));
+    }
+    fn on_vertex_removed(&mut self, id: VertexId<'id>) {
+        self.with(|observer| observer.on_vertex_removed(id));
+    }
+    fn on_edge_added(&mut self, id: EdgeId<'id>, from: VertexId<'id>, to: VertexId<'id>) {
+        self.with(|observer| observer.on_edge_added(id, from, to));
+    }
+    fn on_edge_changed(&mut self, id: EdgeId<'id>) {
+        self.with(|observer| observer.on_edge_changed(id));
+    }
+    fn on_edge_removed(&mut self, id: EdgeId<'id>) {
+        self.with(|observer| observer.on_edge_removed(id));
+    }
+    fn on_cleared(&mut self) {
+        self.with(GraphObserver::on_cleared);
+    }
+}
+
+/// The [`GraphObserver`] a [`Graph`] has been given, which is only
+/// ever used through a mutable reference
+pub enum Observer<'id> {
+    /// Given with [`Graph::set_observer`], so it might only be
+    /// usable from the thread it was given on
+    Local(Box<dyn GraphObserver<'id> + 'id>),
+    /// Given with [`SyncWriteGuard::set_observer`](crate::SyncWriteGuard::set_observer),
+    /// so it can be used from any thread
+    Sendable(Box<dyn GraphObserver<'id> + Send + 'id>),
+}
+
+// SAFETY: Nothing can be reached through a `&Observer`, so sharing
+// one between threads can't race
+unsafe impl<'id> Sync for Observer<'id> {}
+
+impl<'id> Observer<'id> {
+    /// The observer, whichever way it was given
+    fn get(&mut self) -> &mut (dyn GraphObserver<'id> + 'id) {
+        match self {
+            Self::Local(observer) => &mut **observer,
+            Self::Sendable(observer) => &mut **observer,
+        }
+    }
+    /// Unwraps the observer, forgetting whether it can be sent
+    pub(crate) fn into_box(self) -> Box<dyn GraphObserver<'id> + 'id> {
+        match self {
+            Self::Local(observer) => observer,
+            Self::Sendable(observer) => observer,
+        }
+    }
+}
+
+/// A change made to a [`Graph`], passed on to its clock and
+/// observer by [`Graph::notify`]
+#[derive(Clone, Copy)]
+pub enum Change<'id> {
+    VertexAdded(VertexId<'id>),
+    VertexChanged(VertexId<'id>),
+    VertexRemoved(VertexId<'id>),
+    EdgeAdded(EdgeId<'id>, VertexId<'id>, VertexId<'id>),
+    EdgeChanged(EdgeId<'id>),
+    EdgeRemoved(EdgeId<'id>),
+    Cleared,
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Records `change` in the graph's clock, if timestamps are
+    /// being recorded, and tells the graph's observer about it
+    pub(crate) fn notify(&mut self, change: Change<'id>) {
+        if let Some(clock) = &mut self.clock {
+            match change {
+                Change::VertexAdded(id) => clock.create_vertex(id),
+                Change::VertexChanged(id) => {
+                    clock.modify_vertex(id);
+                }
+                Change::VertexRemoved(id) => clock.forget_vertex(id),
+                Change::EdgeAdded(id, ..) => clock.create_edge(id),
+                Change::EdgeChanged(id) => {
+                    clock.modify_edge(id);
+                }
+                Change::EdgeRemoved(id) => clock.forget_edge(id),
+                Change::Cleared => clock.forget_all(),
+            }
+        }
+
+        if let Some(observer) = &mut self.observer {
+            let observer = observer.get();
+            match change {
+                Change::VertexAdded(id) => observer.on_vertex_added(id),
+                Change::VertexChanged(id) => observer.on_vertex_changed(id),
+                Change::VertexRemoved(id) => observer.on_vertex_removed(id),
+                Change::EdgeAdded(id, from, to) => observer.on_edge_added(id, from, to),
+                Change::EdgeChanged(id) => observer.on_edge_changed(id),
+                Change::EdgeRemoved(id) => observer.on_edge_removed(id),
+                Change::Cleared => observer.on_cleared(),
+            }
+        }
+    }
+    /// Sets the [`GraphObserver`] told about every change made to
+    /// the graph from now on, returning the one it replaced
+    ///
+    /// The graph can't see changes made to items or weights through
+    /// a [`GhostToken`](crate::ghost::GhostToken), so observers are
+    /// only told about those when they're passed to
+    /// [`Graph::touch_vertex`] or [`Graph::touch_edge`]
+    pub fn set_observer(
+        &mut self,
+        observer: impl GraphObserver<'id> + 'id,
+    ) -> Option<Box<dyn GraphObserver<'id> + 'id>> {
+        self.observer
+            .replace(Observer::Local(Box::new(observer)))
+            .map(Observer::into_box)
+    }
+    /// Removes the graph's [`GraphObserver`], returning it
+    pub fn take_observer(&mut self) -> Option<Box<dyn GraphObserver<'id> + 'id>> {
+        self.observer.take().map(Observer::into_box)
+    }
+}
@@ -0,0 +1,686 @@
+use core::{hash::BuildHasher, marker::PhantomData};
+
+use alloc::vec::Vec;
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::{
+    edge::{EdgeTrait, LabeledWeight},
+    ghost::GhostToken,
+    id::EdgeId,
+    Allocator, Graph, GraphError, Shared, VertexId,
+};
+
+/// The read-only operations shared by every kind of graph in the
+/// library, so algorithms can be written once and run over a
+/// [`Graph`], a [`DenseGraph`](crate::DenseGraph) or a view of
+/// either
+///
+/// Every method takes a [`GhostToken`], even when the
+/// implementation has no need for it, so that implementations
+/// built on [`GhostCell`](crate::ghost::GhostCell)s can be used
+pub trait GraphOps<'id> {
+    /// The type each vertex contains
+    type Item;
+    /// The type each edge contains
+    type Weight;
+    /// The iterator returned by [`GraphOps::successors`]
+    type Successors<'a>: Iterator<Item = (VertexId<'id>, &'a Self::Weight)>
+    where
+        Self: 'a,
+        'id: 'a;
+    /// The iterator returned by [`GraphOps::predecessors`]
+    type Predecessors<'a>: Iterator<Item = (VertexId<'id>, &'a Self::Weight)>
+    where
+        Self: 'a,
+        'id: 'a;
+
+    /// Whether edges can only be followed in one direction
+    fn is_directed(&self) -> bool;
+    /// The number of vertices in the graph
+    fn vertex_len(&self) -> usize;
+    /// Whether `id` is a vertex within the graph
+    fn contains(&self, id: VertexId<'id>) -> bool;
+    /// Every [`VertexId`] within the graph, in no particular order
+    fn vertex_ids(&self) -> Vec<VertexId<'id>>;
+    /// The item within the vertex `id`, or `None` if `id`
+    /// isn't within the graph
+    fn item<'a>(&'a self, id: VertexId<'id>, token: &'a GhostToken<'id>) -> Option<&'a Self::Item>;
+    /// The weight of the edge that leads from `from` to `to`,
+    /// or `None` if there's no such edge
+    fn weight_between<'a>(
+        &'a self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Option<&'a Self::Weight>;
+    /// The vertices that can be reached by following a single
+    /// edge from `id`, along with the weight of that edge
+    fn successors<'a>(
+        &'a self,
+        id: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Self::Successors<'a>;
+    /// The vertices that can reach `id` by following a single
+    /// edge, along with the weight of that edge
+    fn predecessors<'a>(
+        &'a self,
+        id: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Self::Predecessors<'a>;
+}
+
+/// The iterator over a [`Graph`]'s neighboring vertices, returned
+/// by [`GraphOps::successors`] and [`GraphOps::predecessors`]
+pub struct Neighbors<'a, 'id, Item, Weight, Edge> {
+    edges: Option<hashbrown::hash_map::Values<'a, EdgeId<'id>, Shared<'id, Edge>>>,
+    id: VertexId<'id>,
+    token: &'a GhostToken<'id>,
+    incoming: bool,
+    _marker: PhantomData<(&'a Item, &'a Weight)>,
+}
+
+impl<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>>
+    Neighbors<'a, 'id, Item, Weight, Edge>
+{
+    fn new<S: BuildHasher, A: Allocator>(
+        graph: &'a Graph<'id, Item, Weight, Edge, S, A>,
+        id: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+        incoming: bool,
+    ) -> Self {
+        Self {
+            edges: graph
+                .get_vertex(id)
+                .map(|vertex| vertex.borrow(token).edges.values()),
+            id,
+            token,
+            incoming,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'id, Item, Weight: 'id, Edge: EdgeTrait<'id, Item, Weight>> Iterator
+    for Neighbors<'a, 'id, Item, Weight, Edge>
+{
+    type Item = (VertexId<'id>, &'a Weight);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (id, token, incoming) = (self.id, self.token, self.incoming);
+
+        for edge in self.edges.as_mut()? {
+            let edge = edge.borrow(token);
+
+            let other = if incoming {
+                let other = edge.other(id)?;
+                (edge.traverse(other) == Some(id)).then_some(other)
+            } else {
+                edge.traverse(id)
+            };
+
+            if let Some(other) = other {
+                return Some((other, edge.get_weight()));
+            }
+        }
+
+        None
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S: BuildHasher, A: Allocator>
+    GraphOps<'id> for Graph<'id, Item, Weight, Edge, S, A>
+{
+    type Item = Item;
+    type Weight = Weight;
+    type Successors<'a>
+        = Neighbors<'a, 'id, Item, Weight, Edge>
+    where
+        Self: 'a,
+        'id: 'a;
+    type Predecessors<'a>
+        = Neighbors<'a, 'id, Item, Weight, Edge>
+    where
+        Self: 'a,
+        'id: 'a;
+
+    fn is_directed(&self) -> bool {
+        Edge::DIRECTED
+    }
+
+    fn vertex_len(&self) -> usize {
+        Self::vertex_len(self)
+    }
+
+    fn contains(&self, id: VertexId<'id>) -> bool {
+        self.get_vertex(id).is_some()
+    }
+
+    fn vertex_ids(&self) -> Vec<VertexId<'id>> {
+        self.vertex_ids().collect()
+    }
+
+    fn item<'a>(&'a self, id: VertexId<'id>, token: &'a GhostToken<'id>) -> Option<&'a Item> {
+        self.get_vertex(id)
+            .map(|vertex| vertex.borrow(token).get_item())
+    }
+
+    fn weight_between<'a>(
+        &'a self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Option<&'a Weight> {
+        Neighbors::new(self, from, token, false)
+            .find(|(other, _)| *other == to)
+            .map(|(_, weight)| weight)
+    }
+
+    fn successors<'a>(
+        &'a self,
+        id: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Self::Successors<'a> {
+        Neighbors::new(self, id, token, false)
+    }
+
+    fn predecessors<'a>(
+        &'a self,
+        id: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Self::Predecessors<'a> {
+        Neighbors::new(self, id, token, true)
+    }
+}
+
+impl<'id, G: GraphOps<'id>> GraphOps<'id> for &G {
+    type Item = G::Item;
+    type Weight = G::Weight;
+    type Successors<'a>
+        = G::Successors<'a>
+    where
+        Self: 'a,
+        'id: 'a;
+    type Predecessors<'a>
+        = G::Predecessors<'a>
+    where
+        Self: 'a,
+        'id: 'a;
+
+    fn is_directed(&self) -> bool {
+        G::is_directed(self)
+    }
+
+    fn vertex_len(&self) -> usize {
+        G::vertex_len(self)
+    }
+
+    fn contains(&self, id: VertexId<'id>) -> bool {
+        G::contains(self, id)
+    }
+
+    fn vertex_ids(&self) -> Vec<VertexId<'id>> {
+        G::vertex_ids(self)
+    }
+
+    fn item<'a>(&'a self, id: VertexId<'id>, token: &'a GhostToken<'id>) -> Option<&'a G::Item> {
+        G::item(self, id, token)
+    }
+
+    fn weight_between<'a>(
+        &'a self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Option<&'a G::Weight> {
+        G::weight_between(self, from, to, token)
+    }
+
+    fn successors<'a>(
+        &'a self,
+        id: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Self::Successors<'a> {
+        G::successors(self, id, token)
+    }
+
+    fn predecessors<'a>(
+        &'a self,
+        id: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Self::Predecessors<'a> {
+        G::predecessors(self, id, token)
+    }
+}
+
+/// A view of a graph with the direction of every edge flipped,
+/// without copying the graph
+///
+/// Useful for running an algorithm backwards, for example finding
+/// every vertex that can reach a vertex with a search that follows
+/// successors. Undirected graphs are unchanged by reversing them
+#[derive(Debug, Clone, Copy)]
+pub struct ReversedView<G>(G);
+
+impl<G> ReversedView<G> {
+    /// Constructs a view of `graph` with its edges reversed
+    #[must_use]
+    pub const fn new(graph: G) -> Self {
+        Self(graph)
+    }
+    /// Returns a reference to the graph being viewed
+    #[must_use]
+    pub const fn inner(&self) -> &G {
+        &self.0
+    }
+    /// Returns the graph being viewed
+    #[must_use]
+    pub fn into_inner(self) -> G {
+        self.0
+    }
+}
+
+impl<'id, G: GraphOps<'id>> GraphOps<'id> for ReversedView<G> {
+    type Item = G::Item;
+    type Weight = G::Weight;
+    type Successors<'a>
+        = G::Predecessors<'a>
+    where
+        Self: 'a,
+        'id: 'a;
+    type Predecessors<'a>
+        = G::Successors<'a>
+    where
+        Self: 'a,
+        'id: 'a;
+
+    fn is_directed(&self) -> bool {
+        self.0.is_directed()
+    }
+
+    fn vertex_len(&self) -> usize {
+        self.0.vertex_len()
+    }
+
+    fn contains(&self, id: VertexId<'id>) -> bool {
+        self.0.contains(id)
+    }
+
+    fn vertex_ids(&self) -> Vec<VertexId<'id>> {
+        self.0.vertex_ids()
+    }
+
+    fn item<'a>(&'a self, id: VertexId<'id>, token: &'a GhostToken<'id>) -> Option<&'a G::Item> {
+        self.0.item(id, token)
+    }
+
+    fn weight_between<'a>(
+        &'a self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Option<&'a G::Weight> {
+        self.0.weight_between(to, from, token)
+    }
+
+    fn successors<'a>(
+        &'a self,
+        id: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Self::Successors<'a> {
+        self.0.predecessors(id, token)
+    }
+
+    fn predecessors<'a>(
+        &'a self,
+        id: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Self::Predecessors<'a> {
+        self.0.successors(id, token)
+    }
+}
+
+/// A view of a graph with only the edges whose weights satisfy a
+/// predicate, without copying the graph
+///
+/// Every vertex is kept. Useful for running an algorithm over part
+/// of a graph, such as following only the edges with a certain
+/// [label](crate::edge::Labeled)
+#[derive(Debug, Clone, Copy)]
+pub struct EdgeFilter<G, F> {
+    graph: G,
+    keep: F,
+}
+
+impl<G, F> EdgeFilter<G, F> {
+    /// Constructs a view of `graph` with only the edges whose weight
+    /// `keep` returns `true` for
+    #[must_use]
+    pub const fn new(graph: G, keep: F) -> Self {
+        Self { graph, keep }
+    }
+    /// Returns a reference to the graph being viewed
+    #[must_use]
+    pub const fn inner(&self) -> &G {
+        &self.graph
+    }
+    /// Returns the graph being viewed
+    #[must_use]
+    pub fn into_inner(self) -> G {
+        self.graph
+    }
+}
+
+impl<'id, G: GraphOps<'id>, L: Fn(&<G::Weight as LabeledWeight>::Label) -> bool>
+    EdgeFilter<G, ByLabel<L>>
+where
+    G::Weight: LabeledWeight,
+{
+    /// Constructs a view of `graph` with only the edges whose label
+    /// `keep` returns `true` for
+    #[must_use]
+    pub const fn by_label(graph: G, keep: L) -> Self {
+        Self::new(graph, ByLabel(keep))
+    }
+}
+
+/// The predicate of an [`EdgeFilter`] made by
+/// [`EdgeFilter::by_label`], which tests the label of each weight
+#[derive(Debug, Clone, Copy)]
+pub struct ByLabel<L>(L);
+
+/// A test of whether an [`EdgeFilter`] keeps an edge with a given
+/// weight, implemented for closures over the weight, [`ByLabel`]
+/// and [`AnyEdge`]
+pub trait EdgePredicate<Weight: ?Sized> {
+    /// Whether an edge with `weight` is kept
+    fn keep(&self, weight: &Weight) -> bool;
+}
+
+impl<Weight: ?Sized, F: Fn(&Weight) -> bool> EdgePredicate<Weight> for F {
+    fn keep(&self, weight: &Weight) -> bool {
+        self(weight)
+    }
+}
+
+impl<Weight: LabeledWeight, L: Fn(&Weight::Label) -> bool> EdgePredicate<Weight> for ByLabel<L> {
+    fn keep(&self, weight: &Weight) -> bool {
+        (self.0)(weight.label())
+    }
+}
+
+/// An [`EdgePredicate`] which keeps every edge
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnyEdge;
+
+impl<Weight: ?Sized> EdgePredicate<Weight> for AnyEdge {
+    fn keep(&self, _weight: &Weight) -> bool {
+        true
+    }
+}
+
+/// The iterator over the neighbors of a vertex in an
+/// [`EdgeFilter`], returned by [`GraphOps::successors`] and
+/// [`GraphOps::predecessors`]
+pub struct FilteredNeighbors<'a, I, F> {
+    neighbors: I,
+    keep: &'a F,
+}
+
+impl<'a, 'id: 'a, Weight: 'a, I, F> Iterator for FilteredNeighbors<'a, I, F>
+where
+    I: Iterator<Item = (VertexId<'id>, &'a Weight)>,
+    F: EdgePredicate<Weight>,
+{
+    type Item = (VertexId<'id>, &'a Weight);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let keep = self.keep;
+        self.neighbors.find(|(_, weight)| keep.keep(weight))
+    }
+}
+
+impl<'id, G: GraphOps<'id>, F: EdgePredicate<G::Weight>> GraphOps<'id> for EdgeFilter<G, F> {
+    type Item = G::Item;
+    type Weight = G::Weight;
+    type Successors<'a>
+        = FilteredNeighbors<'a, G::Successors<'a>, F>
+    where
+        Self: 'a,
+        'id: 'a;
+    type Predecessors<'a>
+        = FilteredNeighbors<'a, G::Predecessors<'a>, F>
+    where
+        Self: 'a,
+        'id: 'a;
+
+    fn is_directed(&self) -> bool {
+        self.graph.is_directed()
+    }
+
+    fn vertex_len(&self) -> usize {
+        self.graph.vertex_len()
+    }
+
+    fn contains(&self, id: VertexId<'id>) -> bool {
+        self.graph.contains(id)
+    }
+
+    fn vertex_ids(&self) -> Vec<VertexId<'id>> {
+        self.graph.vertex_ids()
+    }
+
+    fn item<'a>(&'a self, id: VertexId<'id>, token: &'a GhostToken<'id>) -> Option<&'a G::Item> {
+        self.graph.item(id, token)
+    }
+
+    fn weight_between<'a>(
+        &'a self,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Option<&'a G::Weight> {
+        self.graph
+            .weight_between(from, to, token)
+            .filter(|weight| self.keep.keep(weight))
+    }
+
+    fn successors<'a>(
+        &'a self,
+        id: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Self::Successors<'a> {
+        FilteredNeighbors {
+            neighbors: self.graph.successors(id, token),
+            keep: &self.keep,
+        }
+    }
+
+    fn predecessors<'a>(
+        &'a self,
+        id: VertexId<'id>,
+        token: &'a GhostToken<'id>,
+    ) -> Self::Predecessors<'a> {
+        FilteredNeighbors {
+            neighbors: self.graph.predecessors(id, token),
+            keep: &self.keep,
+        }
+    }
+}
+
+type Product<'id, Item, Weight, Edge, S, A> =
+    Result<Graph<'id, Item, Weight, Edge, S, A>, GraphError<'id, Item, Weight, Edge>>;
+
+/// The vertices of two graphs, ordered by id, along with the
+/// position of every vertex of each
+struct Factors<'id> {
+    first: Vec<VertexId<'id>>,
+    second: Vec<VertexId<'id>>,
+    first_index: HashMap<VertexId<'id>, usize>,
+    second_index: HashMap<VertexId<'id>, usize>,
+}
+
+impl<'id> Factors<'id> {
+    fn new<G1: GraphOps<'id>, G2: GraphOps<'id>>(first: &G1, second: &G2) -> Self {
+        let sorted = |mut ids: Vec<VertexId<'id>>| {
+            ids.sort_unstable();
+            let index = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+            (ids, index)
+        };
+        let (first, first_index) = sorted(first.vertex_ids());
+        let (second, second_index) = sorted(second.vertex_ids());
+
+        Self {
+            first,
+            second,
+            first_index,
+            second_index,
+        }
+    }
+    /// The position in the product of the vertex made from the
+    /// `i`th vertex of the first graph and `j`th of the second
+    const fn pair(&self, i: usize, j: usize) -> usize {
+        i * self.second.len() + j
+    }
+    /// Builds the product graph from the items of every pair of
+    /// vertices and its edges, skipping any added twice
+    fn build<Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A>(
+        items: Vec<Item>,
+        edges: Vec<(usize, usize, Weight)>,
+        token: &mut GhostToken<'id>,
+    ) -> Product<'id, Item, Weight, Edge, S, A>
+    where
+        S: BuildHasher + Clone + Default,
+        A: Allocator + Clone + Default,
+    {
+        let mut graph = Graph::with_capacity_and_hasher_in(
+            items.len(),
+            edges.len(),
+            S::default(),
+            A::default(),
+        );
+        graph.add_vertices(items.into_iter());
+
+        let mut added = HashSet::with_capacity(edges.len());
+        for (from, to, weight) in edges {
+            let key = if Edge::DIRECTED || from < to {
+                (from, to)
+            } else {
+                (to, from)
+            };
+            if added.insert(key) {
+                graph.add_edge(VertexId::new(from), VertexId::new(to), weight, token)?;
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+/// Constructs the cartesian product of `first` and `second`, which
+/// has a vertex for every pair of a vertex from `first` and one from
+/// `second`
+///
+/// The vertex for `(u, v)` leads to `(u2, v)` for every edge from `u`
+/// to `u2` in `first`, and to `(u, v2)` for every edge from `v` to
+/// `v2` in `second`. Its item is `combine_items` of the items of `u`
+/// and `v`, and each edge's weight is `combine_weights` of the
+/// weight of the edge it came from, with `None` for the other graph.
+///
+/// The vertex for the `i`th smallest id in `first` and the `j`th in
+/// `second` has the id `i * second.vertex_len() + j`
+///
+/// # Errors
+/// Returns a [`GraphError`] if any edge can't be added to the graph
+pub fn cartesian_product<'id, G1, G2, Item, Weight, Edge, S, A>(
+    first: &G1,
+    second: &G2,
+    mut combine_items: impl FnMut(&G1::Item, &G2::Item) -> Item,
+    mut combine_weights: impl FnMut(Option<&G1::Weight>, Option<&G2::Weight>) -> Weight,
+    token: &mut GhostToken<'id>,
+) -> Product<'id, Item, Weight, Edge, S, A>
+where
+    G1: GraphOps<'id>,
+    G2: GraphOps<'id>,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher + Clone + Default,
+    A: Allocator + Clone + Default,
+{
+    let factors = Factors::new(first, second);
+    let mut items = Vec::with_capacity(factors.first.len() * factors.second.len());
+    let mut edges = Vec::new();
+
+    for (i, u) in factors.first.iter().enumerate() {
+        for (j, v) in factors.second.iter().enumerate() {
+            if let (Some(a), Some(b)) = (first.item(*u, token), second.item(*v, token)) {
+                items.push(combine_items(a, b));
+            }
+
+            for (other, weight) in first.successors(*u, token) {
+                let k = factors.first_index[&other];
+                let weight = combine_weights(Some(weight), None);
+                edges.push((factors.pair(i, j), factors.pair(k, j), weight));
+            }
+            for (other, weight) in second.successors(*v, token) {
+                let k = factors.second_index[&other];
+                let weight = combine_weights(None, Some(weight));
+                edges.push((factors.pair(i, j), factors.pair(i, k), weight));
+            }
+        }
+    }
+
+    Factors::build(items, edges, token)
+}
+
+/// Constructs the tensor product of `first` and `second`, which has
+/// a vertex for every pair of a vertex from `first` and one from
+/// `second`
+///
+/// The vertex for `(u, v)` leads to `(u2, v2)` for every edge from
+/// `u` to `u2` in `first` and every edge from `v` to `v2` in
+/// `second`. Its item is `combine_items` of the items of `u` and
+/// `v`, and each edge's weight is `combine_weights` of the weights
+/// of the two edges it came from.
+///
+/// The vertex for the `i`th smallest id in `first` and the `j`th in
+/// `second` has the id `i * second.vertex_len() + j`
+///
+/// # Errors
+/// Returns a [`GraphError`] if any edge can't be added to the graph
+pub fn tensor_product<'id, G1, G2, Item, Weight, Edge, S, A>(
+    first: &G1,
+    second: &G2,
+    mut combine_items: impl FnMut(&G1::Item, &G2::Item) -> Item,
+    mut combine_weights: impl FnMut(&G1::Weight, &G2::Weight) -> Weight,
+    token: &mut GhostToken<'id>,
+) -> Product<'id, Item, Weight, Edge, S, A>
+where
+    G1: GraphOps<'id>,
+    G2: GraphOps<'id>,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher + Clone + Default,
+    A: Allocator + Clone + Default,
+{
+    let factors = Factors::new(first, second);
+    let mut items = Vec::with_capacity(factors.first.len() * factors.second.len());
+    let mut edges = Vec::new();
+
+    for (i, u) in factors.first.iter().enumerate() {
+        for (j, v) in factors.second.iter().enumerate() {
+            if let (Some(a), Some(b)) = (first.item(*u, token), second.item(*v, token)) {
+                items.push(combine_items(a, b));
+            }
+
+            for (u2, first_weight) in first.successors(*u, token) {
+                let k = factors.first_index[&u2];
+                for (v2, second_weight) in second.successors(*v, token) {
+                    let l = factors.second_index[&v2];
+                    let weight = combine_weights(first_weight, second_weight);
+                    edges.push((factors.pair(i, j), factors.pair(k, l), weight));
+                }
+            }
+        }
+    }
+
+    Factors::build(items, edges, token)
+}
@@ -0,0 +1,388 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{
+    fmt,
+    iter::FromIterator,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use crate::{edge::EdgeTrait, Allocator, EdgeId, Graph, VertexId};
+
+/// An id which can key a [`SecondaryMap`], by being turned into an
+/// index and back
+pub trait IdKey: Copy {
+    /// The index the id is stored at
+    fn index(self) -> usize;
+    /// The id stored at `index`
+    fn from_index(index: usize) -> Self;
+}
+
+impl<'id> IdKey for VertexId<'id> {
+    fn index(self) -> usize {
+        self.id()
+    }
+    fn from_index(index: usize) -> Self {
+        Self::new(index)
+    }
+}
+
+impl<'id> IdKey for EdgeId<'id> {
+    fn index(self) -> usize {
+        self.id
+    }
+    fn from_index(index: usize) -> Self {
+        Self::new(index)
+    }
+}
+
+/// The number of slots a [`SecondaryMap`] can have for each value it
+/// holds before values for higher ids are kept sparsely
+const SLOTS_PER_VALUE: usize = 4;
+
+/// The number of slots a [`SecondaryMap`] can always have, however
+/// few values it holds
+const MIN_SLOTS: usize = 64;
+
+/// The number of slots a [`SecondaryMap`] holding `len` values, with
+/// space for `capacity`, can grow to
+const fn dense_limit(capacity: usize, len: usize) -> usize {
+    let limit = len
+        .saturating_mul(SLOTS_PER_VALUE)
+        .saturating_add(MIN_SLOTS);
+    if capacity > limit {
+        capacity
+    } else {
+        limit
+    }
+}
+
+/// A map from the [`VertexId`]s or [`EdgeId`]s of a graph to values,
+/// for attaching data to vertices or edges, such as colours, distances
+/// or whether they've been visited, without changing the graph
+///
+/// Values are stored densely, at the index of their id, so looking
+/// them up never hashes. As ids are never reused, a map only ever
+/// grows as large as the highest id put into it, and
+/// [`Graph::vertex_map`] and [`Graph::edge_map`] construct a map with
+/// space for every id in the graph already. Values for ids far beyond
+/// the number of values the map holds are kept in an ordered map
+/// instead, so a few high ids can't make the map take up more space
+/// than its values need
+///
+/// ```rust
+/// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+///
+/// GhostToken::new(|mut token| {
+///     let mut graph: Graph<&str, (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+///     let a = graph.add_vertex("a");
+///     let b = graph.add_vertex("b");
+///
+///     let mut colour = graph.vertex_map();
+///     colour.insert(a, "red");
+///     colour.insert(b, "blue");
+///
+///     assert_eq!(colour[a], "red");
+///     assert_eq!(colour.iter().collect::<Vec<_>>(), vec![(a, &"red"), (b, &"blue")]);
+/// })
+/// ```
+pub struct SecondaryMap<K, V> {
+    slots: Vec<Option<V>>,
+    /// Values for ids too high to keep in `slots`, each of which is
+    /// beyond the end of `slots`
+    sparse: BTreeMap<usize, V>,
+    len: usize,
+    _key: PhantomData<K>,
+}
+
+impl<K: IdKey, V> SecondaryMap<K, V> {
+    /// Constructs an empty map
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            sparse: BTreeMap::new(),
+            len: 0,
+            _key: PhantomData,
+        }
+    }
+    /// Constructs an empty map with space for ids with an index below
+    /// `capacity`
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            sparse: BTreeMap::new(),
+            len: 0,
+            _key: PhantomData,
+        }
+    }
+    /// Returns the number of values in the map
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns `true` if the map holds no values
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Returns `true` if there's a value for `key`
+    #[must_use]
+    pub fn contains_key(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+    /// Returns a reference to the value for `key`, if there is one
+    #[must_use]
+    pub fn get(&self, key: K) -> Option<&V> {
+        let index = key.index();
+        self.slots
+            .get(index)
+            .map_or_else(|| self.sparse.get(&index), Option::as_ref)
+    }
+    /// Returns a mutable reference to the value for `key`, if there is
+    /// one
+    #[must_use]
+    pub fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        let index = key.index();
+        match self.slots.get_mut(index) {
+            Some(slot) => slot.as_mut(),
+            None => self.sparse.get_mut(&index),
+        }
+    }
+    /// Sets the value for `key`, returning the value it replaced
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let index = key.index();
+        let old = if self.make_dense(index) {
+            self.slots[index].replace(value)
+        } else {
+            self.sparse.insert(index, value)
+        };
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+    /// Removes the value for `key`, returning it if there was one
+    pub fn remove(&mut self, key: K) -> Option<V> {
+        let index = key.index();
+        let old = match self.slots.get_mut(index) {
+            Some(slot) => slot.take(),
+            None => self.sparse.remove(&index),
+        };
+        if old.is_some() {
+            self.len -= 1;
+        }
+        old
+    }
+    /// Returns a mutable reference to the value for `key`, setting it
+    /// to the result of `default` first if there isn't one
+    pub fn get_or_insert_with(&mut self, key: K, default: impl FnOnce() -> V) -> &mut V {
+        if !self.contains_key(key) {
+            self.len += 1;
+        }
+        let index = key.index();
+        if self.make_dense(index) {
+            self.slots[index].get_or_insert_with(default)
+        } else {
+            self.sparse.entry(index).or_insert_with(default)
+        }
+    }
+    /// Returns a mutable reference to the value for `key`, setting it
+    /// to the default value first if there isn't one
+    pub fn get_or_default(&mut self, key: K) -> &mut V
+    where
+        V: Default,
+    {
+        self.get_or_insert_with(key, V::default)
+    }
+    /// Removes every value, keeping the space they took
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.sparse.clear();
+        self.len = 0;
+    }
+    /// Keeps only the values for which `keep` returns `true`
+    pub fn retain(&mut self, mut keep: impl FnMut(K, &mut V) -> bool) {
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if let Some(value) = slot {
+                if !keep(K::from_index(index), value) {
+                    *slot = None;
+                    self.len -= 1;
+                }
+            }
+        }
+
+        let len = &mut self.len;
+        self.sparse.retain(|index, value| {
+            let kept = keep(K::from_index(*index), value);
+            *len -= usize::from(!kept);
+            kept
+        });
+    }
+    /// Returns an iterator over the ids and values, ordered by id
+    pub fn iter(&self) -> impl Iterator<Item = (K, &V)> + '_ {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| Some((K::from_index(index), slot.as_ref()?)))
+            .chain(
+                self.sparse
+                    .iter()
+                    .map(|(index, value)| (K::from_index(*index), value)),
+            )
+    }
+    /// Returns an iterator over the ids and mutable references to the
+    /// values, ordered by id
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (K, &mut V)> + '_ {
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| Some((K::from_index(index), slot.as_mut()?)))
+            .chain(
+                self.sparse
+                    .iter_mut()
+                    .map(|(index, value)| (K::from_index(*index), value)),
+            )
+    }
+    /// Returns an iterator over the ids with values, in order
+    pub fn keys(&self) -> impl Iterator<Item = K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+    /// Returns an iterator over the values, ordered by id
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.slots.iter().flatten().chain(self.sparse.values())
+    }
+    /// Returns an iterator over mutable references to the values,
+    /// ordered by id
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> + '_ {
+        self.slots
+            .iter_mut()
+            .flatten()
+            .chain(self.sparse.values_mut())
+    }
+    /// Grows `slots` to hold `index` if it's not too far beyond the
+    /// number of values held, moving any values for the new slots out
+    /// of `sparse`, and returns whether `index` has a slot
+    fn make_dense(&mut self, index: usize) -> bool {
+        if index < self.slots.len() {
+            return true;
+        }
+        if index >= dense_limit(self.slots.capacity(), self.len) {
+            return false;
+        }
+
+        self.slots.resize_with(index + 1, || None);
+        let sparse = self.sparse.split_off(&self.slots.len());
+        for (index, value) in core::mem::replace(&mut self.sparse, sparse) {
+            self.slots[index] = Some(value);
+        }
+        true
+    }
+}
+
+impl<K: IdKey, V> Default for SecondaryMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V: Clone> Clone for SecondaryMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            slots: self.slots.clone(),
+            sparse: self.sparse.clone(),
+            len: self.len,
+            _key: PhantomData,
+        }
+    }
+}
+
+impl<K: IdKey + fmt::Debug, V: fmt::Debug> fmt::Debug for SecondaryMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: IdKey, V: PartialEq> PartialEq for SecondaryMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self
+                .iter()
+                .map(|(key, value)| (key.index(), value))
+                .eq(other.iter().map(|(key, value)| (key.index(), value)))
+    }
+}
+
+impl<K: IdKey, V: Eq> Eq for SecondaryMap<K, V> {}
+
+impl<K: IdKey, V> Index<K> for SecondaryMap<K, V> {
+    type Output = V;
+
+    /// # Panics
+    /// Panics if there's no value for `key`
+    fn index(&self, key: K) -> &V {
+        self.get(key).expect("no value for the id")
+    }
+}
+
+impl<K: IdKey, V> IndexMut<K> for SecondaryMap<K, V> {
+    /// # Panics
+    /// Panics if there's no value for `key`
+    fn index_mut(&mut self, key: K) -> &mut V {
+        self.get_mut(key).expect("no value for the id")
+    }
+}
+
+impl<K: IdKey, V> Extend<(K, V)> for SecondaryMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: IdKey, V> FromIterator<(K, V)> for SecondaryMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator>
+    Graph<'id, Item, Weight, Edge, S, A>
+{
+    /// Constructs an empty [`SecondaryMap`] with space for every
+    /// vertex in the graph
+    #[must_use]
+    pub fn vertex_map<V>(&self) -> SecondaryMap<VertexId<'id>, V> {
+        SecondaryMap::with_capacity(
+            self.current_vertex_id
+                .min(dense_limit(0, self.vertices.len())),
+        )
+    }
+    /// Constructs an empty [`SecondaryMap`] with space for every edge
+    /// in the graph
+    #[must_use]
+    pub fn edge_map<V>(&self) -> SecondaryMap<EdgeId<'id>, V> {
+        SecondaryMap::with_capacity(self.current_edge_id.min(dense_limit(0, self.edges.len())))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K: IdKey, V: serde::Serialize> serde::Serialize for SecondaryMap<K, V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter().map(|(key, value)| (key.index(), value)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K: IdKey, V: serde::Deserialize<'de>> serde::Deserialize<'de> for SecondaryMap<K, V> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries = hashbrown::HashMap::<usize, V>::deserialize(deserializer)?;
+        Ok(entries
+            .into_iter()
+            .map(|(index, value)| (K::from_index(index), value))
+            .collect())
+    }
+}
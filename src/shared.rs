@@ -1,9 +1,4 @@
-use core::{
-    alloc::{Allocator, Layout},
-    ptr::NonNull,
-};
-
-use alloc::boxed::Box;
+use core::ptr::NonNull;
 
 use crate::ghost::{GhostCell, GhostToken};
 
@@ -22,17 +17,17 @@ use crate::ghost::{GhostCell, GhostToken};
 #[derive(Debug)]
 pub struct Shared<'id, T>(NonNull<GhostCell<'id, T>>);
 
+// SAFETY: A `Shared` behaves as a shared reference to its
+// `GhostCell`, which can be sent between threads if the cell is `Sync`
+unsafe impl<'id, T: Send + Sync> Send for Shared<'id, T> {}
+// SAFETY: As above, nothing more than a shared reference to the
+// `GhostCell` can be reached through a `&Shared`
+unsafe impl<'id, T: Send + Sync> Sync for Shared<'id, T> {}
+
 impl<'id, T> Shared<'id, T> {
-    /// Makes a new [`Shared`] based off a given item
-    ///
-    /// Makes the item into a [`GhostCell`], allocates it
-    /// on the heap, and then uses the given memory address
-    pub fn new(item: T) -> Self {
-        Self(Box::leak(Box::new(GhostCell::new(item))).into())
-    }
     /// Returns a reference to the underlying [`GhostCell`]
     #[must_use]
-    pub fn ghost(&self) -> &GhostCell<'id, T> {
+    pub const fn ghost(&self) -> &GhostCell<'id, T> {
         unsafe { self.0.as_ref() }
     }
     /// A shorthand for `shared.ghost().g_borrow(&token)`
@@ -44,14 +39,14 @@ impl<'id, T> Shared<'id, T> {
     pub fn borrow_mut<'a>(&'a self, token: &'a mut GhostToken<'id>) -> &'a mut T {
         self.ghost().g_borrow_mut(token)
     }
-    /// Drops the contents of `self`
-    ///
-    /// # Safety
-    /// There can be no other pointers to the
-    /// contents of self
-    pub(crate) unsafe fn drop(&self) {
-        core::ptr::drop_in_place(self.0.as_ptr());
-        alloc::alloc::Global.deallocate(self.0.cast(), Layout::new::<T>());
+    /// Constructs a [`Shared`] from a pointer to an already
+    /// initialised [`GhostCell`]
+    pub(crate) const fn from_ptr(ptr: NonNull<GhostCell<'id, T>>) -> Self {
+        Self(ptr)
+    }
+    /// Returns the pointer to `self`'s [`GhostCell`]
+    pub(crate) const fn as_ptr(&self) -> NonNull<GhostCell<'id, T>> {
+        self.0
     }
     /// Unsafely reads the inner value as mutable
     ///
@@ -59,7 +54,7 @@ impl<'id, T> Shared<'id, T> {
     /// This should only be used if one is certain that
     /// no other pointers exist to `self` which could
     /// be reading or writing at the same time
-    pub(crate) unsafe fn read_mut(&mut self) -> &mut T {
+    pub(crate) const unsafe fn read_mut(&mut self) -> &mut T {
         let x = self.0.as_mut();
         x.get_mut()
     }
@@ -69,29 +64,6 @@ impl<'id, T> Shared<'id, T> {
     pub(crate) const fn clone_shared(&self) -> Self {
         Self(self.0)
     }
-    /// Converts `Shared<T>` to `Shared<U>`.
-    /// Will allocate for a new `Shared<U>`
-    pub fn convert<U>(self, _token: &mut GhostToken<'id>) -> Shared<'id, U>
-    where
-        U: From<T>,
-    {
-        // SAFETY: The pointer will never be non-null,
-        // as it's from a `NonNull`.
-        // As this function takes a mutable reference to
-        // the token, it's guranteed to be the only writer/reader
-        // to the pointer itself
-        let ptr = unsafe { self.0.as_ptr().read() };
-        // SAFETY: Nothing else can be pointing to the old
-        // memory location, due to the mutable reference to
-        // the token
-        unsafe { self.drop() };
-
-        let inner = ptr.into_inner();
-
-        let converted_inner: U = inner.into();
-
-        Shared::new(converted_inner)
-    }
 }
 
 impl<'id, T> PartialEq for Shared<'id, T> {
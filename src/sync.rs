@@ -0,0 +1,292 @@
+use alloc::boxed::Box;
+use core::{
+    cell::UnsafeCell,
+    hint,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use hashbrown::hash_map::DefaultHashBuilder;
+
+use crate::{
+    edge::EdgeTrait, ghost::GhostToken, observer::Observer, Allocator, Global, Graph, GraphObserver,
+};
+
+/// A graph along with its token
+type Parts<'id, Item, Weight, Edge, S, A> = (Graph<'id, Item, Weight, Edge, S, A>, GhostToken<'id>);
+
+/// Set in a [`SyncGraph`]'s state while it's borrowed mutably
+const WRITER: usize = 1 << (usize::BITS - 1);
+
+/// A [`Graph`] paired with its [`GhostToken`] behind a reader-writer
+/// spin lock, so that it can be shared between threads
+///
+/// Any number of threads can hold a [`SyncReadGuard`] at once, and
+/// run read-only algorithms over the graph with the shared token,
+/// while a [`SyncWriteGuard`] gives one thread mutable access to
+/// both. Waiting threads spin rather than sleep, so no operating
+/// system support is needed, but guards should be held briefly.
+/// Readers are preferred, so a steady stream of them can keep a
+/// writer waiting
+///
+/// ```rust
+/// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph, SyncGraph};
+///
+/// GhostToken::new(|token| {
+///     let mut graph: Graph<u32, (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+///     let first = graph.add_vertex(1);
+///
+///     let shared = SyncGraph::new(graph, token);
+///
+///     std::thread::scope(|scope| {
+///         for _ in 0..4 {
+///             scope.spawn(|| {
+///                 let read = shared.read();
+///                 assert_eq!(read.graph().vertex_len(), 1);
+///             });
+///         }
+///     });
+///
+///     let mut write = shared.write();
+///     let (graph, token) = write.parts_mut();
+///     *graph.get_vertex(first).unwrap().borrow_mut(token).get_item_mut() += 1;
+/// })
+/// ```
+pub struct SyncGraph<
+    'id,
+    Item,
+    Weight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S = DefaultHashBuilder,
+    A: Allocator = Global,
+> {
+    /// The number of [`SyncReadGuard`]s alive, or [`WRITER`] while a
+    /// [`SyncWriteGuard`] is
+    state: AtomicUsize,
+    inner: UnsafeCell<Parts<'id, Item, Weight, Edge, S, A>>,
+}
+
+// SAFETY: A graph is only held back from being `Send` by an observer
+// given with `Graph::set_observer`, and a `SyncGraph` never keeps one,
+// see `SyncGraph::new` and `SyncWriteGuard`'s `Drop`. Everything else
+// within the graph can be sent when its items, weights and edges can
+#[allow(clippy::non_send_fields_in_send_ty)]
+unsafe impl<'id, Item, Weight, Edge, S, A> Send for SyncGraph<'id, Item, Weight, Edge, S, A>
+where
+    Item: Send + Sync,
+    Weight: Send + Sync,
+    Edge: EdgeTrait<'id, Item, Weight> + Send + Sync,
+    S: Send,
+    A: Allocator + Send,
+{
+}
+
+// SAFETY: The lock only hands out shared references to the graph and
+// token to several threads at once, and mutable references to one at
+// a time, as `RwLock` does. The token was moved in, and is the only
+// one with its brand, so no thread can reach the graph's cells
+// without going through the lock
+unsafe impl<'id, Item, Weight, Edge, S, A> Sync for SyncGraph<'id, Item, Weight, Edge, S, A>
+where
+    Item: Send + Sync,
+    Weight: Send + Sync,
+    Edge: EdgeTrait<'id, Item, Weight> + Send + Sync,
+    S: Send + Sync,
+    A: Allocator + Send + Sync,
+{
+}
+
+impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator>
+    SyncGraph<'id, Item, Weight, Edge, S, A>
+{
+    /// Pairs `graph` with `token`, the token it's branded with
+    ///
+    /// As `token` is the only token with its brand, the graph's
+    /// vertices and edges can only be reached through the lock
+    ///
+    /// # Panics
+    /// Panics if the graph has an observer given with
+    /// [`Graph::set_observer`], which might not be usable from other
+    /// threads. Observers can instead be given with
+    /// [`SyncWriteGuard::set_observer`]
+    pub fn new(graph: Graph<'id, Item, Weight, Edge, S, A>, token: GhostToken<'id>) -> Self {
+        assert!(
+            !matches!(graph.observer, Some(Observer::Local(_))),
+            "a graph with an observer given with `Graph::set_observer` can't be shared between threads"
+        );
+
+        Self {
+            state: AtomicUsize::new(0),
+            inner: UnsafeCell::new((graph, token)),
+        }
+    }
+    /// Locks the graph for reading, spinning while it's locked for
+    /// writing
+    pub fn read(&self) -> SyncReadGuard<'_, 'id, Item, Weight, Edge, S, A> {
+        loop {
+            if let Some(guard) = self.try_read() {
+                return guard;
+            }
+            hint::spin_loop();
+        }
+    }
+    /// Locks the graph for reading, or returns `None` if it's locked
+    /// for writing
+    pub fn try_read(&self) -> Option<SyncReadGuard<'_, 'id, Item, Weight, Edge, S, A>> {
+        let mut state = self.state.load(Ordering::Relaxed);
+
+        while state & WRITER == 0 {
+            match self.state.compare_exchange_weak(
+                state,
+                state + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Some(SyncReadGuard { lock: self }),
+                Err(current) => state = current,
+            }
+        }
+
+        None
+    }
+    /// Locks the graph for writing, spinning while it's locked
+    pub fn write(&self) -> SyncWriteGuard<'_, 'id, Item, Weight, Edge, S, A> {
+        loop {
+            if let Some(guard) = self.try_write() {
+                return guard;
+            }
+            hint::spin_loop();
+        }
+    }
+    /// Locks the graph for writing, or returns `None` if it's locked
+    pub fn try_write(&self) -> Option<SyncWriteGuard<'_, 'id, Item, Weight, Edge, S, A>> {
+        self.state
+            .compare_exchange(0, WRITER, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| SyncWriteGuard { lock: self })
+    }
+    /// Locks the graph for writing without waiting, as `self` is
+    /// borrowed uniquely
+    pub fn get_mut(&mut self) -> SyncWriteGuard<'_, 'id, Item, Weight, Edge, S, A> {
+        *self.state.get_mut() = WRITER;
+        SyncWriteGuard { lock: self }
+    }
+    /// Consumes the [`SyncGraph`], returning the graph and its token
+    pub fn into_inner(self) -> Parts<'id, Item, Weight, Edge, S, A> {
+        self.inner.into_inner()
+    }
+}
+
+/// Shared access to the graph and token of a [`SyncGraph`], returned
+/// by [`SyncGraph::read`]
+pub struct SyncReadGuard<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator>
+{
+    lock: &'a SyncGraph<'id, Item, Weight, Edge, S, A>,
+}
+
+impl<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator>
+    SyncReadGuard<'a, 'id, Item, Weight, Edge, S, A>
+{
+    /// The locked graph
+    #[must_use]
+    pub const fn graph(&self) -> &Graph<'id, Item, Weight, Edge, S, A> {
+        // SAFETY: No write guard can exist while `self` does
+        unsafe { &(*self.lock.inner.get()).0 }
+    }
+    /// The locked graph's token
+    #[must_use]
+    pub const fn token(&self) -> &GhostToken<'id> {
+        // SAFETY: As above
+        unsafe { &(*self.lock.inner.get()).1 }
+    }
+}
+
+impl<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator> Drop
+    for SyncReadGuard<'a, 'id, Item, Weight, Edge, S, A>
+{
+    fn drop(&mut self) {
+        self.lock.state.fetch_sub(1, Ordering::Release);
+    }
+}
+
+/// Unique access to the graph and token of a [`SyncGraph`], returned
+/// by [`SyncGraph::write`]
+///
+/// An observer given to the graph with [`Graph::set_observer`] while
+/// it's locked is removed and dropped along with the guard, as it
+/// might not be usable from other threads, so observers should be
+/// given with [`SyncWriteGuard::set_observer`] instead
+pub struct SyncWriteGuard<
+    'a,
+    'id,
+    Item,
+    Weight,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S,
+    A: Allocator,
+> {
+    lock: &'a SyncGraph<'id, Item, Weight, Edge, S, A>,
+}
+
+impl<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator>
+    SyncWriteGuard<'a, 'id, Item, Weight, Edge, S, A>
+{
+    /// The locked graph
+    #[must_use]
+    pub const fn graph(&self) -> &Graph<'id, Item, Weight, Edge, S, A> {
+        // SAFETY: No other guard can exist while `self` does
+        unsafe { &(*self.lock.inner.get()).0 }
+    }
+    /// The locked graph's token
+    #[must_use]
+    pub const fn token(&self) -> &GhostToken<'id> {
+        // SAFETY: As above
+        unsafe { &(*self.lock.inner.get()).1 }
+    }
+    /// The locked graph and its token, both mutably
+    pub const fn parts_mut(
+        &mut self,
+    ) -> (
+        &mut Graph<'id, Item, Weight, Edge, S, A>,
+        &mut GhostToken<'id>,
+    ) {
+        // SAFETY: As above, and `self` is borrowed uniquely
+        let (graph, token) = unsafe { &mut *self.lock.inner.get() };
+        (graph, token)
+    }
+    /// Sets the [`GraphObserver`] told about every change made to the
+    /// graph from now on, returning the one it replaced, as with
+    /// [`Graph::set_observer`]
+    ///
+    /// Whichever thread next locks the graph for writing may tell the
+    /// observer about its changes, so it has to be [`Send`]
+    pub fn set_observer(
+        &mut self,
+        observer: impl GraphObserver<'id> + Send + 'id,
+    ) -> Option<Box<dyn GraphObserver<'id> + 'id>> {
+        self.parts_mut()
+            .0
+            .observer
+            .replace(Observer::Sendable(Box::new(observer)))
+            .map(Observer::into_box)
+    }
+}
+
+impl<'a, 'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>, S, A: Allocator> Drop
+    for SyncWriteGuard<'a, 'id, Item, Weight, Edge, S, A>
+{
+    fn drop(&mut self) {
+        let observer = &mut self.parts_mut().0.observer;
+        let local = match observer.take() {
+            Some(Observer::Local(local)) => Some(local),
+            sendable => {
+                *observer = sendable;
+                None
+            }
+        };
+
+        self.lock.state.store(0, Ordering::Release);
+        // Dropped on the thread that gave it, after unlocking in case
+        // dropping it panics
+        drop(local);
+    }
+}
@@ -11,7 +11,7 @@ use crate::{edge::EdgeTrait, id::EdgeId, Shared, VertexId};
 pub struct Vertex<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> {
     id: VertexId<'id>,
     pub(crate) edges: HashMap<EdgeId<'id>, Shared<'id, Edge>>,
-    item: Item,
+    pub(crate) item: Item,
     _phantom: &'id PhantomData<Weight>,
 }
 
@@ -26,15 +26,15 @@ impl<'id, Item, Weight, Edge: EdgeTrait<'id, Item, Weight>> Vertex<'id, Item, We
             _phantom: &PhantomData,
         }
     }
-    pub fn id(&self) -> VertexId<'id> {
+    pub const fn id(&self) -> VertexId<'id> {
         self.id
     }
     /// Gets a reference to `self`'s inner item
-    pub fn get_item(&self) -> &Item {
+    pub const fn get_item(&self) -> &Item {
         &self.item
     }
     /// Gets a mutable reference to `self`'s inner item
-    pub fn get_item_mut(&mut self) -> &mut Item {
+    pub const fn get_item_mut(&mut self) -> &mut Item {
         &mut self.item
     }
     pub fn edges(&self) -> hashbrown::hash_map::Iter<'_, EdgeId<'id>, Shared<'id, Edge>> {
@@ -0,0 +1,181 @@
+use core::{hash::BuildHasher, ops::Add};
+
+use crate::{edge::EdgeTrait, ghost::GhostToken, Allocator, EdgeId, Graph, VertexId};
+
+impl<'id, Item, Weight, Edge, S, A> Graph<'id, Item, Weight, Edge, S, A>
+where
+    Weight: Ord,
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// The sum of the weights of every edge in the graph, with each
+    /// undirected edge counted once, or the default weight if there
+    /// are no edges
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let a = graph.add_vertex(());
+    ///     let b = graph.add_vertex(());
+    ///     let c = graph.add_vertex(());
+    ///     graph.add_edge(a, b, 3, &mut token).unwrap();
+    ///     graph.add_edge(b, c, 4, &mut token).unwrap();
+    ///
+    ///     assert_eq!(graph.total_weight(&token), 7);
+    ///     assert_eq!(graph.weight_sum_at(b, &token), Some(7));
+    /// })
+    /// ```
+    #[must_use]
+    pub fn total_weight(&self, token: &GhostToken<'id>) -> Weight
+    where
+        Weight: Add<Output = Weight> + Clone + Default,
+    {
+        self.unique_edges(token)
+            .fold(Weight::default(), |total, (_, _, _, weight)| {
+                total + weight.clone()
+            })
+    }
+    /// The sum of the weights of every edge at `id`, whichever way
+    /// they lead, or `None` if `id` isn't within the graph
+    ///
+    /// A self-loop is counted once
+    #[must_use]
+    pub fn weight_sum_at(&self, id: VertexId<'id>, token: &GhostToken<'id>) -> Option<Weight>
+    where
+        Weight: Add<Output = Weight> + Clone + Default,
+    {
+        let vertex = self.get_vertex(id)?.borrow(token);
+        Some(vertex.edges().fold(Weight::default(), |total, (_, edge)| {
+            total + edge.borrow(token).get_weight().clone()
+        }))
+    }
+    /// The edge with the lowest weight, along with its weight, or
+    /// `None` if there are no edges
+    ///
+    /// Ties are broken by the lowest [`EdgeId`]
+    #[must_use]
+    pub fn min_weight_edge<'a>(
+        &'a self,
+        token: &'a GhostToken<'id>,
+    ) -> Option<(EdgeId<'id>, &'a Weight)> {
+        self.weighted_edges(token)
+            .min_by(|(one, first), (two, second)| first.cmp(second).then(one.cmp(two)))
+    }
+    /// The edge with the highest weight, along with its weight, or
+    /// `None` if there are no edges
+    ///
+    /// Ties are broken by the lowest [`EdgeId`]
+    #[must_use]
+    pub fn max_weight_edge<'a>(
+        &'a self,
+        token: &'a GhostToken<'id>,
+    ) -> Option<(EdgeId<'id>, &'a Weight)> {
+        self.weighted_edges(token)
+            .max_by(|(one, first), (two, second)| first.cmp(second).then(two.cmp(one)))
+    }
+    /// Every edge in the graph once, along with its weight
+    fn weighted_edges<'a>(
+        &'a self,
+        token: &'a GhostToken<'id>,
+    ) -> impl Iterator<Item = (EdgeId<'id>, &'a Weight)> + 'a {
+        self.unique_edges(token)
+            .map(|(id, _, _, weight)| (id, weight))
+    }
+}
+
+impl<'id, Item, Weight, Edge, S, A> Graph<'id, Item, Weight, Edge, S, A>
+where
+    Edge: EdgeTrait<'id, Item, Weight>,
+    S: BuildHasher,
+    A: Allocator,
+{
+    /// Calls `f` with the [`EdgeId`] and a mutable reference to the
+    /// weight of every edge in the graph, each exactly once, even if
+    /// it's undirected
+    ///
+    /// As with [`Graph::weights_mut`], the changes aren't recorded, so
+    /// [`Graph::touch_edge`] should be called for any edge a
+    /// [`GraphObserver`](crate::GraphObserver) needs to know about
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), f64, UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let a = graph.add_vertex(());
+    ///     let b = graph.add_vertex(());
+    ///     let ab = graph.add_edge(a, b, 8.0, &mut token).unwrap();
+    ///
+    ///     graph.for_each_edge_mut(&mut token, |_, weight| *weight *= 0.5);
+    ///     assert_eq!(graph.weights(&token).collect::<Vec<_>>(), vec![(ab, &4.0)]);
+    /// })
+    /// ```
+    pub fn for_each_edge_mut(
+        &self,
+        token: &mut GhostToken<'id>,
+        mut f: impl FnMut(EdgeId<'id>, &mut Weight),
+    ) {
+        for (id, weight) in self.weights_mut(token) {
+            f(id, weight);
+        }
+    }
+    /// Calls `f` with the [`EdgeId`] and a mutable reference to the
+    /// weight of every edge in the graph, each exactly once, splitting
+    /// the edges into chunks of `chunk_size` ordered by id, and
+    /// running the chunks in parallel using rayon
+    ///
+    /// The token is borrowed mutably throughout, so `f` can't look at
+    /// the rest of the graph. A [`FrozenGraph`](crate::FrozenGraph)
+    /// snapshot taken beforehand can be read from `f` instead, and
+    /// each weight `f` sets is written straight back to the graph.
+    /// As with [`Graph::weights_mut`], the changes aren't recorded
+    ///
+    /// ```rust
+    /// use graph::{edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph};
+    ///
+    /// GhostToken::new(|mut token| {
+    ///     let mut graph: Graph<(), f64, UnDirectedWeightedEdge<_, _>> = Graph::new();
+    ///     let hub = graph.add_vertex(());
+    ///     for _ in 0..3 {
+    ///         let spoke = graph.add_vertex(());
+    ///         graph.add_edge(hub, spoke, 1.0, &mut token).unwrap();
+    ///     }
+    ///
+    ///     // Edges at busier vertices decay faster
+    ///     let frozen = graph.freeze(&token);
+    ///     let busiest = frozen.index_of(hub).map(|i| frozen.degree(i)).unwrap();
+    ///     graph.par_for_each_edge_mut(2, &mut token, |_, weight| {
+    ///         *weight /= busiest as f64;
+    ///     });
+    ///
+    ///     assert!(graph.weights(&token).all(|(_, weight)| *weight == 1.0 / 3.0));
+    /// })
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is `0`
+    #[cfg(feature = "rayon")]
+    pub fn par_for_each_edge_mut(
+        &self,
+        chunk_size: usize,
+        token: &mut GhostToken<'id>,
+        f: impl Fn(EdgeId<'id>, &mut Weight) + Sync,
+    ) where
+        Weight: Send,
+    {
+        use alloc::vec::Vec;
+        use rayon::prelude::*;
+
+        let mut weights: Vec<_> = self.weights_mut(token).collect();
+        weights.sort_unstable_by_key(|(id, _)| *id);
+
+        weights.par_chunks_mut(chunk_size).for_each(|chunk| {
+            for (id, weight) in chunk {
+                f(*id, weight);
+            }
+        });
+    }
+}
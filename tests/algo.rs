@@ -0,0 +1,2410 @@
+use graph::{
+    algo::{
+        articulation_points, biconnected_components, block_cut_tree, compare_snapshots,
+        connected_components, dial, dijkstra, ear_decomposition, earliest_arrival,
+        find_subgraph_isomorphisms, k_shortest_paths, nearest_matching, open_ear_decomposition,
+        resource_constrained_path, strongly_connected_components, topological_sort,
+        tree::{self, RootedTree},
+        Acyclic, Algorithm, BlockCutNode, ComponentChange, ContractionHierarchy, DfsTree,
+        DynamicConnectivity, EdgeClass, Matching, MinCut, Outcome, Registry, TopologicalOrder,
+    },
+    edge::{DirectedWeightedEdge, UnDirectedWeightedEdge},
+    ghost::GhostToken,
+    FiniteWeight, Graph, Measure, SharedObserver,
+};
+
+#[test]
+fn dfs_low_link() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let one = graph.add_vertex(());
+        let two = graph.add_vertex(());
+        let three = graph.add_vertex(());
+        let four = graph.add_vertex(());
+
+        graph.add_edge(one, two, (), &mut t).unwrap();
+        graph.add_edge(two, three, (), &mut t).unwrap();
+        graph.add_edge(three, one, (), &mut t).unwrap();
+        graph.add_edge(three, four, (), &mut t).unwrap();
+
+        let tree = DfsTree::new(&graph, one, &t);
+
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.index(one), Some(0));
+        assert!(tree.parent(one).is_none());
+        // `four` hangs off the cycle by a bridge
+        assert_eq!(tree.low(four), tree.index(four));
+        assert_eq!(tree.low(two), Some(0));
+        assert_eq!(tree.low(three), Some(0));
+    });
+}
+
+#[test]
+fn ear_decomposition_of_theta() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let vertices: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+
+        // A square with a path of length two across it
+        graph
+            .add_edge(vertices[0], vertices[1], (), &mut t)
+            .unwrap();
+        graph
+            .add_edge(vertices[1], vertices[2], (), &mut t)
+            .unwrap();
+        graph
+            .add_edge(vertices[2], vertices[3], (), &mut t)
+            .unwrap();
+        graph
+            .add_edge(vertices[3], vertices[0], (), &mut t)
+            .unwrap();
+        graph
+            .add_edge(vertices[0], vertices[4], (), &mut t)
+            .unwrap();
+        graph
+            .add_edge(vertices[4], vertices[2], (), &mut t)
+            .unwrap();
+
+        let ears = open_ear_decomposition(&graph, &t).unwrap();
+
+        assert_eq!(ears.len(), graph.edge_len() - graph.vertex_len() + 1);
+        assert!(ears[0].is_cycle());
+        assert!(ears.iter().skip(1).all(|ear| !ear.is_cycle()));
+        assert_eq!(
+            ears.iter().map(|ear| ear.edges.len()).sum::<usize>(),
+            graph.edge_len()
+        );
+    });
+}
+
+#[test]
+fn ear_decomposition_rejects_bridges() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let vertices: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+
+        // Two triangles sharing `vertices[0]`
+        graph
+            .add_edge(vertices[0], vertices[1], (), &mut t)
+            .unwrap();
+        graph
+            .add_edge(vertices[1], vertices[2], (), &mut t)
+            .unwrap();
+        graph
+            .add_edge(vertices[2], vertices[0], (), &mut t)
+            .unwrap();
+        graph
+            .add_edge(vertices[0], vertices[3], (), &mut t)
+            .unwrap();
+        graph
+            .add_edge(vertices[3], vertices[4], (), &mut t)
+            .unwrap();
+        graph
+            .add_edge(vertices[4], vertices[0], (), &mut t)
+            .unwrap();
+
+        assert!(ear_decomposition(&graph, &t).is_some());
+        assert!(open_ear_decomposition(&graph, &t).is_none());
+
+        let extra = graph.add_vertex(());
+        graph.add_edge(vertices[1], extra, (), &mut t).unwrap();
+
+        assert!(ear_decomposition(&graph, &t).is_none());
+    });
+}
+
+#[test]
+fn biconnected_components_and_block_cut_tree() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..6).map(|_| graph.add_vertex(())).collect();
+
+        // Two triangles sharing `v[0]`, with a bridge from `v[2]` to `v[5]`
+        graph.add_edge(v[0], v[1], (), &mut t).unwrap();
+        graph.add_edge(v[1], v[2], (), &mut t).unwrap();
+        graph.add_edge(v[2], v[0], (), &mut t).unwrap();
+        graph.add_edge(v[0], v[3], (), &mut t).unwrap();
+        graph.add_edge(v[3], v[4], (), &mut t).unwrap();
+        graph.add_edge(v[4], v[0], (), &mut t).unwrap();
+        graph.add_edge(v[2], v[5], (), &mut t).unwrap();
+
+        let mut blocks = biconnected_components(&graph, &t);
+        blocks.sort_by_key(|block| block.edges.len());
+
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].edges.len(), 1);
+        assert_eq!(blocks[1].edges.len(), 3);
+        assert_eq!(blocks[2].edges.len(), 3);
+        assert_eq!(blocks[2].vertices.len(), 3);
+
+        let mut cuts = articulation_points(&graph, &t);
+        cuts.sort_by_key(|id| id.id());
+        assert_eq!(cuts, vec![v[0], v[2]]);
+
+        GhostToken::new(|mut tree_token| {
+            let tree = block_cut_tree(&graph, &t, &mut tree_token);
+
+            assert_eq!(tree.vertex_len(), 5);
+            assert_eq!(tree.edge_len(), 4);
+
+            let blocks = tree
+                .vertices()
+                .filter(|(_, node)| {
+                    matches!(node.borrow(&tree_token).get_item(), BlockCutNode::Block(_))
+                })
+                .count();
+            assert_eq!(blocks, 3);
+        });
+    });
+}
+
+#[test]
+fn earliest_arrival_waits_for_departures() {
+    GhostToken::new(|mut t| {
+        // Each edge departs every `interval` minutes, taking `duration`
+        let mut graph: Graph<(), (u32, u32), DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let home = graph.add_vertex(());
+        let station = graph.add_vertex(());
+        let work = graph.add_vertex(());
+
+        // Walking, always available
+        graph.add_edge(home, work, (1, 28), &mut t).unwrap();
+        graph.add_edge(home, station, (1, 5), &mut t).unwrap();
+        // A train every 15 minutes
+        graph.add_edge(station, work, (15, 10), &mut t).unwrap();
+
+        let arrive = |&(interval, duration): &(u32, u32), time: u32| {
+            let wait = (interval - time % interval) % interval;
+            Some(time + wait + duration)
+        };
+
+        let from_zero = earliest_arrival(&graph, home, 0, arrive, &t).unwrap();
+        assert_eq!(from_zero.arrival(work), Some(25));
+        assert_eq!(from_zero.path_to(work).unwrap(), vec![home, station, work]);
+
+        // Just missing the train makes walking quicker
+        let from_eleven = earliest_arrival(&graph, home, 11, arrive, &t).unwrap();
+        assert_eq!(from_eleven.arrival(work), Some(39));
+        assert_eq!(from_eleven.path_to(work).unwrap(), vec![home, work]);
+
+        assert_eq!(from_zero.arrival(home), Some(0));
+    });
+}
+
+#[test]
+fn compare_two_snapshots() {
+    GhostToken::new(|mut t| {
+        let mut before: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let mut after: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..4).map(|_| before.add_vertex(())).collect();
+        (0..4).for_each(|_| {
+            after.add_vertex(());
+        });
+
+        // A path before, and a star around `v[0]` after
+        before.add_edge(v[0], v[1], (), &mut t).unwrap();
+        before.add_edge(v[1], v[2], (), &mut t).unwrap();
+
+        after.add_edge(v[1], v[0], (), &mut t).unwrap();
+        after.add_edge(v[0], v[2], (), &mut t).unwrap();
+        after.add_edge(v[0], v[3], (), &mut t).unwrap();
+
+        let report = compare_snapshots(&before, &after, 1, &t);
+
+        assert_eq!(report.added_edges, 2);
+        assert_eq!(report.removed_edges, 1);
+        assert!((report.edge_churn - 0.75).abs() < 1e-9);
+        assert_eq!(report.degree_changes, vec![(v[0], 2)]);
+        assert_eq!(
+            report.component_changes,
+            vec![ComponentChange {
+                before: 3,
+                after: 4
+            }]
+        );
+        assert!(report.degree_divergence > 0.);
+
+        let same = compare_snapshots(&before, &before, 10, &t);
+        assert_eq!(same.edge_churn, 0.);
+        assert_eq!(same.degree_divergence, 0.);
+        assert!(same.degree_changes.is_empty());
+    });
+}
+
+#[test]
+fn connected_components_of_forest() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+
+        graph.add_edge(v[1], v[0], (), &mut t).unwrap();
+        graph.add_edge(v[1], v[2], (), &mut t).unwrap();
+        graph.add_edge(v[3], v[4], (), &mut t).unwrap();
+
+        let mut sizes: Vec<_> = connected_components(&graph, &t)
+            .iter()
+            .map(Vec::len)
+            .collect();
+        sizes.sort_unstable();
+
+        assert_eq!(sizes, vec![2, 3]);
+    });
+}
+
+#[test]
+fn acyclic_critical_path() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+
+        graph.add_edge(v[0], v[1], 3, &mut t).unwrap();
+        graph.add_edge(v[0], v[2], 2, &mut t).unwrap();
+        graph.add_edge(v[1], v[3], 4, &mut t).unwrap();
+        graph.add_edge(v[2], v[3], 6, &mut t).unwrap();
+        graph.add_edge(v[3], v[4], 1, &mut t).unwrap();
+
+        let order = topological_sort(&graph, &t).unwrap();
+        assert_eq!(order.first(), Some(&v[0]));
+        assert_eq!(order.last(), Some(&v[4]));
+
+        let dag = Acyclic::new(&graph, &t).ok().unwrap();
+        let (length, path) = dag.critical_path(&t).unwrap();
+
+        assert_eq!(length, 9);
+        assert_eq!(path, vec![v[0], v[2], v[3], v[4]]);
+
+        graph.add_edge(v[4], v[0], 1, &mut t).unwrap();
+
+        assert_eq!(topological_sort(&graph, &t).unwrap_err().len(), 5);
+        assert!(Acyclic::new(graph, &t).is_err());
+    });
+}
+
+#[test]
+fn incremental_topological_order() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..6).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(v[0], v[1], (), &mut t).unwrap();
+        graph.add_edge(v[1], v[2], (), &mut t).unwrap();
+
+        let mut order = TopologicalOrder::from_graph(&graph, &t).unwrap();
+        assert_eq!(order.len(), 6);
+
+        let edges = [(v[5], v[0]), (v[3], v[1]), (v[4], v[5]), (v[2], v[3])];
+        assert!(order.add_edge(edges[0].0, edges[0].1).is_ok());
+        assert!(order.add_edge(edges[1].0, edges[1].1).is_ok());
+        assert!(order.add_edge(edges[2].0, edges[2].1).is_ok());
+
+        for (from, to) in &edges[..3] {
+            assert!(order.position(*from) < order.position(*to));
+        }
+
+        assert_eq!(
+            order.add_edge(edges[3].0, edges[3].1),
+            Err(vec![v[3], v[1], v[2]])
+        );
+        assert_eq!(order.add_edge(v[4], v[4]), Err(vec![v[4]]));
+
+        assert!(order.add_edge(v[4], v[3]).is_ok());
+        let positions: Vec<_> = order.order().iter().map(|id| order.position(*id)).collect();
+        assert_eq!(positions, (0..6).map(Some).collect::<Vec<_>>());
+        assert!(order.position(v[4]) < order.position(v[3]));
+        assert!(order.position(v[0]) < order.position(v[2]));
+    });
+}
+
+#[test]
+fn resource_constrained_route() {
+    GhostToken::new(|mut t| {
+        // Each edge is weighted by (minutes, charge used)
+        let mut graph: Graph<(), (u32, u32), DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+
+        graph.add_edge(v[0], v[1], (10, 8), &mut t).unwrap();
+        graph.add_edge(v[1], v[3], (10, 8), &mut t).unwrap();
+        graph.add_edge(v[0], v[2], (15, 3), &mut t).unwrap();
+        graph.add_edge(v[2], v[3], (15, 3), &mut t).unwrap();
+        graph.add_edge(v[0], v[3], (50, 1), &mut t).unwrap();
+
+        let measure = |weight: &(u32, u32)| *weight;
+
+        let fastest = resource_constrained_path(&graph, v[0], v[3], 20, measure, &t)
+            .unwrap()
+            .unwrap();
+        assert_eq!((fastest.cost, fastest.resource), (20, 16));
+        assert_eq!(fastest.vertices, vec![v[0], v[1], v[3]]);
+        assert_eq!(fastest.edges.len(), 2);
+
+        let frugal = resource_constrained_path(&graph, v[0], v[3], 10, measure, &t)
+            .unwrap()
+            .unwrap();
+        assert_eq!((frugal.cost, frugal.resource), (30, 6));
+        assert_eq!(frugal.vertices, vec![v[0], v[2], v[3]]);
+
+        let tight = resource_constrained_path(&graph, v[0], v[3], 2, measure, &t)
+            .unwrap()
+            .unwrap();
+        assert_eq!(tight.vertices, vec![v[0], v[3]]);
+
+        assert!(
+            resource_constrained_path(&graph, v[0], v[3], 0, measure, &t)
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            resource_constrained_path(&graph, v[3], v[0], 100, measure, &t)
+                .unwrap()
+                .is_none()
+        );
+    });
+}
+
+#[test]
+fn dial_matches_dijkstra() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), usize, DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..6).map(|_| graph.add_vertex(())).collect();
+        let edges = [
+            (0, 1, 10),
+            (0, 2, 30),
+            (1, 2, 10),
+            (1, 3, 40),
+            (2, 3, 10),
+            (3, 4, 0),
+            (4, 1, 20),
+        ];
+        for (from, to, weight) in edges {
+            graph.add_edge(v[from], v[to], weight, &mut t).unwrap();
+        }
+
+        let heap = dijkstra(&graph, v[0], |w| *w, &t).unwrap();
+        let buckets = dial(&graph, v[0], |w| *w, &t).unwrap();
+
+        for id in &v {
+            assert_eq!(heap.distance(*id), buckets.distance(*id));
+        }
+
+        assert_eq!(buckets.distance(v[4]), Some(30));
+        assert_eq!(
+            buckets.path_to(v[4]),
+            Some(vec![v[0], v[1], v[2], v[3], v[4]])
+        );
+        assert_eq!(buckets.distance(v[5]), None);
+        assert!(dial(&graph, graph::VertexId::new(9), |w| *w, &t).is_err());
+
+        // Too many buckets for the longest edge to fit is an error
+        for longest in [usize::MAX, usize::MAX / 2] {
+            assert!(matches!(
+                dial(&graph, v[0], |_| longest, &t),
+                Err(graph::GraphError::AllocationFailed(_))
+            ));
+        }
+    });
+}
+
+#[test]
+fn dijkstra_with_measures() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), f64, DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        let edges = [(0, 1, 0.5), (1, 2, 0.25), (0, 2, 1.0), (2, 3, 1.5)];
+        for (from, to, weight) in edges {
+            graph.add_edge(v[from], v[to], weight, &mut t).unwrap();
+        }
+
+        let paths = dijkstra(&graph, v[0], |w| *w, &t).unwrap();
+        assert_eq!(paths.distance(v[2]), Some(0.75));
+        assert_eq!(paths.distance(v[3]), Some(2.25));
+        assert_eq!(paths.path_to(v[3]), Some(vec![v[0], v[1], v[2], v[3]]));
+
+        // Lengths which would overflow saturate instead
+        let paths = dijkstra(&graph, v[0], |_| u8::MAX / 2 + 1, &t).unwrap();
+        assert_eq!(paths.distance(v[2]), Some(128));
+        assert_eq!(paths.distance(v[3]), Some(u8::MAX));
+    });
+}
+
+#[test]
+fn finite_weights() {
+    let weight = |w: f64| FiniteWeight::new(w).unwrap();
+
+    assert!(FiniteWeight::new(f64::NAN).is_none());
+    assert!(FiniteWeight::new(f32::INFINITY).is_none());
+    assert_eq!(weight(-0.0), weight(0.0));
+    assert_eq!(
+        weight(f64::MAX).saturating_add(weight(f64::MAX)),
+        weight(f64::MAX)
+    );
+
+    let mut sorted = vec![weight(1.5), weight(-2.0), weight(0.0)];
+    sorted.sort();
+    assert_eq!(sorted, vec![weight(-2.0), weight(0.0), weight(1.5)]);
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), FiniteWeight<f64>, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..3).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(v[0], v[1], weight(0.5), &mut t).unwrap();
+        graph.add_edge(v[1], v[2], weight(0.5), &mut t).unwrap();
+        graph.add_edge(v[0], v[2], weight(1.5), &mut t).unwrap();
+
+        let paths = dijkstra(&graph, v[0], |w| *w, &t).unwrap();
+        assert_eq!(paths.distance(v[2]), Some(weight(1.0)));
+        assert_eq!(f64::from(paths.distance(v[2]).unwrap()), 1.0);
+    });
+}
+
+#[test]
+fn centrality_of_path_and_star() {
+    use graph::algo::centrality;
+
+    GhostToken::new(|mut t| {
+        // A path a - b - c - d
+        let mut graph: Graph<(), f64, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        for pair in v.windows(2) {
+            graph.add_edge(pair[0], pair[1], 1.0, &mut t).unwrap();
+        }
+
+        let between = centrality::betweenness(&graph, |_| 1.0, &t);
+        assert_eq!(between[&v[0]], 0.0);
+        assert_eq!(between[&v[1]], 2.0);
+        assert_eq!(between[&v[2]], 2.0);
+
+        let close = centrality::closeness(&graph, |w| *w, &t);
+        assert!((close[&v[0]] - 0.5).abs() < 1e-12);
+        assert!((close[&v[1]] - 0.75).abs() < 1e-12);
+
+        let degree = centrality::degree(&graph, &t);
+        assert!((degree[&v[1]] - 2.0 / 3.0).abs() < 1e-12);
+
+        let rank = centrality::page_rank(&graph, 0.85, 1e-12, 1000, &t);
+        assert!((rank.values().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(rank[&v[1]] > rank[&v[0]]);
+        assert!((rank[&v[1]] - rank[&v[2]]).abs() < 1e-9);
+    });
+
+    GhostToken::new(|mut t| {
+        // A diamond with two equally short routes from s to e
+        let mut graph: Graph<(), f64, DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(v[0], v[1], 1.0, &mut t).unwrap();
+        graph.add_edge(v[0], v[2], 2.0, &mut t).unwrap();
+        graph.add_edge(v[1], v[3], 2.0, &mut t).unwrap();
+        graph.add_edge(v[2], v[3], 1.0, &mut t).unwrap();
+
+        let between = centrality::betweenness(&graph, |w| *w, &t);
+        assert_eq!(between[&v[1]], 0.5);
+        assert_eq!(between[&v[2]], 0.5);
+
+        let unweighted = centrality::betweenness(&graph, |_| 1.0, &t);
+        assert_eq!(unweighted[&v[1]], 0.5);
+    });
+}
+
+#[test]
+fn algorithm_registry() {
+    type Directed<'id> = Graph<'id, (), (), DirectedWeightedEdge<'id, (), ()>>;
+
+    struct EdgeCount;
+
+    impl<'id> Algorithm<'id, Directed<'id>, Outcome<'id>> for EdgeCount {
+        fn name(&self) -> &str {
+            "edge_count"
+        }
+        fn description(&self) -> &str {
+            "Scores every vertex by the number of edges in the graph"
+        }
+        fn run(&self, graph: &Directed<'id>, _: &GhostToken<'id>) -> Outcome<'id> {
+            #[allow(clippy::cast_precision_loss)]
+            let edges = graph.edge_len() as f64;
+            Outcome::Scores(graph.vertex_ids().map(|id| (id, edges)).collect())
+        }
+    }
+
+    GhostToken::new(|mut t| {
+        let mut graph: Directed = Graph::new();
+
+        let v: Vec<_> = (0..3).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(v[0], v[1], (), &mut t).unwrap();
+        graph.add_edge(v[1], v[2], (), &mut t).unwrap();
+
+        let mut registry = Registry::with_builtins();
+        assert!(registry.names().contains(&"page_rank"));
+        assert!(registry.run("missing", &graph, &t).is_none());
+
+        assert_eq!(
+            registry.run("topological_sort", &graph, &t),
+            Some(Outcome::Order(v.clone()))
+        );
+
+        assert!(registry.register(EdgeCount).is_none());
+        assert!(!registry.get("edge_count").unwrap().description().is_empty());
+        let Some(Outcome::Scores(scores)) = registry.run("edge_count", &graph, &t) else {
+            panic!("expected scores");
+        };
+        assert_eq!(scores[&v[2]], 2.0);
+
+        registry.register_fn("first", "The first vertex", |graph, _| {
+            Outcome::Order(graph.vertex_ids().min().into_iter().collect())
+        });
+        assert_eq!(
+            registry.run("first", &graph, &t),
+            Some(Outcome::Order(vec![v[0]]))
+        );
+
+        assert!(registry.unregister("first").is_some());
+        assert_eq!(registry.len(), 6);
+    });
+}
+
+#[test]
+fn yen_k_shortest_paths() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<char, u32, DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let ids: Vec<_> = "CDEFGH".chars().map(|c| graph.add_vertex(c)).collect();
+        let id = |c: char| ids["CDEFGH".find(c).unwrap()];
+        for (from, to, weight) in [
+            ('C', 'D', 3),
+            ('C', 'E', 2),
+            ('D', 'F', 4),
+            ('E', 'D', 1),
+            ('E', 'F', 2),
+            ('E', 'G', 3),
+            ('F', 'G', 2),
+            ('F', 'H', 1),
+            ('G', 'H', 2),
+        ] {
+            graph.add_edge(id(from), id(to), weight, &mut t).unwrap();
+        }
+
+        let paths = k_shortest_paths(&graph, id('C'), id('H'), 3, |w| *w, &t).unwrap();
+        let lengths: Vec<_> = paths.iter().map(|path| path.length).collect();
+        assert_eq!(lengths, vec![5, 7, 8]);
+        assert_eq!(paths[0].vertices, vec![id('C'), id('E'), id('F'), id('H')]);
+        assert_eq!(paths[1].vertices, vec![id('C'), id('E'), id('G'), id('H')]);
+        for path in &paths {
+            assert_eq!(path.edges.len() + 1, path.vertices.len());
+        }
+
+        let all = k_shortest_paths(&graph, id('C'), id('H'), 100, |w| *w, &t).unwrap();
+        assert_eq!(all.len(), 7);
+        assert!(all.windows(2).all(|pair| pair[0].length <= pair[1].length));
+
+        assert!(k_shortest_paths(&graph, id('H'), id('C'), 3, |w| *w, &t)
+            .unwrap()
+            .is_empty());
+    });
+}
+
+#[test]
+fn nearest_matching_vertex() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<bool, u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        // 0 - 1 - 2 - 3, with 4 hanging far off 0
+        let v: Vec<_> = [false, false, true, true, true]
+            .iter()
+            .map(|item| graph.add_vertex(*item))
+            .collect();
+        graph.add_edge(v[0], v[1], 1, &mut t).unwrap();
+        graph.add_edge(v[1], v[2], 2, &mut t).unwrap();
+        graph.add_edge(v[2], v[3], 1, &mut t).unwrap();
+        graph.add_edge(v[0], v[4], 10, &mut t).unwrap();
+
+        let found = nearest_matching(&graph, v[0], |w| *w, |_, item| *item, None, &t)
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.length, 3);
+        assert_eq!(found.vertices, vec![v[0], v[1], v[2]]);
+        assert_eq!(found.edges.len(), 2);
+
+        let found = nearest_matching(&graph, v[0], |w| *w, |_, item| *item, Some(2), &t).unwrap();
+        assert_eq!(found, None);
+
+        let found = nearest_matching(&graph, v[3], |w| *w, |_, item| *item, None, &t)
+            .unwrap()
+            .unwrap();
+        assert_eq!((found.length, found.vertices), (0, vec![v[3]]));
+
+        let found = nearest_matching(&graph, v[1], |w| *w, |id, _| id == v[4], Some(11), &t)
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.length, 11);
+    });
+}
+
+#[test]
+fn contraction_hierarchy_matches_dijkstra() {
+    use graph::{
+        edge::EdgeTrait,
+        generators::{erdos_renyi, grid, SplitMix64},
+    };
+
+    let length = |i: usize, j: usize| (i * 7 + j * 13) as u32 % 19 + 1;
+
+    GhostToken::new(|mut t| {
+        let grid: Graph<(), u32, UnDirectedWeightedEdge<_, _>> =
+            grid(6, 6, |_, _| (), length, &mut t).unwrap();
+        let random: Graph<(), u32, DirectedWeightedEdge<_, _>> =
+            erdos_renyi(30, 0.15, &mut SplitMix64::new(7), |_| (), length, &mut t).unwrap();
+
+        macro_rules! compare {
+            ($graph:expr) => {{
+                let graph = &$graph;
+                let hierarchy = ContractionHierarchy::build(graph, |w| *w, &t);
+                let ids: Vec<_> = graph.vertex_ids().collect();
+
+                for &from in &ids {
+                    let paths = dijkstra(graph, from, |w| *w, &t).unwrap();
+                    for &to in &ids {
+                        let path = hierarchy.query(from, to);
+                        assert_eq!(path.as_ref().map(|p| p.length), paths.distance(to));
+
+                        if let Some(path) = path {
+                            assert_eq!(path.vertices.first(), Some(&from));
+                            assert_eq!(path.vertices.last(), Some(&to));
+                            assert_eq!(path.edges.len() + 1, path.vertices.len());
+                            let total: u32 = path
+                                .edges
+                                .iter()
+                                .map(|e| *graph.get_edge(*e).unwrap().borrow(&t).get_weight())
+                                .sum();
+                            assert_eq!(total, path.length);
+                        }
+                    }
+                }
+            }};
+        }
+
+        compare!(grid);
+        compare!(random);
+    });
+}
+
+#[test]
+fn maximal_cliques() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let ids: Vec<_> = (0..6).map(|_| graph.add_vertex(())).collect();
+        // A triangle with a tail, a separate edge, and a lone vertex
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (2, 3)] {
+            graph.add_edge(ids[a], ids[b], (), &mut t).unwrap();
+        }
+        let lone = graph.add_vertex(());
+        graph.add_edge(ids[4], ids[5], (), &mut t).unwrap();
+
+        let mut cliques: Vec<_> = graph.maximal_cliques(&t).collect();
+        cliques.sort();
+
+        assert_eq!(
+            cliques,
+            vec![
+                vec![ids[0], ids[1], ids[2]],
+                vec![ids[2], ids[3]],
+                vec![ids[4], ids[5]],
+                vec![lone],
+            ]
+        );
+
+        let empty: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        assert_eq!(empty.maximal_cliques(&t).next(), None);
+    });
+}
+
+#[test]
+fn triangles_and_clustering() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let ids: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+        // Two triangles sharing the edge 1-2, with 4 hanging off 3
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (3, 1), (2, 3), (3, 4)] {
+            graph.add_edge(ids[a], ids[b], (), &mut t).unwrap();
+        }
+
+        assert_eq!(graph.triangle_count(&t), 2);
+        assert_eq!(graph.clustering_coefficient(ids[0], &t), Some(1.0));
+        assert_eq!(graph.clustering_coefficient(ids[1], &t), Some(2.0 / 3.0));
+        assert_eq!(graph.clustering_coefficient(ids[3], &t), Some(1.0 / 3.0));
+        assert_eq!(graph.clustering_coefficient(ids[4], &t), Some(0.0));
+
+        let average = (1.0 + 2.0 / 3.0 + 2.0 / 3.0 + 1.0 / 3.0) / 5.0;
+        assert!((graph.average_clustering(&t) - average).abs() < 1e-12);
+
+        graph.remove(ids[4], &mut t).unwrap();
+        assert_eq!(graph.clustering_coefficient(ids[4], &t), None);
+    });
+}
+
+#[test]
+fn eulerian_and_hamiltonian() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let ids: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+        // A bowtie of two triangles meeting at 2
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2)] {
+            graph.add_edge(ids[a], ids[b], (), &mut t).unwrap();
+        }
+
+        let circuit = graph.eulerian_circuit(&t).unwrap();
+        assert_eq!(circuit.edges.len(), 6);
+        assert_eq!(circuit.vertices.first(), circuit.vertices.last());
+        for (i, edge) in circuit.edges.iter().enumerate() {
+            let (a, b) = graph.endpoints(*edge, &t).unwrap();
+            let (from, to) = (circuit.vertices[i], circuit.vertices[i + 1]);
+            assert!((a, b) == (from, to) || (b, a) == (from, to));
+        }
+
+        let hamiltonian = graph.hamiltonian_path(100, &t).unwrap();
+        assert_eq!(hamiltonian.len(), 5);
+        assert!(graph.hamiltonian_path(0, &t).is_none());
+
+        // Taking away an edge leaves two odd vertices to run between
+        graph.remove_edge_between(ids[3], ids[4], &mut t).unwrap();
+        assert!(graph.eulerian_circuit(&t).is_none());
+        let path = graph.eulerian_path(&t).unwrap();
+        assert_eq!(path.edges.len(), 5);
+        assert_eq!(path.vertices[0], ids[3]);
+        assert_eq!(path.vertices[5], ids[4]);
+
+        // 3 and 4 now both hang off 2, so only one of them can be at
+        // an end of a path through every vertex
+        assert!(graph.hamiltonian_path(1000, &t).is_none());
+
+        // Edges that aren't all connected can't be walked in one go
+        let lone = graph.add_vertex(());
+        let other = graph.add_vertex(());
+        graph.add_edge(lone, other, (), &mut t).unwrap();
+        assert!(!graph.has_eulerian_path(&t));
+    });
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let ids: Vec<_> = (0..3).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(ids[0], ids[1], (), &mut t).unwrap();
+        graph.add_edge(ids[1], ids[2], (), &mut t).unwrap();
+
+        assert!(graph.eulerian_circuit(&t).is_none());
+        assert_eq!(graph.eulerian_path(&t).unwrap().vertices, ids);
+        assert_eq!(graph.hamiltonian_path(10, &t), Some(ids.clone()));
+
+        graph.add_edge(ids[2], ids[0], (), &mut t).unwrap();
+        assert_eq!(graph.eulerian_circuit(&t).unwrap().edges.len(), 3);
+    });
+}
+
+#[test]
+fn condensation() {
+    use graph::ops::GraphOps;
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let ids: Vec<_> = (0..6).map(|_| graph.add_vertex(())).collect();
+        // Two cycles joined by two edges, leading on to 5
+        let mut edges = Vec::new();
+        for (a, b) in [
+            (0, 1),
+            (1, 0),
+            (2, 3),
+            (3, 4),
+            (4, 2),
+            (1, 2),
+            (0, 3),
+            (4, 5),
+        ] {
+            edges.push(graph.add_edge(ids[a], ids[b], (), &mut t).unwrap());
+        }
+
+        let components = strongly_connected_components(&graph, &t);
+        assert_eq!(
+            components,
+            vec![
+                vec![ids[0], ids[1]],
+                vec![ids[2], ids[3], ids[4]],
+                vec![ids[5]],
+            ]
+        );
+
+        let dag: Graph<_, _, DirectedWeightedEdge<_, _>> = graph.condensation(&mut t).unwrap();
+        assert_eq!(dag.vertex_len(), 3);
+        assert_eq!(dag.edge_len(), 2);
+        let Ok(dag) = Acyclic::new(&dag, &t) else {
+            panic!("the condensation has a cycle");
+        };
+        let order = dag.topological_sort(&t);
+        let dag = dag.into_inner();
+        let members: Vec<_> = order
+            .iter()
+            .map(|id| dag.item(*id, &t).unwrap().clone())
+            .collect();
+        assert_eq!(members, components);
+
+        let weight = dag.weight_between(order[0], order[1], &t).unwrap();
+        assert_eq!(weight, &vec![edges[5], edges[6]]);
+    });
+}
+
+#[test]
+fn distances_from() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let ids: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+        for (a, b, w) in [(0, 1, 4), (0, 2, 1), (2, 1, 1), (1, 3, 5)] {
+            graph.add_edge(ids[a], ids[b], w, &mut t).unwrap();
+        }
+
+        let distances = graph.distances_from(ids[0], &t).unwrap();
+        assert_eq!(distances.len(), 4);
+        assert_eq!(distances[&ids[0]], 0);
+        assert_eq!(distances[&ids[1]], 2);
+        assert_eq!(distances[&ids[3]], 7);
+        assert!(!distances.contains_key(&ids[4]));
+
+        let hops = graph.hops_from(ids[0], &t).unwrap();
+        assert_eq!(hops[&ids[1]], 1);
+        assert_eq!(hops[&ids[3]], 2);
+
+        assert_eq!(
+            graph.bfs_layers(ids[0], &t).unwrap(),
+            vec![vec![ids[0]], vec![ids[1], ids[2]], vec![ids[3]]]
+        );
+
+        graph.remove(ids[4], &mut t).unwrap();
+        assert!(graph.hops_from(ids[4], &t).is_err());
+    });
+}
+
+#[test]
+fn bounded_traversal() {
+    use graph::algo::TraversalOptions;
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+
+        // A chain 0 -> 1 -> 2 -> 3 -> 4, with a shortcut 0 -> 3 that
+        // only the heavier edges filter keeps out
+        let ids: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+        for i in 0..4 {
+            graph.add_edge(ids[i], ids[i + 1], 1, &mut t).unwrap();
+        }
+        graph.add_edge(ids[0], ids[3], 5, &mut t).unwrap();
+
+        let all = TraversalOptions::new();
+        assert_eq!(
+            graph.bfs(ids[0], &all, &t).unwrap(),
+            vec![ids[0], ids[1], ids[3], ids[2], ids[4]]
+        );
+        assert_eq!(graph.dfs(ids[0], &all, &t).unwrap(), ids);
+
+        let shallow = TraversalOptions::new().max_depth(1);
+        assert_eq!(
+            graph.bfs(ids[0], &shallow, &t).unwrap(),
+            vec![ids[0], ids[1], ids[3]]
+        );
+        assert_eq!(
+            graph.dfs(ids[0], &shallow, &t).unwrap(),
+            vec![ids[0], ids[1], ids[3]]
+        );
+        let none = TraversalOptions::new().max_depth(0);
+        assert_eq!(graph.dfs(ids[0], &none, &t).unwrap(), vec![ids[0]]);
+
+        let few = TraversalOptions::new().max_visited(2);
+        assert_eq!(graph.bfs(ids[0], &few, &t).unwrap().len(), 2);
+        assert_eq!(graph.dfs(ids[0], &few, &t).unwrap(), vec![ids[0], ids[1]]);
+
+        let light = TraversalOptions::new().edge_filter(|weight: &u32| *weight == 1);
+        assert_eq!(
+            graph.bfs_layers_with(ids[0], &light, &t).unwrap(),
+            vec![
+                vec![ids[0]],
+                vec![ids[1]],
+                vec![ids[2]],
+                vec![ids[3]],
+                vec![ids[4]]
+            ]
+        );
+
+        let target = ids[4];
+        let found = graph.iterative_deepening(ids[0], |id| id == target, &all, &t);
+        assert_eq!(found.unwrap(), Some(vec![ids[0], ids[3], ids[4]]));
+        let found = graph.iterative_deepening(ids[0], |id| id == target, &light, &t);
+        assert_eq!(found.unwrap().map(|path| path.len()), Some(5));
+        let found = graph.iterative_deepening(ids[0], |id| id == target, &shallow, &t);
+        assert_eq!(found.unwrap(), None);
+        let found = graph.iterative_deepening(ids[4], |id| id == ids[0], &all, &t);
+        assert_eq!(found.unwrap(), None);
+    });
+}
+
+#[test]
+fn lazy_traversals() {
+    use graph::algo::TraversalOptions;
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let ids: Vec<_> = (0..6).map(|_| graph.add_vertex(())).collect();
+        for (a, b) in [(0, 1), (1, 2), (0, 3), (4, 5)] {
+            graph.add_edge(ids[a], ids[b], (), &mut t).unwrap();
+        }
+
+        let mut components = graph.components(&t);
+        let first = components.next().unwrap();
+        let rest: Vec<_> = components.collect();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(first.len() + rest[0].len(), 6);
+        assert_eq!(connected_components(&graph, &t).len(), 2);
+
+        let options = TraversalOptions::new();
+        let mut bfs = graph.bfs_iter(ids[0], &options, &t).unwrap();
+        assert_eq!(bfs.next(), Some(ids[0]));
+        assert_eq!(bfs.depth(), 0);
+        assert_eq!(bfs.next(), Some(ids[1]));
+        assert_eq!(bfs.next(), Some(ids[3]));
+        assert_eq!(bfs.depth(), 1);
+        assert_eq!(bfs.next(), Some(ids[2]));
+        assert_eq!(bfs.depth(), 2);
+        assert_eq!(bfs.next(), None);
+
+        let visited: Vec<_> = graph
+            .dfs_iter(ids[0], &options, &t)
+            .unwrap()
+            .take(3)
+            .collect();
+        assert_eq!(visited, vec![ids[0], ids[1], ids[2]]);
+    });
+}
+
+#[test]
+fn dynamic_connectivity() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..6).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(v[1], v[0], (), &mut t).unwrap();
+
+        let mut manual = DynamicConnectivity::from_graph(&graph, &t);
+        assert_eq!(manual.component_len(), 5);
+        assert!(manual.connected(v[0], v[1]));
+        assert!(manual.union(v[2], v[3]));
+        assert!(!manual.union(v[3], v[2]));
+        assert_eq!(manual.component_size(v[3]), Some(2));
+        assert!(!manual.connected(v[0], v[2]));
+
+        let shared = SharedObserver::new(DynamicConnectivity::from_graph(&graph, &t));
+        graph.set_observer(shared.clone());
+
+        graph.add_edge(v[2], v[3], (), &mut t).unwrap();
+        graph.add_edge(v[4], v[3], (), &mut t).unwrap();
+        let added = graph.add_vertex(());
+        shared.with(|c| {
+            assert!(c.connected(v[2], v[4]));
+            assert!(!c.connected(v[0], v[4]));
+            assert_eq!(c.component_len(), 4);
+            assert_eq!(c.vertex_len(), 7);
+            assert!(!c.is_stale());
+        });
+
+        graph.remove_edge_between(v[4], v[3], &mut t).unwrap();
+        shared.with(|c| {
+            assert!(c.is_stale());
+            c.rebuild(&graph, &t);
+            assert!(!c.connected(v[2], v[4]));
+            assert!(!c.connected(v[0], added));
+            assert!(!c.is_stale());
+        });
+    });
+}
+
+#[test]
+fn incremental_shortest_paths_match_dijkstra() {
+    use graph::{
+        algo::IncrementalShortestPaths,
+        edge::EdgeTrait,
+        generators::{erdos_renyi, RandomSource, SplitMix64},
+    };
+
+    let mut rng = SplitMix64::new(11);
+    let length = |i: usize, j: usize| (i * 7 + j * 13) as u32 % 19 + 1;
+
+    GhostToken::new(|mut t| {
+        let directed: Graph<(), u32, DirectedWeightedEdge<_, _>> =
+            erdos_renyi(25, 0.12, &mut rng, |_| (), length, &mut t).unwrap();
+        let undirected: Graph<(), u32, UnDirectedWeightedEdge<_, _>> =
+            erdos_renyi(25, 0.08, &mut rng, |_| (), length, &mut t).unwrap();
+
+        macro_rules! compare {
+            ($graph:expr) => {{
+                let mut graph = $graph;
+                let mut ids: Vec<_> = graph.vertex_ids().collect();
+                ids.sort_unstable();
+                let mut paths = IncrementalShortestPaths::new(&graph, ids[0], |w| *w, &t).unwrap();
+
+                for step in 0..300 {
+                    let mut edges: Vec<_> = graph
+                        .unique_edges(&t)
+                        .map(|(id, from, to, _)| (id, from, to))
+                        .collect();
+                    edges.sort_unstable();
+
+                    let (from, to) = match rng.below(3) {
+                        0 if !edges.is_empty() => {
+                            let (edge, from, to) = edges[rng.below(edges.len())];
+                            *graph
+                                .get_edge(edge)
+                                .unwrap()
+                                .borrow_mut(&mut t)
+                                .get_weight_mut() = rng.below(20) as u32;
+                            (from, to)
+                        }
+                        1 if !edges.is_empty() => {
+                            let (_, from, to) = edges[rng.below(edges.len())];
+                            graph.remove_edge_between(from, to, &mut t).unwrap();
+                            (from, to)
+                        }
+                        _ => {
+                            let (from, to) = (ids[rng.below(25)], ids[rng.below(25)]);
+                            let weight = rng.below(20) as u32;
+                            if graph.add_edge(from, to, weight, &mut t).is_err() {
+                                continue;
+                            }
+                            (from, to)
+                        }
+                    };
+                    paths.update_edge(&graph, from, to, |w| *w, &t);
+
+                    let expected = dijkstra(&graph, ids[0], |w| *w, &t).unwrap();
+                    for &id in &ids {
+                        let distance = paths.paths().distance(id);
+                        assert_eq!(distance, expected.distance(id), "step {}", step);
+
+                        if let Some((previous, edge)) = paths.paths().previous(id) {
+                            let weight = *graph.get_edge(edge).unwrap().borrow(&t).get_weight();
+                            let through = paths.paths().distance(previous).unwrap() + weight;
+                            assert_eq!(Some(through), distance);
+                        }
+                    }
+                }
+            }};
+        }
+
+        compare!(directed);
+        compare!(undirected);
+    });
+}
+
+#[test]
+fn rooted_tree_lowest_common_ancestors() {
+    use graph::generators::{RandomSource, SplitMix64};
+
+    let mut rng = SplitMix64::new(11);
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+        assert!(!tree::is_tree(&graph, &t));
+
+        let ids: Vec<_> = (0..100).map(|_| graph.add_vertex(())).collect();
+        for i in 1..ids.len() {
+            // Edges point either way, since direction is ignored
+            let parent = ids[rng.below(i)];
+            if rng.below(2) == 0 {
+                graph.add_edge(parent, ids[i], (), &mut t).unwrap();
+            } else {
+                graph.add_edge(ids[i], parent, (), &mut t).unwrap();
+            }
+        }
+        assert!(tree::is_tree(&graph, &t));
+
+        let root = ids[rng.below(ids.len())];
+        let rooted = RootedTree::new(&graph, root, &t).unwrap();
+        assert_eq!(rooted.root(), root);
+        assert_eq!(rooted.len(), 100);
+        assert_eq!(rooted.parent(root), None);
+        assert_eq!(rooted.parents().len(), 99);
+
+        let ancestors = |mut id| {
+            let mut path = vec![id];
+            while let Some(parent) = rooted.parent(id) {
+                path.push(parent);
+                id = parent;
+            }
+            path
+        };
+        for &id in &ids {
+            let path = ancestors(id);
+            assert_eq!(rooted.depth(id), Some(path.len() - 1));
+            for (steps, ancestor) in path.iter().enumerate() {
+                assert_eq!(rooted.ancestor(id, steps), Some(*ancestor));
+            }
+            assert_eq!(rooted.ancestor(id, path.len()), None);
+            for child in rooted.children(id) {
+                assert_eq!(rooted.parent(*child), Some(id));
+            }
+        }
+
+        for _ in 0..200 {
+            let (a, b) = (ids[rng.below(100)], ids[rng.below(100)]);
+            let (above_a, above_b) = (ancestors(a), ancestors(b));
+            let expected = *above_a.iter().find(|id| above_b.contains(id)).unwrap();
+
+            assert_eq!(rooted.lowest_common_ancestor(a, b), Some(expected));
+            let depth = rooted.depth(expected).unwrap();
+            assert_eq!(
+                rooted.distance(a, b),
+                Some(above_a.len() + above_b.len() - 2 - 2 * depth)
+            );
+        }
+
+        // A cycle, or a second component, means it's no longer a tree
+        let extra = graph.add_vertex(());
+        assert!(!tree::is_tree(&graph, &t));
+        assert!(RootedTree::new(&graph, root, &t).is_none());
+        graph.add_edge(extra, root, (), &mut t).unwrap();
+        assert!(tree::is_tree(&graph, &t));
+        let other = if root == ids[0] { ids[1] } else { ids[0] };
+        graph.add_edge(extra, other, (), &mut t).unwrap();
+        assert!(!tree::is_tree(&graph, &t));
+    });
+}
+
+#[test]
+fn time_respecting_paths() {
+    use graph::{
+        algo::temporal_bfs,
+        edge::{DirectedTemporalEdge, Temporal, TemporalWeight, UnDirectedTemporalEdge},
+    };
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), Temporal<u32, u8>, UnDirectedTemporalEdge<_, _, _>> = Graph::new();
+        let v: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+        let contact = |intervals: &[(u32, u32)]| Temporal {
+            intervals: intervals.to_vec(),
+            weight: 0,
+        };
+        graph
+            .add_edge(v[0], v[1], contact(&[(2, 3), (8, 9)]), &mut t)
+            .unwrap();
+        graph
+            .add_edge(v[1], v[2], contact(&[(4, 4)]), &mut t)
+            .unwrap();
+        graph
+            .add_edge(v[2], v[3], contact(&[(1, 2), (7, 7)]), &mut t)
+            .unwrap();
+        graph
+            .add_edge(v[1], v[4], contact(&[(0, 1)]), &mut t)
+            .unwrap();
+
+        let mut at = graph.neighbors_at(v[1], 4, &t);
+        at.sort();
+        assert_eq!(at, vec![v[2]]);
+        assert_eq!(graph.neighbors_at(v[1], 3, &t), vec![v[0]]);
+        assert!(graph.neighbors_at(v[1], 5, &t).is_empty());
+
+        let reached = temporal_bfs(&graph, v[0], 0, &t).unwrap();
+        assert_eq!(reached.arrival(v[1]), Some(2));
+        assert_eq!(reached.arrival(v[2]), Some(4));
+        assert_eq!(reached.arrival(v[3]), Some(7));
+        // The only contact with `v[4]` ended before `v[1]` was reached
+        assert_eq!(reached.arrival(v[4]), None);
+        assert_eq!(reached.path_to(v[3]), Some(vec![v[0], v[1], v[2], v[3]]));
+
+        let late = temporal_bfs(&graph, v[0], 5, &t).unwrap();
+        assert_eq!(late.arrival(v[1]), Some(8));
+        assert_eq!(late.arrival(v[2]), None);
+
+        assert_eq!(contact(&[(3, 5), (1, 2)]).next_active(0), Some(1));
+        assert_eq!(contact(&[(3, 5), (1, 2)]).next_active(4), Some(4));
+        assert_eq!(contact(&[(3, 5)]).next_active(6), None);
+    });
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), Temporal<u32>, DirectedTemporalEdge<_, _>> = Graph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        graph
+            .add_edge(a, b, Temporal::new(()).with_interval(0, 10), &mut t)
+            .unwrap();
+
+        assert_eq!(graph.neighbors_at(a, 5, &t), vec![b]);
+        assert!(graph.neighbors_at(b, 5, &t).is_empty());
+        assert_eq!(temporal_bfs(&graph, b, 0, &t).unwrap().arrival(a), None);
+    });
+}
+
+#[test]
+fn monte_carlo_reliability() {
+    use graph::{
+        algo::reliability,
+        edge::{DirectedProbabilisticEdge, Probabilistic, UnDirectedProbabilisticEdge},
+        generators::SplitMix64,
+    };
+
+    let mut rng = SplitMix64::new(9);
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), Probabilistic, UnDirectedProbabilisticEdge<_>> = Graph::new();
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        let half = Probabilistic::new(0.5, ());
+
+        // Two paths of two edges, each of which exists a quarter of
+        // the time
+        for &(a, b) in &[(0, 1), (1, 3), (0, 2), (2, 3)] {
+            graph.add_edge(v[a], v[b], half, &mut t).unwrap();
+        }
+        let estimate = reliability(&graph, v[0], v[3], 20_000, &mut rng, &t).unwrap();
+        assert!((estimate - (1. - 0.75 * 0.75)).abs() < 0.02, "{}", estimate);
+
+        assert_eq!(
+            reliability(&graph, v[2], v[2], 10, &mut rng, &t).unwrap(),
+            1.
+        );
+        assert_eq!(
+            reliability(&graph, v[0], v[3], 0, &mut rng, &t).unwrap(),
+            0.
+        );
+        assert!(reliability(&graph, v[0], graph::VertexId::new(9), 10, &mut rng, &t).is_err());
+        assert_eq!(Probabilistic::new(1.5, ()).probability, 1.);
+    });
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), Probabilistic, DirectedProbabilisticEdge<_>> = Graph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        graph
+            .add_edge(a, b, Probabilistic::new(1., ()), &mut t)
+            .unwrap();
+
+        assert_eq!(reliability(&graph, a, b, 100, &mut rng, &t).unwrap(), 1.);
+        assert_eq!(reliability(&graph, b, a, 100, &mut rng, &t).unwrap(), 0.);
+    });
+}
+
+#[test]
+fn paths_keep_their_edges() {
+    use graph::algo::Path;
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        let there = graph.add_edge(v[0], v[1], 4, &mut t).unwrap();
+        let back = graph.add_edge(v[1], v[0], 1, &mut t).unwrap();
+        let e12 = graph.add_edge(v[1], v[2], 2, &mut t).unwrap();
+        let e23 = graph.add_edge(v[2], v[3], 3, &mut t).unwrap();
+
+        let path = dijkstra(&graph, v[0], |w| *w, &t)
+            .unwrap()
+            .path(v[3])
+            .unwrap();
+        assert_eq!(path.vertices(), &[v[0], v[1], v[2], v[3]]);
+        assert_eq!(path.edges(), &[there, e12, e23]);
+        assert_eq!(path.len(), 3);
+        assert_eq!((path.start(), path.end()), (v[0], v[3]));
+        assert_eq!(path.cost(&graph, &t), Some(9));
+        let steps: Vec<_> = path.iter_edges().collect();
+        assert_eq!(steps[1], (v[1], e12, v[2]));
+
+        // The same vertices along the wrong edge can't be walked
+        let wrong = Path::new(v[0]).with_step(back, v[1]);
+        assert!(!wrong.is_valid(&graph, &t));
+        assert_eq!(wrong.cost(&graph, &t), None);
+        assert_eq!(Path::from_parts(vec![v[0]], vec![there]), None);
+
+        let mut front = Path::from_parts(vec![v[0], v[1]], vec![there]).unwrap();
+        let rest = Path::new(v[1]).with_step(e12, v[2]).with_step(e23, v[3]);
+        assert_eq!(front.clone().concat(rest.clone()), Some(path.clone()));
+        assert_eq!(rest.clone().concat(front.clone()), None);
+        assert_eq!(front.pop(), Some((there, v[1])));
+        assert_eq!(front.pop(), None);
+        assert!(front.is_empty() && front.is_valid(&graph, &t));
+        assert_eq!(front.cost(&graph, &t), Some(0));
+
+        graph.remove(v[2], &mut t).unwrap();
+        assert!(!path.is_valid(&graph, &t));
+    });
+}
+
+#[test]
+fn all_simple_paths() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        for i in 0..4 {
+            for j in i + 1..4 {
+                graph.add_edge(v[i], v[j], (), &mut t).unwrap();
+            }
+        }
+
+        let paths: Vec<_> = graph.all_simple_paths(v[0], v[1], 3, &t).unwrap().collect();
+        assert_eq!(paths.len(), 5);
+        assert_eq!(paths[0].vertices(), &[v[0], v[1]]);
+        for path in &paths {
+            assert!(path.is_valid(&graph, &t));
+            assert_eq!((path.start(), path.end()), (v[0], v[1]));
+            let mut seen = path.vertices().to_vec();
+            seen.sort_unstable();
+            seen.dedup();
+            assert_eq!(seen.len(), path.vertices().len());
+        }
+        let mut distinct = paths.clone();
+        distinct.dedup();
+        assert_eq!(distinct.len(), paths.len());
+
+        let short = graph.all_simple_paths(v[0], v[1], 2, &t).unwrap();
+        assert_eq!(short.count(), 3);
+        let none = graph.all_simple_paths(v[0], v[1], 0, &t).unwrap();
+        assert_eq!(none.count(), 0);
+        let trivial: Vec<_> = graph.all_simple_paths(v[2], v[2], 3, &t).unwrap().collect();
+        assert_eq!(trivial.len(), 1);
+        assert!(trivial[0].is_empty());
+
+        let missing = graph.add_vertex(());
+        graph.remove(missing, &mut t).unwrap();
+        assert!(graph.all_simple_paths(v[0], missing, 3, &t).is_err());
+    });
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..3).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(v[0], v[1], (), &mut t).unwrap();
+        graph.add_edge(v[1], v[2], (), &mut t).unwrap();
+        graph.add_edge(v[2], v[0], (), &mut t).unwrap();
+
+        let forwards: Vec<_> = graph.all_simple_paths(v[0], v[2], 5, &t).unwrap().collect();
+        assert_eq!(forwards.len(), 1);
+        assert_eq!(forwards[0].vertices(), &[v[0], v[1], v[2]]);
+        assert_eq!(
+            graph.all_simple_paths(v[1], v[0], 1, &t).unwrap().count(),
+            0
+        );
+    });
+}
+
+#[test]
+fn path_cache() {
+    use graph::{algo::PathCache, VertexId};
+
+    type Roads<'id> = Graph<'id, (), u32, DirectedWeightedEdge<'id, (), u32>>;
+
+    fn length<'id>(
+        cache: &SharedObserver<PathCache<'id, u32>>,
+        from: VertexId<'id>,
+        to: VertexId<'id>,
+        graph: &Roads<'id>,
+        t: &GhostToken<'id>,
+    ) -> Option<u32> {
+        cache.with(|cache| {
+            let route = cache.shortest_path(graph, from, to, |w| *w, t).unwrap();
+            route.map(|route| route.length)
+        })
+    }
+
+    GhostToken::new(|mut t| {
+        let mut graph: Roads = Graph::new();
+        let v: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(v[0], v[1], 1, &mut t).unwrap();
+        graph.add_edge(v[1], v[2], 1, &mut t).unwrap();
+        graph.add_edge(v[0], v[2], 5, &mut t).unwrap();
+        graph.add_edge(v[3], v[4], 1, &mut t).unwrap();
+
+        let cache = SharedObserver::new(PathCache::new(2));
+        graph.set_observer(cache.clone());
+
+        assert_eq!(length(&cache, v[0], v[2], &graph, &t), Some(2));
+        assert_eq!(length(&cache, v[0], v[2], &graph, &t), Some(2));
+        assert_eq!(length(&cache, v[0], v[4], &graph, &t), None);
+        cache.with(|cache| {
+            assert_eq!((cache.hits(), cache.misses(), cache.len()), (1, 2, 2));
+            assert!(cache.shortest_path(&graph, v[1], v[2], |w| *w, &t).is_ok());
+        });
+
+        // The least recently used route was forgotten to make room
+        cache.with(|cache| {
+            assert_eq!(cache.len(), 2);
+            assert!(!cache.invalidate(v[0], v[2]));
+            assert!(cache.invalidate(v[0], v[4]));
+            cache.shortest_path(&graph, v[0], v[2], |w| *w, &t).unwrap();
+            cache.shortest_path(&graph, v[3], v[4], |w| *w, &t).unwrap();
+        });
+
+        // Removing an edge only forgets the routes along it
+        graph.remove_edge_between(v[3], v[4], &mut t).unwrap();
+        cache.with(|cache| assert_eq!(cache.len(), 1));
+        graph.remove_edge_between(v[1], v[2], &mut t).unwrap();
+        assert_eq!(length(&cache, v[0], v[2], &graph, &t), Some(5));
+
+        graph.remove(v[2], &mut t).unwrap();
+        cache.with(|cache| {
+            assert!(cache.is_empty());
+            assert!(cache.shortest_path(&graph, v[0], v[2], |w| *w, &t).is_err());
+        });
+    });
+}
+
+#[test]
+fn balanced_partitions() {
+    use graph::algo::partition;
+    use hashbrown::HashMap;
+
+    GhostToken::new(|mut t| {
+        let grid: Graph<(usize, usize), u32, UnDirectedWeightedEdge<_, _>> =
+            Graph::grid(8, 8, false, |x, y| (x, y), |_, _| 1, &mut t).unwrap();
+
+        let parts = partition(&grid, 4, |w| f64::from(*w), &t);
+        assert_eq!(parts.len(), 4);
+        assert!(parts.iter().all(|part| part.len() == 16));
+
+        let mut seen: Vec<_> = parts.iter().flatten().copied().collect();
+        seen.sort_unstable();
+        seen.dedup();
+        assert_eq!(seen.len(), 64);
+
+        // Cutting the grid into quarters cuts 16 edges, and any fair
+        // split shouldn't do much worse
+        let part_of: HashMap<_, _> = parts
+            .iter()
+            .enumerate()
+            .flat_map(|(n, part)| part.iter().map(move |id| (*id, n)))
+            .collect();
+        let cut = grid
+            .unique_edges(&t)
+            .filter(|(_, from, to, _)| part_of[from] != part_of[to])
+            .count();
+        assert!(cut <= 24, "cut {} edges", cut);
+
+        let sizes: Vec<_> = partition(&grid, 3, |w| f64::from(*w), &t)
+            .iter()
+            .map(Vec::len)
+            .collect();
+        assert_eq!(sizes, [21, 21, 22]);
+        assert!(partition(&grid, 0, |w| f64::from(*w), &t).is_empty());
+    });
+
+    GhostToken::new(|mut t| {
+        // Two heavy cliques held together by light edges
+        let mut graph: Graph<(), u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..8).map(|_| graph.add_vertex(())).collect();
+        for i in 0..8 {
+            for j in i + 1..8 {
+                let weight = if i % 2 == j % 2 { 10 } else { 1 };
+                graph.add_edge(v[i], v[j], weight, &mut t).unwrap();
+            }
+        }
+
+        let mut parts = partition(&graph, 2, |w| f64::from(*w), &t);
+        parts.sort();
+        let evens: Vec<_> = v.iter().step_by(2).copied().collect();
+        let odds: Vec<_> = v.iter().skip(1).step_by(2).copied().collect();
+        assert_eq!(parts, [evens, odds]);
+
+        let parts = partition(&graph, 10, |w| f64::from(*w), &t);
+        assert_eq!(parts.len(), 10);
+        assert_eq!(parts.iter().filter(|part| part.is_empty()).count(), 2);
+    });
+}
+
+#[test]
+fn girvan_newman() {
+    use graph::algo::edge_betweenness;
+
+    GhostToken::new(|mut t| {
+        // A diamond with two equally short routes from s to e
+        let mut graph: Graph<(), f64, DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        let e01 = graph.add_edge(v[0], v[1], 1.0, &mut t).unwrap();
+        let e02 = graph.add_edge(v[0], v[2], 1.0, &mut t).unwrap();
+        let e13 = graph.add_edge(v[1], v[3], 1.0, &mut t).unwrap();
+        let e23 = graph.add_edge(v[2], v[3], 3.0, &mut t).unwrap();
+
+        let unweighted = edge_betweenness(&graph, |_| 1.0, &t);
+        assert_eq!(unweighted[&e01], 1.5);
+        assert_eq!(unweighted[&e02], 1.5);
+        assert_eq!(unweighted[&e13], 1.5);
+        assert_eq!(unweighted[&e23], 1.5);
+
+        // Weighted, the route through v[1] is the only shortest one
+        let weighted = edge_betweenness(&graph, |w| *w, &t);
+        assert_eq!(weighted[&e01], 2.0);
+        assert_eq!(weighted[&e13], 2.0);
+        assert_eq!(weighted[&e02], 1.0);
+        assert_eq!(weighted[&e23], 1.0);
+    });
+
+    GhostToken::new(|mut t| {
+        // Three squares with both diagonals, joined in a line, and a
+        // vertex on its own
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..13).map(|_| graph.add_vertex(())).collect();
+        for square in v[..12].chunks(4) {
+            for (i, a) in square.iter().enumerate() {
+                for b in &square[i + 1..] {
+                    graph.add_edge(*a, *b, (), &mut t).unwrap();
+                }
+            }
+        }
+        graph.add_edge(v[3], v[4], (), &mut t).unwrap();
+        graph.add_edge(v[7], v[8], (), &mut t).unwrap();
+
+        let levels = graph.girvan_newman(3, &t);
+        let sizes: Vec<Vec<_>> = levels
+            .iter()
+            .map(|level| level.iter().map(Vec::len).collect())
+            .collect();
+        assert_eq!(
+            sizes,
+            vec![
+                vec![12, 1],
+                // Both bridges score the same, so the lower id goes
+                vec![4, 8, 1],
+                vec![4, 4, 4, 1],
+                // Then the squares themselves fall apart, losing the
+                // edges with the lowest ids first
+                vec![1, 3, 4, 4, 1],
+            ]
+        );
+        assert_eq!(levels[2][1], v[4..8].to_vec());
+        assert_eq!(levels[3][0], vec![v[0]]);
+
+        // The graph itself isn't changed
+        assert_eq!(graph.edge_len(), 20);
+        assert_eq!(graph.girvan_newman(0, &t), levels[..1].to_vec());
+        // Splitting stops once every vertex is on its own
+        let all = graph.girvan_newman(100, &t);
+        assert_eq!(all.len(), 12);
+        assert_eq!(all[11].len(), 13);
+    });
+
+    GhostToken::new(|t| {
+        let graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        assert_eq!(graph.girvan_newman(5, &t), vec![Vec::<Vec<_>>::new()]);
+        assert!(edge_betweenness(&graph, |_| 1.0, &t).is_empty());
+    });
+}
+
+#[test]
+fn trimming() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<usize, (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..9).map(|i| graph.add_vertex(i)).collect();
+        // A K4 with a triangle hanging off one corner, a separate edge
+        // and an isolated vertex
+        for (a, b) in [
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (1, 3),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 3),
+            (6, 7),
+        ] {
+            graph.add_edge(v[a], v[b], (), &mut t).unwrap();
+        }
+
+        assert_eq!(graph.remove_isolated_vertices(&mut t), 1);
+        assert_eq!(graph.remove_isolated_vertices(&mut t), 0);
+        assert!(graph.get_vertex(v[8]).is_none());
+
+        assert_eq!(graph.prune(0, &mut t), 0);
+        assert_eq!(graph.prune(2, &mut t), 2);
+        assert_eq!(graph.vertex_len(), 6);
+
+        assert_eq!(graph.prune(3, &mut t), 2);
+        let mut left: Vec<_> = graph.vertex_ids().collect();
+        left.sort_unstable();
+        assert_eq!(left, v[..4].to_vec());
+        assert!(left.iter().all(|id| graph.degree(*id) == Some(3)));
+        assert_eq!(graph.edge_len(), 6);
+
+        assert_eq!(graph.prune(4, &mut t), 4);
+        assert!(graph.is_empty());
+        assert_eq!(graph.edge_len(), 0);
+    });
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        // A directed cycle, with a vertex only leading into it
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (3, 0)] {
+            graph.add_edge(v[a], v[b], (), &mut t).unwrap();
+        }
+
+        assert_eq!(graph.prune(2, &mut t), 1);
+        assert_eq!(graph.vertex_len(), 3);
+        assert_eq!(graph.degree(v[0]), Some(2));
+    });
+}
+
+#[test]
+fn degree_distribution() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+        assert!(graph.degree_histogram().is_empty());
+        assert_eq!(graph.power_law_exponent(1), None);
+
+        let v: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+        // Degrees count edges in both directions: 3, 2, 2, 1, 0
+        for (a, b) in [(0, 1), (2, 0), (1, 2), (3, 0)] {
+            graph.add_edge(v[a], v[b], (), &mut t).unwrap();
+        }
+
+        assert_eq!(graph.degree_histogram(), vec![1, 1, 2, 1]);
+        assert_eq!(graph.power_law_exponent(0), None);
+        assert_eq!(graph.power_law_exponent(4), None);
+
+        let expected = 1.0 + 3.0 / (2.0 * (2.0 / 1.5f64).ln() + (3.0 / 1.5f64).ln());
+        let alpha = graph.power_law_exponent(2).unwrap();
+        assert!((alpha - expected).abs() < 1e-12);
+
+        graph.remove(v[0], &mut t).unwrap();
+        assert_eq!(graph.degree_histogram(), vec![2, 2]);
+    });
+}
+
+/// The number of ways to map the `pattern` vertices to distinct
+/// `target` vertices that keep every pattern edge, found by trying
+/// every way
+fn count_embeddings(pattern: &[Vec<bool>], target: &[Vec<bool>], mapped: &mut Vec<usize>) -> usize {
+    let p = mapped.len();
+    if p == pattern.len() {
+        return 1;
+    }
+    let mut count = 0;
+    for t in 0..target.len() {
+        let keeps = (0..p).all(|q| !pattern[p][q] || target[t][mapped[q]]);
+        if keeps && !mapped.contains(&t) {
+            mapped.push(t);
+            count += count_embeddings(pattern, target, mapped);
+            mapped.pop();
+        }
+    }
+    count
+}
+
+#[test]
+fn subgraph_isomorphisms() {
+    use core::convert::TryFrom;
+
+    GhostToken::new(|mut t| {
+        // Triangles within a K4
+        let mut pattern: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let p: Vec<_> = (0..3).map(|_| pattern.add_vertex(())).collect();
+        for (a, b) in [(0, 1), (1, 2), (2, 0)] {
+            pattern.add_edge(p[a], p[b], (), &mut t).unwrap();
+        }
+        let mut target: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..4).map(|_| target.add_vertex(())).collect();
+        for a in 0..4 {
+            for b in a + 1..4 {
+                target.add_edge(v[a], v[b], (), &mut t).unwrap();
+            }
+        }
+
+        let found: Vec<_> =
+            find_subgraph_isomorphisms(&pattern, &target, |_, _| true, |_, _| true, &t).collect();
+        assert_eq!(found.len(), 24);
+        for mapping in &found {
+            let mut images: Vec<_> = p.iter().map(|id| mapping[id]).collect();
+            images.sort_unstable();
+            images.dedup();
+            assert_eq!(images.len(), 3);
+        }
+
+        // A pattern larger than the target, and an empty pattern
+        let mut large: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        large.add_vertices((0..5).map(|_| ()));
+        let empty: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        assert_eq!(
+            find_subgraph_isomorphisms(&large, &target, |_, _| true, |_, _| true, &t).count(),
+            0
+        );
+        let found: Vec<_> =
+            find_subgraph_isomorphisms(&empty, &target, |_, _| true, |_, _| true, &t).collect();
+        assert_eq!(found.len(), 1);
+        assert!(found[0].is_empty());
+    });
+
+    GhostToken::new(|mut t| {
+        // Directed, labelled, weighted paths
+        let mut pattern: Graph<char, u32, DirectedWeightedEdge<_, _>> = Graph::new();
+        let a = pattern.add_vertex('a');
+        let b = pattern.add_vertex('b');
+        pattern.add_edge(a, b, 1, &mut t).unwrap();
+
+        let mut target: Graph<char, u32, DirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = "abab".chars().map(|c| target.add_vertex(c)).collect();
+        for (from, to, weight) in [(0, 1, 1), (1, 2, 1), (2, 3, 2), (3, 0, 1)] {
+            target.add_edge(v[from], v[to], weight, &mut t).unwrap();
+        }
+
+        let found: Vec<_> = find_subgraph_isomorphisms(
+            &pattern,
+            &target,
+            |one, two| one == two,
+            |one, two| one == two,
+            &t,
+        )
+        .map(|mapping| (mapping[&a], mapping[&b]))
+        .collect();
+        assert_eq!(found, vec![(v[0], v[1])]);
+
+        let structural =
+            find_subgraph_isomorphisms(&pattern, &target, |_, _| true, |_, _| true, &t).count();
+        assert_eq!(structural, 4);
+    });
+
+    GhostToken::new(|mut t| {
+        // Edges both ways each need a target edge of their own
+        let mut pattern: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+        let a = pattern.add_vertex(());
+        let b = pattern.add_vertex(());
+        pattern.add_edge(a, b, (), &mut t).unwrap();
+        pattern.add_edge(b, a, (), &mut t).unwrap();
+
+        let mut undirected: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let u: Vec<_> = (0..2).map(|_| undirected.add_vertex(())).collect();
+        undirected.add_edge(u[0], u[1], (), &mut t).unwrap();
+        let found =
+            find_subgraph_isomorphisms(&pattern, &undirected, |_, _| true, |_, _| true, &t).count();
+        assert_eq!(found, 0);
+
+        let mut directed: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..3).map(|_| directed.add_vertex(())).collect();
+        for (from, to) in [(0, 1), (1, 2), (2, 1)] {
+            directed.add_edge(v[from], v[to], (), &mut t).unwrap();
+        }
+        let mut found: Vec<_> =
+            find_subgraph_isomorphisms(&pattern, &directed, |_, _| true, |_, _| true, &t)
+                .map(|mapping| (mapping[&a], mapping[&b]))
+                .collect();
+        found.sort_unstable();
+        assert_eq!(found, vec![(v[1], v[2]), (v[2], v[1])]);
+
+        // An undirected pattern edge can follow a directed edge either way
+        let found =
+            find_subgraph_isomorphisms(&undirected, &directed, |_, _| true, |_, _| true, &t)
+                .count();
+        assert_eq!(found, 4);
+    });
+
+    // Random graphs, checked against trying every mapping
+    let mut state = 0x2545_f491_u64;
+    let mut random = move |below: usize| {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        usize::try_from(state >> 33).unwrap() % below
+    };
+    for _ in 0..20 {
+        let mut pattern_matrix = vec![vec![false; 4]; 4];
+        let mut target_matrix = vec![vec![false; 7]; 7];
+        for (matrix, edges) in [(&mut pattern_matrix, 4), (&mut target_matrix, 14)] {
+            let n = matrix.len();
+            for _ in 0..edges {
+                let (a, b) = (random(n), random(n));
+                if a != b {
+                    matrix[a][b] = true;
+                    matrix[b][a] = true;
+                }
+            }
+        }
+
+        GhostToken::new(|mut t| {
+            let mut pattern: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+            let mut target: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+            let p: Vec<_> = (0..4).map(|_| pattern.add_vertex(())).collect();
+            let v: Vec<_> = (0..7).map(|_| target.add_vertex(())).collect();
+            for (a, b) in (0..4).flat_map(|a| (a + 1..4).map(move |b| (a, b))) {
+                if pattern_matrix[a][b] {
+                    pattern.add_edge(p[a], p[b], (), &mut t).unwrap();
+                }
+            }
+            for (a, b) in (0..7).flat_map(|a| (a + 1..7).map(move |b| (a, b))) {
+                if target_matrix[a][b] {
+                    target.add_edge(v[a], v[b], (), &mut t).unwrap();
+                }
+            }
+
+            let found =
+                find_subgraph_isomorphisms(&pattern, &target, |_, _| true, |_, _| true, &t).count();
+            let expected = count_embeddings(&pattern_matrix, &target_matrix, &mut Vec::new());
+            assert_eq!(found, expected);
+        });
+    }
+}
+
+#[test]
+fn spectral_sparsification() {
+    use graph::generators::SplitMix64;
+
+    GhostToken::new(|mut t| {
+        // Two dense clusters joined by a single bridge
+        let mut graph: Graph<usize, f64, UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..120).map(|i| graph.add_vertex(i)).collect();
+        for cluster in [0..60, 60..120] {
+            for a in cluster.clone() {
+                for b in a + 1..cluster.end {
+                    graph.add_edge(v[a], v[b], 1.0, &mut t).unwrap();
+                }
+            }
+        }
+        let bridge = graph.add_edge(v[59], v[60], 1.0, &mut t).unwrap();
+        let ignored = graph.add_vertex(120);
+        let zero = graph.add_edge(ignored, v[0], 0.0, &mut t).unwrap();
+
+        let mut rng = SplitMix64::new(3);
+        let (sparse, conductance) = graph
+            .sparsify(1.0, |weight| *weight, &mut rng, &mut t)
+            .unwrap();
+
+        assert_eq!(sparse.vertex_len(), graph.vertex_len());
+        assert!(sparse.edge_len() < graph.edge_len() * 3 / 4);
+        assert_eq!(conductance.len(), sparse.edge_len());
+        assert!(sparse.get_edge(zero).is_none());
+        assert_eq!(sparse.endpoints(bridge, &t), Some((v[59], v[60])));
+        assert!((conductance[bridge] - 1.0).abs() < 1e-9);
+
+        let components = connected_components(&sparse, &t);
+        assert_eq!(components.len(), 2);
+        assert_eq!(
+            components
+                .iter()
+                .find(|c| c.contains(&ignored))
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // The cut around each vertex keeps about its weight
+        for id in &v {
+            let weight: f64 = sparse
+                .unique_edges(&t)
+                .filter(|(_, from, to, _)| from == id || to == id)
+                .map(|(e_id, ..)| conductance[e_id])
+                .sum();
+            let expected = if *id == v[59] || *id == v[60] {
+                60.0
+            } else {
+                59.0
+            };
+            assert!((weight / expected - 1.0).abs() < 0.4, "{}", weight);
+        }
+    });
+}
+
+#[test]
+fn ego_networks() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<usize, usize, DirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..6).map(|i| graph.add_vertex(i)).collect();
+        let mut edges = Vec::new();
+        for (from, to) in [(0, 1), (1, 2), (2, 0), (2, 3), (4, 0), (3, 5)] {
+            edges.push(
+                graph
+                    .add_edge(v[from], v[to], 10 * from + to, &mut t)
+                    .unwrap(),
+            );
+        }
+
+        let ego = graph.ego_network(v[0], 0, &mut t).unwrap();
+        assert_eq!(ego.vertex_ids().collect::<Vec<_>>(), vec![v[0]]);
+        assert_eq!(ego.edge_len(), 0);
+
+        // `v[4]` only leads into `v[0]`, and the edge from `v[2]` back
+        // to `v[0]` wasn't followed, but is between two vertices within
+        let ego = graph.ego_network(v[0], 2, &mut t).unwrap();
+        let mut ids: Vec<_> = ego.vertex_ids().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, v[..3].to_vec());
+        let mut kept: Vec<_> = ego.edge_ids().collect();
+        kept.sort_unstable();
+        assert_eq!(kept, edges[..3].to_vec());
+        assert_eq!(ego.endpoints(edges[2], &t), Some((v[2], v[0])));
+        assert_eq!(ego.find_edge(|weight| *weight == 20, &t), Some(edges[2]));
+        assert_eq!(ego.degree(v[0]), Some(2));
+
+        let ego = graph.ego_network(v[0], 10, &mut t).unwrap();
+        assert_eq!(ego.vertex_len(), 5);
+        assert_eq!(ego.edge_len(), 5);
+
+        graph.remove(v[4], &mut t).unwrap();
+        assert!(graph.ego_network(v[4], 1, &mut t).is_err());
+    });
+}
+
+#[test]
+fn weak_components() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+        assert_eq!(graph.components(&t).next(), None);
+
+        let v: Vec<_> = (0..7).map(|_| graph.add_vertex(())).collect();
+        // Edges leading into 3 from both sides, a cycle, and 6 alone
+        for (a, b) in [(0, 3), (1, 3), (4, 2), (2, 5), (5, 4)] {
+            graph.add_edge(v[a], v[b], (), &mut t).unwrap();
+        }
+
+        let mut weak: Vec<_> = graph
+            .components(&t)
+            .map(|mut component| {
+                component.sort_unstable();
+                component
+            })
+            .collect();
+        weak.sort_unstable();
+        assert_eq!(
+            weak,
+            vec![vec![v[0], v[1], v[3]], vec![v[2], v[4], v[5]], vec![v[6]]]
+        );
+        assert_eq!(strongly_connected_components(&graph, &t).len(), 5);
+        for strong in strongly_connected_components(&graph, &t) {
+            assert!(weak
+                .iter()
+                .any(|weak| strong.iter().all(|id| weak.contains(id))));
+        }
+
+        graph.remove(v[3], &mut t).unwrap();
+        assert_eq!(graph.components(&t).count(), 4);
+    });
+}
+
+#[test]
+fn edge_classification() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..6).map(|_| graph.add_vertex(())).collect();
+        let mut edges = Vec::new();
+        for (a, b) in [
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (0, 2),
+            (3, 1),
+            (0, 3),
+            (4, 5),
+            (5, 4),
+        ] {
+            edges.push(graph.add_edge(v[a], v[b], (), &mut t).unwrap());
+        }
+
+        let kinds = graph.classify_edges([v[0]], &t).unwrap();
+        assert_eq!(kinds.len(), 6);
+        let expected = [
+            EdgeClass::Tree,
+            EdgeClass::Tree,
+            EdgeClass::Back,
+            EdgeClass::Forward,
+            EdgeClass::Cross,
+            EdgeClass::Tree,
+        ];
+        for (e_id, kind) in edges.iter().zip(expected.iter()) {
+            assert_eq!(kinds[*e_id], *kind);
+        }
+        assert!(!kinds.contains_key(edges[6]));
+
+        // Starting from 3 first puts every vertex of the cycle on its path
+        let kinds = graph.classify_edges([v[3], v[0], v[4]], &t).unwrap();
+        assert_eq!(kinds.len(), 8);
+        assert_eq!(kinds[edges[0]], EdgeClass::Back);
+        assert_eq!(kinds[edges[4]], EdgeClass::Tree);
+        assert_eq!(kinds[edges[5]], EdgeClass::Back);
+        assert_eq!(kinds[edges[6]], EdgeClass::Tree);
+        assert_eq!(kinds[edges[7]], EdgeClass::Back);
+
+        graph.remove(v[5], &mut t).unwrap();
+        assert!(graph.classify_edges([v[0], v[5]], &t).is_err());
+    });
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        let mut edges = Vec::new();
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (2, 3)] {
+            edges.push(graph.add_edge(v[a], v[b], (), &mut t).unwrap());
+        }
+
+        let kinds = graph.classify_edges([v[0]], &t).unwrap();
+        let kinds: Vec<_> = edges.iter().map(|e_id| kinds[*e_id]).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                EdgeClass::Tree,
+                EdgeClass::Tree,
+                EdgeClass::Back,
+                EdgeClass::Tree
+            ]
+        );
+    });
+}
+
+#[test]
+fn reachable_sets() {
+    use graph::{
+        algo::TraversalOptions,
+        generators::{RandomSource, SplitMix64},
+    };
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..7).map(|_| graph.add_vertex(())).collect();
+        for (a, b, weight) in [
+            (0, 1, 1),
+            (0, 2, 2),
+            (1, 3, 1),
+            (2, 3, 1),
+            (3, 4, 1),
+            (5, 0, 1),
+        ] {
+            graph.add_edge(v[a], v[b], weight, &mut t).unwrap();
+        }
+
+        let reached = graph.reachable_from(v[0], &t).unwrap();
+        assert_eq!(reached.iter().collect::<Vec<_>>(), v[..5].to_vec());
+        let mut bfs = graph.bfs(v[0], &TraversalOptions::new(), &t).unwrap();
+        bfs.sort_unstable();
+        assert_eq!(reached.iter().collect::<Vec<_>>(), bfs);
+        assert_eq!(graph.reachable_from(v[6], &t).unwrap().len(), 1);
+
+        let mut levels = Vec::new();
+        let shallow = graph
+            .reachable_from_with(
+                v[0],
+                &TraversalOptions::new().max_depth(1),
+                |depth, level| levels.push((depth, level.len())),
+                &t,
+            )
+            .unwrap();
+        assert_eq!(shallow.len(), 3);
+        assert_eq!(levels, vec![(0, 1), (1, 2)]);
+
+        let light = TraversalOptions::new().edge_filter(|weight: &u32| *weight == 1);
+        let reached = graph
+            .reachable_from_with(v[0], &light, |_, _| {}, &t)
+            .unwrap();
+        assert_eq!(
+            reached.iter().collect::<Vec<_>>(),
+            vec![v[0], v[1], v[3], v[4]]
+        );
+
+        let few = TraversalOptions::new().max_visited(2);
+        let reached = graph
+            .reachable_from_with(v[0], &few, |_, _| {}, &t)
+            .unwrap();
+        assert_eq!(reached.iter().collect::<Vec<_>>(), vec![v[0], v[1]]);
+
+        // Stopping after the first level
+        let reached = graph
+            .reachable_from_with(
+                v[0],
+                &TraversalOptions::new(),
+                |depth, level| {
+                    if depth > 1 {
+                        level.clear();
+                    }
+                },
+                &t,
+            )
+            .unwrap();
+        assert_eq!(reached.len(), 3);
+
+        // An epidemic passing each infection on half the time, which
+        // only ever infects vertices the source can reach
+        let mut rng = SplitMix64::new(11);
+        let everywhere = graph.reachable_from(v[0], &t).unwrap();
+        for _ in 0..20 {
+            let infected = graph
+                .reachable_from_with(
+                    v[0],
+                    &TraversalOptions::new(),
+                    |depth, level| {
+                        if depth > 0 {
+                            level.retain(|_| rng.next_f64() < 0.5);
+                        }
+                    },
+                    &t,
+                )
+                .unwrap();
+            assert!(infected.contains(v[0]));
+            assert!(infected.iter().all(|id| everywhere.contains(id)));
+        }
+
+        graph.remove(v[6], &mut t).unwrap();
+        assert!(graph.reachable_from(v[6], &t).is_err());
+    });
+}
+
+#[test]
+fn minimum_cuts() {
+    use core::convert::TryFrom;
+    use graph::{
+        edge::{EdgeTrait, Flow, FlowEdge},
+        GraphError,
+    };
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), Flow<u32>, FlowEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..6).map(|_| graph.add_vertex(())).collect();
+        let edges = [
+            (0, 1, 16),
+            (0, 2, 13),
+            (2, 1, 4),
+            (1, 3, 12),
+            (3, 2, 9),
+            (2, 4, 14),
+            (4, 3, 7),
+            (3, 5, 20),
+            (4, 5, 4),
+        ];
+        for &(from, to, capacity) in &edges {
+            graph
+                .add_edge(v[from], v[to], Flow::new(capacity), &mut t)
+                .unwrap();
+        }
+
+        assert!(matches!(
+            graph.max_flow(v[0], v[0], &mut t),
+            Err(GraphError::IdenticalVertex(_))
+        ));
+
+        let cut = graph.min_cut(v[0], v[5], &mut t).unwrap();
+        assert_eq!(cut.value, 23);
+        assert_eq!(cut.source_side, vec![v[0], v[1], v[2], v[4]]);
+        assert_eq!(cut.sink_side, vec![v[3], v[5]]);
+        assert_eq!(cut.edges.len(), 3);
+        for e_id in &cut.edges {
+            let edge = graph.get_edge(*e_id).unwrap().borrow(&t);
+            assert!(edge.get_weight().is_saturated());
+        }
+
+        // The flow is already a maximum, so nothing more can be sent
+        assert_eq!(graph.max_flow(v[0], v[5], &mut t).unwrap(), 0);
+        graph.clear_flow(&mut t);
+        assert_eq!(graph.max_flow(v[0], v[5], &mut t).unwrap(), 23);
+    });
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let only = graph.add_vertex(());
+        assert_eq!(graph.global_min_cut(|w| *w, &t), None);
+
+        // Already in pieces
+        let other = graph.add_vertex(());
+        assert_eq!(
+            graph.global_min_cut(|w| *w, &t),
+            Some(MinCut {
+                value: 0,
+                edges: Vec::new(),
+                source_side: vec![only],
+                sink_side: vec![other],
+            })
+        );
+        graph.add_edge(only, other, 5, &mut t).unwrap();
+        assert_eq!(graph.global_min_cut(|w| *w, &t).unwrap().value, 5);
+    });
+
+    // Compared against every way of splitting small random graphs
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move |below: u64| {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 33) % below
+    };
+    for _ in 0..40 {
+        GhostToken::new(|mut t| {
+            let mut graph: Graph<(), u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+            let n = 2 + usize::try_from(next(6)).unwrap();
+            let v: Vec<_> = (0..n).map(|_| graph.add_vertex(())).collect();
+            for a in 0..n {
+                for b in a + 1..n {
+                    if next(3) != 0 {
+                        let weight = u32::try_from(next(9)).unwrap() + 1;
+                        graph.add_edge(v[a], v[b], weight, &mut t).unwrap();
+                    }
+                }
+            }
+
+            let mut best = u32::MAX;
+            for mask in 1..(1_usize << (n - 1)) {
+                let side = |id| {
+                    v.iter()
+                        .position(|x| *x == id)
+                        .is_some_and(|i| mask >> i & 1 == 1)
+                };
+                let value = graph
+                    .unique_edges(&t)
+                    .filter(|(_, from, to, _)| side(*from) != side(*to))
+                    .map(|(.., weight)| *weight)
+                    .sum();
+                best = best.min(value);
+            }
+
+            let cut = graph.global_min_cut(|w| *w, &t).unwrap();
+            assert_eq!(cut.value, best);
+            assert!(cut.source_side.contains(&v[0]));
+            assert!(!cut.sink_side.is_empty());
+            assert_eq!(cut.source_side.len() + cut.sink_side.len(), n);
+            let crossing: u32 = cut
+                .edges
+                .iter()
+                .map(|e_id| *graph.get_edge(*e_id).unwrap().borrow(&t).get_weight())
+                .sum();
+            assert_eq!(crossing, best);
+        });
+    }
+}
+
+/// The most edges, and then the greatest weight with or without that
+/// many edges, of any matching of `edges` on vertices `0..n`
+fn best_matchings(n: usize, edges: &[(usize, usize, i64)]) -> ((usize, i64), i64) {
+    fn search(
+        edges: &[(usize, usize, i64)],
+        used: &mut Vec<bool>,
+        count: usize,
+        weight: i64,
+        best: &mut ((usize, i64), i64),
+    ) {
+        let Some((&(a, b, w), rest)) = edges.split_first() else {
+            best.0 = best.0.max((count, weight));
+            best.1 = best.1.max(weight);
+            return;
+        };
+        search(rest, used, count, weight, best);
+        if !used[a] && !used[b] {
+            used[a] = true;
+            used[b] = true;
+            search(rest, used, count + 1, weight + w, best);
+            used[a] = false;
+            used[b] = false;
+        }
+    }
+
+    let mut best = ((0, 0), 0);
+    search(edges, &mut vec![false; n], 0, 0, &mut best);
+    best
+}
+
+#[test]
+fn blossom_matchings() {
+    use core::convert::TryFrom;
+    use graph::edge::EdgeTrait;
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), i64, UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let empty = Matching {
+            weight: 0,
+            edges: Vec::new(),
+            pairs: Vec::new(),
+        };
+        assert_eq!(graph.maximum_weight_matching(|w| *w, &t), empty);
+
+        // Two triangles joined by an edge, each needing a blossom
+        // shrunk before the path through the middle is found
+        let v: Vec<_> = (0..6).map(|_| graph.add_vertex(())).collect();
+        for &(a, b) in &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+            graph.add_edge(v[a], v[b], 1, &mut t).unwrap();
+        }
+        let matching = graph.maximum_cardinality_matching(|_| 0, &t);
+        assert_eq!(matching.edges.len(), 3);
+        let mut matched: Vec<_> = matching.pairs.iter().flat_map(|(a, b)| [*a, *b]).collect();
+        matched.sort_unstable();
+        assert_eq!(matched, v);
+
+        // Negative weights are only matched to match more edges
+        let far = graph.add_vertex(());
+        let near = graph.add_vertex(());
+        graph.add_edge(far, near, -4, &mut t).unwrap();
+        assert_eq!(graph.maximum_weight_matching(|w| *w, &t).weight, 3);
+        assert_eq!(graph.maximum_cardinality_matching(|w| *w, &t).weight, -1);
+    });
+
+    GhostToken::new(|mut t| {
+        // Antiparallel directed edges count once, as the heavier
+        let mut graph: Graph<(), i64, DirectedWeightedEdge<_, _>> = Graph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        graph.add_edge(a, b, 2, &mut t).unwrap();
+        let heavier = graph.add_edge(b, a, 7, &mut t).unwrap();
+        let matching = graph.maximum_weight_matching(|w| *w, &t);
+        assert_eq!(matching.weight, 7);
+        assert_eq!(matching.edges, vec![heavier]);
+        assert_eq!(matching.pairs, vec![(a, b)]);
+    });
+
+    // Compared against every matching of small random graphs
+    let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+    let mut next = move |below: u64| {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 33) % below
+    };
+    for round in 0..300 {
+        GhostToken::new(|mut t| {
+            let mut graph: Graph<(), i64, UnDirectedWeightedEdge<_, _>> = Graph::new();
+            let n = 1 + usize::try_from(next(9)).unwrap();
+            let v: Vec<_> = (0..n).map(|_| graph.add_vertex(())).collect();
+            let mut edges = Vec::new();
+            for a in 0..n {
+                for b in a + 1..n {
+                    if next(5) < 2 {
+                        let weight = i64::try_from(next(20)).unwrap() - 4;
+                        graph.add_edge(v[a], v[b], weight, &mut t).unwrap();
+                        edges.push((a, b, weight));
+                    }
+                }
+            }
+            let ((most, cardinality_weight), weight) = best_matchings(n, &edges);
+
+            for (matching, expected) in [
+                (graph.maximum_weight_matching(|w| *w, &t), None),
+                (graph.maximum_cardinality_matching(|w| *w, &t), Some(most)),
+            ] {
+                let mut matched: Vec<_> =
+                    matching.pairs.iter().flat_map(|(a, b)| [*a, *b]).collect();
+                matched.sort_unstable();
+                matched.dedup();
+                assert_eq!(matched.len(), 2 * matching.edges.len(), "round {}", round);
+
+                let total: i64 = matching
+                    .edges
+                    .iter()
+                    .map(|e_id| *graph.get_edge(*e_id).unwrap().borrow(&t).get_weight())
+                    .sum();
+                assert_eq!(total, matching.weight, "round {}", round);
+
+                match expected {
+                    None => assert_eq!(matching.weight, weight, "round {}", round),
+                    Some(most) => {
+                        assert_eq!(matching.edges.len(), most, "round {}", round);
+                        assert_eq!(matching.weight, cardinality_weight, "round {}", round);
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,65 @@
+use graph::{
+    batch::OnError, edge::UnDirectedWeightedEdge, ghost::GhostToken, Graph, GraphError, VertexId,
+};
+
+#[test]
+fn add_edges_policies() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u8, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..3).map(|_| graph.add_vertex(())).collect();
+        let missing = VertexId::new(10);
+
+        let batch = vec![
+            (v[0], v[1], 1),
+            (v[1], v[1], 2),
+            (v[1], v[2], 3),
+            (v[2], missing, 4),
+        ];
+
+        let (index, error) = graph
+            .add_edges(batch.clone(), OnError::FailFast, &mut t)
+            .unwrap_err();
+        assert_eq!(index, 1);
+        assert!(matches!(error, GraphError::IdenticalVertex(_)));
+        assert_eq!(graph.edge_len(), 1);
+
+        graph.clear();
+        let v: Vec<_> = (0..3).map(|_| graph.add_vertex(())).collect();
+        let report = graph
+            .add_edges(batch.clone(), OnError::Skip, &mut t)
+            .unwrap();
+        assert!(report.is_complete());
+        assert_eq!(report.succeeded.len(), 2);
+        assert!(graph.adjacent(v[1], v[2], &t).unwrap());
+
+        graph.clear();
+        graph.add_vertices((0..3).map(|_| ()));
+        let report = graph.add_edges(batch, OnError::Collect, &mut t).unwrap();
+        assert!(!report.is_complete());
+        assert_eq!(
+            report.succeeded.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        assert_eq!(report.failed.len(), 2);
+        assert_eq!(report.failed[0].0, 1);
+        assert!(matches!(report.failed[1].1, GraphError::VertexNotFound(id) if id == missing));
+    });
+}
+
+#[test]
+fn remove_vertices_collects() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<char, (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let a = graph.add_vertex('a');
+        let b = graph.add_vertex('b');
+
+        let report = graph
+            .remove_vertices(vec![a, a, b], OnError::Collect, &mut t)
+            .unwrap();
+        assert_eq!(report.succeeded, vec![(0, 'a'), (2, 'b')]);
+        assert_eq!(report.failed.len(), 1);
+        assert!(graph.is_empty());
+    });
+}
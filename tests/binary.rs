@@ -0,0 +1,141 @@
+use graph::{
+    binary::{Decode, DecodeError, Encode},
+    edge::{DirectedWeightedEdge, UnDirectedWeightedEdge},
+    generators::{RandomSource, SplitMix64},
+    ghost::GhostToken,
+    ops::GraphOps,
+    Graph, VertexId,
+};
+
+#[test]
+fn values_round_trip() {
+    let mut bytes = Vec::new();
+    (1_u16, -2_i64, 0.5_f32, 'é').encode(&mut bytes);
+    "text".encode(&mut bytes);
+    vec![Some(3_usize), None].encode(&mut bytes);
+    [true, false].encode(&mut bytes);
+    assert_eq!(&bytes[..2], &[1, 0]);
+
+    let mut input = bytes.as_slice();
+    assert_eq!(
+        <(u16, i64, f32, char)>::decode(&mut input),
+        Ok((1, -2, 0.5, 'é'))
+    );
+    assert_eq!(String::decode(&mut input).as_deref(), Ok("text"));
+    assert_eq!(Vec::decode(&mut input), Ok(vec![Some(3_usize), None]));
+    assert_eq!(<[bool; 2]>::decode(&mut input), Ok([true, false]));
+    assert!(input.is_empty());
+
+    assert_eq!(
+        u32::decode(&mut &[1, 2][..]),
+        Err(DecodeError::UnexpectedEnd)
+    );
+    assert_eq!(bool::decode(&mut &[2][..]), Err(DecodeError::InvalidValue));
+}
+
+#[test]
+fn graphs_round_trip() {
+    let mut rng = SplitMix64::new(5);
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<String, (u32, bool), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let ids: Vec<_> = (0..40).map(|i| graph.add_vertex(i.to_string())).collect();
+        for _ in 0..120 {
+            let (from, to) = (ids[rng.below(40)], ids[rng.below(40)]);
+            graph
+                .add_edge(from, to, (rng.below(100) as u32, rng.below(2) == 0), &mut t)
+                .ok();
+        }
+
+        let bytes = graph.to_bytes(&t);
+        assert_eq!(&bytes[..4], b"GRPH");
+        let copy: Graph<String, (u32, bool), UnDirectedWeightedEdge<_, _>> =
+            Graph::from_bytes(&bytes, &mut t).unwrap();
+        assert_eq!(copy.fingerprint(&t), graph.fingerprint(&t));
+        assert_eq!(copy.to_bytes(&t), bytes);
+
+        // Gaps in the ids are closed, numbering vertices in order
+        graph.remove(ids[3], &mut t).unwrap();
+        let bytes = graph.to_bytes(&t);
+        let mut copy: Graph<String, (u32, bool), UnDirectedWeightedEdge<_, _>> =
+            Graph::from_bytes(&bytes, &mut t).unwrap();
+        assert_eq!(copy.vertex_len(), 39);
+        assert_eq!(copy.edge_len(), graph.edge_len());
+        assert_eq!(copy.item(ids[3], &t).map(String::as_str), Some("4"));
+        assert_eq!(copy.item(ids[39], &t), None);
+
+        // New vertices don't reuse any of the ids that were read
+        let added = copy.add_vertex("new".to_string());
+        assert_eq!(added, ids[39]);
+
+        let directed: Result<Graph<String, (u32, bool), DirectedWeightedEdge<_, _>>, _> =
+            Graph::from_bytes(&bytes, &mut t);
+        assert_eq!(directed.err(), Some(DecodeError::DirectionMismatch));
+
+        let read = |bytes: &[u8], t: &mut GhostToken<'_>| {
+            Graph::<String, (u32, bool), UnDirectedWeightedEdge<_, _>>::from_bytes(bytes, t).err()
+        };
+        assert_eq!(
+            read(&bytes[..bytes.len() - 1], &mut t),
+            Some(DecodeError::UnexpectedEnd)
+        );
+        assert_eq!(read(b"JSON", &mut t), Some(DecodeError::BadMagic));
+
+        let mut extra = bytes.clone();
+        extra.push(0);
+        assert_eq!(read(&extra, &mut t), Some(DecodeError::TrailingBytes(1)));
+
+        let mut newer = bytes;
+        newer[4] = 9;
+        assert_eq!(
+            read(&newer, &mut t),
+            Some(DecodeError::UnsupportedVersion(9))
+        );
+    });
+}
+
+#[test]
+fn corrupt_ids() {
+    // A header and a count, in the snapshot format
+    let start = |count: u64| {
+        let mut bytes = b"GRPH".to_vec();
+        bytes.push(1);
+        false.encode(&mut bytes);
+        count.encode(&mut bytes);
+        bytes
+    };
+
+    GhostToken::new(|mut t| {
+        let read = |bytes: &[u8], t: &mut GhostToken<'_>| {
+            Graph::<(), (), UnDirectedWeightedEdge<_, _>>::from_bytes(bytes, t).err()
+        };
+
+        // Ids however high are renumbered, rather than kept
+        let mut far = start(2);
+        for id in &[0, u64::MAX] {
+            id.encode(&mut far);
+        }
+        1_u64.encode(&mut far);
+        for id in &[u64::MAX, u64::MAX, 0] {
+            id.encode(&mut far);
+        }
+        let graph =
+            Graph::<(), (), UnDirectedWeightedEdge<_, _>>::from_bytes(&far, &mut t).unwrap();
+        assert_eq!(graph.vertex_ids().max(), Some(VertexId::new(1)));
+        assert_eq!(
+            graph.weight_between(VertexId::new(0), VertexId::new(1), &t),
+            Some(&())
+        );
+
+        let mut twice = start(2);
+        for id in &[7_u64, 7] {
+            id.encode(&mut twice);
+        }
+        assert_eq!(read(&twice, &mut t), Some(DecodeError::InvalidValue));
+
+        let mut missing = far;
+        let len = missing.len();
+        missing[len - 8..].copy_from_slice(&9_u64.to_le_bytes());
+        assert_eq!(read(&missing, &mut t), Some(DecodeError::InvalidEdge(0)));
+    });
+}
@@ -0,0 +1,96 @@
+use graph::{
+    edge::{DirectedWeightedEdge, UnDirectedWeightedEdge},
+    ghost::GhostToken,
+    ops::GraphOps,
+    DenseGraph, Graph,
+};
+
+#[test]
+fn adjacency() {
+    GhostToken::new(|t| {
+        let mut graph: DenseGraph<usize, u32> = DenseGraph::new(false);
+
+        let ids: Vec<_> = (0..10).map(|i| graph.add_vertex(i)).collect();
+
+        assert_eq!(graph.insert_edge(ids[0], ids[9], 4), None);
+        assert_eq!(graph.insert_edge(ids[9], ids[0], 5), Some(4));
+
+        assert!(graph.adjacent(ids[0], ids[9]));
+        assert_eq!(graph.weight(ids[0], ids[9]), Some(&5));
+        assert_eq!(graph.edge_len(), 1);
+
+        let successors: Vec<_> = graph.successors(ids[9], &t).collect();
+        assert_eq!(successors, vec![(ids[0], &5)]);
+
+        assert_eq!(graph.remove_edge(ids[0], ids[9]), Some(5));
+        assert!(!graph.adjacent(ids[9], ids[0]));
+        assert_eq!(*graph.get_item(ids[7]).unwrap(), 7);
+    });
+}
+
+#[test]
+fn floyd_warshall() {
+    let mut graph: DenseGraph<(), i32> = DenseGraph::new(true);
+
+    let a = graph.add_vertex(());
+    let b = graph.add_vertex(());
+    let c = graph.add_vertex(());
+
+    graph.insert_edge(a, b, 4);
+    graph.insert_edge(b, c, 3);
+    graph.insert_edge(a, c, 10);
+
+    let distances = graph.floyd_warshall();
+
+    assert_eq!(distances[a.id()][c.id()], Some(7));
+    assert_eq!(distances[c.id()][a.id()], None);
+    assert_eq!(distances[b.id()][b.id()], Some(0));
+}
+
+#[test]
+fn convert() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<u8, u32, DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let one = graph.add_vertex(1);
+        let two = graph.add_vertex(2);
+        let three = graph.add_vertex(3);
+
+        graph.add_edge(one, two, 12, &mut t).unwrap();
+        graph.add_edge(three, one, 31, &mut t).unwrap();
+
+        let dense = DenseGraph::from_graph(&graph, &t);
+
+        assert!(dense.is_directed());
+        assert_eq!(dense.edge_len(), 2);
+        assert_eq!(dense.weight(one, two), Some(&12));
+        assert_eq!(dense.weight(two, one), None);
+        assert_eq!(
+            dense.predecessors(one, &t).collect::<Vec<_>>(),
+            vec![(three, &31)]
+        );
+
+        let back: Graph<u8, u32, DirectedWeightedEdge<_, _>> = dense.into_graph(&mut t).unwrap();
+
+        assert_eq!(back.vertex_len(), 3);
+        assert_eq!(back.edge_len(), 2);
+        assert_eq!(back.weight_between(three, one, &t), Some(&31));
+        assert_eq!(back.weight_between(one, three, &t), None);
+    });
+}
+
+#[test]
+fn graph_ops() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let one = graph.add_vertex(());
+        let two = graph.add_vertex(());
+
+        graph.add_edge(one, two, 3, &mut t).unwrap();
+
+        assert!(!graph.is_directed());
+        assert_eq!(graph.weight_between(two, one, &t), Some(&3));
+        assert_eq!(graph.predecessors(one, &t).count(), 1);
+    });
+}
@@ -0,0 +1,58 @@
+use graph::{
+    edge::{DirectedWeightedEdge, UnDirectedWeightedEdge},
+    ghost::GhostToken,
+    Graph,
+};
+
+#[test]
+fn freeze_undirected() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let one = graph.add_vertex(());
+        let two = graph.add_vertex(());
+        let three = graph.add_vertex(());
+
+        graph.add_edge(one, two, 3, &mut t).unwrap();
+        graph.add_edge(two, three, 5, &mut t).unwrap();
+
+        let frozen = graph.freeze(&t);
+
+        assert_eq!(frozen.vertex_len(), 3);
+        assert_eq!(frozen.adjacency_len(), 4);
+
+        let two_index = frozen.index_of(two).unwrap();
+        assert_eq!(frozen.id_at(two_index), two);
+        assert_eq!(frozen.degree(two_index), 2);
+        assert_eq!(frozen.weights(two_index), &[3, 5]);
+
+        let neighbors: Vec<_> = frozen
+            .neighbors(two_index)
+            .iter()
+            .map(|i| frozen.id_at(*i))
+            .collect();
+        assert_eq!(neighbors, vec![one, three]);
+    });
+}
+
+#[test]
+fn freeze_directed() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let one = graph.add_vertex(());
+        let two = graph.add_vertex(());
+
+        let edge = graph.add_edge(one, two, (), &mut t).unwrap();
+
+        let frozen = graph.freeze(&t);
+
+        let one_index = frozen.index_of(one).unwrap();
+        let two_index = frozen.index_of(two).unwrap();
+
+        assert_eq!(frozen.adjacency_len(), 1);
+        assert_eq!(frozen.neighbors(one_index), &[two_index]);
+        assert_eq!(frozen.edge_ids(one_index), &[edge]);
+        assert!(frozen.neighbors(two_index).is_empty());
+    });
+}
@@ -0,0 +1,95 @@
+use graph::{
+    edge::{DirectedWeightedEdge, UnDirectedWeightedEdge},
+    generators::{self, RandomSource, SplitMix64},
+    ghost::GhostToken,
+    Graph,
+};
+
+type Undirected<'id> = Graph<'id, usize, (), UnDirectedWeightedEdge<'id, usize, ()>>;
+
+#[test]
+fn deterministic_shapes() {
+    GhostToken::new(|mut t| {
+        let graph: Undirected = generators::complete(5, |i| i, |_, _| (), &mut t).unwrap();
+        assert_eq!((graph.vertex_len(), graph.edge_len()), (5, 10));
+        assert!(graph.vertex_ids().all(|id| graph.degree(id) == Some(4)));
+
+        let graph: Undirected = generators::cycle(6, |i| i, |_, _| (), &mut t).unwrap();
+        assert_eq!(graph.edge_len(), 6);
+        assert!(graph.vertex_ids().all(|id| graph.degree(id) == Some(2)));
+
+        let graph: Undirected = generators::cycle(2, |i| i, |_, _| (), &mut t).unwrap();
+        assert_eq!(graph.edge_len(), 1);
+
+        let graph: Graph<(usize, usize), (), UnDirectedWeightedEdge<_, _>> =
+            generators::grid(4, 3, |x, y| (x, y), |_, _| (), &mut t).unwrap();
+        assert_eq!((graph.vertex_len(), graph.edge_len()), (12, 17));
+        let corners = graph
+            .vertex_ids()
+            .filter(|id| graph.degree(*id) == Some(2))
+            .count();
+        assert_eq!(corners, 4);
+    });
+}
+
+#[test]
+fn random_graphs() {
+    GhostToken::new(|mut t| {
+        let mut rng = SplitMix64::new(7);
+
+        let empty: Undirected =
+            generators::erdos_renyi(20, 0.0, &mut rng, |i| i, |_, _| (), &mut t).unwrap();
+        assert_eq!(empty.edge_len(), 0);
+
+        let full: Graph<usize, (), DirectedWeightedEdge<_, _>> =
+            generators::erdos_renyi(20, 1.0, &mut rng, |i| i, |_, _| (), &mut t).unwrap();
+        assert_eq!(full.edge_len(), 190);
+
+        let (n, m) = (50, 3);
+        let graph: Undirected =
+            generators::barabasi_albert(n, m, &mut rng, |i| i, |_, _| (), &mut t).unwrap();
+        assert_eq!(graph.edge_len(), (n - m) * m);
+        assert!(graph.vertex_ids().all(|id| graph.degree(id).unwrap() >= m));
+        assert_eq!(graph.validate(&t), Ok(()));
+    });
+
+    let mut first = SplitMix64::new(42);
+    let mut second = SplitMix64::new(42);
+    for _ in 0..100 {
+        assert_eq!(first.next_u64(), second.next_u64());
+        assert!((0.0..1.0).contains(&first.next_f64()));
+        assert!(first.below(10) < 10);
+        second.next_f64();
+        second.below(10);
+    }
+}
+
+#[test]
+fn grid_graphs() {
+    use graph::{ops::GraphOps, VertexId};
+
+    GhostToken::new(|mut t| {
+        let lattice: Graph<(usize, usize), (), UnDirectedWeightedEdge<_, _>> =
+            Graph::grid(4, 3, false, |x, y| (x, y), |_, _| (), &mut t).unwrap();
+        assert_eq!((lattice.vertex_len(), lattice.edge_len()), (12, 17));
+        assert_eq!(lattice.item(VertexId::new(6), &t), Some(&(2, 1)));
+
+        let diagonal: Graph<(usize, usize), (), UnDirectedWeightedEdge<_, _>> =
+            Graph::grid(4, 3, true, |x, y| (x, y), |_, _| (), &mut t).unwrap();
+        assert_eq!(diagonal.edge_len(), 29);
+        assert_eq!(diagonal.degree(VertexId::new(0)), Some(3));
+        assert_eq!(diagonal.degree(VertexId::new(5)), Some(8));
+
+        let directed: Graph<(), usize, DirectedWeightedEdge<_, _>> =
+            Graph::grid(3, 3, false, |_, _| (), |(x, y), _| x + y * 3, &mut t).unwrap();
+        assert_eq!(directed.edge_len(), 24);
+        assert_eq!(
+            directed.weight_between(VertexId::new(4), VertexId::new(1), &t),
+            Some(&4)
+        );
+        assert_eq!(
+            directed.weight_between(VertexId::new(1), VertexId::new(4), &t),
+            Some(&1)
+        );
+    });
+}
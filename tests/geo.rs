@@ -0,0 +1,108 @@
+#![cfg(feature = "geo")]
+
+use graph::{
+    algo::dijkstra,
+    edge::{EdgeTrait, UnDirectedWeightedEdge},
+    generators::{RandomSource, SplitMix64},
+    geo::{astar_euclidean, euclidean, SpatialIndex},
+    ghost::GhostToken,
+    Graph,
+};
+
+#[test]
+fn euclidean_weights() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(f64, f64), f64, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let one = graph.add_vertex((0., 0.));
+        let two = graph.add_vertex((3., 4.));
+
+        let edge = graph
+            .add_edge_with_euclidean_weight(one, two, &mut t)
+            .unwrap();
+        assert_eq!(*graph.get_edge(edge).unwrap().borrow(&t).get_weight(), 5.);
+        assert!(graph
+            .add_edge_with_euclidean_weight(one, graph::VertexId::new(9), &mut t)
+            .is_err());
+    });
+}
+
+#[test]
+fn nearest_vertices() {
+    let mut rng = SplitMix64::new(3);
+
+    GhostToken::new(|t| {
+        let mut graph: Graph<[f64; 3], (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        for _ in 0..200 {
+            graph.add_vertex([rng.next_f64(), rng.next_f64(), rng.next_f64()]);
+        }
+
+        let index = SpatialIndex::build(&graph, &t);
+        assert_eq!(index.len(), 200);
+
+        for _ in 0..50 {
+            let point = [rng.next_f64(), rng.next_f64(), rng.next_f64()];
+
+            let mut expected: Vec<_> = graph
+                .items(&t)
+                .map(|(id, item)| (id, euclidean(item, &point)))
+                .collect();
+            expected.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+            assert_eq!(index.nearest(&point), Some(expected[0]));
+            assert_eq!(index.k_nearest(&point, 7), expected[..7]);
+
+            let mut within = index.within(&point, 0.3);
+            within.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+            let inside = expected.iter().take_while(|(_, d)| *d <= 0.3).count();
+            assert_eq!(within, expected[..inside]);
+        }
+
+        assert_eq!(index.k_nearest(&[0.; 3], 500).len(), 200);
+    });
+}
+
+#[test]
+fn astar_matches_dijkstra() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(f64, f64), f64, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let ids: Vec<_> = (0..64)
+            .map(|i| graph.add_vertex(((i % 8) as f64, (i / 8) as f64)))
+            .collect();
+        for i in 0..64 {
+            // Leave a wall with a gap, so the straight line is blocked
+            if i % 8 == 3 && i / 8 != 6 {
+                continue;
+            }
+            if i % 8 < 7 {
+                graph
+                    .add_edge_with_euclidean_weight(ids[i], ids[i + 1], &mut t)
+                    .unwrap();
+            }
+            if i < 56 {
+                graph
+                    .add_edge_with_euclidean_weight(ids[i], ids[i + 8], &mut t)
+                    .unwrap();
+            }
+        }
+
+        let expected = dijkstra(&graph, ids[0], |w| *w, &t).unwrap();
+        for &target in &ids {
+            let path = astar_euclidean(&graph, ids[0], target, |w| *w, &t).unwrap();
+            assert_eq!(path.as_ref().map(|p| p.length), expected.distance(target));
+
+            if let Some(path) = path {
+                assert_eq!(path.vertices.first(), Some(&ids[0]));
+                assert_eq!(path.vertices.last(), Some(&target));
+                assert_eq!(path.edges.len() + 1, path.vertices.len());
+            }
+        }
+
+        let lonely = graph.add_vertex((20., 20.));
+        assert_eq!(
+            astar_euclidean(&graph, ids[0], lonely, |w| *w, &t).unwrap(),
+            None
+        );
+    });
+}
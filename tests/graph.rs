@@ -1,7 +1,7 @@
 use graph::{
     edge::{EdgeTrait, UnDirectedWeightedEdge},
     ghost::GhostToken,
-    Graph, Node,
+    Graph,
 };
 
 #[test]
@@ -218,3 +218,1625 @@ fn distance() {
         assert_eq!(1., *distance);
     });
 }
+
+#[test]
+fn split_vertex() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<usize, usize, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let center = graph.add_vertex(10);
+        let one = graph.add_vertex(1);
+        let two = graph.add_vertex(2);
+        let three = graph.add_vertex(3);
+
+        graph.add_edge(center, one, 1, &mut t).unwrap();
+        graph.add_edge(center, two, 2, &mut t).unwrap();
+        graph.add_edge(center, three, 3, &mut t).unwrap();
+
+        let (first, second) = graph
+            .split_vertex(
+                center,
+                |e| *e.get_weight() < 2,
+                |item| (item / 2, item - item / 2),
+                Some(0),
+                &mut t,
+            )
+            .unwrap();
+
+        assert_eq!(first, center);
+        assert_eq!(graph.vertex_len(), 5);
+        assert_eq!(graph.edge_len(), 4);
+
+        assert!(graph.adjacent(first, one, &t).unwrap());
+        assert!(!graph.adjacent(first, two, &t).unwrap());
+        assert!(graph.adjacent(second, two, &t).unwrap());
+        assert!(graph.adjacent(second, three, &t).unwrap());
+        assert!(graph.adjacent(first, second, &t).unwrap());
+
+        assert_eq!(*graph.get_vertex(second).unwrap().borrow(&t).get_item(), 5);
+
+        graph.remove(second, &mut t).unwrap();
+
+        assert_eq!(graph.get_vertex(two).unwrap().borrow(&t).edges().len(), 0);
+    });
+}
+
+#[test]
+fn drops_items() {
+    use std::rc::Rc;
+
+    let item = Rc::new(());
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<Rc<()>, Rc<()>, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let ids: Vec<_> = (0..100).map(|_| graph.add_vertex(item.clone())).collect();
+
+        for pair in ids.windows(2) {
+            graph
+                .add_edge(pair[0], pair[1], item.clone(), &mut t)
+                .unwrap();
+        }
+
+        graph.remove(ids[50], &mut t).unwrap();
+
+        assert_eq!(Rc::strong_count(&item), 1 + 99 + 97);
+
+        // Reuses the freed slot
+        graph.add_vertex(item.clone());
+
+        graph.clear();
+
+        assert_eq!(Rc::strong_count(&item), 1);
+
+        let one = graph.add_vertex(item.clone());
+        let two = graph.add_vertex(item.clone());
+        graph.add_edge(one, two, item.clone(), &mut t).unwrap();
+    });
+
+    assert_eq!(Rc::strong_count(&item), 1);
+}
+
+#[test]
+fn clear() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<usize, f64, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        for _ in 0..2 {
+            let ids: Vec<_> = (0..50).map(|i| graph.add_vertex(i)).collect();
+
+            for pair in ids.windows(2) {
+                graph.add_edge(pair[0], pair[1], 1., &mut t).unwrap();
+            }
+
+            assert_eq!(ids[0].id(), 0);
+            assert_eq!(graph.edge_len(), 49);
+
+            graph.clear();
+
+            assert!(graph.is_empty());
+            assert_eq!(graph.edge_len(), 0);
+        }
+
+        // Edges with plain weights are released without visiting them
+        assert!(!std::mem::needs_drop::<UnDirectedWeightedEdge<usize, f64>>());
+    });
+}
+
+#[test]
+fn capacity() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<usize, (), UnDirectedWeightedEdge<_, _>> =
+            Graph::with_capacity(100, 200);
+
+        assert!(graph.vertex_capacity() >= 100);
+        assert!(graph.edge_capacity() >= 200);
+
+        let ids: Vec<_> = (0..100).map(|i| graph.add_vertex(i)).collect();
+        for pair in ids.windows(2) {
+            graph.add_edge(pair[0], pair[1], (), &mut t).unwrap();
+        }
+
+        graph.reserve_vertices(1000);
+        graph.reserve_edges(1000);
+        assert!(graph.vertex_capacity() >= 1100);
+        assert!(graph.edge_capacity() >= 1099);
+
+        graph.clear();
+        graph.shrink_to_fit();
+        assert_eq!(graph.vertex_capacity(), 0);
+
+        let one = graph.add_vertex(1);
+        let two = graph.add_vertex(2);
+        graph.add_edge(one, two, (), &mut t).unwrap();
+        assert!(graph.adjacent(one, two, &t).unwrap());
+    });
+}
+
+#[derive(Default)]
+struct Fnv(u64);
+
+impl std::hash::Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+#[test]
+fn custom_hasher() {
+    type FnvBuilder = std::hash::BuildHasherDefault<Fnv>;
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<usize, (), UnDirectedWeightedEdge<_, _>, FnvBuilder> =
+            Graph::with_capacity_and_hasher(4, 4, FnvBuilder::default());
+
+        let ids: Vec<_> = (0..4).map(|i| graph.add_vertex(i)).collect();
+        for pair in ids.windows(2) {
+            graph.add_edge(pair[0], pair[1], (), &mut t).unwrap();
+        }
+
+        assert_eq!(graph.vertex_len(), 4);
+        assert_eq!(graph.edge_len(), 3);
+        assert!(graph.adjacent(ids[1], ids[2], &t).unwrap());
+        assert_eq!(graph::algo::connected_components(&graph, &t).len(), 1);
+
+        graph.remove(ids[1], &mut t).unwrap();
+        assert_eq!(graph::algo::connected_components(&graph, &t).len(), 2);
+
+        let defaulted: Graph<usize, (), UnDirectedWeightedEdge<_, _>, FnvBuilder> =
+            Graph::default();
+        assert!(defaulted.is_empty());
+    });
+}
+
+#[test]
+fn items() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<usize, (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        graph.add_vertices(0..5);
+
+        let mut ids: Vec<_> = graph.vertex_ids().map(|id| id.id()).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![0, 1, 2, 3, 4]);
+
+        for (id, item) in graph.items_mut(&mut t) {
+            *item += id.id() * 10;
+        }
+
+        let mut items: Vec<_> = graph.items(&t).map(|(_, item)| *item).collect();
+        items.sort_unstable();
+        assert_eq!(items, vec![0, 11, 22, 33, 44]);
+    });
+}
+
+#[test]
+fn weights_mut() {
+    use graph::ops::GraphOps;
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let ids: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        for (i, pair) in ids.windows(2).enumerate() {
+            graph.add_edge(pair[0], pair[1], i as u32, &mut t).unwrap();
+        }
+
+        let mut visited = 0;
+        for (_, weight) in graph.weights_mut(&mut t) {
+            *weight = *weight * 2 + 1;
+            visited += 1;
+        }
+        assert_eq!(visited, 3);
+
+        let mut weights: Vec<_> = graph.weights(&t).map(|(_, weight)| *weight).collect();
+        weights.sort_unstable();
+        assert_eq!(weights, vec![1, 3, 5]);
+        assert_eq!(graph.weight_between(ids[1], ids[0], &t), Some(&1));
+    });
+}
+
+#[test]
+fn unique_edges() {
+    use graph::edge::DirectedWeightedEdge;
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let ids: Vec<_> = (0..3).map(|_| graph.add_vertex(())).collect();
+        let first = graph.add_edge(ids[0], ids[1], 1, &mut t).unwrap();
+        let second = graph.add_edge(ids[1], ids[2], 2, &mut t).unwrap();
+
+        let mut edges: Vec<_> = graph
+            .unique_edges(&t)
+            .map(|(id, _, _, weight)| (id, *weight))
+            .collect();
+        edges.sort_unstable();
+        assert_eq!(edges, vec![(first, 1), (second, 2)]);
+
+        let mut directed: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+        let (a, b) = (directed.add_vertex(()), directed.add_vertex(()));
+        let edge = directed.add_edge(b, a, 7, &mut t).unwrap();
+
+        let edges: Vec<_> = directed.unique_edges(&t).collect();
+        assert_eq!(edges, vec![(edge, b, a, &7)]);
+    });
+}
+
+#[test]
+fn into_items() {
+    use std::rc::Rc;
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<Rc<()>, Rc<()>, UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let counter = Rc::new(());
+
+        let ids: Vec<_> = (0..4).map(|_| graph.add_vertex(counter.clone())).collect();
+        for pair in ids.windows(2) {
+            graph
+                .add_edge(pair[0], pair[1], counter.clone(), &mut t)
+                .unwrap();
+        }
+
+        let mut seen = 0;
+        for (id, vertex) in &graph {
+            assert_eq!(vertex.borrow(&t).id(), id);
+            seen += 1;
+        }
+        assert_eq!(seen, 4);
+
+        graph.remove(ids[3], &mut t).unwrap();
+        assert_eq!(Rc::strong_count(&counter), 6);
+
+        let items = graph.into_items(&mut t);
+        assert_eq!(
+            items.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            ids[..3].to_vec()
+        );
+        assert_eq!(Rc::strong_count(&counter), 4);
+
+        drop(items);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    });
+}
+
+#[test]
+fn remove_take() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<String, (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let first = graph.add_vertex(String::from("first"));
+        let second = graph.add_vertex(String::from("second"));
+        graph.add_edge(first, second, (), &mut t).unwrap();
+
+        assert_eq!(graph.remove_take(first, &mut t).unwrap(), "first");
+        assert!(graph.remove_take(first, &mut t).is_err());
+        assert_eq!(graph.vertex_len(), 1);
+        assert_eq!(
+            graph.get_vertex(second).unwrap().borrow(&t).edges().count(),
+            0
+        );
+    });
+}
+
+#[test]
+fn from_adjacency_matrix() {
+    use graph::{edge::DirectedWeightedEdge, VertexId};
+
+    GhostToken::new(|mut t| {
+        let graph: Graph<char, u8, DirectedWeightedEdge<_, _>> = Graph::from_adjacency_matrix(
+            &[
+                [None, Some(1), None],
+                [None, None, Some(2)],
+                [Some(3), None, None],
+            ],
+            ['a', 'b', 'c'],
+            &mut t,
+        )
+        .unwrap();
+
+        assert_eq!(graph.vertex_len(), 3);
+        assert_eq!(graph.edge_len(), 3);
+        assert_eq!(
+            *graph
+                .get_vertex(VertexId::new(2))
+                .unwrap()
+                .borrow(&t)
+                .get_item(),
+            'c'
+        );
+        assert!(graph
+            .adjacent(VertexId::new(0), VertexId::new(1), &t)
+            .unwrap());
+    });
+
+    GhostToken::new(|mut t| {
+        let matrix = vec![
+            vec![None, Some(()), Some(())],
+            vec![Some(()), None, None],
+            vec![Some(()), None, None],
+        ];
+        let graph: Graph<usize, (), UnDirectedWeightedEdge<_, _>> =
+            Graph::from_adjacency_slice(&matrix, 0..3, &mut t).unwrap();
+
+        assert_eq!(graph.edge_len(), 2);
+        assert!(graph
+            .adjacent(VertexId::new(2), VertexId::new(0), &t)
+            .unwrap());
+        assert!(!graph
+            .adjacent(VertexId::new(1), VertexId::new(2), &t)
+            .unwrap());
+
+        let looped = [[Some(())]];
+        let looped: Result<Graph<usize, (), UnDirectedWeightedEdge<_, _>>, _> =
+            Graph::from_adjacency_matrix(&looped, [0], &mut t);
+        assert!(looped.is_err());
+    });
+}
+
+#[test]
+fn endpoints() {
+    use graph::{
+        edge::{DirectedWeightedEdge, EdgeTrait},
+        EdgeId,
+    };
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let first = graph.add_vertex(());
+        let second = graph.add_vertex(());
+        let edge = graph.add_edge(second, first, (), &mut t).unwrap();
+
+        assert_eq!(graph.endpoints(edge, &t), Some((second, first)));
+        assert_eq!(graph.endpoints(EdgeId::new(5), &t), None);
+
+        // The edge knows its endpoints' ids without the token
+        let stored = graph.get_edge(edge).unwrap().borrow(&t);
+        assert_eq!(stored.endpoints(), (second, first));
+        assert_eq!(stored.other(first), Some(second));
+        assert_eq!(stored.traverse(second), Some(first));
+        assert_eq!(stored.traverse(first), None);
+
+        // and keeps them up to date as the graph is reshaped
+        graph.reverse_edge(edge, &mut t).unwrap();
+        assert_eq!(graph.endpoints(edge, &t), Some((first, second)));
+        let third = graph.add_vertex(());
+        let other = graph.add_edge(third, second, (), &mut t).unwrap();
+        let kept = graph
+            .contract_edge(edge, |_, _| {}, |_, _| {}, &mut t)
+            .unwrap();
+        assert_eq!(graph.endpoints(other, &t), Some((third, kept)));
+        assert_eq!(graph.validate(&t), Ok(()));
+    });
+}
+
+#[test]
+fn degrees() {
+    use graph::edge::DirectedWeightedEdge;
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(v[0], v[1], (), &mut t).unwrap();
+        graph.add_edge(v[0], v[2], (), &mut t).unwrap();
+        graph.add_edge(v[3], v[0], (), &mut t).unwrap();
+
+        assert_eq!(graph.out_degree(v[0]), Some(2));
+        assert_eq!(graph.in_degree(v[0]), Some(1));
+        assert_eq!(graph.degree(v[0]), Some(3));
+
+        graph.remove_edge_between(v[2], v[0], &mut t).unwrap();
+        assert_eq!(graph.out_degree(v[0]), Some(1));
+        assert_eq!(graph.in_degree(v[2]), Some(0));
+
+        graph.remove(v[3], &mut t).unwrap();
+        assert_eq!(graph.in_degree(v[0]), Some(0));
+        assert_eq!(graph.degree(v[3]), None);
+
+        let (first, second) = graph
+            .split_vertex(v[0], |_| false, |()| ((), ()), Some(()), &mut t)
+            .unwrap();
+        assert_eq!(
+            (graph.out_degree(first), graph.in_degree(first)),
+            (Some(1), Some(0))
+        );
+        assert_eq!(
+            (graph.out_degree(second), graph.in_degree(second)),
+            (Some(1), Some(1))
+        );
+    });
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..3).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(v[0], v[1], (), &mut t).unwrap();
+        graph.add_edge(v[2], v[0], (), &mut t).unwrap();
+
+        for id in &v {
+            let edges = graph.get_vertex(*id).unwrap().borrow(&t).edges().count();
+            assert_eq!(graph.degree(*id), Some(edges));
+            assert_eq!(graph.in_degree(*id), Some(edges));
+            assert_eq!(graph.out_degree(*id), Some(edges));
+        }
+
+        graph.clear();
+        assert_eq!(graph.degree(v[0]), None);
+    });
+}
+
+#[test]
+fn repair() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(v[0], v[1], (), &mut t).unwrap();
+        graph.add_edge(v[1], v[2], (), &mut t).unwrap();
+        graph.add_edge(v[2], v[3], (), &mut t).unwrap();
+
+        assert!(graph.repair(&mut t).is_clean());
+        assert_eq!(graph.validate(&t), Ok(()));
+
+        graph.remove(v[1], &mut t).unwrap();
+
+        let report = graph.repair(&mut t);
+        assert!(report.unrecoverable.is_empty());
+        assert_eq!(graph.vertex_len(), 3);
+        assert_eq!(graph.edge_len(), 1);
+        assert_eq!(graph.degree(v[2]), Some(1));
+
+        assert!(graph.repair(&mut t).is_clean());
+        assert_eq!(graph.validate(&t), Ok(()));
+    });
+}
+
+#[test]
+fn timestamps() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let old = graph.add_vertex(());
+        assert_eq!(graph.vertex_timestamp(old), None);
+
+        graph.track_timestamps();
+        let start = graph.vertex_timestamp(old).unwrap();
+
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        let edge = graph.add_edge(a, b, (), &mut t).unwrap();
+
+        let (a_time, b_time) = (
+            graph.vertex_timestamp(a).unwrap(),
+            graph.vertex_timestamp(b).unwrap(),
+        );
+        assert!(start < a_time && a_time < b_time);
+        assert!(graph.edge_timestamp(edge).unwrap().created > b_time.created);
+
+        assert!(graph.touch_vertex(a));
+        let touched = graph.vertex_timestamp(a).unwrap();
+        assert_eq!(touched.created, a_time.created);
+        assert_eq!(touched.modified, graph.logical_time().unwrap());
+
+        graph
+            .create_or_update_edge_between(a, b, (), |(), _, _, _, _| (), &mut t)
+            .unwrap();
+        let updated = graph.edge_timestamp(edge).unwrap();
+        assert!(updated.modified > updated.created);
+
+        graph.remove(b, &mut t).unwrap();
+        assert_eq!(graph.vertex_timestamp(b), None);
+        assert_eq!(graph.edge_timestamp(edge), None);
+        assert!(!graph.touch_vertex(b));
+
+        graph.untrack_timestamps();
+        assert_eq!(graph.logical_time(), None);
+    });
+}
+
+#[test]
+fn vertices_chunks() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..7).map(|_| graph.add_vertex(())).collect();
+        graph.remove(v[2], &mut t).unwrap();
+
+        let chunks: Vec<Vec<_>> = graph
+            .vertices_chunks(2)
+            .map(|chunk| chunk.into_iter().map(|id| id.id()).collect())
+            .collect();
+        assert_eq!(chunks, vec![vec![0, 1], vec![3, 4], vec![5, 6]]);
+
+        let mut chunks = graph.vertices_chunks(3);
+        assert_eq!(chunks.next().unwrap().len(), 3);
+        let resume = chunks.resume_from();
+
+        graph.remove(v[5], &mut t).unwrap();
+        let added = graph.add_vertex(());
+
+        let rest: Vec<_> = graph.vertices_chunks_from(resume, 3).flatten().collect();
+        assert_eq!(rest, vec![v[4], v[6], added]);
+    });
+}
+
+#[test]
+fn lengths_follow_contents() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        let first = graph.add_edge(v[0], v[1], (), &mut t).unwrap();
+        let second = graph.add_edge(v[1], v[2], (), &mut t).unwrap();
+        let third = graph.add_edge(v[2], v[3], (), &mut t).unwrap();
+
+        assert!(graph.add_edge(v[0], v[0], (), &mut t).is_err());
+        assert!(graph.add_edge(v[1], v[0], (), &mut t).is_err());
+        assert_eq!(graph.edge_len(), 3);
+
+        let mut ids: Vec<_> = graph.edge_ids().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![first, second, third]);
+
+        graph.remove_edge_between(v[2], v[3], &mut t).unwrap();
+        assert_eq!(graph.edge_len(), 2);
+
+        graph.remove(v[1], &mut t).unwrap();
+        assert!(graph.remove(v[1], &mut t).is_err());
+        assert_eq!((graph.vertex_len(), graph.edge_len()), (3, 0));
+        assert_eq!(graph.edge_ids().count(), 0);
+        assert_eq!(graph.validate(&t), Ok(()));
+    });
+}
+
+#[test]
+fn diff_and_apply() {
+    GhostToken::new(|mut t| {
+        fn build<'id>(
+            t: &mut GhostToken<'id>,
+        ) -> Graph<'id, u32, u32, UnDirectedWeightedEdge<'id, u32, u32>> {
+            let mut graph = Graph::new();
+            let v: Vec<_> = (0..4).map(|i| graph.add_vertex(i)).collect();
+            graph.add_edge(v[0], v[1], 1, t).unwrap();
+            graph.add_edge(v[1], v[2], 2, t).unwrap();
+            graph.add_edge(v[2], v[3], 3, t).unwrap();
+            graph
+        }
+
+        let mut local = build(&mut t);
+        let mut remote = build(&mut t);
+        assert!(local.diff(&remote, &t).is_empty());
+
+        let v: Vec<_> = remote.vertex_ids().collect();
+        let [zero, one, two, three] =
+            [0, 1, 2, 3].map(|i| *v.iter().find(|id| id.id() == i).unwrap());
+
+        remote.remove(zero, &mut t).unwrap();
+        let four = remote.add_vertex(4);
+        remote.add_edge(four, three, 5, &mut t).unwrap();
+        remote
+            .create_or_update_edge_between(one, two, (), |(), _, _, _, _| 20, &mut t)
+            .unwrap();
+        *remote
+            .get_vertex(three)
+            .unwrap()
+            .borrow_mut(&mut t)
+            .get_item_mut() = 30;
+
+        let diff = local.diff(&remote, &t);
+        assert_eq!(diff.removed_vertices, vec![zero]);
+        assert_eq!(diff.added_vertices, vec![(four, 4)]);
+        assert_eq!(diff.changed_items, vec![(three, 30)]);
+        assert_eq!(diff.removed_edges.len(), 1);
+        assert_eq!(diff.added_edges.len(), 1);
+        assert_eq!(diff.changed_weights.len(), 1);
+
+        local.apply(diff, &mut t).unwrap();
+        assert!(local.diff(&remote, &t).is_empty());
+        assert_eq!(local.validate(&t), Ok(()));
+        assert_eq!(local.add_vertex(5).id(), 5);
+    });
+}
+
+#[test]
+fn observer() {
+    use graph::{EdgeId, GraphObserver, VertexId};
+    use std::{cell::RefCell, rc::Rc};
+
+    struct Log(Rc<RefCell<Vec<String>>>);
+
+    impl<'id> GraphObserver<'id> for Log {
+        fn on_vertex_added(&mut self, id: VertexId<'id>) {
+            self.0.borrow_mut().push(format!("+v{}", id.id()));
+        }
+        fn on_vertex_removed(&mut self, id: VertexId<'id>) {
+            self.0.borrow_mut().push(format!("-v{}", id.id()));
+        }
+        fn on_edge_added(&mut self, id: EdgeId<'id>, from: VertexId<'id>, to: VertexId<'id>) {
+            self.0
+                .borrow_mut()
+                .push(format!("+e{} {}-{}", id.id, from.id(), to.id()));
+        }
+        fn on_edge_changed(&mut self, id: EdgeId<'id>) {
+            self.0.borrow_mut().push(format!("~e{}", id.id));
+        }
+        fn on_edge_removed(&mut self, id: EdgeId<'id>) {
+            self.0.borrow_mut().push(format!("-e{}", id.id));
+        }
+        fn on_cleared(&mut self) {
+            self.0.borrow_mut().push(String::from("clear"));
+        }
+    }
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let unseen = graph.add_vertex(());
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        assert!(graph.set_observer(Log(Rc::clone(&log))).is_none());
+
+        let a = graph.add_vertex(());
+        graph.add_edge(unseen, a, (), &mut t).unwrap();
+        // Timestamps aren't recorded, but the observer is still told
+        assert!(!graph.touch_edge(EdgeId::new(0)));
+        graph.remove(a, &mut t).unwrap();
+        graph.clear();
+
+        assert_eq!(
+            *log.borrow(),
+            ["+v1", "+e0 0-1", "~e0", "-e0", "-v1", "clear"]
+        );
+
+        assert!(graph.take_observer().is_some());
+        graph.add_vertex(());
+        assert_eq!(log.borrow().len(), 6);
+    });
+}
+
+#[test]
+fn index_by() {
+    use graph::IndexedGraph;
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<String, (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let london = graph.add_vertex(String::from("London"));
+        graph.add_vertex(String::from("Paris"));
+        let again = graph.add_vertex(String::from("London"));
+
+        let mut graph = graph.index_by(Clone::clone, &t);
+        assert_eq!(graph.find("London"), Some(london));
+        assert_eq!(graph.index_len(), 2);
+        assert_eq!(graph.vertex_len(), 3);
+
+        graph.remove(again, &mut t).unwrap();
+        assert_eq!(graph.find("London"), Some(london));
+
+        let berlin = graph.add_vertex(String::from("Berlin")).unwrap();
+        assert_eq!(
+            graph.add_vertex(String::from("Berlin")),
+            Err(String::from("Berlin"))
+        );
+        assert_eq!(graph.get_or_add_vertex(String::from("Berlin")), berlin);
+        graph.add_edge(london, berlin, (), &mut t).unwrap();
+
+        *graph
+            .get_vertex(berlin)
+            .unwrap()
+            .borrow_mut(&mut t)
+            .get_item_mut() = String::from("Bonn");
+        graph.reindex(&t);
+        assert_eq!(graph.find("Berlin"), None);
+        assert_eq!(graph.find("Bonn"), Some(berlin));
+
+        assert_eq!(graph.remove_take(london, &mut t).unwrap(), "London");
+        assert!(!graph.contains_key("London"));
+
+        let mut empty: IndexedGraph<_, (), UnDirectedWeightedEdge<_, _>, _, _> =
+            IndexedGraph::new(|item: &u32| item % 10);
+        let one = empty.get_or_add_vertex(1);
+        assert_eq!(empty.get_or_add_vertex(11), one);
+        assert_eq!(empty.into_inner().vertex_len(), 1);
+    });
+}
+
+#[test]
+fn add_edge_bidirectional() {
+    use graph::{edge::DirectedWeightedEdge, ops::GraphOps, GraphError};
+
+    GhostToken::new(|mut t| {
+        let mut roads: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+        let (a, b, c) = (
+            roads.add_vertex(()),
+            roads.add_vertex(()),
+            roads.add_vertex(()),
+        );
+
+        let (up, down) = roads.add_edge_bidirectional(a, b, 5, 3, &mut t).unwrap();
+        assert_eq!(roads.endpoints(up, &t), Some((a, b)));
+        assert_eq!(roads.endpoints(down, &t), Some((b, a)));
+        assert_eq!(roads.weight_between(a, b, &t), Some(&5));
+        assert_eq!(roads.weight_between(b, a, &t), Some(&3));
+        assert_eq!(
+            (roads.in_degree(a), roads.out_degree(a)),
+            (Some(1), Some(1))
+        );
+
+        roads.add_edge(b, c, 1, &mut t).unwrap();
+        assert!(matches!(
+            roads.add_edge_bidirectional(c, b, 1, 1, &mut t),
+            Err(GraphError::AlreadyEdgeBetween)
+        ));
+        assert!(matches!(
+            roads.add_edge_bidirectional(a, a, 1, 1, &mut t),
+            Err(GraphError::IdenticalVertex(_))
+        ));
+        assert_eq!(roads.edge_len(), 3);
+
+        roads.add_edge(c, b, 2, &mut t).unwrap();
+        roads.remove_edge_between(b, c, &mut t).unwrap();
+        assert_eq!(roads.weight_between(c, b, &t), Some(&2));
+        assert_eq!(roads.weight_between(b, c, &t), None);
+
+        roads
+            .create_or_update_edge_between(a, b, (), |(), _, _, _, _| 7, &mut t)
+            .unwrap();
+        assert_eq!(roads.weight_between(a, b, &t), Some(&7));
+        assert_eq!(roads.weight_between(b, a, &t), Some(&3));
+        assert!(roads.validate(&t).is_ok());
+
+        let mut paths: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let (a, b) = (paths.add_vertex(()), paths.add_vertex(()));
+        assert!(matches!(
+            paths.add_edge_bidirectional(a, b, (), (), &mut t),
+            Err(GraphError::RequiresDirectedEdges)
+        ));
+        assert_eq!(paths.edge_len(), 0);
+    });
+}
+
+#[test]
+fn contract_edge() {
+    use graph::{edge::DirectedWeightedEdge, ops::GraphOps};
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<u32, u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (1..=4).map(|i| graph.add_vertex(i)).collect();
+        let ab = graph.add_edge(v[0], v[1], 1, &mut t).unwrap();
+        graph.add_edge(v[0], v[2], 2, &mut t).unwrap();
+        graph.add_edge(v[1], v[2], 3, &mut t).unwrap();
+        let bd = graph.add_edge(v[1], v[3], 4, &mut t).unwrap();
+
+        let merged = graph
+            .contract_edge(ab, |a, b| *a += b, |a, b| *a += *b, &mut t)
+            .unwrap();
+        assert_eq!(merged, v[0]);
+        assert!(graph.get_vertex(v[1]).is_none());
+        assert_eq!(graph.items(&t).map(|(_, item)| item).sum::<u32>(), 10);
+        assert_eq!(*graph.get_vertex(v[0]).unwrap().borrow(&t).get_item(), 3);
+        assert_eq!((graph.vertex_len(), graph.edge_len()), (3, 2));
+        assert_eq!(graph.weight_between(v[0], v[2], &t), Some(&5));
+        assert_eq!(graph.endpoints(bd, &t), Some((v[0], v[3])));
+        assert_eq!(graph.degree(v[0]), Some(2));
+        assert!(graph.validate(&t).is_ok());
+
+        let mut directed: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..3).map(|_| directed.add_vertex(())).collect();
+        let (ab, _) = directed
+            .add_edge_bidirectional(v[0], v[1], 1, 1, &mut t)
+            .unwrap();
+        directed.add_edge(v[1], v[2], 2, &mut t).unwrap();
+        directed.add_edge(v[2], v[0], 3, &mut t).unwrap();
+
+        directed
+            .contract_edge(ab, |(), ()| {}, |_, _| unreachable!(), &mut t)
+            .unwrap();
+        assert_eq!(directed.edge_len(), 2);
+        assert_eq!(directed.weight_between(v[0], v[2], &t), Some(&2));
+        assert_eq!(directed.weight_between(v[2], v[0], &t), Some(&3));
+        assert_eq!(
+            (directed.in_degree(v[0]), directed.out_degree(v[0])),
+            (Some(1), Some(1))
+        );
+        assert!(directed.validate(&t).is_ok());
+    });
+}
+
+#[test]
+fn custom_allocator() {
+    use core::{alloc::Layout, cell::Cell, ptr::NonNull};
+    use graph::{Allocator, Global};
+    use std::rc::Rc;
+
+    #[derive(Clone, Default)]
+    struct Counting(Rc<Cell<usize>>);
+
+    unsafe impl Allocator for Counting {
+        fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+            self.0.set(self.0.get() + layout.size());
+            Global.allocate(layout)
+        }
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.0.set(self.0.get() - layout.size());
+            Global.deallocate(ptr, layout);
+        }
+    }
+
+    let live = Rc::new(Cell::new(0));
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<u32, u32, UnDirectedWeightedEdge<_, _>, _, _> =
+            Graph::new_in(Counting(Rc::clone(&live)));
+        assert_eq!(live.get(), 0);
+
+        let v: Vec<_> = (0..100).map(|i| graph.add_vertex(i)).collect();
+        for pair in v.windows(2) {
+            graph.add_edge(pair[0], pair[1], 1, &mut t).unwrap();
+        }
+        assert!(live.get() > 0);
+        assert_eq!(graph.edge_len(), 99);
+
+        graph.clear();
+        graph.shrink_to_fit();
+        assert_eq!(live.get(), 0);
+
+        graph.add_vertex(0);
+        assert!(live.get() > 0);
+        drop(graph);
+    });
+
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn fallible_allocation() {
+    use core::{alloc::Layout, cell::Cell, ptr::NonNull};
+    use graph::{AllocError, Allocator, Global, GraphError};
+    use std::rc::Rc;
+
+    /// Fails every allocation while the flag is set
+    #[derive(Clone, Default)]
+    struct Limited(Rc<Cell<bool>>);
+
+    unsafe impl Allocator for Limited {
+        fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+            if self.0.get() {
+                None
+            } else {
+                Global.allocate(layout)
+            }
+        }
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            Global.deallocate(ptr, layout);
+        }
+    }
+
+    let exhausted = Rc::new(Cell::new(false));
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<u32, u32, UnDirectedWeightedEdge<_, _>, _, _> =
+            Graph::new_in(Limited(Rc::clone(&exhausted)));
+
+        let a = graph.try_add_vertex(0).unwrap();
+        let b = graph.try_add_vertex(1).unwrap();
+        assert!(graph.try_add_edge(a, b, 1, &mut t).is_ok());
+        assert!(matches!(
+            graph.try_add_edge(a, a, 1, &mut t),
+            Err(GraphError::IdenticalVertex(_))
+        ));
+
+        // The first chunk has room left, so nothing new is allocated
+        exhausted.set(true);
+        let c = graph.try_add_vertex(2).unwrap();
+        graph.try_add_edge(b, c, 2, &mut t).unwrap();
+
+        // Vertices are allocated 16 at a time to begin with
+        while graph.vertex_len() < 16 {
+            graph.try_add_vertex(3).unwrap();
+        }
+        assert_eq!(graph.try_add_vertex(4), Err(AllocError));
+        assert_eq!(graph.vertex_len(), 16);
+
+        exhausted.set(false);
+        assert!(graph.try_add_vertex(4).is_ok());
+        assert!(graph.validate(&t).is_ok());
+    });
+}
+
+#[test]
+fn sync_graph() {
+    use graph::{ghost::GhostToken as Token, GraphObserver, SyncGraph, VertexId};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    struct Removals(Arc<AtomicUsize>);
+
+    impl<'id> GraphObserver<'id> for Removals {
+        fn on_vertex_removed(&mut self, _: VertexId<'id>) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn assert_sync<T: Sync>() {}
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_sync::<Graph<'static, u32, u32, UnDirectedWeightedEdge<'static, u32, u32>>>();
+    assert_send_sync::<SyncGraph<'static, u32, u32, UnDirectedWeightedEdge<'static, u32, u32>>>();
+    assert_send_sync::<Token<'static>>();
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<u32, u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..50).map(|i| graph.add_vertex(i)).collect();
+        for pair in v.windows(2) {
+            graph.add_edge(pair[0], pair[1], 1, &mut t).unwrap();
+        }
+
+        let shared = SyncGraph::new(graph, t);
+
+        std::thread::scope(|scope| {
+            let readers: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let read = shared.read();
+                        assert!(shared.try_write().is_none());
+                        read.graph().components(read.token()).count()
+                    })
+                })
+                .collect();
+            for reader in readers {
+                assert_eq!(reader.join().unwrap(), 1);
+            }
+        });
+
+        let removals = Arc::new(AtomicUsize::new(0));
+        {
+            let mut write = shared.write();
+            assert!(shared.try_read().is_none());
+            assert!(write
+                .set_observer(Removals(Arc::clone(&removals)))
+                .is_none());
+        }
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                let mut write = shared.write();
+                let (graph, token) = write.parts_mut();
+                graph.remove(v[25], token).unwrap();
+            });
+        });
+        assert_eq!(removals.load(Ordering::Relaxed), 1);
+
+        let read = shared.read();
+        assert_eq!(read.graph().components(read.token()).count(), 2);
+        drop(read);
+
+        // An observer that might not be `Send` doesn't outlive the guard
+        {
+            let mut write = shared.write();
+            let (graph, _) = write.parts_mut();
+            assert!(graph
+                .set_observer(Removals(Arc::clone(&removals)))
+                .is_some());
+        }
+
+        let (mut graph, t) = shared.into_inner();
+        assert!(graph.take_observer().is_none());
+        assert!(graph.validate(&t).is_ok());
+    });
+}
+
+#[test]
+fn graph_with_token() {
+    use graph::{edge::DirectedWeightedEdge, GraphWithToken, VertexId};
+
+    struct Network {
+        graph: GraphWithToken<&'static str, u32, DirectedWeightedEdge<'static, &'static str, u32>>,
+        hub: usize,
+    }
+
+    fn build() -> Network {
+        let mut graph = GraphWithToken::new();
+        let hub = graph.with_mut(|graph, token| {
+            let hub = graph.add_vertex("hub");
+            for name in ["a", "b", "c"] {
+                let spoke = graph.add_vertex(name);
+                graph.add_edge(hub, spoke, 1, token).unwrap();
+            }
+            hub.id()
+        });
+        Network { graph, hub }
+    }
+
+    let mut network = build();
+    let hub = network.hub;
+    network.graph.with_mut(|graph, token| {
+        let hub = VertexId::new(hub);
+        *graph
+            .get_vertex(hub)
+            .unwrap()
+            .borrow_mut(token)
+            .get_item_mut() = "centre";
+    });
+
+    network.graph.with(|graph, token| {
+        let hub = VertexId::new(hub);
+        assert_eq!(
+            *graph.get_vertex(hub).unwrap().borrow(token).get_item(),
+            "centre"
+        );
+        assert_eq!(graph.out_degree(hub), Some(3));
+        assert!(graph.validate(token).is_ok());
+    });
+}
+
+#[test]
+fn sibling_graphs() {
+    use graph::{edge::DirectedWeightedEdge, ops::GraphOps, GraphError};
+
+    GhostToken::with_graphs::<u32, u32, DirectedWeightedEdge<u32, u32>, _, 2>(
+        |[mut first, mut second], mut t| {
+            let v: Vec<_> = (0..5).map(|i| first.add_vertex(i)).collect();
+            first.add_edge(v[0], v[1], 1, &mut t).unwrap();
+            first.add_edge(v[1], v[2], 2, &mut t).unwrap();
+            first.add_edge(v[2], v[0], 3, &mut t).unwrap();
+            first.add_edge(v[2], v[3], 4, &mut t).unwrap();
+            first.add_edge(v[3], v[4], 5, &mut t).unwrap();
+
+            assert!(matches!(
+                first.move_vertices(&[v[0], v[0]], &mut second, &mut t),
+                Err(GraphError::IdenticalVertex(_))
+            ));
+            assert!(second.is_empty());
+
+            let moved = first
+                .move_vertices(&[v[2], v[1], v[0]], &mut second, &mut t)
+                .unwrap();
+            assert_eq!((first.vertex_len(), first.edge_len()), (2, 1));
+            assert_eq!((second.vertex_len(), second.edge_len()), (3, 3));
+            let (c, b, a) = (moved[0], moved[1], moved[2]);
+            assert_eq!(*second.get_vertex(c).unwrap().borrow(&t).get_item(), 2);
+            assert_eq!(second.weight_between(a, b, &t), Some(&1));
+            assert_eq!(second.weight_between(c, a, &t), Some(&3));
+            assert_eq!(second.weight_between(a, c, &t), None);
+
+            let d = first.move_vertex(v[3], &mut second, &mut t).unwrap();
+            assert_eq!(second.degree(d), Some(0));
+            assert_eq!(first.edge_len(), 0);
+            assert!(first.validate(&t).is_ok());
+            assert!(second.validate(&t).is_ok());
+
+            // Ids are never shared between siblings, so one of
+            // `second`'s can't be mistaken for one of `first`'s
+            assert!(first.get_vertex(d).is_none());
+            assert!(matches!(
+                first.move_vertex(d, &mut second, &mut t),
+                Err(GraphError::VertexNotFound(_))
+            ));
+            let e = first.add_vertex(5);
+            assert!(second.get_vertex(e).is_none());
+            first.clear();
+            assert_ne!(first.add_vertex(6), d);
+        },
+    );
+}
+
+#[test]
+fn debug_listing() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<&str, u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let a = graph.add_vertex("a");
+        let b = graph.add_vertex("b");
+        let c = graph.add_vertex("c");
+        graph.add_edge(a, c, 2, &mut t).unwrap();
+        graph.add_edge(a, b, 1, &mut t).unwrap();
+
+        assert_eq!(
+            graph.debug(&t).to_string(),
+            "0 (a) -- 1 [1], 2 [2]\n1 (b) -- 0 [1]\n2 (c) -- 0 [2]\n"
+        );
+        assert_eq!(
+            format!("{:?}", graph.debug(&t)),
+            r#"{0: Vertex { item: "a", edges: {1: 1, 2: 2} }, 1: Vertex { item: "b", edges: {0: 1} }, 2: Vertex { item: "c", edges: {0: 2} }}"#
+        );
+        assert!(format!("{:#?}", graph.debug(&t)).contains("item: \"c\""));
+    });
+}
+
+#[test]
+fn fingerprint() {
+    GhostToken::new(|mut t| {
+        let build = |t: &mut GhostToken<'_>, weight: u32| {
+            let mut graph: Graph<u32, u32, UnDirectedWeightedEdge<_, _>> =
+                Graph::with_capacity(64, 64);
+            let ids: Vec<_> = (0..32).map(|i| graph.add_vertex(i)).collect();
+            for pair in ids.windows(2) {
+                graph.add_edge(pair[0], pair[1], weight, t).unwrap();
+            }
+            graph.fingerprint(t)
+        };
+
+        // Separate graphs can store their vertices in different orders
+        assert_eq!(build(&mut t, 1), build(&mut t, 1));
+        assert_ne!(build(&mut t, 1), build(&mut t, 2));
+
+        let mut graph: Graph<u32, u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let a = graph.add_vertex(1);
+        let before = graph.fingerprint(&t);
+        *graph
+            .get_vertex(a)
+            .unwrap()
+            .borrow_mut(&mut t)
+            .get_item_mut() = 2;
+        assert_ne!(graph.fingerprint(&t), before);
+    });
+}
+
+#[test]
+fn reverse_edges() {
+    use graph::{edge::DirectedWeightedEdge, ops::GraphOps, GraphError};
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        let c = graph.add_vertex(());
+        let ab = graph.add_edge(a, b, 4, &mut t).unwrap();
+        let bc = graph.add_edge(b, c, 6, &mut t).unwrap();
+        graph.add_edge(c, b, 1, &mut t).unwrap();
+
+        graph.reverse_edge(ab, &mut t).unwrap();
+        assert_eq!(graph.endpoints(ab, &t), Some((b, a)));
+        assert_eq!(graph.weight_between(b, a, &t), Some(&4));
+        assert_eq!(graph.weight_between(a, b, &t), None);
+        assert_eq!(graph.out_degree(a), Some(0));
+        assert_eq!(graph.in_degree(a), Some(1));
+        assert_eq!(graph.out_degree(b), Some(2));
+        assert_eq!(graph.in_degree(b), Some(1));
+
+        // `c` already has an edge leading back to `b`
+        assert!(matches!(
+            graph.reverse_edge(bc, &mut t),
+            Err(GraphError::AlreadyEdgeBetween)
+        ));
+        assert_eq!(graph.endpoints(bc, &t), Some((b, c)));
+
+        graph.remove_edge_between(b, a, &mut t).unwrap();
+        assert!(matches!(
+            graph.reverse_edge(ab, &mut t),
+            Err(GraphError::EdgeNotFound(_))
+        ));
+        assert_eq!(graph.edge_len(), 2);
+    });
+}
+
+#[test]
+fn per_direction_weights() {
+    use graph::edge::{BiWeight, BiWeightedEdge};
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), BiWeight<u32>, BiWeightedEdge<_, _>> = Graph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        let c = graph.add_vertex(());
+        let ab = graph.add_edge(a, b, BiWeight::new(3, 5), &mut t).unwrap();
+        graph
+            .add_edge(c, b, BiWeight::symmetric(1), &mut t)
+            .unwrap();
+        assert!(graph.add_edge(b, a, BiWeight::new(0, 0), &mut t).is_err());
+
+        assert_eq!(graph.directed_weight(a, b, &t), Some(&3));
+        assert_eq!(graph.directed_weight(b, a, &t), Some(&5));
+        assert_eq!(graph.directed_weight(b, c, &t), Some(&1));
+        assert_eq!(graph.directed_weight(a, c, &t), None);
+        assert_eq!(graph.out_degree(b), Some(2));
+
+        *graph.directed_weight_mut(b, a, &mut t).unwrap() = 8;
+        let edge = graph.get_edge(ab).unwrap().borrow(&t);
+        assert_eq!(edge.get_weight(), &BiWeight::new(3, 8));
+        assert_eq!(edge.weight_from(b), Some(&8));
+        assert_eq!(edge.is_forward_from(c), None);
+    });
+}
+
+#[test]
+fn flow_networks() {
+    use graph::edge::{Flow, FlowEdge};
+    use std::collections::{HashMap, VecDeque};
+
+    assert!(Flow::new(5).augment(5));
+    assert!(!Flow::new(5).augment(6));
+    assert!(!Flow::new(5).cancel(1));
+    assert!(Flow {
+        capacity: 2,
+        flow: 2
+    }
+    .is_saturated());
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), Flow<u32>, FlowEdge<_, _>> = Graph::new();
+        let v: Vec<_> = (0..6).map(|_| graph.add_vertex(())).collect();
+        let edges = [
+            (0, 1, 16),
+            (0, 2, 13),
+            (2, 1, 4),
+            (1, 3, 12),
+            (3, 2, 9),
+            (2, 4, 14),
+            (4, 3, 7),
+            (3, 5, 20),
+            (4, 5, 4),
+        ];
+        for &(from, to, capacity) in &edges {
+            graph
+                .add_edge(v[from], v[to], Flow::new(capacity), &mut t)
+                .unwrap();
+        }
+
+        // Edmonds–Karp, written only in terms of the residual network
+        let mut total = 0;
+        loop {
+            let mut previous = HashMap::new();
+            let mut queue = VecDeque::from(vec![v[0]]);
+            while let Some(vertex) = queue.pop_front() {
+                for &other in &v {
+                    if other != v[0]
+                        && !previous.contains_key(&other)
+                        && graph.residual_capacity(vertex, other, &t) > 0
+                    {
+                        previous.insert(other, vertex);
+                        queue.push_back(other);
+                    }
+                }
+            }
+            if !previous.contains_key(&v[5]) {
+                break;
+            }
+
+            let mut path = vec![v[5]];
+            while let Some(before) = previous.get(path.last().unwrap()) {
+                path.push(*before);
+            }
+            path.reverse();
+            let amount = path
+                .windows(2)
+                .map(|pair| graph.residual_capacity(pair[0], pair[1], &t))
+                .min()
+                .unwrap();
+            for pair in path.windows(2) {
+                assert!(graph.augment(pair[0], pair[1], amount, &mut t));
+            }
+            total += amount;
+        }
+        assert_eq!(total, 23);
+
+        // There's no edge from `v[1]` to `v[2]`, so only the flow
+        // from `v[2]` to `v[1]` can be sent back
+        let back = graph.residual_capacity(v[1], v[2], &t);
+        assert!(!graph.augment(v[1], v[2], back + 1, &mut t));
+        assert_eq!(graph.residual_capacity(v[0], v[5], &t), 0);
+
+        graph.clear_flow(&mut t);
+        assert!(graph.weights(&t).all(|(_, flow)| flow.flow == 0));
+    });
+}
+
+#[test]
+fn edge_conformance() {
+    use graph::{
+        assert_edge_impl,
+        edge::{BiWeight, BiWeightedEdge, DirectedWeightedEdge, UnDirectedWeightedEdge},
+    };
+
+    assert_edge_impl!(DirectedWeightedEdge<'static, u8, u32>, 0, 1);
+    assert_edge_impl!(UnDirectedWeightedEdge<'static, (), ()>, (), ());
+    assert_edge_impl!(
+        BiWeightedEdge<'static, &str, i32>,
+        "x",
+        BiWeight::new(1, -1)
+    );
+}
+
+#[test]
+fn register_edge_checks() {
+    use graph::{edge::RegisterError, EdgeId};
+
+    GhostToken::new(|mut t| {
+        let mut other: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let p = other.add_vertex(());
+        let q = other.add_vertex(());
+
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        let ab = graph.add_edge(a, b, (), &mut t).unwrap();
+
+        let (first, second) = (other.get_vertex(p).unwrap(), other.get_vertex(q).unwrap());
+        assert_eq!(
+            UnDirectedWeightedEdge::add_edge((), first, second, ab, &mut graph, &mut t),
+            Err(RegisterError::EdgeIdInUse(ab))
+        );
+        // `p` shares its id with `a`, but isn't a vertex of `graph`
+        assert_eq!(
+            UnDirectedWeightedEdge::add_edge((), first, second, EdgeId::new(5), &mut graph, &mut t),
+            Err(RegisterError::ForeignVertex(p))
+        );
+
+        assert_eq!(graph.edge_len(), 1);
+        assert!(graph.get_edge(EdgeId::new(5)).is_none());
+        assert_eq!(graph.validate(&t), Ok(()));
+        assert_eq!(other.validate(&t), Ok(()));
+    });
+}
+
+#[test]
+fn weight_aggregates() {
+    use graph::edge::{DirectedWeightedEdge, UnDirectedWeightedEdge};
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+        assert_eq!(graph.total_weight(&t), 0);
+        assert_eq!(graph.min_weight_edge(&t), None);
+
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        let c = graph.add_vertex(());
+        assert_eq!(graph.weight_sum_at(a, &t), Some(0));
+
+        let ab = graph.add_edge(a, b, 5, &mut t).unwrap();
+        let bc = graph.add_edge(b, c, 2, &mut t).unwrap();
+        let ca = graph.add_edge(c, a, 5, &mut t).unwrap();
+
+        // Each undirected edge only counts once
+        assert_eq!(graph.total_weight(&t), 12);
+        assert_eq!(graph.weight_sum_at(a, &t), Some(10));
+        assert_eq!(graph.weight_sum_at(b, &t), Some(7));
+        assert_eq!(graph.min_weight_edge(&t), Some((bc, &2)));
+        assert_eq!(graph.max_weight_edge(&t), Some((ab, &5)));
+
+        graph.remove_edge_between(a, b, &mut t).unwrap();
+        assert_eq!(graph.max_weight_edge(&t), Some((ca, &5)));
+
+        graph.remove(a, &mut t).unwrap();
+        assert_eq!(graph.weight_sum_at(a, &t), None);
+    });
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), i64, DirectedWeightedEdge<_, _>> = Graph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        graph.add_edge(a, b, -3, &mut t).unwrap();
+        graph.add_edge(b, a, 8, &mut t).unwrap();
+
+        assert_eq!(graph.total_weight(&t), 5);
+        assert_eq!(graph.weight_sum_at(b, &t), Some(5));
+    });
+}
+
+#[test]
+fn secondary_maps() {
+    use graph::{EdgeId, SecondaryMap, VertexId};
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        let e = graph.add_edge(v[0], v[3], (), &mut t).unwrap();
+
+        let mut seen = graph.vertex_map();
+        assert!(seen.is_empty());
+        assert_eq!(seen.insert(v[3], 1), None);
+        assert_eq!(seen.insert(v[1], 2), None);
+        assert_eq!(seen.insert(v[3], 3), Some(1));
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen.get(v[0]), None);
+        assert!(seen.contains_key(v[1]));
+        assert_eq!(seen.keys().collect::<Vec<_>>(), vec![v[1], v[3]]);
+
+        *seen.get_or_default(v[0]) += 5;
+        seen[v[1]] *= 10;
+        assert_eq!(seen.values().copied().collect::<Vec<_>>(), vec![5, 20, 3]);
+
+        seen.retain(|id, value| id != v[0] && *value > 4);
+        assert_eq!(seen.iter().collect::<Vec<_>>(), vec![(v[1], &20)]);
+        assert_eq!(seen.remove(v[1]), Some(20));
+        assert_eq!(seen.remove(v[1]), None);
+        assert!(seen.is_empty());
+
+        // Ids beyond the space set aside are still fine
+        let far = VertexId::new(1000);
+        seen.insert(far, 1);
+        assert_eq!(seen[far], 1);
+
+        // As are ids far beyond the number of values, without taking
+        // up space for every id below them
+        let huge = VertexId::new(1 << 50);
+        seen.insert(huge, 2);
+        for i in 0..300 {
+            seen.insert(VertexId::new(i), 0);
+        }
+        assert_eq!(seen.len(), 302);
+        assert_eq!((seen[far], seen[huge]), (1, 2));
+        let keys: Vec<_> = seen.keys().collect();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        seen.retain(|id, _| id.id() >= 1000);
+        assert_eq!(seen.iter().collect::<Vec<_>>(), vec![(far, &1), (huge, &2)]);
+
+        let mut labels = graph.edge_map();
+        labels.insert(e, "bridge");
+        assert_eq!(labels.get(EdgeId::new(e.id + 1)), None);
+
+        let collected: SecondaryMap<_, _> = v.iter().map(|id| (*id, id.id())).collect();
+        let mut again = SecondaryMap::new();
+        again.extend(v.iter().rev().map(|id| (*id, id.id())));
+        assert_eq!(collected, again);
+        again.clear();
+        assert_ne!(collected, again);
+        assert_eq!(format!("{:?}", collected), "{0: 0, 1: 1, 2: 2, 3: 3}");
+    });
+}
+
+#[test]
+fn vertex_bit_sets() {
+    use graph::{VertexBitSet, VertexId};
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..70).map(|_| graph.add_vertex(())).collect();
+        graph.remove(v[1], &mut t).unwrap();
+
+        let mut visited = graph.vertex_set();
+        assert!(visited.is_empty());
+        for id in [v[69], v[0], v[63], v[64]] {
+            assert!(visited.insert(id));
+        }
+        assert!(!visited.insert(v[63]));
+        assert_eq!(visited.len(), 4);
+        assert!(visited.contains(v[64]));
+        assert!(!visited.contains(v[65]));
+        assert_eq!(
+            visited.iter().collect::<Vec<_>>(),
+            vec![v[0], v[63], v[64], v[69]]
+        );
+
+        assert!(visited.remove(v[63]));
+        assert!(!visited.remove(v[63]));
+        assert!(!visited.remove(VertexId::new(10_000)));
+        assert!(!visited.contains(VertexId::new(10_000)));
+        assert_eq!(visited.len(), 3);
+
+        // Ids far beyond the number held are kept without space for
+        // every id below them
+        let huge = VertexId::new(1 << 50);
+        assert!(visited.insert(huge));
+        assert!(visited.contains(huge));
+        assert_eq!(visited.iter().last(), Some(huge));
+        assert!(visited.remove(huge));
+        assert!(!visited.contains(huge));
+
+        // Equal whatever space they set aside
+        let collected: VertexBitSet = [v[69], v[64], v[0]].iter().copied().collect();
+        assert_eq!(visited, collected);
+        assert_eq!(format!("{:?}", collected), "{0, 64, 69}");
+
+        visited.clear();
+        assert!(visited.is_empty());
+        assert_eq!(visited, VertexBitSet::new());
+        assert_ne!(visited, collected);
+    });
+}
+
+#[test]
+fn indexed_heaps() {
+    use core::convert::TryFrom;
+    use graph::{IndexedHeap, VertexId};
+
+    let id = VertexId::new;
+    let mut heap = IndexedHeap::new();
+    assert!(heap.is_empty());
+    assert_eq!(heap.pop(), None);
+
+    // A simple generator, so the heap sees many different orders
+    let mut state = 12_345_u64;
+    let mut next = move || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1);
+        state >> 40
+    };
+
+    let mut expected = vec![None; 200];
+    for _ in 0..2000 {
+        let i = usize::try_from(next() % 200).unwrap();
+        let priority = next() % 1000;
+        match next() % 4 {
+            0 => {
+                assert_eq!(heap.push(id(i), priority), expected[i]);
+                expected[i] = Some(priority);
+            }
+            1 => {
+                assert_eq!(heap.remove(id(i)), expected[i].take());
+            }
+            _ => {
+                let lower = expected[i].is_none_or(|old| priority < old);
+                assert_eq!(heap.decrease_key(id(i), priority), lower);
+                if lower {
+                    expected[i] = Some(priority);
+                }
+            }
+        }
+        assert_eq!(heap.priority(id(i)), expected[i].as_ref());
+        assert_eq!(heap.contains(id(i)), expected[i].is_some());
+    }
+
+    let mut sorted: Vec<_> = expected
+        .iter()
+        .enumerate()
+        .filter_map(|(i, priority)| Some((id(i), (*priority)?)))
+        .collect();
+    sorted.sort_by_key(|(id, priority)| (*priority, *id));
+    assert_eq!(heap.len(), sorted.len());
+    assert_eq!(heap.peek(), sorted.first().map(|(id, p)| (*id, p)));
+
+    let popped: Vec<_> = core::iter::from_fn(|| heap.pop()).collect();
+    assert_eq!(popped, sorted);
+    assert!(heap.is_empty());
+
+    // Equal priorities come out lowest id first
+    heap.push(id(3), 1);
+    heap.push(id(1), 1);
+    heap.push(id(2), 1);
+    assert_eq!(heap.pop(), Some((id(1), 1)));
+    heap.clear();
+    assert_eq!(heap.pop(), None);
+
+    // Ids far beyond the number of vertices in the heap are found
+    // without space being set aside for every id below them
+    let huge = id(1 << 50);
+    heap.push(huge, 2);
+    heap.push(id(0), 3);
+    assert!(heap.decrease_key(huge, 1));
+    assert_eq!(heap.pop(), Some((huge, 1)));
+    assert!(!heap.contains(huge));
+    assert_eq!(heap.pop(), Some((id(0), 3)));
+}
+
+#[test]
+fn find_by_predicate() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<u32, i32, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..6).map(|i| graph.add_vertex(i * 10)).collect();
+        let e: Vec<_> = v
+            .windows(2)
+            .zip([4, -1, 7, -3, 4].iter())
+            .map(|(pair, weight)| graph.add_edge(pair[0], pair[1], *weight, &mut t).unwrap())
+            .collect();
+
+        assert_eq!(graph.find_vertex(|item| *item >= 25, &t), Some(v[3]));
+        assert_eq!(graph.find_vertex(|item| *item > 100, &t), None);
+        assert_eq!(
+            graph.vertex_positions(|item| item % 20 == 0, &t),
+            vec![v[0], v[2], v[4]]
+        );
+
+        assert_eq!(graph.find_edge(|weight| *weight < 0, &t), Some(e[1]));
+        assert_eq!(graph.find_edge(|weight| *weight == 0, &t), None);
+        assert_eq!(
+            graph.edge_positions(|weight| *weight == 4, &t),
+            vec![e[0], e[4]]
+        );
+
+        graph.remove(v[2], &mut t).unwrap();
+        assert_eq!(graph.find_vertex(|item| *item >= 20, &t), Some(v[3]));
+        assert_eq!(graph.edge_positions(|weight| *weight < 0, &t), vec![e[3]]);
+    });
+}
@@ -0,0 +1,68 @@
+use graph::{algo::centrality, ghost::GhostToken, ops::GraphOps, Hypergraph, VertexId};
+
+#[test]
+fn nets() {
+    let mut circuit: Hypergraph<&str, &str> = Hypergraph::new();
+
+    let pins: Vec<_> = ["r1", "r2", "c1", "u1"]
+        .iter()
+        .map(|pin| circuit.add_vertex(*pin))
+        .collect();
+
+    let vcc = circuit
+        .add_hyperedge(&[pins[0], pins[1], pins[3], pins[0]], "vcc")
+        .unwrap();
+    let gnd = circuit.add_hyperedge(&[pins[2], pins[3]], "gnd").unwrap();
+    assert_eq!(
+        circuit.add_hyperedge(&[pins[0], VertexId::new(9)], "x"),
+        Err(VertexId::new(9))
+    );
+
+    assert_eq!(circuit.edge_len(), 2);
+    assert_eq!(
+        circuit.get_hyperedge(vcc).unwrap().vertices(),
+        &[pins[0], pins[1], pins[3]]
+    );
+    assert_eq!(circuit.incident(pins[3]), Some(&[vcc, gnd][..]));
+    assert_eq!(
+        circuit.neighbors(pins[3]),
+        Some(vec![pins[0], pins[1], pins[2]])
+    );
+    assert_eq!(circuit.neighbors(pins[2]), Some(vec![pins[3]]));
+
+    *circuit.get_hyperedge_mut(gnd).unwrap().weight_mut() = "ground";
+    assert_eq!(circuit.remove_hyperedge(gnd), Some("ground"));
+    assert_eq!(circuit.incident(pins[3]), Some(&[vcc][..]));
+
+    assert_eq!(circuit.remove_vertex(pins[1]), Some("r2"));
+    assert_eq!(
+        circuit.get_hyperedge(vcc).unwrap().vertices(),
+        &[pins[0], pins[3]]
+    );
+    circuit.remove_vertex(pins[0]);
+    circuit.remove_vertex(pins[3]);
+    assert_eq!(circuit.edge_len(), 0);
+    assert_eq!(circuit.vertex_len(), 1);
+}
+
+#[test]
+fn clique_expansion() {
+    GhostToken::new(|t| {
+        let mut graph: Hypergraph<(), u32> = Hypergraph::new();
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        graph.add_hyperedge(&v[..3], 1).unwrap();
+        graph.add_hyperedge(&[v[2], v[3]], 2).unwrap();
+
+        assert!(!graph.is_directed());
+        assert_eq!(graph.weight_between(v[0], v[1], &t), Some(&1));
+        assert_eq!(graph.weight_between(v[3], v[2], &t), Some(&2));
+        assert_eq!(graph.weight_between(v[0], v[3], &t), None);
+
+        let mut successors: Vec<_> = graph.successors(v[2], &t).collect();
+        successors.sort_unstable();
+        assert_eq!(successors, vec![(v[0], &1), (v[1], &1), (v[3], &2)]);
+
+        let degrees = centrality::degree(&graph, &t);
+        assert!(degrees[&v[2]] > degrees[&v[3]]);
+    });
+}
@@ -0,0 +1,148 @@
+use graph::{
+    algo::dijkstra,
+    edge::{DirectedWeightedEdge, UnDirectedWeightedEdge},
+    ghost::GhostToken,
+    json::{JsonError, JsonValue},
+    ops::GraphOps,
+    Graph, VertexId,
+};
+
+#[test]
+fn values_round_trip() {
+    let text = r#" { "a" : [1, -2.5e3, true, null], "b\"é😀" : { } } "#;
+    let value = JsonValue::parse(text).unwrap();
+
+    assert_eq!(
+        value.get("a").and_then(JsonValue::as_array),
+        Some(
+            &[
+                JsonValue::Number(1.),
+                JsonValue::Number(-2500.),
+                JsonValue::Bool(true),
+                JsonValue::Null
+            ][..]
+        )
+    );
+    assert_eq!(value.get("b\"é😀"), Some(&JsonValue::Object(Vec::new())));
+
+    let written = value.to_string();
+    assert_eq!(written, "{\"a\":[1,-2500,true,null],\"b\\\"é😀\":{}}");
+    assert_eq!(JsonValue::parse(&written), Ok(value));
+    assert!(serde_json::from_str::<serde_json::Value>(&written).is_ok());
+
+    assert_eq!(JsonValue::parse("[1,]"), Err(JsonError::Syntax(3)));
+    assert_eq!(JsonValue::parse("01"), Err(JsonError::Syntax(1)));
+    assert_eq!(JsonValue::parse("\"\n\""), Err(JsonError::Syntax(1)));
+    assert!(JsonValue::parse(&"[".repeat(1000)).is_err());
+}
+
+#[test]
+fn graphs_round_trip() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<String, f64, UnDirectedWeightedEdge<_, _>> = Graph::new();
+        let a = graph.add_vertex("a \"quoted\"".to_string());
+        let b = graph.add_vertex("b".to_string());
+        let c = graph.add_vertex("c".to_string());
+        graph.add_edge(a, b, 1.5, &mut t).unwrap();
+        graph.add_edge(c, b, -2., &mut t).unwrap();
+        graph.remove(a, &mut t).unwrap();
+        let d = graph.add_vertex("d".to_string());
+        graph.add_edge(d, c, 4., &mut t).unwrap();
+
+        let json = graph.to_json_node_link(
+            |item| item.as_str().into(),
+            |weight| JsonValue::Object(vec![("length".to_string(), (*weight).into())]),
+            &t,
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["directed"], false);
+        assert_eq!(
+            parsed["nodes"][0],
+            serde_json::json!({ "id": 1, "item": "b" })
+        );
+        assert_eq!(
+            parsed["links"][0],
+            serde_json::json!({ "source": 2, "target": 1, "length": -2 })
+        );
+
+        let copy: Graph<String, f64, UnDirectedWeightedEdge<_, _>> = Graph::from_json_node_link(
+            &json,
+            |node| node.get("item")?.as_str().map(String::from),
+            |link| link.get("length")?.as_f64(),
+            &mut t,
+        )
+        .unwrap();
+        // The nodes are renumbered in order, closing the gap `a` left
+        let (b, c, d) = (VertexId::new(0), VertexId::new(1), VertexId::new(2));
+        assert_eq!(copy.vertex_len(), 3);
+        assert_eq!(copy.item(d, &t).map(String::as_str), Some("d"));
+        assert_eq!(copy.weight_between(b, c, &t), Some(&-2.));
+        assert_eq!(copy.weight_between(c, d, &t), Some(&4.));
+
+        let directed = Graph::<String, f64, DirectedWeightedEdge<_, _>>::from_json_node_link(
+            &json,
+            |_| Some(String::new()),
+            |_| Some(0.),
+            &mut t,
+        );
+        assert_eq!(directed.err(), Some(JsonError::DirectionMismatch));
+    });
+}
+
+#[test]
+fn networkx_graphs() {
+    // As written by networkx's `node_link_data`, with string node ids
+    let json = r#"{
+        "directed": true, "multigraph": false, "graph": {},
+        "nodes": [{"id": "x", "size": 3}, {"id": "y", "size": 5}, {"id": "z"}],
+        "edges": [{"source": "x", "target": "y", "weight": 2}, {"source": "z", "target": "x"}]
+    }"#;
+
+    GhostToken::new(|mut t| {
+        let read = |json: &str, t: &mut GhostToken<'_>| {
+            Graph::<u64, u64, DirectedWeightedEdge<_, _>>::from_json_node_link(
+                json,
+                |node| Some(node.get("size").and_then(JsonValue::as_u64).unwrap_or(0)),
+                |link| Some(link.get("weight").and_then(JsonValue::as_u64).unwrap_or(1)),
+                t,
+            )
+            .map(|graph| graph.edge_len())
+        };
+        assert_eq!(read(json, &mut t), Ok(2));
+
+        assert_eq!(
+            read(r#"{"nodes": [{"id": 0}, {"id": 0}]}"#, &mut t),
+            Err(JsonError::DuplicateNode(1))
+        );
+        assert_eq!(
+            read(
+                r#"{"nodes": [{"id": 0}], "links": [{"source": 0, "target": 9}]}"#,
+                &mut t
+            ),
+            Err(JsonError::InvalidLink(0))
+        );
+        assert_eq!(
+            read(r#"{"links": []}"#, &mut t),
+            Err(JsonError::MissingField("nodes"))
+        );
+
+        // Numeric ids, however high, are renumbered like names are
+        let far = format!(
+            r#"{{"nodes": [{{"id": 0}}, {{"id": 1000000000000000}}, {{"id": {}}}],
+                "links": [{{"source": 0, "target": 1000000000000000, "weight": 3}},
+                          {{"source": 1000000000000000, "target": {1}, "weight": 4}}]}}"#,
+            usize::MAX,
+            usize::MAX
+        );
+        let graph = Graph::<u64, u64, DirectedWeightedEdge<_, _>>::from_json_node_link(
+            &far,
+            |_| Some(0),
+            |link| link.get("weight").and_then(JsonValue::as_u64),
+            &mut t,
+        )
+        .unwrap();
+        assert_eq!(graph.vertex_ids().max(), Some(VertexId::new(2)));
+        let paths = dijkstra(&graph, VertexId::new(0), |weight| *weight, &t).unwrap();
+        assert_eq!(paths.distance(VertexId::new(2)), Some(7));
+    });
+}
@@ -0,0 +1,131 @@
+use graph::{
+    algo::topological_sort,
+    edge::{DirectedLabeledEdge, DirectedWeightedEdge, Labeled},
+    ghost::GhostToken,
+    ops::{EdgeFilter, GraphOps, ReversedView},
+    Graph,
+};
+
+#[test]
+fn reversed_view() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(v[0], v[1], 1, &mut t).unwrap();
+        graph.add_edge(v[1], v[2], 2, &mut t).unwrap();
+        graph.add_edge(v[1], v[3], 3, &mut t).unwrap();
+
+        let reversed = ReversedView::new(&graph);
+        assert!(reversed.is_directed());
+        assert_eq!(reversed.vertex_len(), 4);
+
+        let successors: Vec<_> = reversed.successors(v[2], &t).collect();
+        assert_eq!(successors, vec![(v[1], &2)]);
+        let mut predecessors: Vec<_> = reversed.predecessors(v[1], &t).collect();
+        predecessors.sort_unstable();
+        assert_eq!(predecessors, vec![(v[2], &2), (v[3], &3)]);
+
+        assert_eq!(reversed.weight_between(v[1], v[0], &t), Some(&1));
+        assert_eq!(reversed.weight_between(v[0], v[1], &t), None);
+
+        let order = topological_sort(&graph, &t).unwrap();
+        let mut backwards = topological_sort(&reversed, &t).unwrap();
+        assert_eq!(backwards.pop(), Some(v[0]));
+        assert_eq!(order[0], v[0]);
+    });
+}
+
+#[test]
+fn edge_filter() {
+    #[derive(Debug, PartialEq)]
+    enum Kind {
+        Depends,
+        Suggests,
+    }
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), _, DirectedLabeledEdge<_, Kind, u32>> = Graph::new();
+
+        let v: Vec<_> = (0..3).map(|_| graph.add_vertex(())).collect();
+        let depends = |weight| Labeled::new(Kind::Depends, weight);
+        graph.add_edge(v[0], v[1], depends(1), &mut t).unwrap();
+        graph.add_edge(v[1], v[2], depends(2), &mut t).unwrap();
+        graph
+            .add_edge(v[2], v[0], Labeled::new(Kind::Suggests, 3), &mut t)
+            .unwrap();
+
+        assert!(topological_sort(&graph, &t).is_err());
+
+        let required = EdgeFilter::by_label(&graph, |kind: &Kind| *kind == Kind::Depends);
+        assert_eq!(topological_sort(&required, &t).unwrap(), v);
+        assert_eq!(required.successors(v[2], &t).count(), 0);
+        assert_eq!(required.predecessors(v[0], &t).count(), 0);
+        assert_eq!(required.weight_between(v[2], v[0], &t), None);
+        assert_eq!(required.weight_between(v[0], v[1], &t), Some(&depends(1)));
+
+        let heavy = EdgeFilter::new(&graph, |weight: &Labeled<Kind, u32>| weight.weight > 1);
+        let successors: Vec<_> = heavy.successors(v[2], &t).map(|(id, _)| id).collect();
+        assert_eq!(successors, vec![v[0]]);
+        assert_eq!(heavy.successors(v[0], &t).count(), 0);
+    });
+}
+
+#[test]
+fn products() {
+    use graph::{
+        edge::UnDirectedWeightedEdge,
+        generators::{complete, cycle},
+        ops::{cartesian_product, tensor_product},
+        VertexId,
+    };
+
+    GhostToken::new(|mut t| {
+        let path: Graph<u32, u32, UnDirectedWeightedEdge<_, _>> =
+            cycle(2, |i| i as u32, |_, _| 1, &mut t).unwrap();
+        let triangle: Graph<u32, u32, UnDirectedWeightedEdge<_, _>> =
+            complete(3, |i| 10 * i as u32, |_, _| 2, &mut t).unwrap();
+
+        let prism: Graph<u32, u32, UnDirectedWeightedEdge<_, _>> = cartesian_product(
+            &path,
+            &triangle,
+            |a, b| a + b,
+            |a, b| a.or(b).copied().unwrap(),
+            &mut t,
+        )
+        .unwrap();
+        assert_eq!((prism.vertex_len(), prism.edge_len()), (6, 9));
+        assert_eq!(GraphOps::item(&prism, VertexId::new(4), &t), Some(&11));
+        assert_eq!(
+            prism.weight_between(VertexId::new(1), VertexId::new(4), &t),
+            Some(&1)
+        );
+        assert_eq!(
+            prism.weight_between(VertexId::new(1), VertexId::new(2), &t),
+            Some(&2)
+        );
+        assert!(prism.degree(VertexId::new(0)) == Some(3));
+
+        let tensor: Graph<(u32, u32), u32, UnDirectedWeightedEdge<_, _>> =
+            tensor_product(&path, &triangle, |a, b| (*a, *b), |a, b| a * b, &mut t).unwrap();
+        assert_eq!((tensor.vertex_len(), tensor.edge_len()), (6, 6));
+        assert_eq!(
+            tensor.weight_between(VertexId::new(0), VertexId::new(4), &t),
+            Some(&2)
+        );
+        assert_eq!(
+            tensor.weight_between(VertexId::new(0), VertexId::new(3), &t),
+            None
+        );
+
+        let ring: Graph<(), (), DirectedWeightedEdge<_, _>> =
+            cycle(3, |_| (), |_, _| (), &mut t).unwrap();
+        let torus: Graph<(), (), DirectedWeightedEdge<_, _>> =
+            tensor_product(&ring, &ring, |(), ()| (), |(), ()| (), &mut t).unwrap();
+        assert_eq!((torus.vertex_len(), torus.edge_len()), (9, 9));
+        assert_eq!(torus.successors(VertexId::new(0), &t).count(), 1);
+        assert!(torus
+            .weight_between(VertexId::new(0), VertexId::new(4), &t)
+            .is_some());
+    });
+}
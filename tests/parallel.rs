@@ -0,0 +1,94 @@
+#![cfg(feature = "rayon")]
+
+use graph::{
+    algo::parallel::{bfs_layers, connected_components, page_rank},
+    edge::{DirectedWeightedEdge, UnDirectedWeightedEdge},
+    ghost::GhostToken,
+    Graph,
+};
+
+#[test]
+fn components_and_layers() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..7).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(v[0], v[1], (), &mut t).unwrap();
+        graph.add_edge(v[0], v[2], (), &mut t).unwrap();
+        graph.add_edge(v[2], v[3], (), &mut t).unwrap();
+        graph.add_edge(v[5], v[4], (), &mut t).unwrap();
+        graph.add_edge(v[4], v[3], (), &mut t).unwrap();
+
+        let frozen = graph.freeze(&t);
+
+        assert_eq!(
+            connected_components(&frozen),
+            vec![vec![v[0], v[1], v[2], v[3], v[4], v[5]], vec![v[6]]]
+        );
+        assert_eq!(
+            bfs_layers(&frozen, 0),
+            vec![vec![v[0]], vec![v[1], v[2]], vec![v[3]]]
+        );
+    });
+}
+
+#[test]
+fn page_rank_of_cycle_and_star() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        for i in 0..4 {
+            graph.add_edge(v[i], v[(i + 1) % 4], (), &mut t).unwrap();
+        }
+
+        let rank = page_rank(&graph.freeze(&t), 0.85, 50);
+        for r in &rank {
+            assert!((r - 0.25).abs() < 1e-9);
+        }
+    });
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let centre = graph.add_vertex(());
+        for _ in 0..4 {
+            let leaf = graph.add_vertex(());
+            graph.add_edge(centre, leaf, (), &mut t).unwrap();
+        }
+
+        let rank = page_rank(&graph.freeze(&t), 0.85, 100);
+        assert!((rank.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(rank[1..].iter().all(|leaf| rank[0] > *leaf));
+    });
+}
+
+#[test]
+fn parallel_weight_updates() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u64, UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..50).map(|_| graph.add_vertex(())).collect();
+        let edges: Vec<_> = v
+            .windows(2)
+            .map(|pair| graph.add_edge(pair[0], pair[1], 100, &mut t).unwrap())
+            .collect();
+
+        // Every edge is visited once, however the edges are chunked
+        for chunk_size in [1, 7, 1000] {
+            graph.par_for_each_edge_mut(chunk_size, &mut t, |id, weight| {
+                *weight = *weight / 2 + id.id as u64;
+            });
+        }
+        graph.for_each_edge_mut(&mut t, |_, weight| *weight += 1);
+
+        for (id, weight) in graph.weights(&t) {
+            let mut expected = 100;
+            for _ in 0..3 {
+                expected = expected / 2 + id.id as u64;
+            }
+            assert_eq!(*weight, expected + 1);
+        }
+        assert_eq!(graph.weights(&t).count(), edges.len());
+    });
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "serde")]
+
+use graph::{
+    algo::{connected_components, earliest_arrival, DfsTree, EarliestArrival},
+    edge::{DirectedWeightedEdge, UnDirectedWeightedEdge},
+    ghost::GhostToken,
+    Graph, VertexId,
+};
+
+#[test]
+fn ids_round_trip() {
+    GhostToken::new(|_| {
+        let id: VertexId = VertexId::new(7);
+        let json = serde_json::to_string(&id).unwrap();
+
+        assert_eq!(json, "7");
+        assert_eq!(serde_json::from_str::<VertexId>(&json).unwrap(), id);
+    });
+}
+
+#[test]
+fn results_round_trip() {
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..3).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(v[0], v[1], 4, &mut t).unwrap();
+        graph.add_edge(v[1], v[2], 5, &mut t).unwrap();
+
+        let arrival = earliest_arrival(&graph, v[0], 0, |w, t| Some(t + w), &t).unwrap();
+        let json = serde_json::to_string(&arrival).unwrap();
+        let restored: EarliestArrival<u32> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.arrival(v[2]), Some(9));
+        assert_eq!(restored.path_to(v[2]), arrival.path_to(v[2]));
+
+        let components = connected_components(&graph, &t);
+        let json = serde_json::to_string(&components).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<Vec<VertexId>>>(&json).unwrap(),
+            components
+        );
+    });
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), (), UnDirectedWeightedEdge<_, _>> = Graph::new();
+
+        let v: Vec<_> = (0..3).map(|_| graph.add_vertex(())).collect();
+        graph.add_edge(v[0], v[1], (), &mut t).unwrap();
+        graph.add_edge(v[1], v[2], (), &mut t).unwrap();
+
+        let tree = DfsTree::new(&graph, v[0], &t);
+        let json = serde_json::to_string(&tree).unwrap();
+        let restored: DfsTree = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.order(), tree.order());
+        assert_eq!(restored.parent(v[2]), tree.parent(v[2]));
+    });
+}
+
+#[test]
+fn watermarked_export() {
+    use graph::export::{Provenance, Watermarked};
+
+    GhostToken::new(|mut t| {
+        let mut graph: Graph<(), u32, DirectedWeightedEdge<_, _>> = Graph::new();
+
+        let first = graph.add_vertex(());
+        let second = graph.add_vertex(());
+        graph.add_edge(first, second, 3, &mut t).unwrap();
+
+        let provenance = Provenance::new("nightly-etl", 1_700_000_000).with_parameter("seed", "42");
+        let export = Watermarked::new(provenance.clone(), graph.freeze(&t));
+
+        let json = serde_json::to_string(&export).unwrap();
+        let (restored, frozen): (_, graph::FrozenGraph<u32>) =
+            serde_json::from_str::<Watermarked<_>>(&json)
+                .unwrap()
+                .into_parts();
+
+        assert_eq!(restored, provenance);
+        assert_eq!(restored.parameter("seed"), Some("42"));
+        assert_eq!(frozen.weights(0), &[3]);
+    });
+}